@@ -0,0 +1,91 @@
+//! The one word lookup behind both the lexer's `TokenKind::Word` and the
+//! morphology's `MorphAnalysis`, so the two cannot disagree about a word.
+
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+
+use logos_protocol::{Dialect, DictionaryView, LemmaId, MorphFlags, Period};
+
+use crate::morphology::{guess_proper_noun, suffix_analyses, MatchOptions, MatchTrace, MorphAnalysis};
+use crate::normalize::{fold_case, is_all_caps};
+
+/// `analyze_token_with` under the default options, with no lemma known.
+pub fn analyze_token(dict: &(impl DictionaryView + ?Sized), token_text: &str) -> MorphAnalysis {
+    analyze_token_with(dict, token_text, None, &MatchOptions::default())
+}
+
+/// Stem plus ending first, then the lowercased word, an indeclinable
+/// headword, a guessed name, and a prefix match as a last resort.
+/// `known_lemma_id` narrows the stem search to a lemma the caller already
+/// found (the lexer's hit, from this same lookup).
+pub fn analyze_token_with(
+    dict: &(impl DictionaryView + ?Sized),
+    token_text: &str,
+    known_lemma_id: Option<u32>,
+    options: &MatchOptions,
+) -> MorphAnalysis {
+    // 1. Try to find semantic matches via suffix analysis
+    if let Some(analysis) = options.best(suffix_analyses(dict, token_text, known_lemma_id, options, options.candidates())) {
+        return analysis;
+    }
+
+    // 2. Capitals: a sentence-initial common word, or a heading in capitals,
+    // which carry no accents and so match with accents ignored
+    let lower = fold_case(token_text);
+    if let Cow::Owned(lower) = &lower {
+        let options = MatchOptions { accent_insensitive: options.accent_insensitive || is_all_caps(token_text), ..*options };
+        if let Some(mut analysis) = options.best(suffix_analyses(dict, lower, None, &options, options.candidates())) {
+            analysis.trace = match analysis.trace {
+                MatchTrace::Pronoun { class, movable_nu, .. } => MatchTrace::Pronoun { class, lowercased: true, movable_nu },
+                MatchTrace::Suffix { movable_nu, .. } => MatchTrace::Suffix { lowercased: true, movable_nu },
+                trace => trace,
+            };
+            analysis.confidence = analysis.trace.certainty();
+            return analysis;
+        }
+    }
+
+    // 3. A headword written exactly so (or the caller's lemma) that no
+    // ending applies to: an indeclinable
+    let exact = known_lemma_id.or_else(|| dict.find_exact(token_text).first().map(|l| l.id.0));
+    if let Some(id) = exact {
+        return MorphAnalysis {
+            flags: MorphFlags::empty(),
+            lemma_id: Some(LemmaId(id)),
+            trace: MatchTrace::Indeclinable,
+            stem: token_text.to_string(),
+            kind: "Word",
+            dialects: Dialect::empty(),
+            periods: Period::empty(),
+            confidence: MatchTrace::Indeclinable.certainty(),
+        };
+    }
+
+    // 4. Capitalized and not in the dictionary: a name
+    if let Some(analysis) = guess_proper_noun(token_text).filter(|a| options.profile.admits(a.flags)) {
+        return analysis;
+    }
+
+    // 5. Last Resort: Check if we can recover unknown words by simple prefix match 
+    // (This was part of the recover logic in WASM)
+    // Actually, robust resolve above usually handles "recover" if suffix matches.
+    // If not, we check for raw lemma starts_with
+    let text = options.normalize(token_text);
+    if let Some(lemma) = dict.lemmas().find(|l| {
+        let lemma_text = options.normalize(l.text);
+        text.starts_with(lemma_text.as_ref()) || lemma_text.starts_with(text.as_ref())
+    }) {
+         return MorphAnalysis {
+            flags: MorphFlags::empty(),
+            lemma_id: Some(lemma.id),
+            trace: MatchTrace::Recovered,
+            stem: token_text.to_string(),
+            kind: "Word (Recovered)",
+            dialects: Dialect::empty(),
+            periods: Period::empty(),
+            confidence: MatchTrace::Recovered.certainty(),
+        };
+    }
+
+    MorphAnalysis::unknown()
+}
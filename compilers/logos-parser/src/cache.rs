@@ -0,0 +1,145 @@
+//! Memo of morphology results per word form. Running text repeats a small set
+//! of forms (καί, ὁ, δέ) thousands of times, each otherwise re-resolved.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::collections::HashMap;
+
+use crate::morphology::{MatchOptions, MorphAnalysis};
+
+/// What a resolution depends on besides the dictionaries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub form: String,
+    /// Lemma the lexer already matched, if any
+    pub known_lemma: Option<u32>,
+    pub options: MatchOptions,
+}
+
+struct Entry {
+    analysis: MorphAnalysis,
+    /// `MorphCache::clock` at the last use
+    used: u64,
+}
+
+/// Least-recently-used cache of resolved analyses. When full, the least
+/// recently used quarter is evicted at once, keeping inserts cheap.
+pub struct MorphCache {
+    capacity: usize,
+    entries: HashMap<CacheKey, Entry>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl MorphCache {
+    /// A capacity of 0 disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, entries: HashMap::new(), clock: 0, hits: 0, misses: 0 }
+    }
+
+    /// The cached analysis, counting a hit or a miss.
+    pub fn get(&mut self, key: &CacheKey) -> Option<MorphAnalysis> {
+        self.clock += 1;
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                entry.used = self.clock;
+                self.hits += 1;
+                Some(entry.analysis.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: CacheKey, analysis: MorphAnalysis) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            self.evict(self.capacity.div_ceil(4));
+        }
+        self.clock += 1;
+        self.entries.insert(key, Entry { analysis, used: self.clock });
+    }
+
+    fn evict(&mut self, count: usize) {
+        let mut used: Vec<u64> = self.entries.values().map(|e| e.used).collect();
+        let count = count.min(used.len());
+        if count == 0 {
+            return;
+        }
+        let (_, threshold, _) = used.select_nth_unstable(count - 1);
+        let threshold = *threshold;
+        self.entries.retain(|_, e| e.used > threshold);
+    }
+
+    /// Drops every entry (e.g. after the dictionaries change); counters stay.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Shrinks the cache to `capacity` entries if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        if self.entries.len() > capacity {
+            self.evict(self.entries.len() - capacity);
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn key(form: &str) -> CacheKey {
+        CacheKey { form: form.to_string(), known_lemma: None, options: MatchOptions::default() }
+    }
+
+    #[test]
+    fn test_morph_cache() {
+        let mut cache = MorphCache::new(4);
+        assert!(cache.get(&key("καί")).is_none());
+        cache.insert(key("καί"), MorphAnalysis { stem: "καί".to_string(), ..MorphAnalysis::unknown() });
+        assert_eq!(cache.get(&key("καί")).map(|a| a.stem), Some("καί".to_string()));
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // Filling up evicts the least recently used entry first
+        for form in ["ὁ", "δέ", "γάρ"] {
+            cache.insert(key(form), MorphAnalysis::unknown());
+        }
+        assert!(cache.get(&key("καί")).is_some());
+        cache.insert(key("μέν"), MorphAnalysis::unknown());
+        assert_eq!(cache.len(), 4);
+        assert!(cache.get(&key("ὁ")).is_none());
+        assert!(cache.get(&key("καί")).is_some());
+
+        cache.set_capacity(0);
+        assert!(cache.is_empty());
+        cache.insert(key("καί"), MorphAnalysis::unknown());
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+//! Context disambiguation between morphology and syntax: of each word's
+//! candidate analyses, pick the sequence whose tags (`MorphFlags::tag`) the
+//! dictionary's bigram weights rate likeliest, by Viterbi search, and how
+//! sure each choice is.
+
+use alloc::vec::Vec;
+
+use logos_protocol::{MorphFlags, TagTransition};
+
+/// Weight of a bigram the dictionary does not list.
+pub const UNSEEN: i32 = -10_000;
+
+/// Cost of each step down a word's ranked analyses, so that on a tie the
+/// morphology's own choice stands.
+const RANK_PENALTY: i64 = 1;
+
+/// `candidates[i]` holds the analyses of word i, best-ranked first; `weight`
+/// looks up a bigram (None = unseen). Returns the chosen index per word.
+pub fn viterbi(candidates: &[Vec<MorphFlags>], weight: impl Fn(u32, u32) -> Option<i32>) -> Vec<usize> {
+    let weight = |from: u32, to: u32| weight(from, to).unwrap_or(UNSEEN) as i64;
+
+    // score[j] = best total ending in candidate j of the current word
+    let mut score: Vec<i64> = Vec::new();
+    let mut back: Vec<Vec<usize>> = Vec::with_capacity(candidates.len());
+    let mut prev: &[MorphFlags] = &[];
+
+    for (i, word) in candidates.iter().enumerate() {
+        let mut next = Vec::with_capacity(word.len());
+        let mut from = Vec::with_capacity(word.len());
+        for (rank, flags) in word.iter().enumerate() {
+            let emission = -(rank as i64) * RANK_PENALTY;
+            let (best, arg) = if i == 0 {
+                (weight(TagTransition::START, flags.tag()), 0)
+            } else {
+                prev.iter()
+                    .zip(&score)
+                    .enumerate()
+                    .map(|(k, (p, s))| (s + weight(p.tag(), flags.tag()), k))
+                    // Earliest predecessor wins a tie
+                    .min_by_key(|(s, k)| (-s, *k))
+                    .unwrap_or((0, 0))
+            };
+            next.push(best + emission);
+            from.push(arg);
+        }
+        score = next;
+        back.push(from);
+        prev = word;
+    }
+
+    let Some(mut j) = score.iter().enumerate().min_by_key(|(k, s)| (-**s, *k)).map(|(k, _)| k) else {
+        return Vec::new();
+    };
+    let mut path = vec![0; candidates.len()];
+    for i in (0..candidates.len()).rev() {
+        path[i] = j;
+        j = back[i][j];
+    }
+    path
+}
+
+/// Weights are natural logs of probabilities, in thousandths.
+const SCALE: f64 = 1000.0;
+
+/// For each word, the probability of each candidate: the best path through
+/// it against the best paths through its rivals (max-marginals, normalized),
+/// so a choice the bigrams barely prefer stays doubtful.
+pub fn posteriors(candidates: &[Vec<MorphFlags>], weight: impl Fn(u32, u32) -> Option<i32>) -> Vec<Vec<f32>> {
+    let weight = |from: u32, to: u32| weight(from, to).unwrap_or(UNSEEN) as i64;
+    let emission = |rank: usize| -(rank as i64) * RANK_PENALTY;
+    let n = candidates.len();
+
+    // forward[i][j]: best score of words ..=i ending in candidate j
+    let mut forward: Vec<Vec<i64>> = Vec::with_capacity(n);
+    for (i, word) in candidates.iter().enumerate() {
+        let scores = word.iter().enumerate().map(|(rank, flags)| {
+            let best = match i {
+                0 => weight(TagTransition::START, flags.tag()),
+                _ => candidates[i - 1].iter().zip(&forward[i - 1])
+                    .map(|(p, s)| s + weight(p.tag(), flags.tag()))
+                    .max()
+                    .unwrap_or(0),
+            };
+            best + emission(rank)
+        }).collect();
+        forward.push(scores);
+    }
+
+    // backward[i][j]: best score of the words after i, given candidate j
+    let mut backward: Vec<Vec<i64>> = candidates.iter().map(|w| vec![0; w.len()]).collect();
+    for i in (0..n.saturating_sub(1)).rev() {
+        for (j, flags) in candidates[i].iter().enumerate() {
+            backward[i][j] = candidates[i + 1].iter().enumerate()
+                .map(|(k, next)| weight(flags.tag(), next.tag()) + emission(k) + backward[i + 1][k])
+                .max()
+                .unwrap_or(0);
+        }
+    }
+
+    forward.iter().zip(&backward).map(|(f, b)| {
+        let totals: Vec<i64> = f.iter().zip(b).map(|(f, b)| f + b).collect();
+        let top = totals.iter().copied().max().unwrap_or(0);
+        let odds: Vec<f64> = totals.iter().map(|t| ((t - top) as f64 / SCALE).exp()).collect();
+        let sum: f64 = odds.iter().sum();
+        odds.iter().map(|o| (o / sum) as f32).collect()
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viterbi() {
+        let art_nom = MorphFlags::ARTICLE | MorphFlags::NOMINATIVE;
+        let noun_nom = MorphFlags::NOUN | MorphFlags::NOMINATIVE;
+        let noun_acc = MorphFlags::NOUN | MorphFlags::ACCUSATIVE;
+        let words = vec![vec![art_nom], vec![noun_acc, noun_nom]];
+
+        // No weights: the first-ranked analyses stand
+        assert_eq!(viterbi(&words, |_, _| None), [0, 0]);
+
+        // A nominative article wants a nominative noun next
+        let weights = [(art_nom.tag(), noun_nom.tag(), -100), (art_nom.tag(), noun_acc.tag(), -3000)];
+        let lookup = |from, to| weights.iter().find(|w| w.0 == from && w.1 == to).map(|w| w.2);
+        assert_eq!(viterbi(&words, lookup), [0, 1]);
+        assert!(viterbi(&[], lookup).is_empty());
+
+        // ln(p) in thousandths: the nominative is e^2.9 times likelier
+        let odds = posteriors(&words, lookup);
+        assert_eq!(odds[0], [1.0]);
+        assert!(odds[1][1] > 0.9 && odds[1][0] < 0.1);
+        assert!((odds[1].iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        // Unseen bigrams alone leave a tie
+        assert!((posteriors(&words, |_, _| None)[1][0] - 0.5).abs() < 0.01);
+    }
+}
@@ -1,40 +1,82 @@
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod analysis;
 pub mod parser;
 pub mod token;
 pub mod syntax;
 pub mod morphology;
+pub mod normalize;
+#[cfg(feature = "std")]
+pub mod disambiguate;
+#[cfg(feature = "std")]
+pub mod transition;
+#[cfg(feature = "std")]
+pub mod cache;
+pub mod spelling;
+pub mod translit;
+
+use alloc::borrow::Cow;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use logos_protocol::{ArchivedDictionary, DictionaryView, LemmaId};
+use crate::token::{Span, Token, TokenKind};
+use crate::analysis::analyze_token;
+use crate::parser::{default_config, parse_with_config, RawToken};
 
-use logos_protocol::{Dictionary, LemmaId};
-use rkyv::Archived;
-use crate::token::{Token, TokenKind};
-use crate::parser::{parse_with_spans, RawToken};
+pub use crate::parser::TokenizerConfig;
 
-pub struct Lexer<'a> {
-    dict: &'a Archived<Dictionary>,
+/// Tokenizer over any `DictionaryView`: the loaded archive, or a
+/// `Dictionary` built in code.
+pub struct Lexer<'a, D: ?Sized = ArchivedDictionary> {
+    dict: &'a D,
+    config: &'a TokenizerConfig,
 }
 
-impl<'a> Lexer<'a> {
-    pub fn new(dict: &'a Archived<Dictionary>) -> Self {
-        Self { dict }
+impl<D: ?Sized> Clone for Lexer<'_, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D: ?Sized> Copy for Lexer<'_, D> {}
+
+impl<'a, D: DictionaryView + ?Sized> Lexer<'a, D> {
+    pub fn new(dict: &'a D) -> Self {
+        Self { dict, config: default_config() }
+    }
+
+    pub fn new_with_config(dict: &'a D, config: &'a TokenizerConfig) -> Self {
+        Self { dict, config }
     }
 
     /// Primary entry point: Text -> Structured Tokens
-    pub fn tokenize(&self, input: &'a str) -> Vec<Token<'a>> {
-        let raw_tokens = parse_with_spans(input);
+    pub fn tokenize<'s>(&self, input: &'s str) -> Vec<Token<'s>> {
+        let raw_tokens = parse_with_config(input, self.config);
 
         raw_tokens
             .into_iter()
             .map(|(span, raw)| {
-                let text = &input[span.start..span.end];
+                let mut text = Cow::Borrowed(&input[span.start..span.end]);
                 
                 let kind = match raw {
                     RawToken::Punct(c) => TokenKind::Punctuation(c),
-                    RawToken::Word(s) => {
-                        // Lookup in Dictionary
-                        if let Some(lemma_id) = self.lookup_lemma(s) {
-                            TokenKind::Word(lemma_id)
-                        } else {
-                            TokenKind::UnknownWord
-                        }
+                    RawToken::Numeral(value) => TokenKind::Numeral(value),
+                    RawToken::Sigla(siglum) => TokenKind::Sigla(siglum),
+                    RawToken::Foreign(script) => TokenKind::Foreign(script),
+                    RawToken::Word(s) => self.word_kind(s),
+                    RawToken::Hyphenated(s) => {
+                        let joined: String = s.chars().filter(|c| self.config.is_word_char(*c)).collect();
+                        let kind = self.word_kind(&joined);
+                        text = Cow::Owned(joined);
+                        kind
                     }
                 };
 
@@ -43,37 +85,178 @@ impl<'a> Lexer<'a> {
             .collect()
     }
 
-    /// Linear scan lookup (O(N)) - MVP only.
-    /// Phase 5 Optimization: Replace with FST or Hash lookup.
-    fn lookup_lemma(&self, surface_form: &str) -> Option<LemmaId> {
-        // Iterate over archived lemmas
-        for lemma in self.dict.lemmas.iter() {
-            let stem = lemma.text.as_str();
-            
-            // 1. Exact Match (e.g., indeclinable words)
-            if stem == surface_form {
-                let val: u32 = lemma.id.0;
-                return Some(LemmaId(val));
-            }
+    /// Tokens of a text arriving in chunks (lines, file blocks), yielded as
+    /// soon as the text after them shows they are complete. Only the
+    /// unfinished tail is buffered; spans count from the start of the
+    /// first chunk.
+    pub fn tokenize_stream<I>(&self, chunks: I) -> TokenStream<'a, I::IntoIter, D>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        TokenStream {
+            lexer: *self,
+            chunks: chunks.into_iter(),
+            buffer: String::new(),
+            offset: 0,
+            ready: VecDeque::new(),
+            done: false,
+        }
+    }
 
-            // 2. Stem Match (e.g., "άνθρωπ" matches "άνθρωπος")
-            // In a real engine, we would validate the suffix against the paradigm here.
-            // For this phase, if it starts with the stem, we count it!
-            if surface_form.starts_with(stem) {
-                 let val: u32 = lemma.id.0;
-                 return Some(LemmaId(val));
+    fn word_kind(&self, word: &str) -> TokenKind {
+        match self.lookup_lemma(word) {
+            Some(lemma_id) => TokenKind::Word(lemma_id),
+            None => TokenKind::UnknownWord,
+        }
+    }
+
+    /// The lemma `analysis::analyze_token` finds for `surface_form` when it
+    /// is a dictionary word; guesses (names, prefix recoveries) do not count.
+    /// Capitalized words are looked up lowercased; tokens keep their text.
+    pub fn lookup_lemma(&self, surface_form: &str) -> Option<LemmaId> {
+        let analysis = analyze_token(self.dict, surface_form);
+        analysis.lemma_id.filter(|_| analysis.kind == "Word")
+    }
+}
+
+/// Iterator returned by [`Lexer::tokenize_stream`].
+pub struct TokenStream<'a, I, D: ?Sized = ArchivedDictionary> {
+    lexer: Lexer<'a, D>,
+    chunks: I,
+    /// Text received but not tokenized yet
+    buffer: String,
+    /// Position of `buffer` in the whole text
+    offset: usize,
+    ready: VecDeque<Token<'static>>,
+    done: bool,
+}
+
+impl<I, D: DictionaryView + ?Sized> TokenStream<'_, I, D> {
+    /// Tokenizes the first `len` bytes of the buffer and drops them.
+    fn flush(&mut self, len: usize) {
+        let offset = self.offset;
+        self.ready.extend(self.lexer.tokenize(&self.buffer[..len]).into_iter().map(|t| Token {
+            span: Span::new(t.span.start + offset, t.span.end + offset),
+            ..t.into_owned()
+        }));
+        self.buffer.drain(..len);
+        self.offset += len;
+    }
+}
+
+impl<I, D> Iterator for TokenStream<'_, I, D>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+    D: DictionaryView + ?Sized,
+{
+    type Item = Token<'static>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.ready.pop_front() {
+                return Some(token);
+            }
+            if self.done {
+                return None;
+            }
+            match self.chunks.next() {
+                Some(chunk) => {
+                    self.buffer.push_str(chunk.as_ref());
+                    let cut = self.lexer.config.safe_cut(&self.buffer);
+                    self.flush(cut);
+                }
+                None => {
+                    self.done = true;
+                    self.flush(self.buffer.len());
+                }
             }
         }
-        None
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
-    use logos_protocol::{Lemma, Gender};
+    use alloc::string::ToString;
+    use crate::analysis::analyze_token;
+    use logos_protocol::{Dialect, Dictionary, Lemma, Gender, MorphFlags, Paradigm, ParadigmId, PartOfSpeech, Period};
+    use proptest::prelude::*;
     use rkyv::{to_bytes};
 
+    /// Letters of generated words: no iota (it would turn into a subscript
+    /// after α, η, ω) and no sigma (whose final form is put right).
+    const LETTERS: &str = "αβγδεζηθκλμνξοπρτυφχψω";
+
+    /// Any combination of flags, meaningful or not.
+    pub(crate) fn flags() -> impl Strategy<Value = MorphFlags> {
+        any::<u32>().prop_map(MorphFlags::from_bits_truncate)
+    }
+
+    /// Endings of at most two letters (possibly empty) with random flags.
+    pub(crate) fn paradigm(id: u32) -> impl Strategy<Value = Paradigm> {
+        prop::collection::vec((flags(), "[αεηοω]?[νρ]?"), 1..6).prop_map(move |endings| Paradigm {
+            id: ParadigmId(id),
+            dialects: Dialect::empty(),
+            periods: Period::empty(),
+            endings: endings.into_iter().map(|(flags, ending)| (flags.bits(), ending)).collect(),
+        })
+    }
+
+    /// Lemmas of three letters or more, no two with the same first letter,
+    /// so that a stem plus a generated ending can only be read as its own
+    /// lemma, and every paradigm (each applying to every lemma).
+    pub(crate) fn dictionary() -> impl Strategy<Value = Dictionary> {
+        let initials = prop::sample::subsequence(LETTERS.chars().collect::<Vec<_>>(), 1..6);
+        let tails = prop::collection::vec(prop::string::string_regex(&format!("[{}]{{2,4}}", LETTERS)).unwrap(), 6);
+        let paradigms = (1..4u32).prop_flat_map(|n| (1..=n).map(paradigm).collect::<Vec<_>>());
+        (initials, tails, paradigms).prop_map(|(initials, tails, paradigms)| Dictionary {
+            version: 1,
+            lemmas: initials.into_iter().zip(tails).enumerate().map(|(i, (initial, tail))| Lemma {
+                id: LemmaId(i as u32 + 1),
+                text: format!("{}{}", initial, tail),
+                gender: Gender::Masculine,
+                pos: PartOfSpeech::Noun,
+                urn: None,
+                gloss: None,
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                paradigm: None,
+            }).collect(),
+            paradigms,
+            ..Default::default()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_generated_forms_map_back(dict in dictionary()) {
+            let bytes = to_bytes::<_, 1024>(&dict).unwrap();
+            let archived = rkyv::check_archived_root::<Dictionary>(&bytes).unwrap();
+            let lexer = Lexer::new(archived);
+            for lemma in &dict.lemmas {
+                for paradigm in &dict.paradigms {
+                    for (_, ending) in &paradigm.endings {
+                        let form = format!("{}{}", lemma.text, ending);
+                        let analysis = analyze_token(archived, &form);
+                        prop_assert_eq!(analysis.lemma_id, Some(lemma.id), "{}", form);
+
+                        // ... with the flags of an ending that reads it so
+                        let readings = dict.paradigms.iter().flat_map(|p| &p.endings).filter(|(_, other)| {
+                            form.strip_suffix(other.as_str()).is_some_and(|stem| lemma.text.starts_with(stem))
+                        });
+                        prop_assert!(readings.map(|(bits, _)| MorphFlags::from_bits_truncate(*bits)).any(|f| f == analysis.flags), "{}", form);
+
+                        let tokens = lexer.tokenize(&form);
+                        prop_assert_eq!(tokens.len(), 1);
+                        prop_assert_eq!(&tokens[0].kind, &TokenKind::Word(lemma.id));
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_tokenizer_integration() {
         // 1. Setup Mock Dictionary
@@ -85,9 +268,15 @@ mod tests {
                     text: "άνθρωπος".to_string(), 
                     gender: Gender::Masculine,
                     pos: logos_protocol::PartOfSpeech::Noun,
+                    urn: None,
+                    gloss: None,
+                    dialects: Dialect::empty(),
+                    periods: Period::empty(),
+                    paradigm: None,
                 }
             ],
             paradigms: vec![],
+            ..Default::default()
         };
 
         // Serialize to bytes (simulating loading dict.rkyv)
@@ -117,4 +306,311 @@ mod tests {
         // Token 2: "."
         matches!(tokens[2].kind, TokenKind::Punctuation('.'));
     }
+
+    #[test]
+    fn test_owned_dictionary() {
+        use crate::analysis::analyze_token;
+        use logos_protocol::{MorphFlags, Paradigm, ParadigmId, PronounClass, PronounParadigm};
+
+        // Built in code and read as is: no archive round trip
+        let dict = logos_protocol::Dictionary {
+            version: 1,
+            lemmas: vec![Lemma { id: LemmaId(1), text: "λόγ".to_string(), gender: Gender::Masculine, pos: logos_protocol::PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None }],
+            paradigms: vec![Paradigm {
+                id: ParadigmId(1),
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                endings: vec![((MorphFlags::NOUN | MorphFlags::GENITIVE | MorphFlags::SINGULAR).bits(), "ου".to_string())],
+            }],
+            pronouns: vec![PronounParadigm { lemma: LemmaId(2), class: PronounClass::Personal, forms: vec![(MorphFlags::NOMINATIVE.bits(), "ἐγώ".to_string())] }],
+            ..Default::default()
+        };
+        let tokens = Lexer::new(&dict).tokenize("λόγου ἐγώ λόγχη");
+        assert_eq!(tokens[0].kind, TokenKind::Word(LemmaId(1)));
+        // Starts with the stem, but -χη is no ending of its paradigm
+        assert_eq!(tokens[2].kind, TokenKind::UnknownWord);
+        // The lexer and the morphology share one lookup
+        for token in &tokens {
+            let analysis = analyze_token(&dict, &token.text);
+            let word = analysis.lemma_id.filter(|_| analysis.kind == "Word").map(TokenKind::Word);
+            assert_eq!(token.kind, word.unwrap_or(TokenKind::UnknownWord), "{}", token.text);
+        }
+
+        let bytes = to_bytes::<_, 256>(&dict).unwrap();
+        let archived = unsafe { rkyv::archived_root::<logos_protocol::Dictionary>(&bytes) };
+        for form in ["λόγου", "ἐγώ"] {
+            let (owned, pooled) = (analyze_token(&dict, form), analyze_token(archived, form));
+            assert_eq!((owned.lemma_id, owned.flags), (pooled.lemma_id, pooled.flags));
+            assert!(owned.lemma_id.is_some(), "{}", form);
+        }
+    }
+
+    #[test]
+    fn test_lookup_needs_whole_stem() {
+        use logos_protocol::{MorphFlags, Paradigm, ParadigmId};
+
+        let noun = |id, text: &str, paradigm| Lemma { id: LemmaId(id), text: text.to_string(), gender: Gender::Masculine, pos: logos_protocol::PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: Some(ParadigmId(paradigm)) };
+        let (nom, gen) = ((MorphFlags::NOUN | MorphFlags::NOMINATIVE | MorphFlags::SINGULAR).bits(), (MorphFlags::NOUN | MorphFlags::GENITIVE | MorphFlags::SINGULAR).bits());
+        let mut dict = logos_protocol::Dictionary {
+            version: 1,
+            // A stem, and a headword stored whole
+            lemmas: vec![noun(1, "λόγ", 1), noun(2, "τιμή", 2)],
+            paradigms: vec![
+                Paradigm { id: ParadigmId(1), dialects: Dialect::empty(), periods: Period::empty(), endings: vec![(nom, "ος".to_string()), (gen, "ου".to_string())] },
+                Paradigm { id: ParadigmId(2), dialects: Dialect::empty(), periods: Period::empty(), endings: vec![(nom, "ή".to_string()), (gen, "ῆς".to_string())] },
+            ],
+            ..Default::default()
+        };
+        let lexer = Lexer::new(&dict);
+        assert_eq!(lexer.lookup_lemma("λόγου"), Some(LemmaId(1)));
+        assert_eq!(lexer.lookup_lemma("τιμῆς"), Some(LemmaId(2)));
+        // A bare ending leaves no stem at all
+        assert_eq!(lexer.lookup_lemma("ου"), None);
+        assert_eq!(lexer.lookup_lemma("ος"), None);
+        // Truncated stems only begin the lemma's
+        assert_eq!(lexer.lookup_lemma("λος"), None);
+        assert_eq!(lexer.lookup_lemma("τιῆς"), None);
+        // λόγ takes the endings of its own paradigm only
+        assert_eq!(lexer.lookup_lemma("λόγῆς"), None);
+
+        dict.lemmas[0].paradigm = None;
+        assert_eq!(Lexer::new(&dict).lookup_lemma("λόγῆς"), Some(LemmaId(1)));
+    }
+
+    #[test]
+    fn test_surface_form_table() {
+        use crate::morphology::{suffix_analyses, MatchOptions};
+        use logos_protocol::{DictionaryArchive, MorphFlags, Paradigm, ParadigmId, SurfaceForm};
+
+        let noun = |id, text: &str| Lemma { id: LemmaId(id), text: text.to_string(), gender: Gender::Masculine, pos: logos_protocol::PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None };
+        let (nom, gen) = (MorphFlags::NOUN | MorphFlags::NOMINATIVE | MorphFlags::SINGULAR, MorphFlags::NOUN | MorphFlags::GENITIVE | MorphFlags::SINGULAR);
+        let dict = logos_protocol::Dictionary {
+            version: 1,
+            lemmas: vec![noun(1, "λόγ"), noun(2, "ἵππ")],
+            paradigms: vec![
+                Paradigm { id: ParadigmId(1), dialects: Dialect::empty(), periods: Period::empty(), endings: vec![(nom.bits(), "ος".to_string()), (gen.bits(), "ου".to_string())] },
+                Paradigm { id: ParadigmId(2), dialects: Dialect::DORIC, periods: Period::empty(), endings: vec![(gen.bits(), "ω".to_string())] },
+            ],
+            ..Default::default()
+        };
+        // What `atlas-compiler --expand-forms` generates
+        let mut forms = Vec::new();
+        for lemma in &dict.lemmas {
+            for paradigm in dict.paradigms.iter().filter(|p| lemma.inflects_by(p.id)) {
+                for (flags, ending) in &paradigm.endings {
+                    let form = format!("{}{}", lemma.text, ending);
+                    forms.push(SurfaceForm { form, lemma: lemma.id, flags: *flags, ending_len: ending.len() as u8, dialects: paradigm.dialects, periods: paradigm.periods });
+                }
+            }
+        }
+        let (archive, _) = DictionaryArchive::build_with_forms(&dict, &forms).unwrap();
+        let bytes = to_bytes::<_, 1024>(&archive).unwrap();
+        let archived = rkyv::check_archived_root::<logos_protocol::Dictionary>(&bytes).unwrap();
+        assert!(archived.has_surface_forms());
+
+        let strict = MatchOptions { dialects: Dialect::ATTIC, restrict_dialect: true, ..MatchOptions::default() };
+        for options in [MatchOptions::default(), strict] {
+            for form in ["λόγος", "ἵππου", "ἵππω", "λόγχη"] {
+                let scanned: Vec<_> = suffix_analyses(&dict, form, None, &options, usize::MAX).into_iter().map(|a| (a.lemma_id, a.flags, a.stem)).collect();
+                let probed: Vec<_> = suffix_analyses(archived, form, None, &options, usize::MAX).into_iter().map(|a| (a.lemma_id, a.flags, a.stem)).collect();
+                assert_eq!(scanned, probed, "{}", form);
+            }
+        }
+        assert_eq!(suffix_analyses(archived, "ἵππω", None, &strict, usize::MAX).len(), 0);
+        assert_eq!(suffix_analyses(archived, "ἵππου", Some(1), &MatchOptions::default(), usize::MAX).len(), 0);
+    }
+
+    #[test]
+    fn test_orthographic_variants() {
+        use crate::analysis::analyze_token;
+        use logos_protocol::{MorphFlags, Paradigm, ParadigmId};
+
+        let dict = logos_protocol::Dictionary {
+            version: 1,
+            lemmas: vec![
+                Lemma { id: LemmaId(1), text: "λόγ".to_string(), gender: Gender::Masculine, pos: logos_protocol::PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None },
+                Lemma { id: LemmaId(2), text: "λέγ".to_string(), gender: Gender::Masculine, pos: logos_protocol::PartOfSpeech::Verb, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None },
+            ],
+            paradigms: vec![Paradigm {
+                id: ParadigmId(1),
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                endings: vec![
+                    ((MorphFlags::NOUN | MorphFlags::NOMINATIVE | MorphFlags::SINGULAR).bits(), "ος".to_string()),
+                    ((MorphFlags::VERB | MorphFlags::THIRD_PERSON | MorphFlags::PLURAL).bits(), "ουσι".to_string()),
+                    ((MorphFlags::NOUN | MorphFlags::DATIVE | MorphFlags::SINGULAR).bits(), "ῳ".to_string()),
+                ],
+            }],
+            ..Default::default()
+        };
+
+        // Both spellings of the movable ν, a sigma in the wrong shape, an iota adscript
+        for (written, standard) in [("λέγουσιν", "λέγουσι"), ("λόγοσ", "λόγος"), ("λόγωι", "λόγῳ")] {
+            let (variant, listed) = (analyze_token(&dict, written), analyze_token(&dict, standard));
+            assert_eq!((variant.lemma_id, variant.flags, variant.kind), (listed.lemma_id, listed.flags, "Word"), "{}", written);
+        }
+
+        // Each change is reported with the form it left
+        use crate::morphology::MatchOptions;
+        use crate::normalize::Normalization;
+        let steps = |written: &str| analyze_token(&dict, written).normalizations(written, &MatchOptions::default());
+        assert_eq!(steps("λέγουσιν"), [(Normalization::MovableNu, "λέγουσι".to_string())]);
+        assert_eq!(steps("Λόγωι"), [
+            (Normalization::CaseFolding, "λόγωι".to_string()),
+            (Normalization::IotaSubscript, "λόγῳ".to_string()),
+        ]);
+        assert_eq!(steps("λο\u{301}γοσ"), [
+            (Normalization::Nfc, "λόγοσ".to_string()),
+            (Normalization::FinalSigma, "λόγος".to_string()),
+        ]);
+        assert!(steps("λόγος").is_empty());
+
+        // A decomposed word is one token
+        let tokens = crate::parser::parse_with_spans("λο\u{301}γοσ ἐ\u{313}ν");
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].0.end, "λο\u{301}γοσ".len());
+    }
+
+    #[test]
+    fn test_numeral_tokens() {
+        let dict = logos_protocol::Dictionary::default();
+        let bytes = to_bytes::<_, 256>(&dict).unwrap();
+        let archived = unsafe { rkyv::archived_root::<logos_protocol::Dictionary>(&bytes) };
+        let lexer = Lexer::new(archived);
+
+        let kinds: Vec<TokenKind> = lexer.tokenize("42 ρκγʹ ͵αφκγʹ ͵β Ϛʹ").into_iter().map(|t| t.kind).collect();
+        assert_eq!(kinds, [
+            TokenKind::Numeral(42),
+            TokenKind::Numeral(123),
+            TokenKind::Numeral(1523),
+            TokenKind::Numeral(2000),
+            TokenKind::Numeral(6),
+        ]);
+
+        // Letters without a keraia are words, even if they spell a number
+        let tokens = lexer.tokenize("και ρκγ");
+        assert!(tokens.iter().all(|t| t.kind == TokenKind::UnknownWord));
+        // Spans cover the keraia
+        assert_eq!(lexer.tokenize("γʹ.")[0].text, "γʹ");
+    }
+
+    #[test]
+    fn test_tokenizer_config() {
+        use crate::token::Script;
+
+        let dict = logos_protocol::Dictionary::default();
+        let bytes = to_bytes::<_, 256>(&dict).unwrap();
+        let archived = unsafe { rkyv::archived_root::<logos_protocol::Dictionary>(&bytes) };
+        let texts = |config: &TokenizerConfig, input: &str| -> Vec<String> {
+            Lexer::new_with_config(archived, config).tokenize(input).into_iter().map(|t| t.text.into_owned()).collect()
+        };
+        let input = "ὁ λό-\n  γος· cf. Plat.";
+
+        // The default splits the hyphenated word and knows no ano teleia
+        assert_eq!(texts(&TokenizerConfig::default(), input), ["ὁ", "λό", "γος", "cf", ".", "Plat", "."]);
+
+        let config = TokenizerConfig {
+            keep_latin: false,
+            rejoin_hyphens: true,
+            punctuation: vec!['.', '·'],
+            ..Default::default()
+        };
+        let tokens = Lexer::new_with_config(archived, &config).tokenize(input);
+        assert_eq!(tokens.iter().map(|t| t.text.as_ref()).collect::<Vec<_>>(), ["ὁ", "λόγος", "·", ".", "."]);
+        // The rejoined word's span still covers both halves
+        assert_eq!(&input[tokens[1].span.start..tokens[1].span.end], "λό-\n  γος");
+        let halves: Vec<&str> = tokens[1].fragments(input).iter().map(|s| &input[s.start..s.end]).collect();
+        assert_eq!(halves, ["λό", "γος"]);
+        assert_eq!(tokens[0].fragments(input), [tokens[0].span]);
+        // Soft hyphens join without a line break; a hyphen inside a line does not
+        assert_eq!(texts(&config, "λό\u{00AD}γος ἀνθρωπο-λόγος"), ["λόγος", "ἀνθρωπο", "λόγος"]);
+        assert_eq!(tokens[2].kind, TokenKind::Punctuation('·'));
+
+        // Letters of scripts left out are skipped
+        let greek_only = TokenizerConfig { scripts: vec![Script::Greek], ..Default::default() };
+        assert_eq!(texts(&greek_only, "λόγοςabc ἄνθρωπος"), ["λόγος", "ἄνθρωπος"]);
+    }
+
+    #[test]
+    fn test_tokenize_stream() {
+        let dict = logos_protocol::Dictionary::default();
+        let bytes = to_bytes::<_, 256>(&dict).unwrap();
+        let archived = unsafe { rkyv::archived_root::<logos_protocol::Dictionary>(&bytes) };
+        let config = TokenizerConfig { rejoin_hyphens: true, ..Default::default() };
+        let lexer = Lexer::new_with_config(archived, &config);
+        let input = "ἐν ἀρχῇ ἦν ὁ λό-\n  γος, καὶ ὁ λόγος ἦν πρὸς τὸν θεόν. ͵αφκγʹ 1.23";
+        let whole: Vec<_> = lexer.tokenize(input).into_iter().map(|t| (t.span, t.text.into_owned(), t.kind)).collect();
+
+        // Cut anywhere, even inside a word or in the hyphen's gap
+        let cuts: Vec<usize> = input.char_indices().map(|(i, _)| i).skip(1).collect();
+        for &cut in &cuts {
+            let chunks = [&input[..cut], &input[cut..]];
+            let streamed: Vec<_> = lexer.tokenize_stream(chunks).map(|t| (t.span, t.text.into_owned(), t.kind)).collect();
+            assert_eq!(streamed, whole, "cut at {}", cut);
+        }
+        // One character at a time
+        let chars: Vec<String> = input.chars().map(String::from).collect();
+        assert_eq!(lexer.tokenize_stream(&chars).count(), whole.len());
+    }
+
+    #[test]
+    fn test_foreign_tokens() {
+        use crate::token::Script;
+
+        let dict = logos_protocol::Dictionary::default();
+        let bytes = to_bytes::<_, 256>(&dict).unwrap();
+        let archived = unsafe { rkyv::archived_root::<logos_protocol::Dictionary>(&bytes) };
+        let input = "λόγος cf. Rep. 327a, 1.23 καὶ 3 Москва";
+        let tokens = Lexer::new(archived).tokenize(input);
+        let kinds: Vec<_> = tokens.iter().map(|t| (t.text.as_ref(), t.kind.clone())).collect();
+        assert_eq!(kinds, [
+            ("λόγος", TokenKind::UnknownWord),
+            ("cf", TokenKind::Foreign(Script::Latin)),
+            (".", TokenKind::Punctuation('.')),
+            ("Rep", TokenKind::Foreign(Script::Latin)),
+            (".", TokenKind::Punctuation('.')),
+            ("327a", TokenKind::Foreign(Script::Latin)),
+            (",", TokenKind::Punctuation(',')),
+            ("1.23", TokenKind::Foreign(Script::Latin)),
+            ("καὶ", TokenKind::UnknownWord),
+            ("3", TokenKind::Numeral(3)),
+            ("Москва", TokenKind::Foreign(Script::Other)),
+        ]);
+        assert_eq!(&input[tokens[5].span.start..tokens[5].span.end], "327a");
+
+        // Without Latin, references go too
+        let config = TokenizerConfig { keep_latin: false, ..Default::default() };
+        let tokens = Lexer::new_with_config(archived, &config).tokenize(input);
+        assert!(!tokens.iter().any(|t| t.kind == TokenKind::Foreign(Script::Latin)));
+    }
+
+    #[test]
+    fn test_editorial_sigla() {
+        use crate::token::{editorial_scope, Editorial, Sigla};
+
+        let dict = logos_protocol::Dictionary::default();
+        let bytes = to_bytes::<_, 256>(&dict).unwrap();
+        let archived = unsafe { rkyv::archived_root::<logos_protocol::Dictionary>(&bytes) };
+        let lexer = Lexer::new(archived);
+
+        let input = "[λόγος] \u{27E8}καὶ\u{27E9} †ἄνθρωπος† {τὸν} … ἔ[λεγε";
+        let tokens = lexer.tokenize(input);
+        assert_eq!(tokens[0].kind, TokenKind::Sigla(Sigla::Open(Editorial::Restored)));
+        assert_eq!(tokens[12].kind, TokenKind::Sigla(Sigla::Lacuna));
+        // Spans stay exact around multi-byte sigla
+        assert!(tokens.iter().all(|t| input[t.span.start..t.span.end] == t.text));
+
+        let scope = editorial_scope(&tokens);
+        assert_eq!(scope[1], Some(Editorial::Restored));
+        assert_eq!(scope[4], Some(Editorial::Supplied));
+        assert_eq!(scope[7], Some(Editorial::Corrupt));
+        assert_eq!(scope[10], Some(Editorial::Deleted));
+        // An unclosed bracket runs to the end, splitting the word it falls in
+        assert_eq!((tokens[13].text.as_ref(), scope[13]), ("ἔ", None));
+        assert_eq!((tokens[15].text.as_ref(), scope[15]), ("λεγε", Some(Editorial::Restored)));
+
+        // A lone crux marks only the next word
+        let tokens = lexer.tokenize("†ἄνθρωπος λόγος");
+        assert_eq!(editorial_scope(&tokens), [None, Some(Editorial::Corrupt), None]);
+    }
 }
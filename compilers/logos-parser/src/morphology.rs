@@ -1,91 +1,425 @@
-use logos_protocol::{Dictionary, MorphFlags, LemmaId};
-use rkyv::Archived;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use logos_protocol::{Dialect, DictionaryView, LanguageProfile, MorphFlags, LemmaId, Period, PronounClass};
+
+use crate::normalize::{compose, fold_accents, fold_case, iota_subscript, is_all_caps, movable_nu, place_sigmas, Normalization};
+use crate::token::{Script, Sigla};
 
 #[derive(Debug, Clone)]
 pub struct MorphAnalysis {
     pub flags: MorphFlags,
     pub lemma_id: Option<LemmaId>,
-    pub debug_msg: String,
+    /// How the analysis was found (see `describe`)
+    pub trace: MatchTrace,
     pub stem: String,
-    pub kind: String,
+    pub kind: &'static str,
+    /// Dialects of the matched form; empty when it is not marked for one
+    pub dialects: Dialect,
+    /// Periods of the matched form; empty when it is not marked for one
+    pub periods: Period,
+    /// How sure the analysis is, 0 to 1: the certainty of the `trace` rule,
+    /// or the disambiguation model's probability of the choice
+    pub confidence: f32,
 }
 
 impl MorphAnalysis {
-    pub fn unknown(debug: String) -> Self {
+    pub fn unknown() -> Self {
         Self {
             flags: MorphFlags::empty(),
             lemma_id: None,
-            debug_msg: debug,
+            trace: MatchTrace::Unknown,
             stem: String::new(),
-            kind: "Unknown".to_string(),
+            kind: "Unknown",
+            dialects: Dialect::empty(),
+            periods: Period::empty(),
+            confidence: 0.0,
+        }
+    }
+
+    /// Renders `trace` for `form` (the token text the analysis came from);
+    /// `headword` is the text of the resolved lemma. Only reports asking for
+    /// it pay for the formatting.
+    #[cfg(feature = "std")]
+    pub fn describe(&self, form: &str, headword: Option<&str>, options: &MatchOptions) -> String {
+        let headword = headword.unwrap_or("?");
+        match self.trace {
+            MatchTrace::Suffix { lowercased, .. } => {
+                let form = if lowercased { fold_case(form) } else { Cow::Borrowed(form) };
+                let compared = options.normalize(&form);
+                let suffix = compared.strip_prefix(self.stem.as_str()).unwrap_or_default();
+                let note = if lowercased { " (lowercased)" } else { "" };
+                format!("Matched! Stem: '{}', Suffix: '{}', Lemma: '{}'{}", self.stem, suffix, headword, note)
+            }
+            MatchTrace::Pronoun { class, lowercased, .. } => {
+                let note = if lowercased { " (lowercased)" } else { "" };
+                format!("{:?} pronoun form '{}'{}", class, self.stem, note)
+            }
+            MatchTrace::Indeclinable => "Lexer matched lemma, but no inflectional rule applied (Indeclinable?)".to_string(),
+            MatchTrace::Recovered => format!("Recovered via raw prefix match against '{}'", headword),
+            MatchTrace::ProperNoun { ending } => format!("Capitalized, not in the dictionary: proper noun guessed from '-{}'", ending),
+            MatchTrace::Punctuation => "Punctuation".to_string(),
+            MatchTrace::Editorial(siglum) => format!("Editorial {:?}", siglum),
+            MatchTrace::Foreign(script) => format!("Foreign ({:?} script), not analyzed", script),
+            MatchTrace::Numeral(value) => format!("Numeral = {}", value),
+            MatchTrace::Unknown => format!("No match found for '{}'", form),
+        }
+    }
+
+    /// The spelling changes the lookup made to `form` (the token text the
+    /// analysis came from), each with the form it left. Only the rules that
+    /// compare spellings (paradigm and pronoun matches, the prefix match,
+    /// a guessed name's ending) normalize; the others report none.
+    pub fn normalizations(&self, form: &str, options: &MatchOptions) -> Vec<(Normalization, String)> {
+        let mut steps = Vec::new();
+        let mut text = form.to_string();
+        let mut apply = |kind, normalize: &dyn Fn(&str) -> Cow<'_, str>| {
+            let next = normalize(&text).into_owned();
+            if next != text {
+                steps.push((kind, next.clone()));
+                text = next;
+            }
+        };
+        match self.trace {
+            MatchTrace::Suffix { lowercased, movable_nu: respelled } | MatchTrace::Pronoun { lowercased, movable_nu: respelled, .. } => {
+                if lowercased {
+                    apply(Normalization::CaseFolding, &fold_case);
+                }
+                apply(Normalization::Nfc, &compose);
+                apply(Normalization::FinalSigma, &place_sigmas);
+                apply(Normalization::IotaSubscript, &iota_subscript);
+                // Lowercased capitals were matched without their accents
+                if options.accent_insensitive || (lowercased && is_all_caps(form)) {
+                    apply(Normalization::AccentStripping, &fold_accents);
+                }
+                if respelled {
+                    apply(Normalization::MovableNu, &|word| movable_nu(word).map_or(Cow::Borrowed(word), Cow::Owned));
+                }
+            }
+            MatchTrace::Recovered => {
+                apply(Normalization::IotaSubscript, &iota_subscript);
+                if options.accent_insensitive {
+                    apply(Normalization::AccentStripping, &fold_accents);
+                }
+            }
+            MatchTrace::ProperNoun { .. } => {
+                apply(Normalization::CaseFolding, &fold_case);
+                apply(Normalization::AccentStripping, &fold_accents);
+            }
+            _ => {}
         }
+        steps
     }
 }
 
-pub fn resolve_morphology(
-    dict: &Archived<Dictionary>, 
-    token_text: &str, 
-    known_lemma_id: Option<u32>
-) -> MorphAnalysis {
-    // 1. Try to find semantic matches via suffix analysis
-    for lemma in dict.lemmas.iter() {
+/// Which rule produced a `MorphAnalysis`. Plain data, so resolving a token
+/// never formats a message; `MorphAnalysis::describe` renders one on demand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchTrace {
+    /// Stem of a lemma plus a paradigm ending; `movable_nu` when it matched
+    /// only with its movable ν dropped or added
+    Suffix { lowercased: bool, movable_nu: bool },
+    /// A full form of a pronoun table
+    Pronoun { class: PronounClass, lowercased: bool, movable_nu: bool },
+    /// The lexer matched the lemma, but no ending applied
+    Indeclinable,
+    /// Prefix match against a lemma, as a last resort
+    Recovered,
+    /// Unlisted capitalized word, case guessed from its ending
+    ProperNoun { ending: &'static str },
+    Punctuation,
+    Editorial(Sigla),
+    Foreign(Script),
+    Numeral(u32),
+    Unknown,
+}
+
+impl MatchTrace {
+    /// How far the rule can be trusted: a paradigm or table match is sure
+    /// (less so after lowercasing), the fallbacks are guesses.
+    pub fn certainty(self) -> f32 {
+        match self {
+            MatchTrace::Suffix { lowercased, .. } | MatchTrace::Pronoun { lowercased, .. } => if lowercased { 0.9 } else { 1.0 },
+            MatchTrace::Indeclinable => 0.7,
+            MatchTrace::ProperNoun { .. } => 0.5,
+            MatchTrace::Recovered => 0.3,
+            MatchTrace::Punctuation | MatchTrace::Editorial(_) | MatchTrace::Foreign(_) | MatchTrace::Numeral(_) => 1.0,
+            MatchTrace::Unknown => 0.0,
+        }
+    }
+}
+
+/// Knobs for how surface forms are compared against the dictionary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MatchOptions {
+    /// Compare forms with accents and breathings folded away.
+    pub accent_insensitive: bool,
+    /// Dialects the text is read as; empty for no preference.
+    pub dialects: Dialect,
+    /// Drop forms marked for other dialects instead of merely ranking them last.
+    pub restrict_dialect: bool,
+    /// Periods the text was written in; empty for no preference.
+    pub periods: Period,
+    /// Drop forms marked for other periods (e.g. load only the Koine paradigm
+    /// sets for the New Testament) instead of ranking them last.
+    pub restrict_period: bool,
+    /// Ancient or Modern Greek: analyses outside its flag schema are dropped.
+    pub profile: LanguageProfile,
+}
+
+impl MatchOptions {
+    /// `text` as compared against the dictionary: iota adscripts as
+    /// subscripts, and without accents when `accent_insensitive`.
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let text = iota_subscript(text);
+        if !self.accent_insensitive {
+            return text;
+        }
+        match text {
+            Cow::Borrowed(text) => fold_accents(text),
+            Cow::Owned(text) => Cow::Owned(fold_accents(&text).into_owned()),
+        }
+    }
+
+    /// Whether forms of `dialects` and `periods` may be reported at all.
+    fn admits(&self, dialects: Dialect, periods: Period) -> bool {
+        (!self.restrict_dialect || dialects.suits(self.dialects))
+            && (!self.restrict_period || periods.suits(self.periods))
+    }
+
+    /// How many analyses to gather when only the best one is kept: with a
+    /// dialect or period preference, an earlier form of another must not win.
+    pub(crate) fn candidates(&self) -> usize {
+        let prefers_dialect = !self.dialects.is_empty() && !self.restrict_dialect;
+        let prefers_period = !self.periods.is_empty() && !self.restrict_period;
+        if prefers_dialect || prefers_period { usize::MAX } else { 1 }
+    }
+
+    /// The first of `found` that suits the preferred dialects and periods;
+    /// failing that, the dialect weighs more than the period.
+    pub(crate) fn best(&self, mut found: Vec<MorphAnalysis>) -> Option<MorphAnalysis> {
+        let (i, _) = found.iter().enumerate()
+            .min_by_key(|(i, a)| (!a.dialects.suits(self.dialects), !a.periods.suits(self.periods), *i))?;
+        Some(found.swap_remove(i))
+    }
+}
+
+/// Case guesses for unlisted names, longest ending first (accent-folded, lowercase).
+const NAME_ENDINGS: &[(&str, MobileFlags)] = &[
+    ("ους", MobileFlags::ACCUSATIVE.union(MobileFlags::MASCULINE).union(MobileFlags::PLURAL)),
+    ("οις", MobileFlags::DATIVE.union(MobileFlags::MASCULINE).union(MobileFlags::PLURAL)),
+    ("αις", MobileFlags::DATIVE.union(MobileFlags::FEMININE).union(MobileFlags::PLURAL)),
+    ("ος", MobileFlags::NOMINATIVE.union(MobileFlags::MASCULINE).union(MobileFlags::SINGULAR)),
+    ("ης", MobileFlags::NOMINATIVE.union(MobileFlags::MASCULINE).union(MobileFlags::SINGULAR)),
+    ("ας", MobileFlags::NOMINATIVE.union(MobileFlags::MASCULINE).union(MobileFlags::SINGULAR)),
+    ("ου", MobileFlags::GENITIVE.union(MobileFlags::MASCULINE).union(MobileFlags::SINGULAR)),
+    ("ον", MobileFlags::ACCUSATIVE.union(MobileFlags::MASCULINE).union(MobileFlags::SINGULAR)),
+    ("ην", MobileFlags::ACCUSATIVE.union(MobileFlags::SINGULAR)),
+    ("αν", MobileFlags::ACCUSATIVE.union(MobileFlags::SINGULAR)),
+    ("ει", MobileFlags::DATIVE.union(MobileFlags::SINGULAR)),
+    ("ων", MobileFlags::GENITIVE.union(MobileFlags::PLURAL)),
+    ("αι", MobileFlags::NOMINATIVE.union(MobileFlags::FEMININE).union(MobileFlags::PLURAL)),
+    ("οι", MobileFlags::NOMINATIVE.union(MobileFlags::MASCULINE).union(MobileFlags::PLURAL)),
+    ("ες", MobileFlags::NOMINATIVE.union(MobileFlags::PLURAL)),
+    ("ῳ", MobileFlags::DATIVE.union(MobileFlags::MASCULINE).union(MobileFlags::SINGULAR)),
+    ("ῃ", MobileFlags::DATIVE.union(MobileFlags::FEMININE).union(MobileFlags::SINGULAR)),
+    ("ᾳ", MobileFlags::DATIVE.union(MobileFlags::FEMININE).union(MobileFlags::SINGULAR)),
+    ("α", MobileFlags::NOMINATIVE.union(MobileFlags::FEMININE).union(MobileFlags::SINGULAR)),
+    ("η", MobileFlags::NOMINATIVE.union(MobileFlags::FEMININE).union(MobileFlags::SINGULAR)),
+];
+
+/// Treats an unlisted capitalized word (Σωκράτης, Ἀθῆναι) as a proper noun,
+/// guessing case, number and (where the ending tells) gender. Words shorter
+/// than three letters are left alone, since those are mostly abbreviations.
+pub fn guess_proper_noun(token_text: &str) -> Option<MorphAnalysis> {
+    let starts_upper = token_text.chars().next().is_some_and(char::is_uppercase);
+    if !starts_upper || token_text.chars().count() < 3 || !token_text.chars().all(char::is_alphabetic) {
+        return None;
+    }
+
+    let lower = token_text.to_lowercase();
+    let folded = fold_accents(&lower);
+    let (ending, guess) = NAME_ENDINGS.iter().find(|(ending, _)| folded.ends_with(ending))?;
+    Some(MorphAnalysis {
+        flags: MobileFlags::NOUN | *guess,
+        lemma_id: None,
+        trace: MatchTrace::ProperNoun { ending },
+        stem: folded[..folded.len() - ending.len()].to_string(),
+        kind: "ProperNoun",
+        dialects: Dialect::empty(),
+        periods: Period::empty(),
+        confidence: MatchTrace::ProperNoun { ending }.certainty(),
+    })
+}
+
+/// Every paradigm-licensed analysis of the token (deduplicated by lemma and
+/// flags), in dictionary order, capped at `limit`. Pronoun forms come first:
+/// they are full-form matches, so `known_lemma_id` does not restrict them.
+/// Forms whose lemma and paradigm share no dialect or period are never built,
+/// and with `restrict_dialect`/`restrict_period` neither are forms outside
+/// the wanted ones. Flags the `profile` lacks (the Modern dative) never match.
+/// The word is composed (NFC) and a misplaced sigma put right first, and a
+/// word with no analysis is tried again with its movable ν dropped or added
+/// (λέγουσιν, λέγουσι).
+/// The stem left by an ending must be the lemma's whole text, or, for a
+/// lemma stored as its headword (λόγος), the headword minus another ending
+/// of the same paradigm; a lemma naming its paradigm takes no other one.
+/// An archive with a surface-form table is probed instead of scanned; the
+/// table holds stems as stored, so it does not reproduce the scan's
+/// matches through a stored headword.
+pub fn suffix_analyses(
+    dict: &(impl DictionaryView + ?Sized),
+    token_text: &str,
+    known_lemma_id: Option<u32>,
+    options: &MatchOptions,
+    limit: usize,
+) -> Vec<MorphAnalysis> {
+    if limit == 0 {
+        return Vec::new();
+    }
+    let composed = compose(token_text);
+    let written = place_sigmas(&composed);
+    let text = options.normalize(&written);
+    let found = analyses_of(dict, &text, known_lemma_id, options, limit);
+    match movable_nu(&text) {
+        Some(variant) if found.is_empty() => {
+            let mut found = analyses_of(dict, &variant, known_lemma_id, options, limit);
+            for analysis in &mut found {
+                if let MatchTrace::Suffix { movable_nu, .. } | MatchTrace::Pronoun { movable_nu, .. } = &mut analysis.trace {
+                    *movable_nu = true;
+                }
+            }
+            found
+        }
+        _ => found,
+    }
+}
+
+/// `suffix_analyses` of an already normalized `text`.
+fn analyses_of(
+    dict: &(impl DictionaryView + ?Sized),
+    text: &str,
+    known_lemma_id: Option<u32>,
+    options: &MatchOptions,
+    limit: usize,
+) -> Vec<MorphAnalysis> {
+    let mut found: Vec<MorphAnalysis> = Vec::new();
+
+    for pronoun in dict.pronouns() {
+        let class = pronoun.class;
+        for (flags_bits, form) in pronoun.forms.iter() {
+            if options.normalize(form) != text {
+                continue;
+            }
+            let flags = MobileFlags::from_bits_truncate(flags_bits) | class.flags();
+            if !options.profile.admits(flags) {
+                continue;
+            }
+            let lemma_id = Some(pronoun.lemma);
+            if found.iter().any(|a| a.lemma_id == lemma_id && a.flags == flags) {
+                continue;
+            }
+            found.push(MorphAnalysis {
+                flags,
+                lemma_id,
+                trace: MatchTrace::Pronoun { class, lowercased: false, movable_nu: false },
+                stem: text.to_string(),
+                kind: "Word",
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                confidence: 1.0,
+            });
+            if found.len() >= limit {
+                return found;
+            }
+        }
+    }
+
+    // A pre-expanded table answers in one probe; it lists forms as written,
+    // so accent-folded matching still scans
+    if let Some(forms) = dict.surface_forms(text).filter(|_| !options.accent_insensitive) {
+        for form in forms {
+            if known_lemma_id.is_some_and(|id| form.lemma.0 != id) || !options.admits(form.dialects, form.periods) {
+                continue;
+            }
+            let flags = MobileFlags::from_bits_truncate(form.flags);
+            let lemma_id = Some(form.lemma);
+            if !options.profile.admits(flags) || found.iter().any(|a| a.lemma_id == lemma_id && a.flags == flags) {
+                continue;
+            }
+            found.push(MorphAnalysis {
+                flags,
+                lemma_id,
+                trace: MatchTrace::Suffix { lowercased: false, movable_nu: false },
+                stem: text.len().checked_sub(form.ending_len).and_then(|at| text.get(..at)).unwrap_or(text).to_string(),
+                kind: "Word",
+                dialects: form.dialects,
+                periods: form.periods,
+                confidence: 1.0,
+            });
+            if found.len() >= limit {
+                break;
+            }
+        }
+        return found;
+    }
+
+    for lemma in dict.lemmas() {
         // Optimization: If we know the lemma ID, only check that one
         if let Some(id) = known_lemma_id {
             if lemma.id.0 != id { continue; }
         }
+        let lemma_text = options.normalize(lemma.text);
 
-        for paradigm in dict.paradigms.iter() {
+        for paradigm in dict.paradigms() {
+            if !lemma.inflects_by(paradigm.id) {
+                continue;
+            }
+            let Some(dialects) = lemma.dialects.combine(paradigm.dialects) else { continue };
+            let Some(periods) = lemma.periods.combine(paradigm.periods) else { continue };
+            if !options.admits(dialects, periods) {
+                continue;
+            }
             for (flags_bits, rule_suffix) in paradigm.endings.iter() {
-                let suffix_str = rule_suffix.as_str();
-                if token_text.ends_with(suffix_str) {
-                    let stem_len = token_text.len() - suffix_str.len();
-                    let candidate_stem = &token_text[..stem_len];
-                    
-                    if lemma.text.starts_with(candidate_stem) {
-                        let flags = MobileFlags::from_bits_truncate(*flags_bits);
-                        
+                let suffix = options.normalize(rule_suffix);
+                let suffix_str = suffix.as_ref();
+                if text.ends_with(suffix_str) {
+                    let stem_len = text.len() - suffix_str.len();
+                    let candidate_stem = &text[..stem_len];
+                    let headword_ending = |rest: &str| paradigm.endings.iter().any(|(_, ending)| options.normalize(ending) == rest);
+                    let whole_stem = lemma_text.strip_prefix(candidate_stem).is_some_and(|rest| rest.is_empty() || headword_ending(rest));
+
+                    if !candidate_stem.is_empty() && whole_stem {
+                        let flags = MobileFlags::from_bits_truncate(flags_bits);
+                        if !options.profile.admits(flags) {
+                            continue;
+                        }
+                        let lemma_id = Some(lemma.id);
+                        if found.iter().any(|a| a.lemma_id == lemma_id && a.flags == flags) {
+                            continue;
+                        }
+
                         // We found a match!
-                        return MorphAnalysis {
+                        found.push(MorphAnalysis {
                             flags,
-                            lemma_id: Some(LemmaId(lemma.id.0)),
-                            debug_msg: format!("Matched! Stem: '{}', Suffix: '{}', Lemma: '{}'", candidate_stem, suffix_str, lemma.text),
+                            lemma_id,
+                            trace: MatchTrace::Suffix { lowercased: false, movable_nu: false },
                             stem: candidate_stem.to_string(),
-                            kind: "Word".to_string(), // Or Word(Recovered) if known_lemma_id was None? 
-                                                      // Let's keep it simple "Word"
-                        };
+                            kind: "Word",
+                            dialects,
+                            periods,
+                            confidence: 1.0,
+                        });
+                        if found.len() >= limit {
+                            return found;
+                        }
                     }
                 }
             }
         }
     }
-
-    // 2. If no match found but we had a known ID (Lexer found it exact match or prefix)
-    // We should still return that ID but maybe empty morphology?
-    if let Some(id) = known_lemma_id {
-        return MorphAnalysis {
-            flags: MorphFlags::empty(),
-            lemma_id: Some(LemmaId(id)),
-            debug_msg: "Lexer matched lemma, but no inflectional rule applied (Indeclinable?)".to_string(),
-            stem: token_text.to_string(),
-            kind: "Word".to_string(),
-        };
-    }
-
-    // 3. Last Resort: Check if we can recover unknown words by simple prefix match 
-    // (This was part of the recover logic in WASM)
-    // Actually, robust resolve above usually handles "recover" if suffix matches.
-    // If not, we check for raw lemma starts_with
-    if let Some(lemma) = dict.lemmas.iter().find(|l| token_text.starts_with(l.text.as_str()) || l.text.starts_with(token_text)) {
-         return MorphAnalysis {
-            flags: MorphFlags::empty(),
-            lemma_id: Some(LemmaId(lemma.id.0)),
-            debug_msg: format!("Recovered via raw prefix match against '{}'", lemma.text),
-            stem: token_text.to_string(),
-            kind: "Word (Recovered)".to_string(),
-        };
-    }
-
-    MorphAnalysis::unknown(format!("No match found for '{}'", token_text))
+    found
 }
 
 // Helper alias to avoid import issues if names collide
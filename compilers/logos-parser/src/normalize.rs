@@ -0,0 +1,197 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use unicode_normalization::UnicodeNormalization;
+
+/// Combining marks removed by accent folding: acute, grave, circumflex
+/// (perispomeni) and both breathings. Diaeresis and iota subscript are kept
+/// because they change how the word is segmented/spelled, not just its pitch.
+fn is_foldable_mark(c: char) -> bool {
+    matches!(c, '\u{0300}' | '\u{0301}' | '\u{0342}' | '\u{0313}' | '\u{0314}')
+}
+
+/// Strips accents and breathings so "ἄνθρωπος", "άνθρωπος" and "ανθρωπος"
+/// compare equal. Returns the input untouched when it is already plain.
+pub fn fold_accents(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let folded: String = text.nfd().filter(|c| !is_foldable_mark(*c)).nfc().collect();
+    if folded == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(folded)
+    }
+}
+
+/// Composes `text` (Unicode NFC), so a word typed with combining marks
+/// (α + U+0301) finds the precomposed ά of the dictionary. Borrowed when
+/// it already is composed.
+pub fn compose(text: &str) -> Cow<'_, str> {
+    if unicode_normalization::is_nfc(text) {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(text.nfc().collect())
+    }
+}
+
+fn takes_adscript(c: char) -> bool {
+    matches!(c.nfd().next(), Some('ω' | 'η'))
+}
+
+/// Writes an iota adscript (ωι, ηι) as the subscript it stands for (ῳ, ῃ),
+/// so "ὠιδή" finds "ᾠδή". An adscript after α cannot be told from the
+/// diphthong αι and is left alone.
+pub fn iota_subscript(text: &str) -> Cow<'_, str> {
+    let adscript = |(a, b): (char, char)| b == 'ι' && takes_adscript(a);
+    if !text.chars().zip(text.chars().skip(1)).any(adscript) {
+        return Cow::Borrowed(text);
+    }
+    let mut written = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if takes_adscript(c) && chars.peek() == Some(&'ι') {
+            chars.next();
+            written.extend(c.nfd().chain(['\u{0345}']).nfc());
+        } else {
+            written.push(c);
+        }
+    }
+    Cow::Owned(written)
+}
+
+/// Capitals with an iota (ᾈ, ᾼ) are titlecase, not uppercase, to Unicode.
+fn is_capital(c: char) -> bool {
+    c.is_uppercase() || c.to_lowercase().ne([c])
+}
+
+/// Lowercase `text` for dictionary lookup; borrowed when it has no
+/// capitals. Accented capitals lowercase with their marks (Ἐν -> ἐν, ᾈ -> ᾀ)
+/// and a word-final Σ becomes ς (ΛΟΓΟΣ -> λογος).
+pub fn fold_case(text: &str) -> Cow<'_, str> {
+    if text.chars().any(is_capital) {
+        Cow::Owned(text.to_lowercase())
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// True for a word of two or more letters written all in capitals, which
+/// by convention drop their accents (ΛΟΓΟΣ for λόγος).
+pub fn is_all_caps(text: &str) -> bool {
+    let mut letters = 0;
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        if !is_capital(c) {
+            return false;
+        }
+        letters += 1;
+    }
+    letters >= 2
+}
+
+/// Puts each sigma in its place: final ς at the end of a word, σ anywhere
+/// else ("λόγοσ" and "ςοφός" are typing slips, not other words).
+pub fn place_sigmas(text: &str) -> Cow<'_, str> {
+    let mut chars = text.chars().peekable();
+    let mut placed = String::with_capacity(text.len());
+    let mut changed = false;
+    while let Some(c) = chars.next() {
+        let last = !chars.peek().is_some_and(|next| next.is_alphabetic() || is_foldable_mark(*next));
+        let fixed = match c {
+            'σ' if last => 'ς',
+            'ς' if !last => 'σ',
+            c => c,
+        };
+        changed |= fixed != c;
+        placed.push(fixed);
+    }
+    if changed { Cow::Owned(placed) } else { Cow::Borrowed(text) }
+}
+
+/// The other spelling of a word with a movable ν: λέγουσιν <-> λέγουσι,
+/// ἔλεγεν <-> ἔλεγε. Only the endings that take it (-σι, -ε) qualify, so
+/// λόγον has none; a word like ἐν that merely looks the part gets one too,
+/// which is why lookups try this only when the word itself fails.
+pub fn movable_nu(word: &str) -> Option<String> {
+    let folded = fold_accents(word);
+    if let Some(without) = word.strip_suffix('ν') {
+        let rest = folded.strip_suffix('ν')?;
+        return (rest.ends_with("σι") || rest.ends_with('ε')).then(|| without.to_string());
+    }
+    (folded.ends_with("σι") || folded.ends_with('ε')).then(|| format!("{}ν", word))
+}
+
+/// A change the lookup makes to a word's spelling, in the order they are
+/// applied (see `MorphAnalysis::normalizations`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Normalization {
+    /// Capitals lowercased (`fold_case`)
+    CaseFolding,
+    /// Combining marks composed (`compose`)
+    Nfc,
+    /// σ and ς put in their places (`place_sigmas`)
+    FinalSigma,
+    /// Iota adscript written as a subscript (`iota_subscript`)
+    IotaSubscript,
+    /// Accents and breathings stripped (`fold_accents`)
+    AccentStripping,
+    /// Movable ν dropped or added (`movable_nu`)
+    MovableNu,
+}
+
+impl Normalization {
+    /// "case_folding", "nfc", "final_sigma", "iota_subscript",
+    /// "accent_stripping" or "movable_nu".
+    pub fn name(self) -> &'static str {
+        match self {
+            Normalization::CaseFolding => "case_folding",
+            Normalization::Nfc => "nfc",
+            Normalization::FinalSigma => "final_sigma",
+            Normalization::IotaSubscript => "iota_subscript",
+            Normalization::AccentStripping => "accent_stripping",
+            Normalization::MovableNu => "movable_nu",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_accents() {
+        // Polytonic (smooth breathing + acute), monotonic tonos, and plain all fold together
+        assert_eq!(fold_accents("ἄνθρωπος"), "ανθρωπος");
+        assert_eq!(fold_accents("άνθρωπος"), "ανθρωπος");
+        assert!(matches!(fold_accents("ανθρωπος"), Cow::Borrowed(_)));
+
+        // Iota subscript survives (ᾠδή -> ῳδη)
+        assert_eq!(fold_accents("ᾠδή"), "ῳδη");
+    }
+
+    #[test]
+    fn test_orthographic_variants() {
+        assert_eq!(place_sigmas("λόγοσ"), "λόγος");
+        assert_eq!(place_sigmas("ςοφόσ λόγος"), "σοφός λόγος");
+        assert!(matches!(place_sigmas("σοφός"), Cow::Borrowed(_)));
+
+        assert_eq!(movable_nu("λέγουσιν").as_deref(), Some("λέγουσι"));
+        assert_eq!(movable_nu("λέγουσι").as_deref(), Some("λέγουσιν"));
+        assert_eq!(movable_nu("ἔλεγε").as_deref(), Some("ἔλεγεν"));
+        assert_eq!(movable_nu("λόγον"), None);
+        assert_eq!(movable_nu("λόγος"), None);
+
+        assert_eq!(fold_case("ΛΟΓΟΣ"), "λογος");
+        assert_eq!(fold_case("Ἐν"), "ἐν");
+        assert_eq!(fold_case("ᾈδης"), "ᾀδης");
+        assert!(matches!(fold_case("λόγος"), Cow::Borrowed(_)));
+        assert!(is_all_caps("ΛΟΓΟΣ") && !is_all_caps("Λόγος") && !is_all_caps("Ὁ"));
+
+        assert_eq!(iota_subscript("ὠιδή"), "ᾠδή");
+        assert_eq!(iota_subscript("τῶι λόγωι"), "τῷ λόγῳ");
+        assert_eq!(iota_subscript("τῆι"), "τῇ");
+        assert!(matches!(iota_subscript("παιδί"), Cow::Borrowed(_)));
+
+        assert_eq!(compose("λο\u{301}γος"), "λόγος");
+        assert!(matches!(compose("λόγος"), Cow::Borrowed(_)));
+    }
+}
@@ -1,11 +1,100 @@
 use nom::{
     branch::alt,
-    bytes::complete::{take_while1},
-    character::complete::{char, multispace0},
+    bytes::complete::{take_while, take_while1},
+    character::complete::multispace0,
     combinator::{map},
+    error::{Error, ErrorKind},
     IResult,
 };
-use crate::token::Span;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use once_cell::race::OnceBox;
+
+use crate::token::{Editorial, Script, Sigla, Span};
+
+/// What the tokenizer treats as words and punctuation. The default keeps the
+/// built-in behaviour: letters of any script form words, five punctuation
+/// marks, no rejoining.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TokenizerConfig {
+    /// Scripts whose letters make up words; other letters are skipped.
+    pub scripts: Vec<Script>,
+    /// Keep words written entirely in Latin script (sigla, Latin glosses in a
+    /// commentary) and references such as "327a" as `Foreign` tokens; when
+    /// false they are dropped.
+    pub keep_latin: bool,
+    /// Rejoin a word hyphenated across a line break ("λό-" / "γος"), as
+    /// OCR of printed editions leaves them, and drop soft hyphens inside
+    /// a word.
+    pub rejoin_hyphens: bool,
+    /// Characters reported as punctuation (add '·' for the ano teleia).
+    pub punctuation: Vec<char>,
+}
+
+impl Default for TokenizerConfig {
+    fn default() -> Self {
+        Self {
+            scripts: vec![Script::Greek, Script::Latin, Script::Other],
+            keep_latin: true,
+            rejoin_hyphens: false,
+            punctuation: vec!['.', ',', ';', '?', '!'],
+        }
+    }
+}
+
+impl TokenizerConfig {
+    pub(crate) fn is_word_char(&self, c: char) -> bool {
+        Script::of(c).is_some_and(|s| self.scripts.contains(&s))
+    }
+
+    /// A word character, or a combining mark of a decomposed letter
+    /// (ο + U+0301), which belongs to the word it follows.
+    fn continues_word(&self, c: char) -> bool {
+        self.is_word_char(c) || ('\u{0300}'..='\u{036F}').contains(&c)
+    }
+
+    /// The hyphen, line break and indentation between the halves of a
+    /// word split at the end of a line, if `input` starts with one. A soft
+    /// hyphen needs no line break after it.
+    fn line_break_hyphen<'a>(&self, input: &'a str) -> Option<&'a str> {
+        let rest = input.strip_prefix(['-', '\u{2010}', '\u{00AD}'])?;
+        let gap = rest.len() - rest.trim_start().len();
+        let (gap, next) = rest.split_at(gap);
+        let soft = input.starts_with('\u{00AD}');
+        (((soft && gap.is_empty()) || gap.contains('\n')) && next.starts_with(|c| self.is_word_char(c)))
+            .then(|| &input[..input.len() - next.len()])
+    }
+
+    /// Where `text` can be cut without splitting a token: just after its
+    /// last whitespace, unless that whitespace follows a hyphen that more
+    /// text could rejoin. 0 when there is no such place yet.
+    pub(crate) fn safe_cut(&self, text: &str) -> usize {
+        let mut end = text.len();
+        while let Some((at, c)) = text[..end].char_indices().rfind(|(_, c)| c.is_whitespace()) {
+            let run_start = text[..at].trim_end().len();
+            if !(self.rejoin_hyphens && text[..run_start].ends_with(['-', '\u{2010}', '\u{00AD}'])) {
+                return at + c.len_utf8();
+            }
+            end = run_start;
+        }
+        0
+    }
+}
+
+/// Script of a word that has no Greek letter in it (the first letter's).
+fn foreign_script(word: &str) -> Option<Script> {
+    let mut scripts = word.chars().filter_map(Script::of);
+    let first = scripts.next()?;
+    (first != Script::Greek && scripts.all(|s| s != Script::Greek)).then_some(first)
+}
+
+static DEFAULT_CONFIG: OnceBox<TokenizerConfig> = OnceBox::new();
+
+pub(crate) fn default_config() -> &'static TokenizerConfig {
+    DEFAULT_CONFIG.get_or_init(|| Box::new(TokenizerConfig::default()))
+}
 
 /// Predicate to define what constitutes a "Greek Word" character.
 /// Includes Standard Greek and Extended Greek (Polytonic).
@@ -20,22 +109,175 @@ fn is_greek_alphabetic(c: char) -> bool {
     }
 }
 
+/// Keraia (ʹ) closes an alphabetic numeral; NFC turns U+0374 into U+02B9.
+fn is_keraia(c: char) -> bool {
+    matches!(c, '\u{0374}' | '\u{02B9}')
+}
+
+/// Lower numeral sign (͵): the following letter counts thousands.
+const LOWER_NUMERAL_SIGN: char = '\u{0375}';
+
+/// Milesian value of a Greek letter used as a numeral (either case,
+/// including the archaic stigma, koppa and sampi).
+fn greek_digit(c: char) -> Option<u32> {
+    let value = match c.to_lowercase().next()? {
+        'α' => 1, 'β' => 2, 'γ' => 3, 'δ' => 4, 'ε' => 5, 'ϛ' | 'ς' => 6, 'ζ' => 7, 'η' => 8, 'θ' => 9,
+        'ι' => 10, 'κ' => 20, 'λ' => 30, 'μ' => 40, 'ν' => 50, 'ξ' => 60, 'ο' => 70, 'π' => 80, 'ϟ' | 'ϙ' => 90,
+        'ρ' => 100, 'σ' => 200, 'τ' => 300, 'υ' => 400, 'φ' => 500, 'χ' => 600, 'ψ' => 700, 'ω' => 800, 'ϡ' => 900,
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Alphabetic numeral such as "ρκγʹ" (123) or "͵αφκγʹ" (1523). Letters must
+/// end in a keraia unless a thousands sign marks them, so ordinary words
+/// never parse as numbers.
+fn greek_numeral(input: &str) -> IResult<&str, u32> {
+    let fail = || nom::Err::Error(Error::new(input, ErrorKind::Digit));
+    let mut chars = input.char_indices().peekable();
+    let mut value = 0u32;
+    let mut marked = false;
+    let mut digits = 0;
+    let mut end = 0;
+
+    while let Some(&(i, c)) = chars.peek() {
+        if c == LOWER_NUMERAL_SIGN {
+            chars.next();
+            let (j, d) = chars.next().ok_or_else(fail)?;
+            let thousands = greek_digit(d).filter(|v| *v < 10).ok_or_else(fail)?;
+            value = value.saturating_add(thousands * 1000);
+            marked = true;
+            end = j + d.len_utf8();
+        } else if let Some(v) = greek_digit(c) {
+            chars.next();
+            value = value.saturating_add(v);
+            digits += 1;
+            end = i + c.len_utf8();
+        } else {
+            break;
+        }
+    }
+
+    match chars.peek() {
+        Some(&(i, c)) if is_keraia(c) && (digits > 0 || marked) => end = i + c.len_utf8(),
+        _ if marked && digits == 0 => {}
+        _ => return Err(fail()),
+    }
+    // "αʹβ" is not a numeral followed by a word
+    if input[end..].chars().next().is_some_and(is_greek_alphabetic) {
+        return Err(fail());
+    }
+    Ok((&input[end..], value))
+}
+
+/// Arabic digits; values past u32::MAX saturate.
+fn arabic_numeral(input: &str) -> IResult<&str, u32> {
+    let (rest, digits) = take_while1(|c: char| c.is_ascii_digit())(input)?;
+    let value = digits.bytes().fold(0u32, |acc, d| acc.saturating_mul(10).saturating_add((d - b'0') as u32));
+    Ok((rest, value))
+}
+
+/// Reference to a passage: "1.23", "3:16", Stephanus "327a", Bekker
+/// "1094b15". Plain digits are numerals, not references.
+fn citation(input: &str) -> IResult<&str, &str> {
+    let fail = || nom::Err::Error(Error::new(input, ErrorKind::Digit));
+    let bytes = input.as_bytes();
+    let digits = |from: usize| from + bytes[from..].iter().take_while(|b| b.is_ascii_digit()).count();
+
+    let mut end = digits(0);
+    if end == 0 {
+        return Err(fail());
+    }
+    let mut reference = false;
+    while end + 1 < bytes.len() && matches!(bytes[end], b'.' | b':') && bytes[end + 1].is_ascii_digit() {
+        end = digits(end + 1);
+        reference = true;
+    }
+    if bytes.get(end).is_some_and(u8::is_ascii_lowercase) {
+        end = digits(end + 1);
+        reference = true;
+    }
+    if !reference || input[end..].chars().next().is_some_and(char::is_alphabetic) {
+        return Err(fail());
+    }
+    Ok((&input[end..], &input[..end]))
+}
+
+/// Editorial sigla of critical editions. Several bracket styles are in use
+/// for supplements (⟨⟩, 〈〉 and plain <>); "..." and "***" mark lacunae.
+fn sigla(input: &str) -> IResult<&str, Sigla> {
+    for lacuna in ["...", "***", "\u{2026}"] {
+        if let Some(rest) = input.strip_prefix(lacuna) {
+            return Ok((rest, Sigla::Lacuna));
+        }
+    }
+    let mut chars = input.chars();
+    let siglum = match chars.next() {
+        Some('[') => Sigla::Open(Editorial::Restored),
+        Some(']') => Sigla::Close(Editorial::Restored),
+        Some('\u{27E8}' | '\u{2329}' | '\u{3008}' | '<') => Sigla::Open(Editorial::Supplied),
+        Some('\u{27E9}' | '\u{232A}' | '\u{3009}' | '>') => Sigla::Close(Editorial::Supplied),
+        Some('{') => Sigla::Open(Editorial::Deleted),
+        Some('}') => Sigla::Close(Editorial::Deleted),
+        Some('†') => Sigla::Crux,
+        _ => return Err(nom::Err::Error(Error::new(input, ErrorKind::Char))),
+    };
+    Ok((chars.as_str(), siglum))
+}
+
 #[derive(Debug, Clone)]
 pub enum RawToken<'a> {
     Word(&'a str),
+    /// A word split by a hyphen and a line break, as written
+    Hyphenated(&'a str),
+    /// A word without Greek letters, or a passage reference
+    Foreign(Script),
     Punct(char),
+    Numeral(u32),
+    Sigla(Sigla),
+}
+
+pub fn parse_with_spans(original_input: &str) -> Vec<(Span, RawToken<'_>)> {
+    parse_with_config(original_input, default_config())
 }
 
-pub fn parse_with_spans(original_input: &str) -> Vec<(Span, RawToken)> {
+pub fn parse_with_config<'a>(original_input: &'a str, config: &TokenizerConfig) -> Vec<(Span, RawToken<'a>)> {
+    // A letter, then letters and their marks
+    let letters = |input: &'a str| -> IResult<&'a str, &'a str> {
+        take_while1(|c| config.is_word_char(c))(input)?;
+        take_while(|c| config.continues_word(c))(input)
+    };
+    let word = |input: &'a str| -> IResult<&'a str, RawToken<'a>> {
+        let (mut rest, _) = letters(input)?;
+        let mut hyphenated = false;
+        while let Some(gap) = config.rejoin_hyphens.then(|| config.line_break_hyphen(rest)).flatten() {
+            let (next, _) = letters(&rest[gap.len()..])?;
+            rest = next;
+            hyphenated = true;
+        }
+        let text = &input[..input.len() - rest.len()];
+        let token = if hyphenated {
+            RawToken::Hyphenated(text)
+        } else {
+            match foreign_script(text) {
+                Some(script) => RawToken::Foreign(script),
+                None => RawToken::Word(text),
+            }
+        };
+        Ok((rest, token))
+    };
+    let punct = |input: &'a str| -> IResult<&'a str, RawToken<'a>> {
+        match input.chars().next() {
+            Some(c) if config.punctuation.contains(&c) => Ok((&input[c.len_utf8()..], RawToken::Punct(c))),
+            _ => Err(nom::Err::Error(Error::new(input, ErrorKind::Char))),
+        }
+    };
+
     let mut input = original_input;
     let mut result = Vec::new();
 
-    loop {
-        // 1. Skip whitespace
-        let (next_input, _) = match multispace0::<&str, nom::error::Error<&str>>(input) {
-            Ok(res) => res,
-            Err(_) => break,
-        };
+    // 1. Skip whitespace
+    while let Ok((next_input, _)) = multispace0::<&str, nom::error::Error<&str>>(input) {
         input = next_input;
 
         if input.is_empty() {
@@ -44,15 +286,18 @@ pub fn parse_with_spans(original_input: &str) -> Vec<(Span, RawToken)> {
 
         // 2. Try to match a token
         let parse_res: IResult<&str, RawToken> = alt((
-            map(take_while1(is_greek_alphabetic), RawToken::Word),
-            map(char('.'), |_| RawToken::Punct('.')),
-            map(char(','), |_| RawToken::Punct(',')),
-            map(char(';'), |_| RawToken::Punct(';')),
-            map(char('?'), |_| RawToken::Punct('?')),
-            map(char('!'), |_| RawToken::Punct('!')),
+            map(citation, |_| RawToken::Foreign(Script::Latin)),
+            map(arabic_numeral, RawToken::Numeral),
+            map(greek_numeral, RawToken::Numeral),
+            map(sigla, RawToken::Sigla),
+            word,
+            punct,
         ))(input);
 
         match parse_res {
+            Ok((next_input, RawToken::Foreign(Script::Latin))) if !config.keep_latin => {
+                input = next_input;
+            }
             Ok((next_input, token)) => {
                 // Calculate span
                 // We know 'token' came from 'input', which came from 'original_input'
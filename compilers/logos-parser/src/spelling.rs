@@ -0,0 +1,164 @@
+//! Near-miss correction for words the dictionary does not know. Distances
+//! weigh what students actually get wrong: a misplaced accent or breathing
+//! costs little, a wrong vowel more, a wrong consonant most.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use logos_protocol::{DictionaryView, LemmaId};
+use unicode_normalization::UnicodeNormalization;
+
+/// Same letter, different accent, breathing, iota subscript or case
+pub const MARK_COST: u32 = 1;
+/// One vowel for another (ο/ω, ε/η, ει/ι spellings)
+pub const VOWEL_COST: u32 = 2;
+/// A letter added or dropped
+pub const INDEL_COST: u32 = 3;
+/// Any other substitution
+pub const CONSONANT_COST: u32 = 4;
+
+/// A dictionary form close to a misspelled word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub form: String,
+    /// Dictionary-local ID of the lemma the form belongs to
+    pub lemma_id: LemmaId,
+    /// Weighted edit distance from the word
+    pub cost: u32,
+}
+
+/// A letter without its diacritics, lowercased.
+fn base(c: char) -> char {
+    let first = c.nfd().next().unwrap_or(c);
+    first.to_lowercase().next().unwrap_or(first)
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'α' | 'ε' | 'η' | 'ι' | 'ο' | 'υ' | 'ω')
+}
+
+fn substitution(a: char, b: char) -> u32 {
+    if a == b {
+        return 0;
+    }
+    let (a, b) = (base(a), base(b));
+    if a == b {
+        MARK_COST
+    } else if is_vowel(a) && is_vowel(b) {
+        VOWEL_COST
+    } else {
+        CONSONANT_COST
+    }
+}
+
+/// Weighted edit distance between `a` and `b`, or `None` once it exceeds
+/// `max_cost`.
+pub fn distance(a: &str, b: &str, max_cost: u32) -> Option<u32> {
+    let a: Vec<char> = a.nfc().collect();
+    let b: Vec<char> = b.nfc().collect();
+    if a.len().abs_diff(b.len()) as u32 * INDEL_COST > max_cost {
+        return None;
+    }
+    let mut row: Vec<u32> = (0..=b.len() as u32).map(|j| j * INDEL_COST).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = (i as u32 + 1) * INDEL_COST;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = (diagonal + substitution(ca, cb))
+                .min(row[j] + INDEL_COST)
+                .min(row[j + 1] + INDEL_COST);
+            diagonal = row[j + 1];
+            row[j + 1] = cost;
+        }
+        if row.iter().all(|&d| d > max_cost) {
+            return None;
+        }
+    }
+    row.last().copied().filter(|&d| d <= max_cost)
+}
+
+/// Up to `limit` dictionary forms within `max_cost` of `word`, cheapest
+/// first. Forms are the headwords and every paradigm ending on each
+/// headword's stem (the headword minus the longest ending it carries), so
+/// both stem-stored and full-word lemmas yield their inflections. Scans the
+/// whole dictionary: meant for the odd unknown word, not every token.
+pub fn suggestions(dict: &(impl DictionaryView + ?Sized), word: &str, max_cost: u32, limit: usize) -> Vec<Suggestion> {
+    let mut found: Vec<Suggestion> = Vec::new();
+    if limit == 0 {
+        return found;
+    }
+    let mut consider = |form: String, lemma_id: LemmaId| {
+        if found.iter().any(|s| s.form == form) {
+            return;
+        }
+        if let Some(cost) = distance(word, &form, max_cost) {
+            found.push(Suggestion { form, lemma_id, cost });
+        }
+    };
+
+    for lemma in dict.lemmas() {
+        consider(lemma.text.to_string(), lemma.id);
+        for paradigm in dict.paradigms().filter(|p| lemma.inflects_by(p.id)) {
+            let ending = paradigm.endings.iter()
+                .map(|(_, ending)| ending)
+                .filter(|ending| lemma.text.ends_with(ending))
+                .max_by_key(|ending| ending.len())
+                .unwrap_or("");
+            let stem = &lemma.text[..lemma.text.len() - ending.len()];
+            for (_, ending) in paradigm.endings.iter() {
+                consider(format!("{}{}", stem, ending), lemma.id);
+            }
+        }
+    }
+
+    found.sort_by(|a, b| a.cost.cmp(&b.cost).then_with(|| a.form.cmp(&b.form)));
+    found.truncate(limit);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logos_protocol::{Dialect, Dictionary, Gender, Lemma, MorphFlags, Paradigm, ParadigmId, PartOfSpeech, Period};
+
+    #[test]
+    fn test_distance_weights() {
+        assert_eq!(distance("λόγος", "λόγος", 10), Some(0));
+        assert_eq!(distance("λογος", "λόγος", 10), Some(MARK_COST));
+        assert_eq!(distance("ἀνθρωπος", "ἄνθρωπος", 10), Some(MARK_COST));
+        assert_eq!(distance("λόγως", "λόγος", 10), Some(VOWEL_COST));
+        assert_eq!(distance("λόκος", "λόγος", 10), Some(CONSONANT_COST));
+        assert_eq!(distance("λόγοςς", "λόγος", 10), Some(INDEL_COST));
+        assert_eq!(distance("ἵππος", "λόγος", 4), None);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let dict = Dictionary {
+            version: 1,
+            lemmas: vec![
+                Lemma { id: LemmaId(1), text: "λόγος".to_string(), gender: Gender::Masculine, pos: PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None },
+                Lemma { id: LemmaId(2), text: "ἵππ".to_string(), gender: Gender::Masculine, pos: PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None },
+            ],
+            paradigms: vec![Paradigm {
+                id: ParadigmId(1),
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                endings: vec![
+                    ((MorphFlags::NOMINATIVE | MorphFlags::SINGULAR).bits(), "ος".to_string()),
+                    ((MorphFlags::GENITIVE | MorphFlags::SINGULAR).bits(), "ου".to_string()),
+                ],
+            }],
+            ..Default::default()
+        };
+
+        // A full-word headword inflects from its stem, a stem-stored one from itself
+        let found = suggestions(&dict, "λογου", 4, 5);
+        assert_eq!(found[0], Suggestion { form: "λόγου".to_string(), lemma_id: LemmaId(1), cost: MARK_COST });
+        let found = suggestions(&dict, "ἱππου", 4, 5);
+        assert_eq!((found[0].form.as_str(), found[0].lemma_id), ("ἵππου", LemmaId(2)));
+
+        assert!(suggestions(&dict, "θάλασσα", 4, 5).is_empty());
+        assert_eq!(suggestions(&dict, "λογου", 4, 1).len(), 1);
+    }
+}
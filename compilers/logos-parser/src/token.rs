@@ -1,3 +1,6 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
 use logos_protocol::LemmaId;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,6 +17,10 @@ impl Span {
     pub fn len(&self) -> usize {
         self.end - self.start
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -24,6 +31,13 @@ pub enum TokenKind {
     UnknownWord,
     /// Punctuation mark
     Punctuation(char),
+    /// Arabic digits or a Greek alphabetic numeral, with its value
+    Numeral(u32),
+    /// Editorial markup of a critical edition
+    Sigla(Sigla),
+    /// A word in another script (Latin in a commentary) or a passage
+    /// reference ("327a", "1.23"): kept for its span, but never analyzed
+    Foreign(Script),
     /// Numbers, etc. (MVP placeholder)
     Other,
 }
@@ -31,6 +45,127 @@ pub enum TokenKind {
 #[derive(Debug, Clone)]
 pub struct Token<'a> {
     pub span: Span,
-    pub text: &'a str,
+    /// The input under `span`, except for a word rejoined across a
+    /// hyphenated line break ("λό-\nγος" reads "λόγος")
+    pub text: Cow<'a, str>,
     pub kind: TokenKind,
 }
+
+impl Token<'_> {
+    /// The token with its text copied out of the input.
+    pub fn into_owned(self) -> Token<'static> {
+        Token { span: self.span, text: Cow::Owned(self.text.into_owned()), kind: self.kind }
+    }
+
+    /// The pieces of `input` that make up the token: one for most tokens,
+    /// each half of a word rejoined across a line break.
+    pub fn fragments(&self, input: &str) -> Vec<Span> {
+        if let Cow::Borrowed(_) = self.text {
+            return vec![self.span];
+        }
+        let mut fragments: Vec<Span> = Vec::new();
+        let mut open = false;
+        for (i, c) in input[self.span.start..self.span.end].char_indices() {
+            let at = self.span.start + i;
+            let gap = c.is_whitespace() || matches!(c, '-' | '\u{2010}' | '\u{00AD}');
+            match fragments.last_mut() {
+                Some(last) if open && !gap => last.end = at + c.len_utf8(),
+                _ if !gap => fragments.push(Span::new(at, at + c.len_utf8())),
+                _ => {}
+            }
+            open = !gap;
+        }
+        fragments
+    }
+}
+
+/// Writing system of a letter, as far as the tokenizer cares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Script {
+    /// Greek and Coptic, Greek Extended
+    Greek,
+    /// Basic Latin through Latin Extended-B, Latin Extended Additional
+    Latin,
+    /// Any other alphabetic character
+    Other,
+}
+
+impl Script {
+    /// None for characters that are not letters. The Greek blocks count
+    /// whole, as they always have (keraia and numeral signs included).
+    pub fn of(c: char) -> Option<Script> {
+        match c {
+            '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Some(Script::Greek),
+            _ if !c.is_alphabetic() => None,
+            '\u{0041}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}' => Some(Script::Latin),
+            _ => Some(Script::Other),
+        }
+    }
+}
+
+/// Status an editor gives a stretch of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Editorial {
+    /// [λόγος]: lost in the witness, restored by the editor
+    Restored,
+    /// ⟨λόγος⟩: never in the witness, added by the editor
+    Supplied,
+    /// {λόγος}: in the witness, deleted as an interpolation
+    Deleted,
+    /// †λόγος†: transmitted but corrupt
+    Corrupt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sigla {
+    Open(Editorial),
+    Close(Editorial),
+    /// † (opens and closes a corrupt passage)
+    Crux,
+    /// … or *** (text missing, not restored)
+    Lacuna,
+}
+
+/// Editorial status of each token: the innermost bracket around it, if any.
+/// Brackets left open run to the end; a crux without a partner marks only
+/// the word after it. Sigla tokens themselves get `None`.
+pub fn editorial_scope(tokens: &[Token]) -> Vec<Option<Editorial>> {
+    let mut scope = vec![None; tokens.len()];
+    let mut open: Vec<Editorial> = Vec::new();
+    let cruxes: Vec<usize> = tokens.iter().enumerate()
+        .filter(|(_, t)| t.kind == TokenKind::Sigla(Sigla::Crux))
+        .map(|(i, _)| i)
+        .collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            TokenKind::Sigla(Sigla::Open(e)) => open.push(e),
+            TokenKind::Sigla(Sigla::Close(e)) => {
+                if let Some(pos) = open.iter().rposition(|o| *o == e) {
+                    open.truncate(pos);
+                }
+            }
+            TokenKind::Sigla(_) => {}
+            _ => scope[i] = open.last().copied(),
+        }
+    }
+
+    for pair in cruxes.chunks(2) {
+        let (start, end) = match *pair {
+            [start, end] => (start, end),
+            [start] => match (start + 1..tokens.len()).find(|&i| !matches!(tokens[i].kind, TokenKind::Sigla(_))) {
+                Some(word) => (start, word + 1),
+                None => continue,
+            },
+            _ => continue,
+        };
+        for (i, slot) in scope.iter_mut().enumerate().take(end).skip(start + 1) {
+            if !matches!(tokens[i].kind, TokenKind::Sigla(_)) {
+                *slot = Some(Editorial::Corrupt);
+            }
+        }
+    }
+    scope
+}
@@ -0,0 +1,451 @@
+//! Arc-standard transition parser scored by learned feature weights
+//! (`ParserModel`, fitted by `logos-trainer`), as an alternative to the
+//! rule-based `syntax::parse_greedy`. A swap action reorders the words, so
+//! it reaches the crossing arcs of Greek hyperbaton too (Nivre 2009).
+
+use alloc::vec::Vec;
+use std::collections::HashMap;
+
+use logos_protocol::{MorphFlags, ParserModel};
+
+use crate::syntax::{Dependency, MorphToken, SyntaxRole};
+
+/// Relations the parser can assign; `SyntaxRole::None` stands for any
+/// treebank relation without a counterpart.
+pub const LABELS: [SyntaxRole; 14] = [
+    SyntaxRole::Subject,
+    SyntaxRole::Object,
+    SyntaxRole::IndirectObject,
+    SyntaxRole::Modifier,
+    SyntaxRole::PrepositionArg,
+    SyntaxRole::Coordinator,
+    SyntaxRole::Conjunct,
+    SyntaxRole::PassiveAgent,
+    SyntaxRole::AbsoluteClause,
+    SyntaxRole::Complement,
+    SyntaxRole::RelativeClause,
+    SyntaxRole::Reflexive,
+    SyntaxRole::Comparison,
+    SyntaxRole::None,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Move the next word onto the stack
+    Shift,
+    /// The top of the stack governs the word below it, which is popped
+    Left(usize),
+    /// The word below the top governs the top, which is popped
+    Right(usize),
+    /// The word below the top goes back to the buffer, to be read after
+    /// the top: words are reordered so that crossing arcs become adjacent
+    Swap,
+}
+
+impl Action {
+    /// Number of distinct actions.
+    pub const COUNT: u16 = 2 + 2 * LABELS.len() as u16;
+
+    /// Dense index, as stored in `FeatureWeight::action`. Swap comes last,
+    /// so models trained before it keep their indices.
+    pub fn index(self) -> u16 {
+        match self {
+            Action::Shift => 0,
+            Action::Left(label) => 1 + 2 * label as u16,
+            Action::Right(label) => 2 + 2 * label as u16,
+            Action::Swap => Self::COUNT - 1,
+        }
+    }
+
+    pub fn from_index(index: u16) -> Option<Self> {
+        match index {
+            0 => Some(Action::Shift),
+            i if i == Self::COUNT - 1 => Some(Action::Swap),
+            i if i < Self::COUNT && i % 2 == 1 => Some(Action::Left(((i - 1) / 2) as usize)),
+            i if i < Self::COUNT => Some(Action::Right(((i - 2) / 2) as usize)),
+            _ => None,
+        }
+    }
+}
+
+/// Position in LABELS of `role` (Root has none).
+pub fn label_of(role: &SyntaxRole) -> Option<usize> {
+    LABELS.iter().position(|l| l == role)
+}
+
+/// Parser state: the stack, the unread words, and the arcs so far.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub stack: Vec<usize>,
+    /// The next unread word in sentence order
+    pub next: usize,
+    /// Words swapped back to the buffer, read before `next` (last first)
+    pub swapped: Vec<usize>,
+    /// (head, label) per word
+    pub heads: Vec<Option<(usize, usize)>>,
+}
+
+impl Config {
+    pub fn new(len: usize) -> Self {
+        Self { stack: Vec::new(), next: 0, swapped: Vec::new(), heads: vec![None; len] }
+    }
+
+    /// The `k`th unread word.
+    pub fn buffer(&self, k: usize) -> Option<usize> {
+        match self.swapped.len().checked_sub(k + 1) {
+            Some(i) => Some(self.swapped[i]),
+            None => Some(self.next + k - self.swapped.len()).filter(|&i| i < self.heads.len()),
+        }
+    }
+
+    /// Done once every word is read and at most the root is left.
+    pub fn is_terminal(&self) -> bool {
+        self.buffer(0).is_none() && self.stack.len() <= 1
+    }
+
+    /// Swap only moves a word back behind one that follows it in the
+    /// sentence, so that parsing terminates.
+    pub fn is_legal(&self, action: Action) -> bool {
+        match action {
+            Action::Shift => self.buffer(0).is_some(),
+            Action::Left(_) | Action::Right(_) => self.stack.len() >= 2,
+            Action::Swap => matches!(self.stack[..], [.., below, top] if below < top),
+        }
+    }
+
+    pub fn apply(&mut self, action: Action) {
+        match action {
+            Action::Shift => match self.swapped.pop() {
+                Some(word) => self.stack.push(word),
+                None => {
+                    self.stack.push(self.next);
+                    self.next += 1;
+                }
+            },
+            Action::Swap => {
+                let top = self.stack.pop().unwrap_or_default();
+                if let Some(below) = self.stack.pop() {
+                    self.swapped.push(below);
+                }
+                self.stack.push(top);
+            }
+            Action::Left(label) => {
+                let top = self.stack.pop().unwrap_or_default();
+                if let Some(below) = self.stack.pop() {
+                    self.heads[below] = Some((top, label));
+                }
+                self.stack.push(top);
+            }
+            Action::Right(label) => {
+                let top = self.stack.pop().unwrap_or_default();
+                if let Some(&below) = self.stack.last() {
+                    self.heads[top] = Some((below, label));
+                }
+            }
+        }
+    }
+
+    /// Static oracle: the action leading to `gold` (head and label per
+    /// word, None for the root). Words are swapped into the projective
+    /// order of `gold`, so crossing arcs are reached too; None once no
+    /// action leads on (several roots).
+    pub fn oracle(&self, gold: &[Option<(usize, usize)>]) -> Option<Action> {
+        if let [.., below, top] = self.stack[..] {
+            let complete = |word: usize| (0..gold.len()).all(|d| gold[d].is_none_or(|(h, _)| h != word) || self.heads[d].is_some());
+            if let Some((head, label)) = gold[below] {
+                if head == top && complete(below) {
+                    return Some(Action::Left(label));
+                }
+            }
+            if let Some((head, label)) = gold[top] {
+                if head == below && complete(top) {
+                    return Some(Action::Right(label));
+                }
+            }
+            let order = projective_order(gold);
+            if self.is_legal(Action::Swap) && order[top] < order[below] {
+                return Some(Action::Swap);
+            }
+        }
+        self.is_legal(Action::Shift).then_some(Action::Shift)
+    }
+}
+
+/// Position of each word in the order under which `gold` has no crossing
+/// arcs: every head among its dependents, each subtree kept together.
+fn projective_order(gold: &[Option<(usize, usize)>]) -> Vec<usize> {
+    fn visit(word: usize, gold: &[Option<(usize, usize)>], order: &mut Vec<usize>, depth: usize) {
+        // A cycle in the gold tree must not recurse forever
+        if depth > gold.len() {
+            return;
+        }
+        let dependents = (0..gold.len()).filter(|&d| gold[d].is_some_and(|(h, _)| h == word));
+        let (left, right): (Vec<usize>, Vec<usize>) = dependents.partition(|&d| d < word);
+        for d in left {
+            visit(d, gold, order, depth + 1);
+        }
+        order.push(word);
+        for d in right {
+            visit(d, gold, order, depth + 1);
+        }
+    }
+
+    let mut sequence = Vec::with_capacity(gold.len());
+    for root in (0..gold.len()).filter(|&w| gold[w].is_none()) {
+        visit(root, gold, &mut sequence, 0);
+    }
+    let mut order = vec![usize::MAX; gold.len()];
+    for (position, word) in sequence.into_iter().enumerate() {
+        order[word] = position;
+    }
+    order
+}
+
+/// Scores an action given the active features of a configuration.
+pub trait Scorer {
+    fn score(&self, features: &[u64], action: u16) -> f32;
+}
+
+/// The feature weights of a `ParserModel`, indexed for scoring.
+#[derive(Debug, Clone, Default)]
+pub struct Weights {
+    by_feature: HashMap<u64, Vec<(u16, f32)>>,
+}
+
+impl Weights {
+    pub fn from_model(model: &ParserModel) -> Self {
+        let mut by_feature: HashMap<u64, Vec<(u16, f32)>> = HashMap::new();
+        for w in &model.features {
+            by_feature.entry(w.feature).or_default().push((w.action, w.weight));
+        }
+        Self { by_feature }
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_feature.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_feature.is_empty()
+    }
+}
+
+impl Scorer for Weights {
+    fn score(&self, features: &[u64], action: u16) -> f32 {
+        features.iter()
+            .filter_map(|f| self.by_feature.get(f))
+            .flat_map(|weights| weights.iter().filter(|(a, _)| *a == action))
+            .map(|(_, w)| w)
+            .sum()
+    }
+}
+
+/// Agreement bits compared between stack words.
+const AGREEMENT: MorphFlags = MorphFlags::MASCULINE
+    .union(MorphFlags::FEMININE)
+    .union(MorphFlags::NEUTER)
+    .union(MorphFlags::SINGULAR)
+    .union(MorphFlags::PLURAL);
+
+/// Stands in for an empty stack or buffer slot.
+const NONE: u32 = u32::MAX;
+
+fn hash(template: u32, values: &[u32]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for v in std::iter::once(template).chain(values.iter().copied()) {
+        for b in v.to_le_bytes() {
+            h ^= b as u64;
+            h = h.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    h
+}
+
+/// Hashed features of a configuration: the tags of the top two stack words
+/// and the next two words, their combinations, agreement and distance.
+pub fn features(tokens: &[MorphToken], config: &Config) -> Vec<u64> {
+    let tag = |i: Option<usize>| i.map_or(NONE, |i| tokens[i].flags.tag());
+    let agreement = |i: Option<usize>| i.map_or(NONE, |i| (tokens[i].flags & AGREEMENT).bits());
+    let s0 = config.stack.last().copied();
+    let s1 = config.stack.len().checked_sub(2).map(|i| config.stack[i]);
+    let (b0, b1) = (config.buffer(0), config.buffer(1));
+    let distance = match (s1, s0) {
+        (Some(a), Some(b)) => a.abs_diff(b).min(5) as u32,
+        _ => NONE,
+    };
+
+    vec![
+        hash(0, &[]),
+        hash(1, &[tag(s0)]),
+        hash(2, &[tag(s1)]),
+        hash(3, &[tag(b0)]),
+        hash(4, &[tag(b1)]),
+        hash(5, &[tag(s1), tag(s0)]),
+        hash(6, &[tag(s0), tag(b0)]),
+        hash(7, &[tag(s1), tag(s0), tag(b0)]),
+        hash(8, &[tag(s1), tag(s0), (agreement(s1) == agreement(s0)) as u32]),
+        hash(9, &[tag(s1), tag(s0), distance]),
+    ]
+}
+
+/// Legal actions with their scores, in index order.
+fn scored_actions(scorer: &impl Scorer, features: &[u64], config: &Config) -> Vec<(Action, f32)> {
+    (0..Action::COUNT)
+        .filter_map(Action::from_index)
+        .filter(|a| config.is_legal(*a))
+        .map(|a| (a, scorer.score(features, a.index())))
+        .collect()
+}
+
+/// The best of `scored`; the first action wins a tie, so an untrained
+/// model shifts.
+fn best(scored: &[(Action, f32)]) -> Option<(Action, f32)> {
+    scored.iter().copied().fold(None, |best: Option<(Action, f32)>, (a, s)| match best {
+        Some((_, b)) if b >= s => best,
+        _ => Some((a, s)),
+    })
+}
+
+/// Highest-scoring legal action (None once the configuration is terminal).
+pub fn best_action(scorer: &impl Scorer, features: &[u64], config: &Config) -> Option<Action> {
+    best(&scored_actions(scorer, features, config)).map(|(a, _)| a)
+}
+
+/// Parses one sentence. Like `parse_greedy`, the root gets no dependency.
+/// An arc's confidence is the softmax probability, among the legal
+/// actions, of the action that drew it.
+pub fn parse_transition(tokens: &[MorphToken], scorer: &impl Scorer) -> Vec<Dependency> {
+    let mut config = Config::new(tokens.len());
+    let mut confidence = vec![1.0; tokens.len()];
+    while !config.is_terminal() {
+        let features = features(tokens, &config);
+        let scored = scored_actions(scorer, &features, &config);
+        let Some((action, top)) = best(&scored) else { break };
+        let dependent = match action {
+            Action::Left(_) => config.stack.len().checked_sub(2).map(|i| config.stack[i]),
+            Action::Right(_) => config.stack.last().copied(),
+            Action::Shift | Action::Swap => None,
+        };
+        if let Some(dependent) = dependent {
+            confidence[dependent] = 1.0 / scored.iter().map(|(_, s)| (s - top).exp()).sum::<f32>();
+        }
+        config.apply(action);
+    }
+
+    config.heads.iter().enumerate()
+        .filter_map(|(dependent, head)| {
+            let (head, label) = (*head)?;
+            Some(Dependency {
+                head_index: head,
+                dependent_index: dependent,
+                role: LABELS[label].clone(),
+                confidence: confidence[dependent],
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::tests::sentence;
+    use logos_protocol::FeatureWeight;
+    use proptest::prelude::*;
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    /// Scores every feature and action at random, from -1 to 1.
+    struct Noise(u64);
+
+    impl Scorer for Noise {
+        fn score(&self, features: &[u64], action: u16) -> f32 {
+            let mut hasher = DefaultHasher::new();
+            (self.0, features, action).hash(&mut hasher);
+            (hasher.finish() % 2001) as f32 / 1000.0 - 1.0
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_transition_parse_is_a_tree(tokens in sentence(), seed in any::<u64>()) {
+            let deps = parse_transition(&tokens, &Noise(seed));
+            let mut heads = vec![None; tokens.len()];
+            for d in &deps {
+                prop_assert!(d.head_index < tokens.len() && d.dependent_index < tokens.len() && d.head_index != d.dependent_index, "{:?}", d);
+                prop_assert!(d.confidence > 0.0 && d.confidence <= 1.0);
+                prop_assert!(heads[d.dependent_index].replace(d.head_index).is_none(), "two heads for {}", d.dependent_index);
+            }
+        }
+    }
+
+    #[test]
+    fn test_transition_parse() {
+        let tokens = [
+            MorphToken { text: "ὁ", flags: MorphFlags::ARTICLE | MorphFlags::NOMINATIVE },
+            MorphToken { text: "ἄνθρωπος", flags: MorphFlags::NOUN | MorphFlags::NOMINATIVE },
+            MorphToken { text: "λέγει", flags: MorphFlags::VERB | MorphFlags::THIRD_PERSON },
+        ];
+        // ὁ <- ἄνθρωπος <- λέγει
+        let det = label_of(&SyntaxRole::Modifier).unwrap();
+        let subj = label_of(&SyntaxRole::Subject).unwrap();
+        let gold = [Some((1, det)), Some((2, subj)), None];
+
+        // The oracle reaches the gold tree
+        let mut config = Config::new(3);
+        let mut actions = Vec::new();
+        while let Some(action) = config.oracle(&gold) {
+            actions.push(action);
+            config.apply(action);
+        }
+        assert!(config.is_terminal());
+        assert_eq!(config.heads, gold);
+        assert_eq!(actions, [Action::Shift, Action::Shift, Action::Left(det), Action::Shift, Action::Left(subj)]);
+        for action in &actions {
+            assert_eq!(Action::from_index(action.index()), Some(*action));
+        }
+
+        // One weight per step, on the (s1, s0) pair feature
+        let mut model = ParserModel::default();
+        let mut config = Config::new(3);
+        for action in actions {
+            let pair = features(&tokens, &config)[5];
+            if action != Action::Shift {
+                model.features.push(FeatureWeight { feature: pair, action: action.index(), weight: 1.0 });
+            }
+            config.apply(action);
+        }
+        let deps = parse_transition(&tokens, &Weights::from_model(&model));
+        let arcs: Vec<(usize, usize, SyntaxRole)> = deps.iter().map(|d| (d.head_index, d.dependent_index, d.role.clone())).collect();
+        assert_eq!(arcs, [(1, 0, SyntaxRole::Modifier), (2, 1, SyntaxRole::Subject)]);
+        // A weight of 1 against the other legal actions' 0 leaves room for doubt
+        assert!(deps.iter().all(|d| d.confidence > 0.0 && d.confidence < 0.5));
+    }
+
+    #[test]
+    fn test_swap_reaches_crossing_arcs() {
+        // ἀγαθὸν ἀνὴρ ἵππον ἔχει: ἀγαθὸν -> ἵππον crosses ἀνήρ -> ἔχει
+        let (modifier, subj, obj) = (label_of(&SyntaxRole::Modifier).unwrap(), label_of(&SyntaxRole::Subject).unwrap(), label_of(&SyntaxRole::Object).unwrap());
+        let gold = [Some((2, modifier)), Some((3, subj)), Some((3, obj)), None];
+
+        let mut config = Config::new(4);
+        let mut actions = Vec::new();
+        while let Some(action) = config.oracle(&gold) {
+            actions.push(action);
+            config.apply(action);
+        }
+        assert!(config.is_terminal());
+        assert_eq!(config.heads, gold);
+        assert!(actions.contains(&Action::Swap));
+        assert_eq!(Action::from_index(Action::Swap.index()), Some(Action::Swap));
+        assert_eq!(Action::from_index(Action::Right(LABELS.len() - 1).index()), Some(Action::Right(LABELS.len() - 1)));
+
+        // A swapped word is read again before the rest of the sentence
+        let mut config = Config::new(3);
+        config.apply(Action::Shift);
+        config.apply(Action::Shift);
+        assert!(config.is_legal(Action::Swap));
+        config.apply(Action::Swap);
+        assert_eq!((config.stack.as_slice(), config.buffer(0), config.buffer(1)), ([1].as_slice(), Some(0), Some(2)));
+        config.apply(Action::Shift);
+        assert!(!config.is_legal(Action::Swap));
+    }
+}
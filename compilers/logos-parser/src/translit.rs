@@ -0,0 +1,431 @@
+//! Greek in Latin letters and back, in three schemes:
+//!
+//! ```text
+//! Ἑλλάς  ἄγγελος  ᾠδῇ    ala-lc     Hellas  angelos  ǭdę̄
+//!                         iso-843    Hellás  ággelos  ōidī̃i
+//!                         scholarly  Hellas  angelos  ōidēi
+//! ```
+//!
+//! A rough breathing is an h before the word (a ῥ is rh), a smooth one is
+//! not written. ALA-LC and the scholarly scheme spell γ before a velar (γγ,
+//! γκ, γξ, γχ) as n and drop accents; ISO 843 keeps both. Back in Greek,
+//! an h gives the rough breathing, and ALA-LC and the scholarly scheme,
+//! being for ancient texts, give other initial vowels the smooth one.
+//! Accents only come back from ISO 843, and an iota subscript written as
+//! i only after η and ω (ᾳ and αι are both "ai").
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+const SMOOTH: char = '\u{0313}';
+const ROUGH: char = '\u{0314}';
+const ACUTE: char = '\u{0301}';
+const GRAVE: char = '\u{0300}';
+const CIRCUMFLEX: char = '\u{0342}';
+const SUBSCRIPT: char = '\u{0345}';
+const DIAERESIS: char = '\u{0308}';
+const MACRON: char = '\u{0304}';
+const OGONEK: char = '\u{0328}';
+const TILDE: char = '\u{0303}';
+const LATIN_CIRCUMFLEX: char = '\u{0302}';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scheme {
+    /// Library of Congress: η ē, ω ō, υ y, φ ph, iota subscript as an
+    /// ogonek (ᾳ ą, ῳ ǭ)
+    AlaLc,
+    /// ISO 843 transliteration: η ī, β v, φ f, accents on the Latin vowels
+    Iso843,
+    /// ALA-LC with the iota subscript written as i (ῳ ōi)
+    Scholarly,
+}
+
+impl Scheme {
+    pub const ALL: [Scheme; 3] = [Scheme::AlaLc, Scheme::Iso843, Scheme::Scholarly];
+
+    /// "ala-lc", "iso-843" or "scholarly".
+    pub fn tag(self) -> &'static str {
+        match self {
+            Scheme::AlaLc => "ala-lc",
+            Scheme::Iso843 => "iso-843",
+            Scheme::Scholarly => "scholarly",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Scheme> {
+        Scheme::ALL.into_iter().find(|scheme| scheme.tag() == tag)
+    }
+
+    /// γ before a velar written n, initial ρ always rh, no accents
+    fn classical(self) -> bool {
+        self != Scheme::Iso843
+    }
+}
+
+/// A letter with the marks that matter here. `base` is lowercase Greek
+/// (σ for ς), or a Latin letter with no Greek counterpart.
+#[derive(Debug, Clone, Copy, Default)]
+struct Letter {
+    base: char,
+    upper: bool,
+    smooth: bool,
+    rough: bool,
+    subscript: bool,
+    diaeresis: bool,
+    /// Greek accent (acute, grave or circumflex)
+    accent: Option<char>,
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'α' | 'ε' | 'η' | 'ι' | 'ο' | 'υ' | 'ω')
+}
+
+/// Whether `second` closes a diphthong opened by `first` (αι, ευ, ηυ, υι...).
+fn is_diphthong(first: &Letter, second: &Letter) -> bool {
+    !second.diaeresis
+        && match second.base {
+            'ι' => matches!(first.base, 'α' | 'ε' | 'ο' | 'υ'),
+            'υ' => matches!(first.base, 'α' | 'ε' | 'η' | 'ο'),
+            _ => false,
+        }
+}
+
+/// The lowercase Greek letter `c` is, and whether it is a capital.
+fn greek_letter(c: char) -> Option<(char, bool)> {
+    let lower = c.to_lowercase().next()?;
+    let base = match lower {
+        'ς' | 'ϲ' => 'σ',
+        'ϐ' => 'β',
+        'ϑ' => 'θ',
+        'ϕ' => 'φ',
+        'α'..='ω' => lower,
+        _ => return None,
+    };
+    Some((base, lower != c))
+}
+
+/// Whether `text` has any Greek letters.
+pub fn is_greek(text: &str) -> bool {
+    text.nfd().any(|c| greek_letter(c).is_some())
+}
+
+/// `text` in the other script: Greek letters to Latin, or, for text without
+/// any, Latin letters to Greek.
+pub fn transliterate(text: &str, scheme: Scheme) -> String {
+    if is_greek(text) {
+        to_latin(text, scheme)
+    } else {
+        to_greek(text, scheme)
+    }
+}
+
+/// The Greek words of `text` in Latin letters; everything else is kept.
+pub fn to_latin(text: &str, scheme: Scheme) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut word: Vec<Letter> = Vec::new();
+    for c in text.nfd() {
+        if let Some((base, upper)) = greek_letter(c) {
+            word.push(Letter { base, upper, ..Default::default() });
+            continue;
+        }
+        let Some(letter) = word.last_mut() else {
+            out.push(c);
+            continue;
+        };
+        match c {
+            ROUGH => letter.rough = true,
+            SMOOTH => letter.smooth = true,
+            SUBSCRIPT => letter.subscript = true,
+            DIAERESIS => letter.diaeresis = true,
+            ACUTE | GRAVE | CIRCUMFLEX => letter.accent = Some(c),
+            // Vowel length and the like
+            _ if is_combining_mark(c) => {}
+            _ => {
+                romanize(&word, scheme, &mut out);
+                word.clear();
+                out.push(c);
+            }
+        }
+    }
+    romanize(&word, scheme, &mut out);
+    out.nfc().collect()
+}
+
+fn romanize(word: &[Letter], scheme: Scheme, out: &mut String) {
+    let Some(first) = word.first() else { return };
+    let caps = word.len() > 1 && word.iter().all(|l| l.upper);
+    let opening = word.iter().take_while(|l| is_vowel(l.base)).count();
+    let rough = word[..opening].iter().any(|l| l.rough);
+    if rough {
+        out.push(if first.upper { 'H' } else { 'h' });
+    }
+
+    for (i, letter) in word.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|p| &word[p]);
+        let next = word.get(i + 1);
+        let mut piece = String::from(match letter.base {
+            'α' => "a",
+            'β' if scheme == Scheme::Iso843 => "v",
+            'β' => "b",
+            'γ' if scheme.classical() && next.is_some_and(|n| matches!(n.base, 'γ' | 'κ' | 'ξ' | 'χ')) => "n",
+            'γ' => "g",
+            'δ' => "d",
+            'ε' => "e",
+            'ζ' => "z",
+            'η' if scheme == Scheme::Iso843 => "i\u{0304}",
+            'η' => "e\u{0304}",
+            'θ' => "th",
+            'ι' => "i",
+            'κ' => "k",
+            'λ' => "l",
+            'μ' => "m",
+            'ν' => "n",
+            'ξ' => "x",
+            'ο' => "o",
+            'π' => "p",
+            'ρ' if scheme.classical() && (i == 0 || prev.is_some_and(|p| p.base == 'ρ')) => "rh",
+            'ρ' if letter.rough => "rh",
+            'ρ' => "r",
+            'σ' => "s",
+            'τ' => "t",
+            'υ' if prev.is_some_and(|p| is_diphthong(p, letter)) => "u",
+            'υ' if scheme.classical() && next.is_some_and(|n| is_diphthong(letter, n)) => "u",
+            'υ' => "y",
+            'φ' if scheme == Scheme::Iso843 => "f",
+            'φ' => "ph",
+            'χ' => "ch",
+            'ψ' => "ps",
+            'ω' => "o\u{0304}",
+            _ => "",
+        });
+        if letter.diaeresis {
+            piece.push(DIAERESIS);
+        }
+        if scheme == Scheme::Iso843 {
+            match letter.accent {
+                Some(CIRCUMFLEX) => piece.push(TILDE),
+                Some(accent) => piece.push(accent),
+                None => {}
+            }
+        }
+        if letter.subscript {
+            piece.push(if scheme == Scheme::AlaLc { OGONEK } else { 'i' });
+        }
+
+        // After an h the capital has been written
+        let upper = letter.upper && !(i == 0 && rough && !caps);
+        let mut chars = piece.chars();
+        if caps {
+            out.extend(chars.flat_map(char::to_uppercase));
+        } else if upper {
+            out.extend(chars.next().into_iter().flat_map(char::to_uppercase));
+            out.extend(chars);
+        } else {
+            out.push_str(&piece);
+        }
+    }
+}
+
+/// A Latin letter with its marks, lowercased.
+#[derive(Debug, Clone, Copy, Default)]
+struct Latin {
+    c: char,
+    upper: bool,
+    macron: bool,
+    ogonek: bool,
+    diaeresis: bool,
+    /// As the Greek accent it stands for
+    accent: Option<char>,
+}
+
+/// The Latin words of `text` in Greek letters; everything else is kept.
+pub fn to_greek(text: &str, scheme: Scheme) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut word: Vec<Latin> = Vec::new();
+    for c in text.nfd() {
+        if c.is_ascii_alphabetic() {
+            word.push(Latin { c: c.to_ascii_lowercase(), upper: c.is_ascii_uppercase(), ..Default::default() });
+            continue;
+        }
+        let Some(letter) = word.last_mut() else {
+            out.push(c);
+            continue;
+        };
+        match c {
+            MACRON => letter.macron = true,
+            OGONEK => letter.ogonek = true,
+            DIAERESIS => letter.diaeresis = true,
+            ACUTE | GRAVE => letter.accent = Some(c),
+            TILDE | LATIN_CIRCUMFLEX => letter.accent = Some(CIRCUMFLEX),
+            _ if is_combining_mark(c) => {}
+            _ => {
+                write_greek(&hellenize(&word, scheme), &mut out);
+                word.clear();
+                out.push(c);
+            }
+        }
+    }
+    write_greek(&hellenize(&word, scheme), &mut out);
+    out.nfc().collect()
+}
+
+fn hellenize(word: &[Latin], scheme: Scheme) -> Vec<Letter> {
+    let at = |k: usize| word.get(k).map(|l| l.c);
+    let is_latin_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut letters: Vec<Letter> = Vec::with_capacity(word.len());
+    let mut i = 0;
+    // An initial h is the rough breathing of the vowel after it
+    let rough = at(0) == Some('h') && at(1).is_some_and(is_latin_vowel);
+    let mut capital = false;
+    if rough {
+        capital = word[0].upper;
+        i = 1;
+    }
+
+    while i < word.len() {
+        let l = word[i];
+        let next = at(i + 1);
+        let (base, len) = match l.c {
+            'r' if next == Some('r') && at(i + 2) == Some('h') => {
+                letters.push(Letter { base: 'ρ', upper: l.upper, ..Default::default() });
+                ('ρ', 3)
+            }
+            'r' if next == Some('h') => ('ρ', 2),
+            't' if next == Some('h') => ('θ', 2),
+            'p' if next == Some('h') => ('φ', 2),
+            'c' | 'k' if next == Some('h') => ('χ', 2),
+            'p' if next == Some('s') => ('ψ', 2),
+            'n' if scheme.classical() && matches!(next, Some('g' | 'k' | 'x')) => ('γ', 1),
+            'n' if scheme.classical() && next == Some('c') && at(i + 2) == Some('h') => ('γ', 1),
+            'i' if !l.diaeresis && l.accent.is_none() && scheme != Scheme::AlaLc
+                && letters.last().is_some_and(|prev| matches!(prev.base, 'η' | 'ω') && !prev.subscript) =>
+            {
+                if let Some(prev) = letters.last_mut() {
+                    prev.subscript = true;
+                }
+                i += 1;
+                continue;
+            }
+            // A stray h has nothing to stand for
+            'h' => {
+                i += 1;
+                continue;
+            }
+            'a' => ('α', 1),
+            'b' | 'v' => ('β', 1),
+            'g' => ('γ', 1),
+            'd' => ('δ', 1),
+            'e' if l.macron || l.ogonek => ('η', 1),
+            'e' => ('ε', 1),
+            'z' => ('ζ', 1),
+            'i' if l.macron && scheme == Scheme::Iso843 => ('η', 1),
+            'i' => ('ι', 1),
+            'c' | 'k' => ('κ', 1),
+            'l' => ('λ', 1),
+            'm' => ('μ', 1),
+            'n' => ('ν', 1),
+            'x' => ('ξ', 1),
+            'o' if l.macron || l.ogonek => ('ω', 1),
+            'o' => ('ο', 1),
+            'p' => ('π', 1),
+            'r' => ('ρ', 1),
+            's' => ('σ', 1),
+            't' => ('τ', 1),
+            'u' | 'y' => ('υ', 1),
+            'f' => ('φ', 1),
+            other => (other, 1),
+        };
+        let initial_rho = base == 'ρ' && letters.is_empty() && len > 1;
+        letters.push(Letter {
+            base,
+            upper: l.upper || core::mem::take(&mut capital),
+            rough: initial_rho,
+            subscript: l.ogonek,
+            diaeresis: l.diaeresis,
+            accent: l.accent.filter(|_| scheme == Scheme::Iso843),
+            ..Default::default()
+        });
+        i += len;
+    }
+
+    // Breathings go on the first vowel, or the second of a diphthong
+    if letters.first().is_some_and(|l| is_vowel(l.base)) {
+        let at = usize::from(letters.len() > 1 && is_diphthong(&letters[0], &letters[1]));
+        if rough {
+            letters[at].rough = true;
+        } else if scheme.classical() {
+            letters[at].smooth = true;
+        }
+    }
+    letters
+}
+
+fn write_greek(word: &[Letter], out: &mut String) {
+    for (i, letter) in word.iter().enumerate() {
+        let base = if letter.base == 'σ' && i + 1 == word.len() && !letter.upper { 'ς' } else { letter.base };
+        if letter.upper {
+            out.extend(base.to_uppercase());
+        } else {
+            out.push(base);
+        }
+        if letter.smooth {
+            out.push(SMOOTH);
+        }
+        if letter.rough {
+            out.push(ROUGH);
+        }
+        if letter.diaeresis {
+            out.push(DIAERESIS);
+        }
+        out.extend(letter.accent);
+        if letter.subscript {
+            out.push(SUBSCRIPT);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn latin(text: &str, scheme: Scheme) -> String {
+        to_latin(text, scheme)
+    }
+
+    #[test]
+    fn test_to_latin() {
+        let words = "Ἑλλάς ἄγγελος ᾠδῇ ῥήτωρ Πύρρος υἱός αὐτοῦ ἐγκώμιον ΘΕΟΣ";
+        assert_eq!(latin(words, Scheme::AlaLc), "Hellas angelos ǭdę̄ rhētōr Pyrrhos huios autou enkōmion THEOS");
+        assert_eq!(latin(words, Scheme::Scholarly), "Hellas angelos ōidēi rhētōr Pyrrhos huios autou enkōmion THEOS");
+        assert_eq!(
+            latin(words, Scheme::Iso843).nfc().collect::<String>(),
+            "Hellás ággelos ōidī̃i rhī́tōr Pýrros hyiós autoũ egkṓmion THEOS".nfc().collect::<String>(),
+        );
+        // A diaeresis keeps the vowels apart; other text is left as it is
+        assert_eq!(latin("ἄϋπνος, ἀϊδής.", Scheme::AlaLc), "aÿpnos, aïdēs.");
+        assert_eq!(latin("Αἱ", Scheme::Scholarly), "Hai");
+    }
+
+    #[test]
+    fn test_to_greek() {
+        assert_eq!(to_greek("Hellas angelos ǭdę̄ rhētōr Pyrrhos huios", Scheme::AlaLc), "Ἑλλας ἀγγελος ᾠδῃ ῥητωρ Πυρρος υἱος");
+        assert_eq!(to_greek("ōidēi autou, Hai", Scheme::Scholarly), "ᾠδῃ αὐτου, Αἱ");
+        assert_eq!(to_greek("Hellás ággelos", Scheme::Iso843), "Ἑλλάς άγγελος".nfc().collect::<String>());
+        assert_eq!(to_greek("THEOS", Scheme::AlaLc), "ΘΕΟΣ");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for scheme in Scheme::ALL {
+            assert_eq!(Scheme::from_tag(scheme.tag()), Some(scheme));
+            for word in ["ἀγγελος", "ᾠδῃ", "ῥητωρ", "Ἑλλας", "αὐτου", "ΘΕΟΣ"] {
+                let back = transliterate(&transliterate(word, scheme), scheme);
+                // ISO 843 has no smooth breathing
+                let expected: String = if scheme == Scheme::Iso843 { word.nfd().filter(|&c| c != SMOOTH).nfc().collect() } else { word.into() };
+                assert_eq!(back, expected, "{} in {}", word, scheme.tag());
+            }
+        }
+    }
+}
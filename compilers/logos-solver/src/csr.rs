@@ -0,0 +1,101 @@
+use logos_protocol::{LemmaId, Relation};
+
+/// A static concept graph in compressed sparse row form: the concepts sorted
+/// by ID and, for each, a slice of its outgoing edges in one shared array.
+/// Three flat vectors instead of petgraph's node and edge lists plus an ID
+/// index, which is all a browser needs for lookups. Edges are listed most
+/// recent first, in the order petgraph walks them.
+#[derive(Debug, Clone)]
+pub struct CsrGraph {
+    concepts: Vec<LemmaId>,
+    /// `edges[offsets[i]..offsets[i + 1]]` leave `concepts[i]`
+    offsets: Vec<u32>,
+    edges: Vec<(Relation, LemmaId)>,
+}
+
+impl Default for CsrGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsrGraph {
+    pub fn new() -> Self {
+        Self { concepts: Vec::new(), offsets: vec![0], edges: Vec::new() }
+    }
+
+    /// Builds the graph in one pass over `(from, to, relation)` edges.
+    pub fn from_edges(edges: &[(LemmaId, LemmaId, Relation)]) -> Self {
+        let mut concepts: Vec<LemmaId> = edges.iter().flat_map(|&(from, to, _)| [from, to]).collect();
+        concepts.sort_unstable();
+        concepts.dedup();
+
+        let mut offsets = vec![0u32; concepts.len() + 1];
+        let row = |id: LemmaId| concepts.binary_search(&id).unwrap_or_else(|_| unreachable!("every endpoint is a concept"));
+        for &(from, _, _) in edges {
+            offsets[row(from) + 1] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        // Filling each row from its start with the edges taken last to first
+        // puts the most recent first
+        let mut next: Vec<u32> = offsets[..concepts.len()].to_vec();
+        let mut slots = vec![(Relation::IsA, LemmaId(0)); edges.len()];
+        for &(from, to, relation) in edges.iter().rev() {
+            let r = row(from);
+            slots[next[r] as usize] = (relation, to);
+            next[r] += 1;
+        }
+        Self { concepts, offsets, edges: slots }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.concepts.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn contains(&self, concept: LemmaId) -> bool {
+        self.concepts.binary_search(&concept).is_ok()
+    }
+
+    /// Adds a concept without edges; a no-op if it is known.
+    pub fn add_concept(&mut self, concept: LemmaId) {
+        self.row_or_insert(concept);
+    }
+
+    /// Adds an edge ahead of `from`'s others. Shifts the arrays, so building
+    /// a large graph edge by edge is slow; `from_edges` is not.
+    pub fn add_edge(&mut self, from: LemmaId, to: LemmaId, relation: Relation) {
+        self.row_or_insert(to);
+        let row = self.row_or_insert(from);
+        self.edges.insert(self.offsets[row] as usize, (relation, to));
+        for offset in &mut self.offsets[row + 1..] {
+            *offset += 1;
+        }
+    }
+
+    /// The outgoing edges of `concept`, most recent first.
+    pub fn edges(&self, concept: LemmaId) -> impl Iterator<Item = (Relation, LemmaId)> + '_ {
+        let range = match self.concepts.binary_search(&concept) {
+            Ok(row) => self.offsets[row] as usize..self.offsets[row + 1] as usize,
+            Err(_) => 0..0,
+        };
+        self.edges[range].iter().copied()
+    }
+
+    fn row_or_insert(&mut self, concept: LemmaId) -> usize {
+        match self.concepts.binary_search(&concept) {
+            Ok(row) => row,
+            Err(row) => {
+                self.concepts.insert(row, concept);
+                self.offsets.insert(row, self.offsets[row]);
+                row
+            }
+        }
+    }
+}
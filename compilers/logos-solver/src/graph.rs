@@ -1,29 +1,45 @@
+#[cfg(feature = "petgraph")]
 use petgraph::graph::{Graph, NodeIndex};
+#[cfg(feature = "petgraph")]
 use petgraph::Directed;
+#[cfg(feature = "petgraph")]
 use petgraph::visit::EdgeRef;
-use logos_protocol::{LemmaId, Relation, SemanticNetwork};
-use std::collections::HashMap;
-use rkyv::Archived;
+use logos_protocol::{Animacy, AnimacyRole, LemmaId, PrepositionRole, Relation, SemanticNetwork};
+use std::collections::{HashMap, VecDeque};
+use rkyv::{Archive, Archived, Deserialize, Infallible};
+
+/// The concepts and their edges: petgraph with the `petgraph` feature (for
+/// tooling that grows graphs edge by edge), the compact `CsrGraph` without.
+#[cfg(feature = "petgraph")]
+type Concepts = PetGraph;
+#[cfg(not(feature = "petgraph"))]
+type Concepts = crate::csr::CsrGraph;
 
 pub struct SemanticGraph {
-    graph: Graph<LemmaId, Relation, Directed>,
-    index_map: HashMap<LemmaId, NodeIndex>,
+    concepts: Concepts,
+    prepositions: HashMap<LemmaId, Vec<PrepositionRole>>,
+    animacy: HashMap<LemmaId, Animacy>,
+    animacy_constraints: HashMap<(LemmaId, AnimacyRole), Animacy>,
+}
+
+impl Default for SemanticGraph {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SemanticGraph {
     pub fn new() -> Self {
         Self {
-            graph: Graph::new(),
-            index_map: HashMap::new(),
+            concepts: Concepts::new(),
+            prepositions: HashMap::new(),
+            animacy: HashMap::new(),
+            animacy_constraints: HashMap::new(),
         }
     }
 
     pub fn from_archived(archived: &Archived<SemanticNetwork>) -> Self {
-        let mut slf = Self::new();
-        for edge in archived.edges.iter() {
-            let from = LemmaId(edge.from.0);
-            let to = LemmaId(edge.to.0);
-             
+        let edges: Vec<(LemmaId, LemmaId, Relation)> = archived.edges.iter().map(|edge| {
             // Manual mapping from ArchivedRelation to Relation
             // Since we know the variants match exactly in protocol
             let rel = match edge.relation {
@@ -31,24 +47,86 @@ impl SemanticGraph {
                 logos_protocol::ArchivedRelation::RequiresAttribute => Relation::RequiresAttribute,
                 logos_protocol::ArchivedRelation::HasAttribute => Relation::HasAttribute,
             };
+            (LemmaId(edge.from.0), LemmaId(edge.to.0), rel)
+        }).collect();
 
-            slf.add_relation(from, to, rel);
+        let mut slf = Self { concepts: Concepts::from_edges(&edges), ..Self::new() };
+        for role in archived.prepositions.iter() {
+            slf.add_preposition_role(unarchive(role));
+        }
+        for mark in archived.animacy.iter() {
+            slf.set_animacy(LemmaId(mark.concept.0), unarchive(&mark.animacy));
+        }
+        for constraint in archived.animacy_constraints.iter() {
+            slf.add_animacy_constraint(LemmaId(constraint.verb.0), unarchive(&constraint.role), unarchive(&constraint.minimum));
         }
         slf
     }
 
+    pub fn node_count(&self) -> usize {
+        self.concepts.node_count()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.concepts.edge_count()
+    }
+
     pub fn add_concept(&mut self, lemma: LemmaId) {
-        if !self.index_map.contains_key(&lemma) {
-            let idx = self.graph.add_node(lemma);
-            self.index_map.insert(lemma, idx);
-        }
+        self.concepts.add_concept(lemma);
     }
 
     pub fn add_relation(&mut self, from: LemmaId, to: LemmaId, rel: Relation) {
-        let from_idx = *self.index_map.entry(from).or_insert_with(|| self.graph.add_node(from));
-        let to_idx = *self.index_map.entry(to).or_insert_with(|| self.graph.add_node(to));
-        
-        self.graph.add_edge(from_idx, to_idx, rel);
+        self.concepts.add_edge(from, to, rel);
+    }
+
+    pub fn add_preposition_role(&mut self, role: PrepositionRole) {
+        self.prepositions.entry(role.preposition).or_default().push(role);
+    }
+
+    /// The roles a preposition gives an argument in these case flags
+    /// (`MorphFlags` bits).
+    pub fn preposition_roles(&self, preposition: LemmaId, cases: u32) -> Vec<&PrepositionRole> {
+        self.prepositions.get(&preposition).into_iter().flatten()
+            .filter(|r| r.cases == 0 || r.cases & cases != 0)
+            .collect()
+    }
+
+    /// Marks the animacy of a concept, inherited by its kinds (IsA).
+    pub fn set_animacy(&mut self, concept: LemmaId, animacy: Animacy) {
+        self.animacy.insert(concept, animacy);
+    }
+
+    /// The animacy of a concept: its own mark, or the nearest marked
+    /// ancestor's. None when nothing above it is marked.
+    pub fn animacy_of(&self, concept: LemmaId) -> Option<Animacy> {
+        self.animacy_source(concept).map(|(_, animacy)| animacy)
+    }
+
+    /// `animacy_of`, with the concept the mark was found on.
+    pub fn animacy_source(&self, concept: LemmaId) -> Option<(LemmaId, Animacy)> {
+        if let Some(&animacy) = self.animacy.get(&concept) {
+            return Some((concept, animacy));
+        }
+        self.ancestors(concept).into_iter().find_map(|a| Some((a, *self.animacy.get(&a)?)))
+    }
+
+    pub fn add_animacy_constraint(&mut self, verb: LemmaId, role: AnimacyRole, minimum: Animacy) {
+        self.animacy_constraints.insert((verb, role), minimum);
+    }
+
+    /// The least animate argument `verb` takes in `role`, if it is limited.
+    pub fn animacy_constraint(&self, verb: LemmaId, role: AnimacyRole) -> Option<Animacy> {
+        self.animacy_constraints.get(&(verb, role)).copied()
+    }
+
+    /// Whether `concept` is `other` or one of its kinds (IsA), or has it
+    /// as an attribute.
+    pub fn fits(&self, concept: LemmaId, other: LemmaId) -> bool {
+        concept == other || self.ancestors(concept).contains(&other) || self.satisfies_constraint(concept, other)
+    }
+
+    pub fn contains(&self, concept: LemmaId) -> bool {
+        self.concepts.contains(concept)
     }
 
     /// Check if 'subject' satisfies a constraint required by 'verb'.
@@ -56,33 +134,27 @@ impl SemanticGraph {
     /// 1. Verb requires 'AttributeX'.
     /// 2. Subject must have 'AttributeX' (directly or via IsA inheritance).
     pub fn satisfies_constraint(&self, subject: LemmaId, attribute: LemmaId) -> bool {
-        let start_idx = match self.index_map.get(&subject) {
-            Some(idx) => *idx,
-            None => return false, // Unknown concept
-        };
+        if !self.concepts.contains(subject) {
+            return false; // Unknown concept
+        }
 
         // BFS traversal to find if subject or its parents have the attribute
-        let mut stack = vec![start_idx];
+        let mut stack = vec![subject];
         let mut visited = vec![];
 
-        while let Some(current_idx) = stack.pop() {
-            if visited.contains(&current_idx) { continue; }
-            visited.push(current_idx);
+        while let Some(current) = stack.pop() {
+            if visited.contains(&current) { continue; }
+            visited.push(current);
 
             // Check outgoing edges
-            for edge in self.graph.edges(current_idx) {
-                let target = edge.target();
-                let relation = edge.weight();
-
+            for (relation, target) in self.concepts.edges(current) {
                 // If we found the attribute directly
-                if *relation == Relation::HasAttribute {
-                     if self.graph[target] == attribute {
-                         return true;
-                     }
+                if relation == Relation::HasAttribute && target == attribute {
+                    return true;
                 }
 
                 // If IsA, add parent to stack to check *their* attributes
-                if *relation == Relation::IsA {
+                if relation == Relation::IsA {
                     stack.push(target);
                 }
             }
@@ -91,15 +163,95 @@ impl SemanticGraph {
         false
     }
 
-    pub fn get_required_attributes(&self, subject: LemmaId) -> Vec<LemmaId> {
-        let mut reqs = Vec::new();
-        if let Some(idx) = self.index_map.get(&subject) {
-            for edge in self.graph.edges(*idx) {
-                if *edge.weight() == Relation::RequiresAttribute {
-                    reqs.push(self.graph[edge.target()]);
+    /// Outgoing edges of a concept (no inheritance).
+    pub fn relations(&self, concept: LemmaId) -> Vec<(Relation, LemmaId)> {
+        self.concepts.edges(concept).collect()
+    }
+
+    /// Every concept reachable through IsA edges, nearest first.
+    pub fn ancestors(&self, concept: LemmaId) -> Vec<LemmaId> {
+        let mut found: Vec<LemmaId> = Vec::new();
+        let mut queue = VecDeque::from([concept]);
+
+        while let Some(current) = queue.pop_front() {
+            for (relation, target) in self.relations(current) {
+                if relation == Relation::IsA && target != concept && !found.contains(&target) {
+                    found.push(target);
+                    queue.push_back(target);
                 }
             }
         }
-        reqs
+        found
+    }
+
+    pub fn get_required_attributes(&self, subject: LemmaId) -> Vec<LemmaId> {
+        self.concepts.edges(subject)
+            .filter(|(relation, _)| *relation == Relation::RequiresAttribute)
+            .map(|(_, target)| target)
+            .collect()
+    }
+}
+
+/// petgraph behind the interface of `CsrGraph`.
+#[cfg(feature = "petgraph")]
+#[derive(Default)]
+struct PetGraph {
+    graph: Graph<LemmaId, Relation, Directed>,
+    index_map: HashMap<LemmaId, NodeIndex>,
+}
+
+#[cfg(feature = "petgraph")]
+impl PetGraph {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn from_edges(edges: &[(LemmaId, LemmaId, Relation)]) -> Self {
+        let mut slf = Self::new();
+        for &(from, to, rel) in edges {
+            slf.add_edge(from, to, rel);
+        }
+        slf
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn contains(&self, concept: LemmaId) -> bool {
+        self.index_map.contains_key(&concept)
+    }
+
+    fn add_concept(&mut self, lemma: LemmaId) {
+        if !self.index_map.contains_key(&lemma) {
+            let idx = self.graph.add_node(lemma);
+            self.index_map.insert(lemma, idx);
+        }
+    }
+
+    fn add_edge(&mut self, from: LemmaId, to: LemmaId, rel: Relation) {
+        let from_idx = *self.index_map.entry(from).or_insert_with(|| self.graph.add_node(from));
+        let to_idx = *self.index_map.entry(to).or_insert_with(|| self.graph.add_node(to));
+
+        self.graph.add_edge(from_idx, to_idx, rel);
+    }
+
+    fn edges(&self, concept: LemmaId) -> impl Iterator<Item = (Relation, LemmaId)> + '_ {
+        self.index_map.get(&concept).into_iter()
+            .flat_map(move |&idx| self.graph.edges(idx).map(|e| (*e.weight(), self.graph[e.target()])))
+    }
+}
+
+fn unarchive<T: Archive>(archived: &T::Archived) -> T
+where
+    T::Archived: Deserialize<T, Infallible>,
+{
+    match archived.deserialize(&mut Infallible) {
+        Ok(value) => value,
+        Err(never) => match never {},
     }
 }
@@ -1,16 +1,20 @@
+pub mod csr;
 pub mod graph;
+pub mod roles;
 pub mod solver;
 
+pub use csr::CsrGraph;
 pub use graph::SemanticGraph;
 pub use logos_protocol::Relation;
-pub use solver::validate_semantics;
+pub use roles::{thematic_roles, ThematicAssignment, ThematicRole};
+pub use solver::{validate_semantics, SemanticError, SemanticEvidence, SemanticSystem};
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use logos_ecs::LogosWorld;
     use logos_ecs::components::DependencyRole;
-    use logos_protocol::{LemmaId, MorphFlags};
+    use logos_protocol::{Animacy, AnimacyRole, LemmaId, MorphFlags, PrepositionRole, SemanticRole};
 
     #[test]
     fn test_semantic_validation() {
@@ -38,8 +42,8 @@ mod tests {
         // 2. Setup Sentence: "Eat Stone"
         let mut world = LogosWorld::new();
         
-        let verb_entity = world.add_token("Eat".to_string(), Some(eat), MorphFlags::empty());
-        let obj_entity = world.add_token("Stone".to_string(), Some(stone), MorphFlags::empty());
+        let verb_entity = world.add_token("Eat", Some(eat), MorphFlags::empty());
+        let obj_entity = world.add_token("Stone", Some(stone), MorphFlags::empty());
         
         world.set_dependency(obj_entity, verb_entity, DependencyRole::Object);
 
@@ -50,12 +54,209 @@ mod tests {
 
         // 4. Setup Sentence: "Eat Apple"
         let mut world2 = LogosWorld::new();
-        let verb2 = world2.add_token("Eat".to_string(), Some(eat), MorphFlags::empty());
-        let obj2 = world2.add_token("Apple".to_string(), Some(apple), MorphFlags::empty());
+        let verb2 = world2.add_token("Eat", Some(eat), MorphFlags::empty());
+        let obj2 = world2.add_token("Apple", Some(apple), MorphFlags::empty());
         world2.set_dependency(obj2, verb2, DependencyRole::Object);
 
         // 5. Validate (Should Pass)
         let errors2 = validate_semantics(&world2, &graph);
         assert_eq!(errors2.len(), 0);
+
+        // 6. Inspection helpers
+        assert_eq!(graph.ancestors(apple), vec![food]);
+        assert_eq!(graph.relations(food), vec![(Relation::HasAttribute, edible)]);
+
+        // 7. As a registered system, run in one pass with the agreement checks
+        world.register_system(Box::new(SemanticSystem::new(std::sync::Arc::new(graph))));
+        let diagnostics = world.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].system, diagnostics[0].entity), (SemanticSystem::NAME, obj_entity));
+    }
+
+    #[test]
+    fn test_preposition_roles() {
+        let (en, eis) = (LemmaId(1), LemmaId(2));
+        let (place, time, city, night, virtue, quality) = (LemmaId(10), LemmaId(11), LemmaId(12), LemmaId(13), LemmaId(14), LemmaId(15));
+        let mut graph = SemanticGraph::new();
+        graph.add_relation(city, place, Relation::IsA);
+        graph.add_relation(night, time, Relation::IsA);
+        graph.add_relation(virtue, quality, Relation::IsA);
+        let dative = MorphFlags::DATIVE.bits();
+        graph.add_preposition_role(PrepositionRole { preposition: en, cases: dative, role: SemanticRole::Location, concepts: vec![place] });
+        graph.add_preposition_role(PrepositionRole { preposition: en, cases: dative, role: SemanticRole::Time, concepts: vec![time] });
+        graph.add_preposition_role(PrepositionRole { preposition: eis, cases: 0, role: SemanticRole::Destination, concepts: vec![place] });
+
+        let check = |preposition: (&str, LemmaId), argument: (&str, LemmaId), flags: MorphFlags| {
+            let mut world = LogosWorld::new();
+            let head = world.add_token(preposition.0, Some(preposition.1), MorphFlags::empty());
+            let arg = world.add_token(argument.0, Some(argument.1), flags);
+            world.set_dependency(arg, head, DependencyRole::PrepositionArg);
+            validate_semantics(&world, &graph).into_iter().map(|e| e.message).collect::<Vec<_>>()
+        };
+
+        // Either role of ἐν will do
+        assert!(check(("ἐν", en), ("πόλει", city), MorphFlags::DATIVE).is_empty());
+        assert!(check(("ἐν", en), ("νυκτί", night), MorphFlags::DATIVE).is_empty());
+        assert_eq!(check(("ἐν", en), ("ἀρετῇ", virtue), MorphFlags::DATIVE), vec!["ἐν expects a location- or time-like argument, got 'ἀρετῇ'"]);
+        // The roles are for the dative only
+        assert!(check(("ἐν", en), ("ἀρετήν", virtue), MorphFlags::ACCUSATIVE).is_empty());
+
+        assert!(check(("εἰς", eis), ("πόλιν", city), MorphFlags::ACCUSATIVE).is_empty());
+        assert_eq!(check(("εἰς", eis), ("ἀρετήν", virtue), MorphFlags::ACCUSATIVE), vec!["εἰς expects a destination-like argument, got 'ἀρετήν'"]);
+        // Concepts missing from the graph are not judged
+        assert!(check(("εἰς", eis), ("λίθον", LemmaId(99)), MorphFlags::ACCUSATIVE).is_empty());
+    }
+
+    #[test]
+    fn test_animacy() {
+        let (speak, seem) = (LemmaId(1), LemmaId(2));
+        let (human, teacher, animal, horse, living, stone, virtue) = (LemmaId(10), LemmaId(11), LemmaId(12), LemmaId(13), LemmaId(14), LemmaId(15), LemmaId(16));
+        let mut graph = SemanticGraph::new();
+        graph.add_relation(teacher, human, Relation::IsA);
+        graph.add_relation(human, living, Relation::IsA);
+        graph.add_relation(horse, animal, Relation::IsA);
+        graph.add_relation(animal, living, Relation::IsA);
+        graph.set_animacy(living, Animacy::Inanimate);
+        graph.set_animacy(human, Animacy::Human);
+        graph.set_animacy(animal, Animacy::Animal);
+        graph.set_animacy(stone, Animacy::Inanimate);
+        graph.set_animacy(virtue, Animacy::Abstract);
+        graph.add_animacy_constraint(speak, AnimacyRole::Subject, Animacy::Human);
+        graph.add_animacy_constraint(seem, AnimacyRole::Experiencer, Animacy::Animal);
+
+        // The nearest mark wins
+        assert_eq!(graph.animacy_of(teacher), Some(Animacy::Human));
+        assert_eq!(graph.animacy_of(horse), Some(Animacy::Animal));
+        assert_eq!(graph.animacy_of(living), Some(Animacy::Inanimate));
+        assert_eq!(graph.animacy_of(LemmaId(99)), None);
+        assert!(Animacy::Human > Animacy::Animal && Animacy::Inanimate > Animacy::Abstract);
+
+        let check = |verb: (&str, LemmaId), argument: (&str, LemmaId), role: DependencyRole| {
+            let mut world = LogosWorld::new();
+            let head = world.add_token(verb.0, Some(verb.1), MorphFlags::empty());
+            let arg = world.add_token(argument.0, Some(argument.1), MorphFlags::empty());
+            world.set_dependency(arg, head, role);
+            validate_semantics(&world, &graph).into_iter().map(|e| e.message).collect::<Vec<_>>()
+        };
+        assert!(check(("λέγει", speak), ("διδάσκαλος", teacher), DependencyRole::Subject).is_empty());
+        assert_eq!(check(("λέγει", speak), ("λίθος", stone), DependencyRole::Subject), vec!["λέγει expects a human subject, got 'λίθος' (inanimate)"]);
+        assert!(check(("δοκεῖ", seem), ("ἵππῳ", horse), DependencyRole::IndirectObject).is_empty());
+        assert_eq!(check(("δοκεῖ", seem), ("ἀρετῇ", virtue), DependencyRole::IndirectObject), vec!["δοκεῖ expects a human or animal experiencer, got 'ἀρετῇ' (abstract)"]);
+        // Unconstrained verbs and unmarked arguments are not judged
+        assert!(check(("δοκεῖ", seem), ("λίθος", stone), DependencyRole::Subject).is_empty());
+        assert!(check(("λέγει", speak), ("τι", LemmaId(99)), DependencyRole::Subject).is_empty());
+    }
+
+    #[test]
+    fn test_error_evidence() {
+        let (eat, speak) = (LemmaId(1), LemmaId(2));
+        let (edible, stone, mineral, tree, living) = (LemmaId(10), LemmaId(11), LemmaId(12), LemmaId(13), LemmaId(14));
+        let mut graph = SemanticGraph::new();
+        graph.add_relation(eat, edible, Relation::RequiresAttribute);
+        graph.add_relation(stone, mineral, Relation::IsA);
+        graph.add_relation(tree, living, Relation::IsA);
+        graph.set_animacy(living, Animacy::Inanimate);
+        graph.add_animacy_constraint(speak, AnimacyRole::Subject, Animacy::Human);
+        assert_eq!(graph.animacy_source(tree), Some((living, Animacy::Inanimate)));
+
+        let check = |verb: LemmaId, argument: LemmaId, role: DependencyRole| {
+            let mut world = LogosWorld::new();
+            let head = world.add_token("V", Some(verb), MorphFlags::empty());
+            let arg = world.add_token("N", Some(argument), MorphFlags::empty());
+            world.set_dependency(arg, head, role);
+            validate_semantics(&world, &graph).into_iter().map(|e| (e.rule, e.evidence)).collect::<Vec<_>>()
+        };
+
+        // The argument and every kind it inherits from were searched
+        assert_eq!(check(eat, stone, DependencyRole::Object), vec![(
+            "verb-requirement",
+            SemanticEvidence::Path { searched: vec![stone, mineral], sought: vec![edible] },
+        )]);
+        // The animacy is the one inherited from `living`
+        assert_eq!(check(speak, tree, DependencyRole::Subject), vec![(
+            "animacy-constraint",
+            SemanticEvidence::Animacy { from: living, animacy: Animacy::Inanimate, minimum: Animacy::Human },
+        )]);
+    }
+
+    #[test]
+    fn test_thematic_roles() {
+        let (give, seem, friend, stone) = (LemmaId(1), LemmaId(2), LemmaId(3), LemmaId(4));
+        let mut graph = SemanticGraph::new();
+        graph.set_animacy(friend, Animacy::Human);
+        graph.set_animacy(stone, Animacy::Inanimate);
+        graph.add_animacy_constraint(seem, AnimacyRole::Experiencer, Animacy::Animal);
+
+        // ὁ παῖς δίδωσι τὸ βιβλίον τῷ φίλῳ: agent, patient, recipient
+        let mut world = LogosWorld::new();
+        let boy = world.add_token("παῖς", None, MorphFlags::NOMINATIVE);
+        let verb = world.add_token("δίδωσι", Some(give), MorphFlags::ACTIVE);
+        let book = world.add_token("βιβλίον", None, MorphFlags::ACCUSATIVE);
+        let to_friend = world.add_token("φίλῳ", Some(friend), MorphFlags::DATIVE);
+        let with_stone = world.add_token("λίθῳ", Some(stone), MorphFlags::DATIVE);
+        world.set_dependency(boy, verb, DependencyRole::Subject);
+        world.set_dependency(book, verb, DependencyRole::Object);
+        world.set_dependency(to_friend, verb, DependencyRole::IndirectObject);
+        world.set_dependency(with_stone, verb, DependencyRole::IndirectObject);
+        let roles: Vec<_> = thematic_roles(&world, &graph).into_iter().map(|r| (r.argument, r.predicate, r.role)).collect();
+        assert_eq!(roles, vec![
+            (boy, verb, ThematicRole::Agent),
+            (book, verb, ThematicRole::Patient),
+            (to_friend, verb, ThematicRole::Recipient),
+            (with_stone, verb, ThematicRole::Instrument),
+        ]);
+
+        // ὁ παῖς βάλλεται ὑπὸ τοῦ φίλου: the passive subject is the patient,
+        // the ὑπό phrase the agent
+        let mut world = LogosWorld::new();
+        let boy = world.add_token("παῖς", None, MorphFlags::NOMINATIVE);
+        let verb = world.add_token("βάλλεται", None, MorphFlags::PASSIVE);
+        let by = world.add_token("ὑπό", None, MorphFlags::empty());
+        let friend_token = world.add_token("φίλου", Some(friend), MorphFlags::GENITIVE);
+        world.set_dependency(boy, verb, DependencyRole::Subject);
+        world.set_dependency(by, verb, DependencyRole::PassiveAgent);
+        world.set_dependency(friend_token, by, DependencyRole::PrepositionArg);
+        let roles: Vec<_> = thematic_roles(&world, &graph).into_iter().map(|r| (r.argument, r.predicate, r.role)).collect();
+        assert_eq!(roles, vec![(boy, verb, ThematicRole::Patient), (friend_token, verb, ThematicRole::Agent)]);
+
+        // A verb constraining its experiencer makes the dative one
+        let mut world = LogosWorld::new();
+        let verb = world.add_token("δοκεῖ", Some(seem), MorphFlags::empty());
+        let to_friend = world.add_token("φίλῳ", Some(friend), MorphFlags::DATIVE);
+        world.set_dependency(to_friend, verb, DependencyRole::IndirectObject);
+        assert_eq!(thematic_roles(&world, &graph)[0].role, ThematicRole::Experiencer);
+    }
+
+    #[test]
+    fn test_csr_graph() {
+        let (animal, horse, living, edible, grass) = (LemmaId(5), LemmaId(9), LemmaId(2), LemmaId(7), LemmaId(3));
+        let edges = [
+            (horse, animal, Relation::IsA),
+            (animal, living, Relation::IsA),
+            (horse, edible, Relation::RequiresAttribute),
+            (grass, edible, Relation::HasAttribute),
+        ];
+        let built = CsrGraph::from_edges(&edges);
+        let mut grown = CsrGraph::new();
+        let mut graph = SemanticGraph::new();
+        for &(from, to, relation) in &edges {
+            grown.add_edge(from, to, relation);
+            graph.add_relation(from, to, relation);
+        }
+
+        // Built at once or edge by edge, the rows list what the graph walks
+        for csr in [&built, &grown] {
+            assert_eq!((csr.node_count(), csr.edge_count()), (graph.node_count(), graph.edge_count()));
+            for concept in [animal, horse, living, edible, grass, LemmaId(1)] {
+                assert_eq!(csr.contains(concept), graph.contains(concept));
+                assert_eq!(csr.edges(concept).collect::<Vec<_>>(), graph.relations(concept));
+            }
+        }
+        assert_eq!(built.edges(horse).collect::<Vec<_>>(), vec![(Relation::RequiresAttribute, edible), (Relation::IsA, animal)]);
+
+        grown.add_concept(LemmaId(1));
+        assert!(grown.contains(LemmaId(1)));
+        assert_eq!(grown.edges(LemmaId(1)).count(), 0);
+        assert_eq!(grown.edges(grass).collect::<Vec<_>>(), vec![(Relation::HasAttribute, edible)]);
     }
 }
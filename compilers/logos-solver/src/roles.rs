@@ -0,0 +1,86 @@
+//! A shallow semantic layer over the dependency tree: which argument of a
+//! verb does, undergoes or receives its action, read off the syntactic
+//! role and the verb's voice.
+
+use logos_ecs::{Entity, LogosWorld};
+use logos_ecs::components::{DependencyRole, Morphology, TokenData};
+use logos_protocol::{Animacy, AnimacyRole, MorphFlags};
+use crate::graph::SemanticGraph;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThematicRole {
+    Agent,
+    Patient,
+    Recipient,
+    Instrument,
+    Experiencer,
+}
+
+impl ThematicRole {
+    pub fn name(self) -> &'static str {
+        match self {
+            ThematicRole::Agent => "agent",
+            ThematicRole::Patient => "patient",
+            ThematicRole::Recipient => "recipient",
+            ThematicRole::Instrument => "instrument",
+            ThematicRole::Experiencer => "experiencer",
+        }
+    }
+}
+
+/// The role `argument` plays in the action of `predicate`, its verb.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThematicAssignment {
+    pub argument: Entity,
+    pub predicate: Entity,
+    pub role: ThematicRole,
+}
+
+/// Thematic roles of the verbs' arguments, in token order:
+///
+/// - the subject is the agent, or the patient when the verb is passive;
+/// - the object is the patient;
+/// - the argument of a ὑπό phrase (a `PassiveAgent`) is the agent;
+/// - a dative indirect object is the experiencer of a verb that constrains
+///   one, else the recipient when it is animate and the instrument when the
+///   graph knows it to be inanimate or abstract. Other indirect objects
+///   (the genitive of Modern Greek) are recipients.
+///
+/// Dependencies that cross sentences are left out, as in `validate_semantics`.
+pub fn thematic_roles(world: &LogosWorld, graph: &SemanticGraph) -> Vec<ThematicAssignment> {
+    let inner = world.inner();
+    let flags = |entity| inner.get::<&Morphology>(entity).map_or(MorphFlags::empty(), |m| m.flags);
+    let lemma = |entity| inner.get::<&TokenData>(entity).ok().and_then(|t| t.lemma_id);
+
+    let mut roles = Vec::new();
+    for &id in world.tokens() {
+        let Some((head, role)) = world.dependency(id) else { continue };
+        if world.sentence_of(id) != world.sentence_of(head) {
+            continue;
+        }
+        let (predicate, role) = match role {
+            DependencyRole::Subject if flags(head).contains(MorphFlags::PASSIVE) => (head, ThematicRole::Patient),
+            DependencyRole::Subject => (head, ThematicRole::Agent),
+            DependencyRole::Object => (head, ThematicRole::Patient),
+            DependencyRole::PrepositionArg => match world.dependency(head) {
+                Some((verb, DependencyRole::PassiveAgent)) => (verb, ThematicRole::Agent),
+                _ => continue,
+            },
+            DependencyRole::IndirectObject => {
+                let experiencer = lemma(head).is_some_and(|verb| graph.animacy_constraint(verb, AnimacyRole::Experiencer).is_some());
+                let animacy = lemma(id).and_then(|argument| graph.animacy_of(argument));
+                let role = if experiencer {
+                    ThematicRole::Experiencer
+                } else if flags(id).contains(MorphFlags::DATIVE) && animacy.is_some_and(|a| a < Animacy::Animal) {
+                    ThematicRole::Instrument
+                } else {
+                    ThematicRole::Recipient
+                };
+                (head, role)
+            }
+            _ => continue,
+        };
+        roles.push(ThematicAssignment { argument: id, predicate, role });
+    }
+    roles
+}
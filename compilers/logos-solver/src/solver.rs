@@ -1,12 +1,53 @@
-use logos_ecs::LogosWorld;
-use logos_ecs::components::{Syntax, DependencyRole, TokenData};
+use std::sync::Arc;
+
+use logos_ecs::{Entity, LogosWorld};
+use logos_ecs::components::{DependencyRole, Morphology, TokenData};
+use logos_ecs::systems::{certainty, Diagnostic, ValidationSystem};
+use logos_protocol::messages::{render, Locale};
+use logos_protocol::{Animacy, AnimacyRole, LemmaId, MessageArgs};
 use crate::graph::{SemanticGraph};
 
 #[derive(Debug)]
 pub struct SemanticError {
     pub verb_text: String,
     pub object_text: String,
+    /// The message in English (see `localized`)
     pub message: String,
+    /// Stable kind of the violation, e.g. "semantic.animacy"
+    pub code: &'static str,
+    /// The rule that found it: "verb-requirement", "preposition-role" or
+    /// "animacy-constraint"
+    pub rule: &'static str,
+    /// What the rule looked up in the graph
+    pub evidence: SemanticEvidence,
+    /// Parameters of the `code`'s message template (see
+    /// `logos_protocol::messages`)
+    pub args: MessageArgs,
+    /// The offending object token
+    pub entity: Entity,
+}
+
+impl SemanticError {
+    /// `message` in `locale`.
+    pub fn localized(&self, locale: Locale) -> String {
+        render(locale, self.code, &self.args)
+    }
+}
+
+/// Why a semantic rule failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SemanticEvidence {
+    /// None of `searched`, the argument's concept and its kinds (IsA),
+    /// nearest first, is or has any of `sought`
+    Path { searched: Vec<LemmaId>, sought: Vec<LemmaId> },
+    /// The argument's animacy, marked on `from` (its concept or a kind of
+    /// it), is below the verb's `minimum`
+    Animacy { from: LemmaId, animacy: Animacy, minimum: Animacy },
+}
+
+/// `concept` and the kinds it inherits from, the path a lookup searches.
+fn search_path(graph: &SemanticGraph, concept: LemmaId) -> Vec<LemmaId> {
+    std::iter::once(concept).chain(graph.ancestors(concept)).collect()
 }
 
 pub fn validate_semantics(world: &LogosWorld, graph: &SemanticGraph) -> Vec<SemanticError> {
@@ -14,13 +55,33 @@ pub fn validate_semantics(world: &LogosWorld, graph: &SemanticGraph) -> Vec<Sema
     let inner = world.inner();
 
     // 1. Iterate over all syntactic dependencies
-    for (_id, (syntax, object_token)) in inner.query::<(&Syntax, &TokenData)>().iter() {
+    for &id in world.tokens() {
+        let Some((head, role)) = world.dependency(id) else { continue };
+        let Ok(object_token) = inner.get::<&TokenData>(id) else { continue };
+
+        if role == DependencyRole::PrepositionArg && world.sentence_of(id) == world.sentence_of(head) {
+            let Ok(preposition) = inner.get::<&TokenData>(head) else { continue };
+            let cases = inner.get::<&Morphology>(id).map_or(0, |m| m.flags.bits());
+            errors.extend(check_preposition_arg(graph, &preposition, &object_token, id, cases));
+            continue;
+        }
+
+        let animacy_role = match role {
+            DependencyRole::Subject => Some(AnimacyRole::Subject),
+            DependencyRole::IndirectObject => Some(AnimacyRole::Experiencer),
+            _ => None,
+        };
+        if let Some(animacy_role) = animacy_role.filter(|_| world.sentence_of(id) == world.sentence_of(head)) {
+            let Ok(verb) = inner.get::<&TokenData>(head) else { continue };
+            errors.extend(check_animacy(graph, &verb, &object_token, id, animacy_role));
+            continue;
+        }
         
-        // We only care about Verb-Object relations
-        if syntax.role == DependencyRole::Object {
+        // We only care about Verb-Object relations within one sentence
+        if role == DependencyRole::Object && world.sentence_of(id) == world.sentence_of(head) {
             
             // Get the Head (The Verb)
-            if let Ok(verb_token) = inner.get::<&TokenData>(syntax.head) {
+            if let Ok(verb_token) = inner.get::<&TokenData>(head) {
                 
                 // Ensure both have Lemmas (if Unknown, we can't check semantics)
                 if let (Some(verb_id), Some(object_id)) = (verb_token.lemma_id, object_token.lemma_id) {
@@ -31,10 +92,16 @@ pub fn validate_semantics(world: &LogosWorld, graph: &SemanticGraph) -> Vec<Sema
                     
                     for req_attr in requirements {
                         if !graph.satisfies_constraint(object_id, req_attr) {
+                            let args = vec![("object", object_token.text.to_string()), ("verb", verb_token.text.to_string())];
                             errors.push(SemanticError {
-                                verb_text: verb_token.text.clone(),
-                                object_text: object_token.text.clone(),
-                                message: format!("Constraint Violation: Object '{}' does not satisfy requirement of '{}'.", object_token.text, verb_token.text),
+                                entity: id,
+                                verb_text: verb_token.text.to_string(),
+                                object_text: object_token.text.to_string(),
+                                code: "semantic.constraint",
+                                rule: "verb-requirement",
+                                evidence: SemanticEvidence::Path { searched: search_path(graph, object_id), sought: vec![req_attr] },
+                                message: render(Locale::English, "semantic.constraint", &args),
+                                args,
                             });
                         }
                     }
@@ -45,3 +112,114 @@ pub fn validate_semantics(world: &LogosWorld, graph: &SemanticGraph) -> Vec<Sema
     
     errors
 }
+
+/// Whether the argument of a preposition fits one of the roles the
+/// preposition gives it in the argument's case. Arguments the graph knows
+/// nothing about are let through: the mapping of prepositions is meant to
+/// catch contradictions, not to demand an ontology entry for every noun.
+fn check_preposition_arg(graph: &SemanticGraph, preposition: &TokenData, argument: &TokenData, entity: Entity, cases: u32) -> Option<SemanticError> {
+    let (Some(preposition_id), Some(argument_id)) = (preposition.lemma_id, argument.lemma_id) else { return None };
+    let roles = graph.preposition_roles(preposition_id, cases);
+    if roles.is_empty() || !graph.contains(argument_id) {
+        return None;
+    }
+    if roles.iter().any(|r| r.concepts.iter().any(|&c| graph.fits(argument_id, c))) {
+        return None;
+    }
+    let mut names: Vec<&str> = roles.iter().map(|r| r.role.name()).collect();
+    names.dedup();
+    let expected = names.join("- or ");
+    let article = if expected.starts_with(['a', 'e', 'i', 'o', 'u']) { "an" } else { "a" };
+    let args = vec![
+        ("preposition", preposition.text.to_string()),
+        ("article", article.to_string()),
+        ("expected", expected),
+        ("choices", names.join(", ")),
+        ("argument", argument.text.to_string()),
+    ];
+    Some(SemanticError {
+        entity,
+        verb_text: preposition.text.to_string(),
+        object_text: argument.text.to_string(),
+        code: "semantic.preposition",
+        rule: "preposition-role",
+        evidence: SemanticEvidence::Path {
+            searched: search_path(graph, argument_id),
+            sought: roles.iter().flat_map(|r| r.concepts.iter().copied()).collect(),
+        },
+        message: render(Locale::English, "semantic.preposition", &args),
+        args,
+    })
+}
+
+/// Whether a verb's subject or experiencer (its indirect object: the
+/// dative, or the genitive of Modern Greek) is as animate as the
+/// verb asks. Arguments whose animacy is unknown are let through.
+fn check_animacy(graph: &SemanticGraph, verb: &TokenData, argument: &TokenData, entity: Entity, role: AnimacyRole) -> Option<SemanticError> {
+    let (Some(verb_id), Some(argument_id)) = (verb.lemma_id, argument.lemma_id) else { return None };
+    let minimum = graph.animacy_constraint(verb_id, role)?;
+    let (from, animacy) = graph.animacy_source(argument_id)?;
+    if animacy >= minimum {
+        return None;
+    }
+    let expected: Vec<&str> = Animacy::ALL.iter().rev().filter(|&&a| a >= minimum).map(|a| a.name()).collect();
+    let what = match role {
+        AnimacyRole::Subject => "subject",
+        AnimacyRole::Experiencer => "experiencer",
+    };
+    let article = if expected[0].starts_with(['a', 'e', 'i', 'o', 'u']) { "an" } else { "a" };
+    let args = vec![
+        ("verb", verb.text.to_string()),
+        ("article", article.to_string()),
+        ("expected", expected.join(" or ")),
+        ("choices", expected.join(", ")),
+        ("role", what.to_string()),
+        ("argument", argument.text.to_string()),
+        ("animacy", animacy.name().to_string()),
+    ];
+    Some(SemanticError {
+        entity,
+        verb_text: verb.text.to_string(),
+        object_text: argument.text.to_string(),
+        code: "semantic.animacy",
+        rule: "animacy-constraint",
+        evidence: SemanticEvidence::Animacy { from, animacy, minimum },
+        message: render(Locale::English, "semantic.animacy", &args),
+        args,
+    })
+}
+
+/// `validate_semantics` as a `ValidationSystem`, for `LogosWorld::register_system`.
+pub struct SemanticSystem {
+    graph: Arc<SemanticGraph>,
+}
+
+impl SemanticSystem {
+    pub const NAME: &'static str = "semantics";
+
+    pub fn new(graph: Arc<SemanticGraph>) -> Self {
+        Self { graph }
+    }
+}
+
+impl ValidationSystem for SemanticSystem {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check(&self, world: &LogosWorld) -> Vec<Diagnostic> {
+        validate_semantics(world, &self.graph)
+            .into_iter()
+            .map(|e| Diagnostic {
+                system: Self::NAME,
+                entity: e.entity,
+                source: e.object_text,
+                target: e.verb_text,
+                message: e.message,
+                code: e.code,
+                args: e.args,
+                confidence: world.head_of(e.entity).map_or(1.0, |head| certainty(world.inner(), e.entity, head)),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,140 @@
+//! Fluent construction of analyzed sentences for tests and examples:
+//!
+//! ```
+//! use logos_ecs::LogosWorld;
+//!
+//! let mut world = LogosWorld::new();
+//! let mut s = world.sentence();
+//! let verb = s.verb("λέγει").sg().third().add();
+//! s.noun("παῖδες").nom().pl().masc().subject_of(verb);
+//! assert_eq!(world.validate().len(), 1); // number mismatch
+//! ```
+
+use hecs::Entity;
+use logos_protocol::{LemmaId, MorphFlags};
+
+use crate::components::DependencyRole;
+use crate::LogosWorld;
+
+/// Adds words to one sentence of a `LogosWorld` (see `LogosWorld::sentence`).
+pub struct SentenceBuilder<'w> {
+    world: &'w mut LogosWorld,
+}
+
+/// One word being described; `add` or a linking method puts it in the world.
+pub struct WordBuilder<'a> {
+    world: &'a mut LogosWorld,
+    text: String,
+    lemma_id: Option<LemmaId>,
+    flags: MorphFlags,
+}
+
+impl LogosWorld {
+    /// Starts a sentence for fluent construction (a new one unless the world
+    /// is still empty).
+    pub fn sentence(&mut self) -> SentenceBuilder<'_> {
+        if !self.tokens.is_empty() {
+            self.add_sentence();
+        }
+        SentenceBuilder { world: self }
+    }
+}
+
+impl<'w> SentenceBuilder<'w> {
+    pub fn word(&mut self, text: &str) -> WordBuilder<'_> {
+        WordBuilder { world: self.world, text: text.to_string(), lemma_id: None, flags: MorphFlags::empty() }
+    }
+
+    pub fn noun(&mut self, text: &str) -> WordBuilder<'_> {
+        self.word(text).flag(MorphFlags::NOUN)
+    }
+
+    pub fn verb(&mut self, text: &str) -> WordBuilder<'_> {
+        self.word(text).flag(MorphFlags::VERB)
+    }
+
+    pub fn adjective(&mut self, text: &str) -> WordBuilder<'_> {
+        self.word(text).flag(MorphFlags::ADJECTIVE)
+    }
+
+    pub fn article(&mut self, text: &str) -> WordBuilder<'_> {
+        self.word(text).flag(MorphFlags::ARTICLE)
+    }
+
+    pub fn pronoun(&mut self, text: &str) -> WordBuilder<'_> {
+        self.word(text).flag(MorphFlags::PRONOUN)
+    }
+
+    /// Starts a new clause within the sentence.
+    pub fn clause(&mut self) -> &mut Self {
+        self.world.add_clause();
+        self
+    }
+}
+
+impl<'a> WordBuilder<'a> {
+    pub fn flag(mut self, flags: MorphFlags) -> Self {
+        self.flags |= flags;
+        self
+    }
+
+    pub fn lemma(mut self, id: LemmaId) -> Self {
+        self.lemma_id = Some(id);
+        self
+    }
+
+    pub fn nom(self) -> Self { self.flag(MorphFlags::NOMINATIVE) }
+    pub fn gen(self) -> Self { self.flag(MorphFlags::GENITIVE) }
+    pub fn dat(self) -> Self { self.flag(MorphFlags::DATIVE) }
+    pub fn acc(self) -> Self { self.flag(MorphFlags::ACCUSATIVE) }
+    pub fn voc(self) -> Self { self.flag(MorphFlags::VOCATIVE) }
+
+    pub fn sg(self) -> Self { self.flag(MorphFlags::SINGULAR) }
+    pub fn pl(self) -> Self { self.flag(MorphFlags::PLURAL) }
+
+    pub fn masc(self) -> Self { self.flag(MorphFlags::MASCULINE) }
+    pub fn fem(self) -> Self { self.flag(MorphFlags::FEMININE) }
+    pub fn neut(self) -> Self { self.flag(MorphFlags::NEUTER) }
+
+    pub fn first(self) -> Self { self.flag(MorphFlags::FIRST_PERSON) }
+    pub fn second(self) -> Self { self.flag(MorphFlags::SECOND_PERSON) }
+    pub fn third(self) -> Self { self.flag(MorphFlags::THIRD_PERSON) }
+
+    pub fn active(self) -> Self { self.flag(MorphFlags::ACTIVE) }
+    pub fn passive(self) -> Self { self.flag(MorphFlags::PASSIVE) }
+
+    pub fn present(self) -> Self { self.flag(MorphFlags::PRESENT) }
+    pub fn past(self) -> Self { self.flag(MorphFlags::PAST) }
+    pub fn future(self) -> Self { self.flag(MorphFlags::FUTURE) }
+
+    pub fn reflexive(self) -> Self { self.flag(MorphFlags::REFLEXIVE) }
+
+    /// Adds the word without attaching it to anything.
+    pub fn add(self) -> Entity {
+        self.world.add_token(&self.text, self.lemma_id, self.flags)
+    }
+
+    /// Adds the word as the `role` dependent of `head`.
+    pub fn depends_on(self, head: Entity, role: DependencyRole) -> Entity {
+        let world = &mut *self.world;
+        let entity = world.add_token(&self.text, self.lemma_id, self.flags);
+        world.set_dependency(entity, head, role);
+        entity
+    }
+
+    pub fn subject_of(self, head: Entity) -> Entity {
+        self.depends_on(head, DependencyRole::Subject)
+    }
+
+    pub fn object_of(self, head: Entity) -> Entity {
+        self.depends_on(head, DependencyRole::Object)
+    }
+
+    pub fn modifier_of(self, head: Entity) -> Entity {
+        self.depends_on(head, DependencyRole::Modifier)
+    }
+
+    pub fn reflexive_of(self, head: Entity) -> Entity {
+        self.depends_on(head, DependencyRole::Reflexive)
+    }
+}
@@ -1,10 +1,13 @@
+use std::sync::Arc;
+
 use hecs::Entity;
 use logos_protocol::{LemmaId, MorphFlags};
 
 /// Basic data about the token (Source of Truth)
 #[derive(Debug, Clone)]
 pub struct TokenData {
-    pub text: String,
+    /// Interned by `LogosWorld`: every token of one form shares the string
+    pub text: Arc<str>,
     pub lemma_id: Option<LemmaId>,
 }
 
@@ -13,16 +16,19 @@ pub struct TokenData {
 #[derive(Debug, Clone, Copy)]
 pub struct Morphology {
     pub flags: MorphFlags,
+    /// How sure the analysis is, 0 to 1
+    pub confidence: f32,
 }
 
 impl Morphology {
     pub fn new(flags: MorphFlags) -> Self {
-        Self { flags }
+        Self { flags, confidence: 1.0 }
     }
 }
 
 /// The Syntactic Role of a word
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DependencyRole {
     Root,
     Subject,
@@ -36,12 +42,41 @@ pub enum DependencyRole {
     AbsoluteClause,
     Complement,
     RelativeClause,
+    Reflexive,
+    Comparison,
     // Add more as needed (IndirectObj, etc.)
 }
 
+/// Marks a word the text leaves out (the copula of a nominal sentence, a
+/// gapped verb) that the parser restored so its arguments have a head
+#[derive(Debug, Clone, Copy)]
+pub struct Reconstructed;
+
 /// The Syntactic Tree Structure
 #[derive(Debug, Clone, Copy)]
 pub struct Syntax {
     pub head: Entity, // The parent node in the dependency tree
     pub role: DependencyRole,
+    /// How sure the parser is of the arc, 0 to 1
+    pub confidence: f32,
+}
+
+/// How an adjective stands to its noun's article
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdjectivePosition {
+    /// ὁ ἀγαθὸς ἄνθρωπος, ὁ ἄνθρωπος ὁ ἀγαθός: "the good man"
+    Attributive,
+    /// ὁ ἄνθρωπος ἀγαθός: "the man is good"
+    Predicative,
+    /// No article to decide by
+    Bare,
+}
+
+/// Attached to adjectives: the noun they belong to (not necessarily their
+/// syntactic head) and their position relative to it
+#[derive(Debug, Clone, Copy)]
+pub struct Placement {
+    pub noun: Entity,
+    pub position: AdjectivePosition,
 }
@@ -1,17 +1,91 @@
+pub mod builder;
 pub mod components;
+pub mod snapshot;
 pub mod systems;
+pub mod tree;
 
-use hecs::{World, Entity};
-use components::{TokenData, Morphology, Syntax, DependencyRole};
-use systems::agreement::{check_agreement, AgreementError};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
+use hecs::World;
+pub use hecs::Entity;
+use components::{AdjectivePosition, TokenData, Morphology, Placement, Reconstructed, Syntax, DependencyRole};
+pub use builder::{SentenceBuilder, WordBuilder};
+use systems::agreement::{check_agreement, AgreementError, Inflector};
+use systems::{AgreementSystem, Diagnostic, ValidationSystem};
+use logos_protocol::{ClauseId, SentenceId};
+
+/// Every token also carries the `SentenceId` and `ClauseId` current when it
+/// was added, so checks never relate words of different sentences.
 pub struct LogosWorld {
     world: World,
+    /// Tokens in the order they were added
+    tokens: Vec<Entity>,
+    /// Distinct token texts; a corpus repeats a few forms many times over
+    forms: HashSet<Arc<str>>,
+    sentence: SentenceId,
+    clause: ClauseId,
+    systems: Vec<Box<dyn ValidationSystem>>,
+}
+
+/// One token of a sentence added in bulk (see `LogosWorld::add_sentence_tokens`).
+#[derive(Debug, Clone, Copy)]
+pub struct TokenInit<'a> {
+    pub text: &'a str,
+    pub lemma_id: Option<logos_protocol::LemmaId>,
+    pub flags: logos_protocol::MorphFlags,
+    /// How sure the analysis is, 0 to 1
+    pub confidence: f32,
+}
+
+impl<'a> TokenInit<'a> {
+    pub fn new(text: &'a str, lemma_id: Option<logos_protocol::LemmaId>, flags: logos_protocol::MorphFlags) -> Self {
+        Self { text, lemma_id, flags, confidence: 1.0 }
+    }
+}
+
+impl Default for LogosWorld {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl LogosWorld {
     pub fn new() -> Self {
-        Self { world: World::new() }
+        Self {
+            world: World::new(),
+            tokens: Vec::new(),
+            forms: HashSet::new(),
+            sentence: SentenceId(0),
+            clause: ClauseId(0),
+            systems: vec![Box::new(AgreementSystem)],
+        }
+    }
+
+    /// Starts the next sentence (in a clause of its own). Tokens added before
+    /// the first call belong to sentence 0.
+    pub fn add_sentence(&mut self) -> SentenceId {
+        self.sentence = SentenceId(self.sentence.0 + 1);
+        self.add_clause();
+        self.sentence
+    }
+
+    /// Starts a new clause within the current sentence.
+    pub fn add_clause(&mut self) -> ClauseId {
+        self.clause = ClauseId(self.clause.0 + 1);
+        self.clause
+    }
+
+    pub fn current_sentence(&self) -> SentenceId {
+        self.sentence
+    }
+
+    pub fn current_clause(&self) -> ClauseId {
+        self.clause
+    }
+
+    pub fn sentence_of(&self, token: Entity) -> Option<SentenceId> {
+        self.world.get::<&SentenceId>(token).ok().map(|s| *s)
     }
 
     /// Expose the inner hecs World for external solvers
@@ -22,25 +96,163 @@ impl LogosWorld {
     /// Add a word to the sentence
     pub fn add_token(
         &mut self, 
-        text: String, 
+        text: &str, 
         lemma_id: Option<logos_protocol::LemmaId>, 
         flags: logos_protocol::MorphFlags
     ) -> Entity {
-        self.world.spawn((
+        self.add_token_with_confidence(text, lemma_id, flags, 1.0)
+    }
+
+    /// `add_token` for an analysis only `confidence` (0 to 1) sure.
+    pub fn add_token_with_confidence(
+        &mut self,
+        text: &str,
+        lemma_id: Option<logos_protocol::LemmaId>,
+        flags: logos_protocol::MorphFlags,
+        confidence: f32,
+    ) -> Entity {
+        let text = self.intern(text);
+        let entity = self.world.spawn((
             TokenData { text, lemma_id },
-            Morphology { flags },
-        ))
+            Morphology { flags, confidence },
+            self.sentence,
+            self.clause,
+        ));
+        self.tokens.push(entity);
+        entity
+    }
+
+    /// Adds a whole sentence in one batched spawn, with storage reserved for
+    /// all of it, and returns its entities. It starts a new sentence unless
+    /// the world has no tokens yet, so a book goes in sentence by sentence
+    /// without per-token `add_token` calls.
+    pub fn add_sentence_tokens(&mut self, tokens: &[TokenInit]) -> &[Entity] {
+        if !self.tokens.is_empty() {
+            self.add_sentence();
+        }
+        let (sentence, clause) = (self.sentence, self.clause);
+        let bundles: Vec<_> = tokens.iter().map(|t| (
+            TokenData { text: self.intern(t.text), lemma_id: t.lemma_id },
+            Morphology { flags: t.flags, confidence: t.confidence },
+            sentence,
+            clause,
+        )).collect();
+
+        let start = self.tokens.len();
+        self.tokens.reserve(bundles.len());
+        self.tokens.extend(self.world.spawn_batch(bundles));
+        &self.tokens[start..]
+    }
+
+    /// Empties the world for the next text, keeping its storage, interned
+    /// forms and systems. Sentence and clause numbers carry on instead of
+    /// starting over, so a tag kept from the old text never matches a new
+    /// token, and the old entities are despawned rather than recycled, so
+    /// their handles no longer resolve.
+    pub fn reset(&mut self) {
+        let entities: Vec<Entity> = self.world.iter().map(|e| e.entity()).collect();
+        for entity in entities {
+            let _ = self.world.despawn(entity);
+        }
+        self.tokens.clear();
+        self.add_sentence();
+    }
+
+    /// Adds a word the text leaves out, in the sentence and clause of
+    /// `alongside`. It can head dependencies like any token, but is not one
+    /// of `tokens`.
+    pub fn add_reconstructed(
+        &mut self,
+        text: &str,
+        lemma_id: Option<logos_protocol::LemmaId>,
+        flags: logos_protocol::MorphFlags,
+        alongside: Entity,
+    ) -> Entity {
+        let text = self.intern(text);
+        let sentence = self.sentence_of(alongside).unwrap_or(self.sentence);
+        let clause = self.world.get::<&ClauseId>(alongside).map_or(self.clause, |c| *c);
+        self.world.spawn((TokenData { text, lemma_id }, Morphology::new(flags), sentence, clause, Reconstructed))
+    }
+
+    pub fn is_reconstructed(&self, entity: Entity) -> bool {
+        self.world.get::<&Reconstructed>(entity).is_ok()
+    }
+
+    fn intern(&mut self, text: &str) -> Arc<str> {
+        if let Some(form) = self.forms.get(text) {
+            return form.clone();
+        }
+        let form: Arc<str> = Arc::from(text);
+        self.forms.insert(form.clone());
+        form
+    }
+
+    /// Token entities in the order they were added.
+    pub fn tokens(&self) -> &[Entity] {
+        &self.tokens
     }
 
     /// Define the syntactic tree structure
     pub fn set_dependency(&mut self, child: Entity, head: Entity, role: DependencyRole) {
+        self.set_dependency_with_confidence(child, head, role, 1.0);
+    }
+
+    /// `set_dependency` for an arc the parser is only `confidence` (0 to 1) sure of.
+    pub fn set_dependency_with_confidence(&mut self, child: Entity, head: Entity, role: DependencyRole, confidence: f32) {
         // We use insert_one to add the Syntax component to an existing Entity
-        let _ = self.world.insert_one(child, Syntax { head, role });
+        let _ = self.world.insert_one(child, Syntax { head, role, confidence });
     }
 
-    /// Run all validation systems
+    /// Records where an adjective stands relative to `noun` and its article
+    pub fn set_placement(&mut self, adjective: Entity, noun: Entity, position: AdjectivePosition) {
+        let _ = self.world.insert_one(adjective, Placement { noun, position });
+    }
+
+    pub fn placement(&self, adjective: Entity) -> Option<Placement> {
+        self.world.get::<&Placement>(adjective).ok().map(|p| *p)
+    }
+
+    /// Run the agreement checks only
     pub fn validate(&self) -> Vec<AgreementError> {
-        check_agreement(&self.world)
+        let mut errors = check_agreement(&self.world, None);
+        self.sort_findings(&mut errors, |e| (e.entity, e.code, &e.details));
+        errors
+    }
+
+    /// `validate`, with corrected forms from `inflector` attached as suggestions
+    pub fn validate_with(&self, inflector: &dyn Inflector) -> Vec<AgreementError> {
+        let mut errors = check_agreement(&self.world, Some(inflector));
+        self.sort_findings(&mut errors, |e| (e.entity, e.code, &e.details));
+        errors
+    }
+
+    /// Adds a check to those `diagnostics` runs.
+    pub fn register_system(&mut self, system: Box<dyn ValidationSystem>) {
+        self.systems.push(system);
+    }
+
+    /// Drops every registered system, including the default `AgreementSystem`.
+    pub fn clear_systems(&mut self) {
+        self.systems.clear();
+    }
+
+    /// Runs every registered system, with the findings in text order.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self.systems.iter().flat_map(|s| s.check(self)).collect();
+        self.sort_findings(&mut diagnostics, |d| (d.entity, d.code, &d.message));
+        diagnostics
+    }
+
+    /// Puts findings in text order, whatever order the hecs queries visited
+    /// the entities in: by sentence, then token (restored words after the
+    /// tokens), then code, then message.
+    fn sort_findings<T>(&self, findings: &mut [T], key: impl Fn(&T) -> (Entity, &'static str, &str)) {
+        let position: HashMap<Entity, usize> = self.tokens.iter().enumerate().map(|(i, e)| (*e, i)).collect();
+        let order = |entity| (self.sentence_of(entity), position.get(&entity).copied().unwrap_or(usize::MAX));
+        findings.sort_by(|a, b| {
+            let ((a, a_code, a_message), (b, b_code, b_message)) = (key(a), key(b));
+            order(a).cmp(&order(b)).then(a_code.cmp(b_code)).then(a_message.cmp(b_message))
+        });
     }
 }
 
@@ -57,14 +269,14 @@ mod tests {
         
         // Verb: "plays" (Singular)
         let verb = lw.add_token(
-            "plays".to_string(), 
+            "plays", 
             None, 
             MorphFlags::SINGULAR | MorphFlags::THIRD_PERSON
         );
 
         // Subject: "kids" (Plural)
         let subject = lw.add_token(
-            "kids".to_string(), 
+            "kids", 
             None, 
             MorphFlags::PLURAL | MorphFlags::THIRD_PERSON
         );
@@ -77,6 +289,296 @@ mod tests {
         
         assert_eq!(errors.len(), 1);
         assert!(errors[0].details.contains("Number mismatch"));
+        assert_eq!(errors[0].confidence, 1.0);
         println!("Caught expected error: {:?}", errors[0]);
+
+        // The error is only as sure as the analyses and the arc it rests on
+        let doubtful = lw.add_token_with_confidence("plays", None, MorphFlags::SINGULAR, 0.9);
+        let guessed = lw.add_token("kids", None, MorphFlags::PLURAL);
+        lw.set_dependency_with_confidence(guessed, doubtful, DependencyRole::Subject, 0.5);
+        let errors = lw.validate();
+        assert_eq!(errors.iter().map(|e| e.confidence).collect::<Vec<_>>(), [1.0, 0.5]);
+        assert_eq!(lw.inner().get::<&Morphology>(doubtful).unwrap().confidence, 0.9);
+
+        // Tokens of one form share their text
+        let again = lw.add_token("kids", None, MorphFlags::PLURAL);
+        let text = |e| lw.inner().get::<&TokenData>(e).unwrap().text.clone();
+        assert!(std::sync::Arc::ptr_eq(&text(subject), &text(again)));
+    }
+
+    #[test]
+    fn test_agreement_suggestions() {
+        let mut lw = LogosWorld::new();
+        let mut s = lw.sentence();
+        let verb = s.verb("λέγει").sg().third().add();
+        s.noun("παῖδες").nom().pl().subject_of(verb);
+
+        // Stands in for the dictionaries: knows the plural of λέγει only
+        let inflector = |word: &str, flags: MorphFlags| {
+            (word == "λέγει" && flags == MorphFlags::VERB | MorphFlags::PLURAL | MorphFlags::THIRD_PERSON)
+                .then(|| "λέγουσι".to_string())
+        };
+        let errors = lw.validate_with(&inflector);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].suggestion.as_deref(), Some("λέγουσι"));
+        assert_eq!(lw.validate()[0].suggestion, None);
+    }
+
+    #[test]
+    fn test_agreement_is_scoped_per_sentence() {
+        let mut lw = LogosWorld::new();
+        let subject = lw.add_token("παῖδες", None, MorphFlags::PLURAL | MorphFlags::NOUN);
+        assert_eq!(lw.add_sentence(), SentenceId(1));
+        let verb = lw.add_token("λέγει", None, MorphFlags::SINGULAR | MorphFlags::THIRD_PERSON);
+        assert_ne!(lw.sentence_of(subject), lw.sentence_of(verb));
+
+        // A (bogus) link across sentences is never reported
+        lw.set_dependency(subject, verb, DependencyRole::Subject);
+        assert!(lw.validate().is_empty());
+
+        // Clauses number on across sentences
+        let clause = lw.current_clause();
+        assert_eq!(lw.add_clause(), ClauseId(clause.0 + 1));
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let mut lw = LogosWorld::new();
+        let verb = lw.add_token("λέγει", None, MorphFlags::SINGULAR | MorphFlags::THIRD_PERSON);
+        lw.add_sentence();
+        let subject = lw.add_token("παῖδες", None, MorphFlags::PLURAL | MorphFlags::NOUN);
+        lw.set_dependency(subject, verb, DependencyRole::Subject);
+
+        let snapshot = lw.to_snapshot();
+        assert_eq!(snapshot.tokens[1].dependency.map(|d| d.head), Some(0));
+        let restored = LogosWorld::from_snapshot(&snapshot).unwrap();
+        assert_eq!(restored.to_snapshot(), snapshot);
+        assert_eq!(restored.current_sentence(), SentenceId(1));
+
+        let mut broken = snapshot.clone();
+        broken.tokens[0].dependency = Some(snapshot::DependencySnapshot { head: 9, role: DependencyRole::Object });
+        assert!(LogosWorld::from_snapshot(&broken).is_err());
+    }
+
+    #[test]
+    fn test_sentence_builder() {
+        let mut lw = LogosWorld::new();
+        let mut s = lw.sentence();
+        let verb = s.verb("λέγει").present().active().sg().third().add();
+        let subject = s.noun("ἄνθρωπος").nom().sg().masc().subject_of(verb);
+        s.article("οἱ").nom().pl().masc().modifier_of(subject);
+        s.pronoun("ἐμαυτόν").reflexive().acc().sg().first().reflexive_of(subject);
+
+        let details: Vec<String> = lw.validate().into_iter().map(|e| e.details).collect();
+        assert_eq!(details.len(), 2, "{:?}", details);
+        assert!(details.iter().any(|d| d.contains("Det-Noun")));
+        assert!(details.iter().any(|d| d.contains("Reflexive person mismatch")));
+
+        // A second sentence starts fresh
+        let mut s = lw.sentence();
+        s.noun("παῖδες").nom().pl().add();
+        assert_eq!(lw.current_sentence(), SentenceId(1));
+    }
+
+    #[test]
+    fn test_validation_registry() {
+        struct NoVocatives;
+        impl ValidationSystem for NoVocatives {
+            fn name(&self) -> &'static str {
+                "no-vocatives"
+            }
+            fn check(&self, world: &LogosWorld) -> Vec<Diagnostic> {
+                world.inner().query::<(&Morphology, &TokenData)>().iter()
+                    .filter(|(_, (m, _))| m.flags.contains(MorphFlags::VOCATIVE))
+                    .map(|(entity, (_, t))| Diagnostic {
+                        system: self.name(),
+                        entity,
+                        source: t.text.to_string(),
+                        target: String::new(),
+                        message: "Vocative".to_string(),
+                        code: "style.vocative",
+                        args: Vec::new(),
+                        confidence: 1.0,
+                    })
+                    .collect()
+            }
+        }
+
+        let mut lw = LogosWorld::new();
+        lw.register_system(Box::new(NoVocatives));
+        let mut s = lw.sentence();
+        let verb = s.verb("λέγει").sg().add();
+        s.noun("παῖδες").nom().pl().subject_of(verb);
+        s.noun("ἄνθρωπε").voc().sg().add();
+
+        let systems: Vec<&str> = lw.diagnostics().iter().map(|d| d.system).collect();
+        assert_eq!(systems, [AgreementSystem::NAME, "no-vocatives"]);
+
+        lw.clear_systems();
+        assert!(lw.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_findings_in_text_order() {
+        // Arcs set last to first, so the queries meet the later words first;
+        // the determiner check runs after the subject-verb one
+        let mut lw = LogosWorld::new();
+        let mut s = lw.sentence();
+        let article = s.article("οἱ").nom().pl().add();
+        let noun = s.noun("ἄνθρωπος").nom().sg().add();
+        let verb = s.verb("λέγουσι").pl().third().add();
+        let mut s = lw.sentence();
+        let verb2 = s.verb("λέγει").sg().third().add();
+        let subject2 = s.noun("παῖδες").nom().pl().third().add();
+        lw.set_dependency(subject2, verb2, DependencyRole::Subject);
+        lw.set_dependency(noun, verb, DependencyRole::Subject);
+        lw.set_dependency(article, noun, DependencyRole::Modifier);
+
+        let order = |lw: &LogosWorld| lw.validate().into_iter().map(|e| (e.entity, e.code)).collect::<Vec<_>>();
+        assert_eq!(order(&lw), [
+            (article, "agreement.determiner"),
+            (noun, "agreement.number"),
+            (subject2, "agreement.number"),
+        ]);
+        let codes: Vec<&str> = lw.diagnostics().iter().map(|d| d.code).collect();
+        assert_eq!(codes, ["agreement.determiner", "agreement.number", "agreement.number"]);
+    }
+
+    #[test]
+    fn test_tree_helpers() {
+        let mut lw = LogosWorld::new();
+        let mut s = lw.sentence();
+        let article = s.article("ὁ").nom().sg().add();
+        let noun = s.noun("ἄνθρωπος").nom().sg().add();
+        let verb = s.verb("λέγει").sg().third().add();
+        let object = s.noun("λόγον").acc().sg().object_of(verb);
+        lw.set_dependency(article, noun, DependencyRole::Modifier);
+        lw.set_dependency(noun, verb, DependencyRole::Subject);
+
+        assert_eq!(lw.root(), Some(verb));
+        assert_eq!(lw.children_of(verb), [noun, object]);
+        assert_eq!(lw.subtree(noun), [article, noun]);
+        assert_eq!(lw.depth(article), Some(2));
+        assert!(lw.is_projective());
+        assert!(lw.crossing_arcs().is_empty());
+
+        // ὁ attached across λέγει to λόγον crosses the root's arc
+        lw.set_dependency(article, object, DependencyRole::Modifier);
+        assert!(!lw.is_projective());
+        assert_eq!(lw.crossing_arcs(), [(article, verb)]);
+
+        // A loop is found, not followed forever
+        lw.set_dependency(verb, article, DependencyRole::Object);
+        assert_eq!(lw.depth(noun), None);
+        assert_eq!(lw.find_cycle().map(|c| c.len()), Some(3));
+        assert!(!lw.is_projective());
+    }
+
+    #[test]
+    fn test_definiteness() {
+        use logos_protocol::LemmaId;
+        use systems::definiteness::{DefinitenessSystem, Strictness};
+
+        let mut lw = LogosWorld::new();
+        // ὁ ἄνθρωπος ἀγαθός: predicative, "the man is good"
+        let mut s = lw.sentence();
+        let article = s.article("ὁ").nom().sg().masc().add();
+        let first = s.noun("ἄνθρωπος").nom().sg().masc().lemma(LemmaId(1)).add();
+        let trailing = s.adjective("ἀγαθός").nom().sg().masc().add();
+        lw.set_dependency(article, first, DependencyRole::Modifier);
+
+        // ἀγαθὸς ὁ ἄνθρωπος: predicative too; ὁ ἀγαθὸς ἄνθρωπος would be attributive
+        let mut s = lw.sentence();
+        let leading = s.adjective("ἀγαθός").nom().sg().masc().add();
+        let article = s.article("ὁ").nom().sg().masc().add();
+        let noun = s.noun("ἄνθρωπος").nom().sg().masc().lemma(LemmaId(1)).add();
+        lw.set_dependency(leading, noun, DependencyRole::Modifier);
+        lw.set_dependency(article, noun, DependencyRole::Modifier);
+
+        // ἄνθρωπον without the article, right after it was mentioned
+        let mut s = lw.sentence();
+        let verb = s.verb("βλέπω").sg().first().add();
+        let bare = s.noun("ἄνθρωπον").acc().sg().masc().lemma(LemmaId(1)).object_of(verb);
+
+        let flagged = |strictness| -> Vec<Entity> {
+            DefinitenessSystem::new(strictness).check(&lw).into_iter().map(|d| d.entity).collect()
+        };
+        assert_eq!(flagged(Strictness::Standard), [trailing, leading, bare]);
+        assert_eq!(flagged(Strictness::Lenient), [trailing, leading]);
+
+        // Placements from the parser take over from the positional guess
+        lw.set_placement(trailing, first, AdjectivePosition::Attributive);
+        lw.set_placement(leading, noun, AdjectivePosition::Predicative);
+        assert_eq!(lw.placement(leading).map(|p| p.noun), Some(noun));
+        let flagged = DefinitenessSystem::new(Strictness::Lenient).check(&lw);
+        assert_eq!(flagged.iter().map(|d| d.entity).collect::<Vec<_>>(), [leading]);
+
+        // Not registered by default
+        assert!(lw.diagnostics().iter().all(|d| d.system == AgreementSystem::NAME));
+    }
+
+    #[test]
+    fn test_discourse() {
+        use systems::discourse::{resolve_references, DiscourseSystem, Reference};
+
+        let mut lw = LogosWorld::new();
+        // ὁ στρατηγὸς τὴν πόλιν λαμβάνει.
+        let mut s = lw.sentence();
+        let verb = s.verb("λαμβάνει").sg().third().add();
+        s.noun("στρατηγός").nom().sg().masc().subject_of(verb);
+        let city = s.noun("πόλιν").acc().sg().fem().object_of(verb);
+        let messenger = s.noun("ἄγγελον").acc().sg().masc().add();
+        // αὐτὴν καίει: αὐτήν is the city
+        let mut s = lw.sentence();
+        let verb = s.verb("καίει").sg().third().add();
+        let her = s.pronoun("αὐτήν").acc().sg().fem().object_of(verb);
+        // ὁ δὲ ταῦτα λέγει: ὁ is not the general, the subject just before,
+        // and nothing neuter plural was mentioned for ταῦτα
+        let mut s = lw.sentence();
+        let article = s.article("ὁ").nom().sg().masc().add();
+        s.word("δέ").flag(MorphFlags::CONJUNCTION).add();
+        let these = s.pronoun("ταῦτα").acc().pl().neut().add();
+        s.verb("λέγει").sg().third().add();
+        // αὐτός ὁ στρατηγός is not an anaphor
+        let mut s = lw.sentence();
+        let noun = s.noun("στρατηγός").nom().sg().masc().add();
+        s.pronoun("αὐτός").nom().sg().masc().modifier_of(noun);
+
+        assert_eq!(resolve_references(&lw), [
+            Reference { anaphor: her, antecedent: Some(city) },
+            Reference { anaphor: article, antecedent: Some(messenger) },
+            Reference { anaphor: these, antecedent: None },
+        ]);
+        let flagged = DiscourseSystem.check(&lw);
+        assert_eq!(flagged.iter().map(|d| d.entity).collect::<Vec<_>>(), [these]);
+        assert!(flagged[0].message.contains("neuter plural"), "{}", flagged[0].message);
+    }
+
+    #[test]
+    fn test_bulk_sentences_and_reset() {
+        let mut lw = LogosWorld::new();
+        let first = lw.add_sentence_tokens(&[
+            TokenInit::new("παῖδες", None, MorphFlags::NOMINATIVE | MorphFlags::PLURAL | MorphFlags::THIRD_PERSON),
+            TokenInit { confidence: 0.5, ..TokenInit::new("λέγει", None, MorphFlags::SINGULAR | MorphFlags::THIRD_PERSON) },
+        ]).to_vec();
+        let second = lw.add_sentence_tokens(&[TokenInit::new("παῖδες", None, MorphFlags::PLURAL)]).to_vec();
+        assert_eq!(lw.tokens(), [first[0], first[1], second[0]]);
+        assert_eq!(lw.sentence_of(first[1]), Some(SentenceId(0)));
+        assert_eq!(lw.sentence_of(second[0]), Some(SentenceId(1)));
+        // Bulk tokens are interned and validated like any others
+        let text = |e| lw.inner().get::<&TokenData>(e).unwrap().text.clone();
+        assert!(Arc::ptr_eq(&text(first[0]), &text(second[0])));
+        lw.set_dependency(first[0], first[1], DependencyRole::Subject);
+        assert_eq!(lw.validate()[0].confidence, 0.5);
+
+        // A reset world forgets the old text: its entities and sentence tags
+        // match nothing added afterwards
+        let old_sentence = lw.current_sentence();
+        lw.reset();
+        assert!(lw.tokens().is_empty() && lw.validate().is_empty());
+        assert_eq!(lw.sentence_of(first[0]), None);
+        let fresh = lw.add_sentence_tokens(&[TokenInit::new("λέγει", None, MorphFlags::SINGULAR)])[0];
+        assert!(lw.sentence_of(fresh) > Some(old_sentence));
+        assert!(!first.contains(&fresh) && !second.contains(&fresh));
     }
 }
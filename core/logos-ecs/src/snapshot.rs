@@ -0,0 +1,90 @@
+//! Plain-data image of a `LogosWorld`, for caching analyses on disk, diffing
+//! them between engine versions and replaying them in tests.
+
+use std::sync::Arc;
+
+use logos_protocol::{ClauseId, LemmaId, MorphFlags, SentenceId};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::components::{DependencyRole, Morphology, Syntax, TokenData};
+use crate::LogosWorld;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WorldSnapshot {
+    /// In the order they were added
+    pub tokens: Vec<TokenSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TokenSnapshot {
+    pub text: String,
+    pub lemma_id: Option<LemmaId>,
+    /// Raw `MorphFlags` bits
+    pub flags: u32,
+    pub sentence: SentenceId,
+    pub clause: ClauseId,
+    pub dependency: Option<DependencySnapshot>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DependencySnapshot {
+    /// Index of the head in `WorldSnapshot::tokens`
+    pub head: usize,
+    pub role: DependencyRole,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SnapshotError {
+    #[error("token {token} depends on head {head}, but the snapshot has {len} tokens")]
+    HeadOutOfRange { token: usize, head: usize, len: usize },
+}
+
+impl LogosWorld {
+    pub fn to_snapshot(&self) -> WorldSnapshot {
+        let tokens = self.tokens.iter().map(|&entity| {
+            let token = self.world.get::<&TokenData>(entity).map(|t| (*t).clone())
+                .unwrap_or(TokenData { text: Arc::from(""), lemma_id: None });
+            let dependency = self.world.get::<&Syntax>(entity).ok().and_then(|syntax| {
+                let head = self.tokens.iter().position(|e| *e == syntax.head)?;
+                Some(DependencySnapshot { head, role: syntax.role })
+            });
+            TokenSnapshot {
+                text: token.text.to_string(),
+                lemma_id: token.lemma_id,
+                flags: self.world.get::<&Morphology>(entity).map(|m| m.flags.bits()).unwrap_or(0),
+                sentence: self.sentence_of(entity).unwrap_or(SentenceId(0)),
+                clause: self.world.get::<&ClauseId>(entity).map(|c| *c).unwrap_or(ClauseId(0)),
+                dependency,
+            }
+        }).collect();
+        WorldSnapshot { tokens }
+    }
+
+    /// Rebuilds the world; new tokens continue the last sentence and clause.
+    pub fn from_snapshot(snapshot: &WorldSnapshot) -> Result<Self, SnapshotError> {
+        let len = snapshot.tokens.len();
+        if let Some((token, dep)) = snapshot.tokens.iter().enumerate()
+            .find_map(|(i, t)| t.dependency.filter(|d| d.head >= len).map(|d| (i, d)))
+        {
+            return Err(SnapshotError::HeadOutOfRange { token, head: dep.head, len });
+        }
+
+        let mut world = LogosWorld::new();
+        for t in &snapshot.tokens {
+            world.sentence = t.sentence;
+            world.clause = t.clause;
+            world.add_token(&t.text, t.lemma_id, MorphFlags::from_bits_retain(t.flags));
+        }
+        for (i, t) in snapshot.tokens.iter().enumerate() {
+            if let Some(dep) = t.dependency {
+                world.set_dependency(world.tokens[i], world.tokens[dep.head], dep.role);
+            }
+        }
+        Ok(world)
+    }
+}
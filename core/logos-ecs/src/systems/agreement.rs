@@ -1,73 +1,161 @@
-use hecs::World;
+use hecs::{Entity, World};
 use crate::components::{Morphology, Syntax, DependencyRole, TokenData};
-use logos_protocol::MorphFlags;
+use crate::systems::certainty;
+use logos_protocol::messages::{render, Locale};
+use logos_protocol::{MessageArgs, MorphFeatures, MorphFlags, Number, Person, SentenceId};
 
 #[derive(Debug, Clone)]
 pub struct AgreementError {
     pub source: String, // Renamed from subject_text
     pub target: String, // Renamed from verb_text
+    /// The message in English (see `localized`)
     pub details: String,
+    /// Stable kind of the mismatch, e.g. "agreement.number"
+    pub code: &'static str,
+    /// The rule that found it: "subject-verb", "determiner-noun" or
+    /// "reflexive-antecedent"
+    pub rule: &'static str,
+    /// The features the rule compared
+    pub evidence: AgreementEvidence,
+    /// Token the error is reported on (the `source`)
+    pub entity: Entity,
+    /// Corrected form of the word to change, when an `Inflector` could produce it
+    pub suggestion: Option<String>,
+    /// How sure the error is: the weakest of the two analyses and the arc
+    pub confidence: f32,
 }
 
-pub fn check_agreement(world: &World) -> Vec<AgreementError> {
+/// Why an agreement rule failed: the features of one category on the two
+/// words, masked to that category (with the 3rd person nouns are given).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgreementEvidence {
+    /// "number" or "person"
+    pub category: &'static str,
+    pub source: MorphFlags,
+    pub target: MorphFlags,
+}
+
+impl AgreementError {
+    /// `details` in `locale`.
+    pub fn localized(&self, locale: Locale) -> String {
+        render(locale, self.code, &self.evidence.args())
+    }
+}
+
+impl AgreementEvidence {
+    /// The parameters of the agreement messages: the `source` and `target`
+    /// features.
+    pub fn args(&self) -> MessageArgs {
+        vec![("source", MorphFeatures::from(self.source).to_string()), ("target", MorphFeatures::from(self.target).to_string())]
+    }
+}
+
+/// Regenerates a word with other morphology, for `AgreementError::suggestion`.
+/// The ECS holds no paradigms; the engine answers from its dictionaries.
+pub trait Inflector {
+    /// The form of `word`'s lemma carrying exactly `flags`.
+    fn inflect(&self, word: &str, flags: MorphFlags) -> Option<String>;
+}
+
+impl<F: Fn(&str, MorphFlags) -> Option<String>> Inflector for F {
+    fn inflect(&self, word: &str, flags: MorphFlags) -> Option<String> {
+        self(word, flags)
+    }
+}
+
+pub fn check_agreement(world: &World, inflector: Option<&dyn Inflector>) -> Vec<AgreementError> {
     let mut errors = Vec::new();
 
     // 1. Subject-Verb Agreement (Existing)
-    errors.extend(check_subject_verb_agreement(world));
+    errors.extend(check_subject_verb_agreement(world, inflector));
 
     // 2. Determiner-Noun Agreement (New)
-    errors.extend(check_determiner_agreement(world));
+    errors.extend(check_determiner_agreement(world, inflector));
+
+    // 3. Reflexive-Antecedent Agreement
+    errors.extend(check_reflexive_agreement(world, inflector));
 
     errors
 }
 
-fn check_subject_verb_agreement(world: &World) -> Vec<AgreementError> {
+/// `token` re-inflected with its `mask` features replaced by `wanted`.
+fn suggest(world: &World, inflector: Option<&dyn Inflector>, token: Entity, mask: MorphFlags, wanted: MorphFlags) -> Option<String> {
+    let inflector = inflector?;
+    let flags = world.get::<&Morphology>(token).ok()?.flags;
+    let text = world.get::<&TokenData>(token).ok()?.text.clone();
+    inflector.inflect(&text, flags.difference(mask) | wanted)
+}
+
+/// Links that cross a sentence boundary are ignored, not reported: words of
+/// different sentences never agree with each other.
+fn same_sentence(world: &World, a: Entity, b: Entity) -> bool {
+    match (world.get::<&SentenceId>(a), world.get::<&SentenceId>(b)) {
+        (Ok(a), Ok(b)) => *a == *b,
+        _ => true,
+    }
+}
+
+fn check_subject_verb_agreement(world: &World, inflector: Option<&dyn Inflector>) -> Vec<AgreementError> {
     let mut errors = Vec::new();
 
     // Query: Get all entities that have Morphology, Syntax, and TokenData
-    for (_id, (subject_morph, syntax, subject_token)) in world.query::<(&Morphology, &Syntax, &TokenData)>().iter() {
+    for (id, (subject_morph, syntax, subject_token)) in world.query::<(&Morphology, &Syntax, &TokenData)>().iter() {
         
-        // Filter: We only care about Subjects
-        if syntax.role == DependencyRole::Subject {
+        // Filter: We only care about Subjects (in the same sentence)
+        if syntax.role == DependencyRole::Subject && same_sentence(world, id, syntax.head) {
             
             // Look up the Head (The Verb)
             // Note: In hecs, random access is O(1) via world.get
             if let Ok(verb_morph) = world.get::<&Morphology>(syntax.head) {
                 
                 // 1. Check Number Agreement
-                let subj_num = subject_morph.flags.intersection(MorphFlags::SINGULAR | MorphFlags::PLURAL);
-                let verb_num = verb_morph.flags.intersection(MorphFlags::SINGULAR | MorphFlags::PLURAL);
+                let subj_num = subject_morph.flags.intersection(Number::MASK);
+                let verb_num = verb_morph.flags.intersection(Number::MASK);
                 
                 // If both have Number defined (non-empty) and they don't match
                 if !subj_num.is_empty() && !verb_num.is_empty() && subj_num != verb_num {
                     
                     // Fetch verb text for the error message
                     let verb_text = world.get::<&TokenData>(syntax.head)
-                        .map(|t| t.text.clone())
+                        .map(|t| t.text.to_string())
                         .unwrap_or_else(|_| "Unknown Verb".to_string());
 
+                    let evidence = AgreementEvidence { category: "number", source: subj_num, target: verb_num };
                     errors.push(AgreementError {
-                        source: subject_token.text.clone(),
+                        entity: id,
+                        source: subject_token.text.to_string(),
                         target: verb_text,
-                        details: format!("Number mismatch: {:?} vs {:?}", subj_num, verb_num),
+                        code: "agreement.number",
+                        rule: "subject-verb",
+                        details: render(Locale::English, "agreement.number", &evidence.args()),
+                        evidence,
+                        suggestion: suggest(world, inflector, syntax.head, Number::MASK, subj_num),
+                        confidence: certainty(world, id, syntax.head),
                     });
                 }
                 
                 // 2. Check Person Agreement (Optional: Nouns are 3rd person by default)
                 // If the subject is a Pronoun, it might be 1st/2nd. 
                 // Nouns don't usually have Person flags in simple lexers, so we skip if empty.
-                let subj_person = subject_morph.flags.intersection(MorphFlags::FIRST_PERSON | MorphFlags::SECOND_PERSON | MorphFlags::THIRD_PERSON);
-                let verb_person = verb_morph.flags.intersection(MorphFlags::FIRST_PERSON | MorphFlags::SECOND_PERSON | MorphFlags::THIRD_PERSON);
+                let subj_person = subject_morph.flags.intersection(Person::MASK);
+                let verb_person = verb_morph.flags.intersection(Person::MASK);
 
                 if !subj_person.is_empty() && !verb_person.is_empty() && subj_person != verb_person {
                      let verb_text = world.get::<&TokenData>(syntax.head)
-                        .map(|t| t.text.clone())
+                        .map(|t| t.text.to_string())
                         .unwrap_or_else(|_| "Unknown Verb".to_string());
 
+                     let evidence = AgreementEvidence { category: "person", source: subj_person, target: verb_person };
                      errors.push(AgreementError {
-                        source: subject_token.text.clone(),
+                        entity: id,
+                        source: subject_token.text.to_string(),
                         target: verb_text,
-                        details: format!("Person mismatch: {:?} vs {:?}", subj_person, verb_person),
+                        code: "agreement.person",
+                        rule: "subject-verb",
+                        details: render(Locale::English, "agreement.person", &evidence.args()),
+                        evidence,
+                        suggestion: suggest(world, inflector, syntax.head, Person::MASK, subj_person),
+                        confidence: certainty(world, id, syntax.head),
                     });
                 }
             }
@@ -76,26 +164,33 @@ fn check_subject_verb_agreement(world: &World) -> Vec<AgreementError> {
     errors
 }
 
-fn check_determiner_agreement(world: &World) -> Vec<AgreementError> {
+fn check_determiner_agreement(world: &World, inflector: Option<&dyn Inflector>) -> Vec<AgreementError> {
     let mut errors = Vec::new();
 
-    for (_id, (det_morph, syntax, det_token)) in world.query::<(&Morphology, &Syntax, &TokenData)>().iter() {
-        if syntax.role == DependencyRole::Modifier {
+    for (id, (det_morph, syntax, det_token)) in world.query::<(&Morphology, &Syntax, &TokenData)>().iter() {
+        if syntax.role == DependencyRole::Modifier && same_sentence(world, id, syntax.head) {
              if let Ok(head_morph) = world.get::<&Morphology>(syntax.head) {
                  
                  // Check Number Agreement
-                 let det_num = det_morph.flags.intersection(MorphFlags::SINGULAR | MorphFlags::PLURAL);
-                 let head_num = head_morph.flags.intersection(MorphFlags::SINGULAR | MorphFlags::PLURAL);
+                 let det_num = det_morph.flags.intersection(Number::MASK);
+                 let head_num = head_morph.flags.intersection(Number::MASK);
                  
                  if !det_num.is_empty() && !head_num.is_empty() && det_num != head_num {
                      let head_text = world.get::<&TokenData>(syntax.head)
-                        .map(|t| t.text.clone())
+                        .map(|t| t.text.to_string())
                         .unwrap_or_else(|_| "Head".to_string());
 
+                     let evidence = AgreementEvidence { category: "number", source: det_num, target: head_num };
                      errors.push(AgreementError {
-                        source: det_token.text.clone(),
+                        entity: id,
+                        source: det_token.text.to_string(),
                         target: head_text,
-                        details: format!("Agreement Mismatch (Det-Noun): {:?} vs {:?}", det_num, head_num),
+                        code: "agreement.determiner",
+                        rule: "determiner-noun",
+                        details: render(Locale::English, "agreement.determiner", &evidence.args()),
+                        evidence,
+                        suggestion: suggest(world, inflector, id, Number::MASK, head_num),
+                        confidence: certainty(world, id, syntax.head),
                     });
                  }
              }
@@ -103,3 +198,56 @@ fn check_determiner_agreement(world: &World) -> Vec<AgreementError> {
     }
     errors
 }
+
+fn check_reflexive_agreement(world: &World, inflector: Option<&dyn Inflector>) -> Vec<AgreementError> {
+    let mut errors = Vec::new();
+
+    for (id, (refl_morph, syntax, refl_token)) in world.query::<(&Morphology, &Syntax, &TokenData)>().iter() {
+        if syntax.role != DependencyRole::Reflexive || !same_sentence(world, id, syntax.head) {
+            continue;
+        }
+        let Ok(head_morph) = world.get::<&Morphology>(syntax.head) else { continue };
+        let head_text = world.get::<&TokenData>(syntax.head)
+            .map(|t| t.text.to_string())
+            .unwrap_or_else(|_| "Antecedent".to_string());
+
+        // Nouns carry no Person flag but are always 3rd person
+        let refl_person = refl_morph.flags & Person::MASK;
+        let mut head_person = head_morph.flags & Person::MASK;
+        if head_person.is_empty() && head_morph.flags.contains(MorphFlags::NOUN) {
+            head_person = MorphFlags::THIRD_PERSON;
+        }
+        if !refl_person.is_empty() && !head_person.is_empty() && refl_person != head_person {
+            let evidence = AgreementEvidence { category: "person", source: refl_person, target: head_person };
+            errors.push(AgreementError {
+                entity: id,
+                source: refl_token.text.to_string(),
+                target: head_text.clone(),
+                code: "agreement.reflexive-person",
+                rule: "reflexive-antecedent",
+                details: render(Locale::English, "agreement.reflexive-person", &evidence.args()),
+                evidence,
+                suggestion: suggest(world, inflector, id, Person::MASK, head_person),
+                confidence: certainty(world, id, syntax.head),
+            });
+        }
+
+        let refl_num = refl_morph.flags & Number::MASK;
+        let head_num = head_morph.flags & Number::MASK;
+        if !refl_num.is_empty() && !head_num.is_empty() && refl_num != head_num {
+            let evidence = AgreementEvidence { category: "number", source: refl_num, target: head_num };
+            errors.push(AgreementError {
+                entity: id,
+                source: refl_token.text.to_string(),
+                target: head_text,
+                code: "agreement.reflexive-number",
+                rule: "reflexive-antecedent",
+                details: render(Locale::English, "agreement.reflexive-number", &evidence.args()),
+                evidence,
+                suggestion: suggest(world, inflector, id, Number::MASK, head_num),
+                confidence: certainty(world, id, syntax.head),
+            });
+        }
+    }
+    errors
+}
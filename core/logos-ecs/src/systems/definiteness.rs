@@ -0,0 +1,176 @@
+//! Article use, for learners: re-mentioned referents written without the
+//! article, and adjectives standing outside the article group of their noun
+//! (predicative position: ὁ ἄνθρωπος ἀγαθός "the man is good") where an
+//! attribute (ὁ ἀγαθὸς ἄνθρωπος "the good man") was probably meant.
+
+use hecs::Entity;
+use logos_protocol::messages::{render, Locale};
+use logos_protocol::MorphFlags;
+
+use crate::components::{AdjectivePosition, DependencyRole, Morphology, TokenData};
+use crate::systems::{certainty, Diagnostic, ValidationSystem};
+use crate::LogosWorld;
+
+/// How far `DefinitenessSystem` looks for probable mistakes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Strictness {
+    /// Article/adjective position only
+    Lenient,
+    /// Also re-mentions without the article, when the earlier mention is in
+    /// the same or the previous sentence
+    #[default]
+    Standard,
+    /// Re-mentions anywhere earlier, including prepositional objects
+    /// (which often go without the article: ἐν ἀρχῇ)
+    Strict,
+}
+
+/// Article attachment checks; not registered by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefinitenessSystem {
+    pub strictness: Strictness,
+}
+
+impl DefinitenessSystem {
+    pub const NAME: &'static str = "definiteness";
+
+    pub fn new(strictness: Strictness) -> Self {
+        Self { strictness }
+    }
+}
+
+impl ValidationSystem for DefinitenessSystem {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    /// Adjective positions come from the parser's `Placement` components
+    /// when the world has any, and are worked out here otherwise.
+    fn check(&self, world: &LogosWorld) -> Vec<Diagnostic> {
+        let placed = world.tokens().iter().any(|t| world.placement(*t).is_some());
+        let mut diagnostics = Vec::new();
+        for (position, &token) in world.tokens().iter().enumerate() {
+            if let Some(placement) = world.placement(token).filter(|p| p.position == AdjectivePosition::Predicative) {
+                diagnostics.push(predicative(world, token, placement.noun));
+            }
+            if !flags(world, token).contains(MorphFlags::NOUN) {
+                continue;
+            }
+            match article_of(world, token) {
+                Some(_) if placed => {}
+                Some(article) => diagnostics.extend(
+                    predicative_by_position(world, position, article, token).into_iter()
+                        .map(|adjective| predicative(world, adjective, token)),
+                ),
+                None => diagnostics.extend(self.missing_article(world, position, token)),
+            }
+        }
+        diagnostics
+    }
+}
+
+const CASE: MorphFlags = MorphFlags::NOMINATIVE.union(MorphFlags::GENITIVE).union(MorphFlags::DATIVE)
+    .union(MorphFlags::ACCUSATIVE).union(MorphFlags::VOCATIVE);
+const NUMBER: MorphFlags = MorphFlags::SINGULAR.union(MorphFlags::PLURAL);
+const GENDER: MorphFlags = MorphFlags::MASCULINE.union(MorphFlags::FEMININE).union(MorphFlags::NEUTER);
+
+fn flags(world: &LogosWorld, token: Entity) -> MorphFlags {
+    world.inner().get::<&Morphology>(token).map(|m| m.flags).unwrap_or(MorphFlags::empty())
+}
+
+fn text(world: &LogosWorld, token: Entity) -> String {
+    world.inner().get::<&TokenData>(token).map(|t| t.text.to_string()).unwrap_or_default()
+}
+
+fn lemma(world: &LogosWorld, token: Entity) -> Option<u32> {
+    world.inner().get::<&TokenData>(token).ok()?.lemma_id.map(|id| id.0)
+}
+
+/// Same case, number and gender wherever both words mark them.
+fn agrees(a: MorphFlags, b: MorphFlags) -> bool {
+    [CASE, NUMBER, GENDER].into_iter().all(|mask| {
+        let (a, b) = (a & mask, b & mask);
+        a.is_empty() || b.is_empty() || a == b
+    })
+}
+
+fn article_of(world: &LogosWorld, noun: Entity) -> Option<Entity> {
+    world.children_of(noun).into_iter().find(|t| flags(world, *t).contains(MorphFlags::ARTICLE))
+}
+
+/// Adjectives of an articular noun that precede its article, or that
+/// follow the noun without an article of their own. The parser leaves
+/// the latter unattached, so adjacent agreeing ones count too.
+fn predicative_by_position(world: &LogosWorld, position: usize, article: Entity, noun: Entity) -> Vec<Entity> {
+    let tokens = world.tokens();
+    let Some(article_position) = tokens.iter().position(|t| *t == article) else { return Vec::new() };
+    let is_adjective = |t: Entity| {
+        let f = flags(world, t);
+        f.contains(MorphFlags::ADJECTIVE) && !f.contains(MorphFlags::ARTICLE)
+    };
+
+    let mut predicative: Vec<Entity> = world.children_of(noun).into_iter()
+        .filter(|t| is_adjective(*t) && tokens.iter().position(|x| x == t).is_some_and(|p| p < article_position))
+        .collect();
+    let follower = tokens.get(position + 1).copied()
+        .filter(|t| world.sentence_of(*t) == world.sentence_of(noun));
+    if let Some(next) = follower {
+        if is_adjective(next) && world.head_of(next).is_none() && agrees(flags(world, next), flags(world, noun)) {
+            predicative.push(next);
+        }
+    }
+
+    predicative
+}
+
+fn predicative(world: &LogosWorld, adjective: Entity, noun: Entity) -> Diagnostic {
+    let (adjective_text, noun_text) = (text(world, adjective), text(world, noun));
+    let args = vec![("adjective", adjective_text.clone()), ("noun", noun_text.clone())];
+    Diagnostic {
+        system: DefinitenessSystem::NAME,
+        entity: adjective,
+        message: render(Locale::English, "definiteness.predicative", &args),
+        source: adjective_text,
+        target: noun_text,
+        code: "definiteness.predicative",
+        args,
+        confidence: certainty(world.inner(), adjective, noun),
+    }
+}
+
+impl DefinitenessSystem {
+
+    /// An anarthrous noun whose lemma was mentioned before.
+    fn missing_article(&self, world: &LogosWorld, position: usize, noun: Entity) -> Option<Diagnostic> {
+        if self.strictness == Strictness::Lenient {
+            return None;
+        }
+        let in_phrase = world.dependency(noun).is_some_and(|(_, role)| role == DependencyRole::PrepositionArg);
+        if in_phrase && self.strictness != Strictness::Strict {
+            return None;
+        }
+
+        let lemma_id = lemma(world, noun)?;
+        let sentence = world.sentence_of(noun).map_or(0, |s| s.0);
+        let earlier = world.tokens()[..position].iter().rev().copied().find(|t| {
+            let near = self.strictness == Strictness::Strict
+                || world.sentence_of(*t).map_or(0, |s| s.0) + 1 >= sentence;
+            near && lemma(world, *t) == Some(lemma_id) && flags(world, *t).contains(MorphFlags::NOUN)
+        })?;
+
+        let (noun_text, earlier_text) = (text(world, noun), text(world, earlier));
+        let args = vec![("noun", noun_text.clone()), ("earlier", earlier_text.clone())];
+        Some(Diagnostic {
+            system: Self::NAME,
+            entity: noun,
+            message: render(Locale::English, "definiteness.missing-article", &args),
+            source: noun_text,
+            target: earlier_text,
+            code: "definiteness.missing-article",
+            args,
+            confidence: certainty(world.inner(), noun, earlier),
+        })
+    }
+}
@@ -0,0 +1,167 @@
+//! Referents across sentences: third person pronouns (αὐτόν, οὗτος,
+//! ἐκεῖνος) and the article standing for one (ὁ δέ, ἡ μέν) are linked to
+//! the nearest earlier noun of their gender and number, and a pronoun
+//! that no noun nearby matches is reported.
+
+use hecs::Entity;
+use logos_protocol::messages::{render, Locale};
+use logos_protocol::MorphFlags;
+
+use crate::components::{DependencyRole, Morphology, TokenData};
+use crate::systems::{certainty, Diagnostic, ValidationSystem};
+use crate::LogosWorld;
+
+/// Sentences before the pronoun's own that are searched for its antecedent.
+pub const LOOKBACK: u32 = 2;
+
+const NUMBER: MorphFlags = MorphFlags::SINGULAR.union(MorphFlags::PLURAL);
+const GENDER: MorphFlags = MorphFlags::MASCULINE.union(MorphFlags::FEMININE).union(MorphFlags::NEUTER);
+
+/// Particles after which an article stands alone for a person (ὁ δέ "and he").
+const ARTICLE_PARTICLES: [&str; 7] = ["δέ", "δὲ", "δ'", "δ’", "δʼ", "μέν", "μὲν"];
+
+/// An anaphoric word and the noun it refers to, if one was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reference {
+    pub anaphor: Entity,
+    pub antecedent: Option<Entity>,
+}
+
+/// The anaphors of the world, in token order, each with the nearest
+/// earlier noun (in its own sentence or the `LOOKBACK` before it) whose
+/// gender and number agree wherever both words mark them. A pronoun
+/// referring to an earlier resolved pronoun takes that pronoun's noun, so
+/// a chain of mentions ends at one referent. ὁ δέ usually switches to
+/// someone other than the previous sentence's subject, so that subject is
+/// passed over while another noun agrees.
+pub fn resolve_references(world: &LogosWorld) -> Vec<Reference> {
+    let tokens = world.tokens();
+    let mut references: Vec<Reference> = Vec::new();
+    for (position, &token) in tokens.iter().enumerate() {
+        let article = is_pronominal_article(world, position);
+        if !article && !is_anaphoric_pronoun(world, token) {
+            continue;
+        }
+        let sentence = sentence_number(world, token);
+        let wanted = flags(world, token);
+        let candidates: Vec<Entity> = tokens[..position].iter().rev().copied()
+            .take_while(|t| sentence_number(world, *t) + LOOKBACK >= sentence)
+            .filter_map(|t| {
+                if flags(world, t).contains(MorphFlags::NOUN) {
+                    Some(t)
+                } else {
+                    references.iter().find(|r| r.anaphor == t).and_then(|r| r.antecedent)
+                }
+            })
+            .filter(|t| agrees(flags(world, *t), wanted))
+            .collect();
+        let switched = article.then(|| {
+            candidates.iter().copied().find(|t| {
+                let subject = world.dependency(*t).is_some_and(|(_, role)| role == DependencyRole::Subject);
+                !(subject && sentence_number(world, *t) + 1 == sentence)
+            })
+        }).flatten();
+        references.push(Reference { anaphor: token, antecedent: switched.or(candidates.first().copied()) });
+    }
+    references
+}
+
+/// Anaphors without an antecedent, when there are nouns of another gender
+/// or number they could have been meant for; not registered by default.
+pub struct DiscourseSystem;
+
+impl DiscourseSystem {
+    pub const NAME: &'static str = "discourse";
+}
+
+impl ValidationSystem for DiscourseSystem {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check(&self, world: &LogosWorld) -> Vec<Diagnostic> {
+        let tokens = world.tokens();
+        let mut diagnostics = Vec::new();
+        for reference in resolve_references(world).into_iter().filter(|r| r.antecedent.is_none()) {
+            let Some(position) = tokens.iter().position(|t| *t == reference.anaphor) else { continue };
+            let sentence = sentence_number(world, reference.anaphor);
+            let nearest = tokens[..position].iter().rev().copied()
+                .take_while(|t| sentence_number(world, *t) + LOOKBACK >= sentence)
+                .find(|t| {
+                    let f = flags(world, *t);
+                    f.contains(MorphFlags::NOUN) && f.intersects(GENDER | NUMBER)
+                });
+            let Some(nearest) = nearest else { continue };
+            let (anaphor_text, noun_text) = (text(world, reference.anaphor), text(world, nearest));
+            let args = vec![
+                ("anaphor", anaphor_text.clone()),
+                ("anaphor_features", features(flags(world, reference.anaphor))),
+                ("noun", noun_text.clone()),
+                ("noun_features", features(flags(world, nearest))),
+            ];
+            diagnostics.push(Diagnostic {
+                system: Self::NAME,
+                entity: reference.anaphor,
+                message: render(Locale::English, "discourse.no-antecedent", &args),
+                source: anaphor_text,
+                target: noun_text,
+                code: "discourse.no-antecedent",
+                args,
+                confidence: certainty(world.inner(), reference.anaphor, nearest),
+            });
+        }
+        diagnostics
+    }
+}
+
+fn flags(world: &LogosWorld, token: Entity) -> MorphFlags {
+    world.inner().get::<&Morphology>(token).map(|m| m.flags).unwrap_or(MorphFlags::empty())
+}
+
+fn text(world: &LogosWorld, token: Entity) -> String {
+    world.inner().get::<&TokenData>(token).map(|t| t.text.to_string()).unwrap_or_default()
+}
+
+fn sentence_number(world: &LogosWorld, token: Entity) -> u32 {
+    world.sentence_of(token).map_or(0, |s| s.0)
+}
+
+/// Same number and gender wherever both words mark them; case is free.
+fn agrees(a: MorphFlags, b: MorphFlags) -> bool {
+    [NUMBER, GENDER].into_iter().all(|mask| {
+        let (a, b) = (a & mask, b & mask);
+        a.is_empty() || b.is_empty() || a == b
+    })
+}
+
+fn features(flags: MorphFlags) -> String {
+    let names: Vec<&str> = [
+        (MorphFlags::MASCULINE, "masculine"),
+        (MorphFlags::FEMININE, "feminine"),
+        (MorphFlags::NEUTER, "neuter"),
+        (MorphFlags::SINGULAR, "singular"),
+        (MorphFlags::PLURAL, "plural"),
+    ].into_iter().filter(|(f, _)| flags.contains(*f)).map(|(_, name)| name).collect();
+    names.join(" ")
+}
+
+/// A third person pronoun standing on its own: not one of the first or
+/// second person, reflexive or relative, and not attached to a noun as
+/// οὗτος in οὗτος ὁ ἀνήρ or αὐτός "himself" are.
+fn is_anaphoric_pronoun(world: &LogosWorld, token: Entity) -> bool {
+    let f = flags(world, token);
+    let excluded = MorphFlags::FIRST_PERSON | MorphFlags::SECOND_PERSON | MorphFlags::REFLEXIVE | MorphFlags::RELATIVE;
+    f.contains(MorphFlags::PRONOUN) && !f.intersects(excluded) && f.intersects(GENDER | NUMBER)
+        && !world.head_of(token).is_some_and(|head| flags(world, head).contains(MorphFlags::NOUN))
+}
+
+/// An article followed by δέ or μέν and attached to no noun.
+fn is_pronominal_article(world: &LogosWorld, position: usize) -> bool {
+    let tokens = world.tokens();
+    let token = tokens[position];
+    flags(world, token).contains(MorphFlags::ARTICLE)
+        && tokens.get(position + 1).is_some_and(|next| {
+            world.sentence_of(*next) == world.sentence_of(token) && ARTICLE_PARTICLES.contains(&text(world, *next).as_str())
+        })
+        && !world.head_of(token).is_some_and(|head| flags(world, head).contains(MorphFlags::NOUN))
+}
@@ -1 +1,92 @@
 pub mod agreement;
+pub mod definiteness;
+pub mod discourse;
+
+use hecs::{Entity, World};
+use logos_protocol::messages::{render, template, Locale};
+use logos_protocol::MessageArgs;
+
+use crate::components::{Morphology, Syntax};
+use crate::LogosWorld;
+use agreement::{check_agreement, AgreementError};
+
+/// A check over the analyzed world. Register extra ones with
+/// `LogosWorld::register_system`; `LogosWorld::diagnostics` runs them all.
+pub trait ValidationSystem: Send + Sync {
+    /// Stable name, copied into every `Diagnostic` the system reports.
+    fn name(&self) -> &'static str;
+
+    fn check(&self, world: &LogosWorld) -> Vec<Diagnostic>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// `ValidationSystem::name` of the reporting system
+    pub system: &'static str,
+    /// Token the diagnostic is reported on
+    pub entity: Entity,
+    pub source: String,
+    pub target: String,
+    /// The message in English (see `localized`)
+    pub message: String,
+    /// Stable kind of the finding, e.g. "definiteness.missing-article": the
+    /// engine's diagnostic kind, then what the system found
+    pub code: &'static str,
+    /// Parameters of the `code`'s message template (see
+    /// `logos_protocol::messages`)
+    pub args: MessageArgs,
+    /// How sure the diagnostic is, 0 to 1 (see `certainty`)
+    pub confidence: f32,
+}
+
+impl Diagnostic {
+    /// `message` in `locale`; as it is for codes of no catalog.
+    pub fn localized(&self, locale: Locale) -> String {
+        match template(locale, self.code) {
+            Some(_) => render(locale, self.code, &self.args),
+            None => self.message.clone(),
+        }
+    }
+}
+
+/// Confidence of a finding about `source` and `target`: the weakest of their
+/// analyses and, when `source` depends on `target`, of the arc between them.
+pub fn certainty(world: &World, source: Entity, target: Entity) -> f32 {
+    let analysis = |token| world.get::<&Morphology>(token).map_or(1.0, |m| m.confidence);
+    let arc = world.get::<&Syntax>(source).ok()
+        .filter(|s| s.head == target)
+        .map_or(1.0, |s| s.confidence);
+    analysis(source).min(analysis(target)).min(arc)
+}
+
+/// Subject-verb, determiner-noun and reflexive agreement; registered by default.
+pub struct AgreementSystem;
+
+impl AgreementSystem {
+    pub const NAME: &'static str = "agreement";
+}
+
+impl ValidationSystem for AgreementSystem {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check(&self, world: &LogosWorld) -> Vec<Diagnostic> {
+        check_agreement(world.inner(), None).into_iter().map(Diagnostic::from).collect()
+    }
+}
+
+impl From<AgreementError> for Diagnostic {
+    fn from(e: AgreementError) -> Self {
+        Self {
+            system: AgreementSystem::NAME,
+            entity: e.entity,
+            source: e.source,
+            target: e.target,
+            message: e.details,
+            code: e.code,
+            args: e.evidence.args(),
+            confidence: e.confidence,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Dependency-tree queries over the `Syntax` components. Every walk is
+//! guarded against cycles, which a faulty parser could produce.
+
+use std::collections::{HashMap, HashSet};
+
+use hecs::Entity;
+
+use crate::components::{DependencyRole, Syntax};
+use crate::LogosWorld;
+
+impl LogosWorld {
+    /// Head and role of `token`, if it is attached to anything.
+    pub fn dependency(&self, token: Entity) -> Option<(Entity, DependencyRole)> {
+        let syntax = self.world.get::<&Syntax>(token).ok()?;
+        (syntax.role != DependencyRole::Root && syntax.head != token).then_some((syntax.head, syntax.role))
+    }
+
+    pub fn head_of(&self, token: Entity) -> Option<Entity> {
+        self.dependency(token).map(|(head, _)| head)
+    }
+
+    /// Direct dependents of `head`, in token order.
+    pub fn children_of(&self, head: Entity) -> Vec<Entity> {
+        self.tokens.iter().copied().filter(|t| self.head_of(*t) == Some(head)).collect()
+    }
+
+    /// One root per sentence: its first unattached token with dependents, or
+    /// its first token when nothing in it is attached.
+    pub fn roots(&self) -> Vec<Entity> {
+        let mut roots = Vec::new();
+        let mut start = 0;
+        while start < self.tokens.len() {
+            let sentence = self.sentence_of(self.tokens[start]);
+            let end = self.tokens[start..].iter()
+                .position(|t| self.sentence_of(*t) != sentence)
+                .map_or(self.tokens.len(), |n| start + n);
+            let tokens = &self.tokens[start..end];
+            let root = tokens.iter()
+                .find(|t| self.head_of(**t).is_none() && !self.children_of(**t).is_empty())
+                .unwrap_or(&tokens[0]);
+            roots.push(*root);
+            start = end;
+        }
+        roots
+    }
+
+    /// Root of the first sentence.
+    pub fn root(&self) -> Option<Entity> {
+        self.roots().first().copied()
+    }
+
+    /// `token` and everything below it, in token order.
+    pub fn subtree(&self, token: Entity) -> Vec<Entity> {
+        let mut inside = HashSet::from([token]);
+        let mut frontier = vec![token];
+        while let Some(head) = frontier.pop() {
+            for child in self.children_of(head) {
+                if inside.insert(child) {
+                    frontier.push(child);
+                }
+            }
+        }
+        self.tokens.iter().copied().filter(|t| inside.contains(t)).collect()
+    }
+
+    /// Number of arcs from `token` up to its root; `None` if the way up loops.
+    pub fn depth(&self, token: Entity) -> Option<usize> {
+        let mut depth = 0;
+        let mut current = token;
+        while let Some(head) = self.head_of(current) {
+            depth += 1;
+            if depth > self.tokens.len() {
+                return None;
+            }
+            current = head;
+        }
+        Some(depth)
+    }
+
+    /// The tokens of the first dependency cycle, if the tree has one.
+    pub fn find_cycle(&self) -> Option<Vec<Entity>> {
+        let start = self.tokens.iter().copied().find(|t| self.depth(*t).is_none())?;
+        // Walking up from `start` must enter the loop within tokens.len() steps
+        let mut entry = start;
+        for _ in 0..self.tokens.len() {
+            entry = self.head_of(entry)?;
+        }
+        let mut cycle = vec![entry];
+        let mut current = self.head_of(entry)?;
+        while current != entry {
+            cycle.push(current);
+            current = self.head_of(current)?;
+        }
+        Some(cycle)
+    }
+
+    /// True if no arc crosses another: every token between a dependent and
+    /// its head descends from that head. A tree with a cycle is not projective.
+    pub fn is_projective(&self) -> bool {
+        if self.find_cycle().is_some() {
+            return false;
+        }
+        let position: HashMap<Entity, usize> = self.tokens.iter().enumerate().map(|(i, t)| (*t, i)).collect();
+        self.tokens.iter().enumerate().all(|(i, token)| {
+            let Some(head) = self.head_of(*token) else { return true };
+            let Some(&h) = position.get(&head) else { return true };
+            let (lo, hi) = if i < h { (i, h) } else { (h, i) };
+            self.tokens[lo + 1..hi].iter().all(|between| self.dominates(head, *between))
+        })
+    }
+
+    /// Pairs of arcs that cross, as their dependents, in token order: one
+    /// arc has exactly one end strictly between the ends of the other
+    /// (hyperbaton: ἀγαθὸν ἀνὴρ ἵππον ἔχει). Each sentence's root hangs
+    /// from a point before its first token, so an arc spanning the root
+    /// crosses the root's. Arcs to words that are not tokens (reconstructed
+    /// ones) have no position and cross nothing.
+    pub fn crossing_arcs(&self) -> Vec<(Entity, Entity)> {
+        // Tokens at odd positions, leaving an even one before each for its sentence's root
+        let position: HashMap<Entity, usize> = self.tokens.iter().enumerate().map(|(i, t)| (*t, 2 * i + 1)).collect();
+        let roots = self.roots();
+        let arcs: Vec<(Entity, usize, usize)> = self.tokens.iter().enumerate()
+            .filter_map(|(i, token)| {
+                let h = match self.head_of(*token) {
+                    Some(head) => *position.get(&head)?,
+                    None if roots.contains(token) => {
+                        let sentence = self.sentence_of(*token);
+                        2 * self.tokens.iter().position(|t| self.sentence_of(*t) == sentence)?
+                    }
+                    None => return None,
+                };
+                let i = 2 * i + 1;
+                Some((*token, i.min(h), i.max(h)))
+            })
+            .collect();
+        let mut crossings = Vec::new();
+        for (n, &(a, lo, hi)) in arcs.iter().enumerate() {
+            for &(b, other_lo, other_hi) in &arcs[n + 1..] {
+                if (lo < other_lo && other_lo < hi && hi < other_hi) || (other_lo < lo && lo < other_hi && other_hi < hi) {
+                    crossings.push((a, b));
+                }
+            }
+        }
+        crossings
+    }
+
+    /// True if `ancestor` is `token` or lies on its way up to the root.
+    fn dominates(&self, ancestor: Entity, token: Entity) -> bool {
+        let mut current = token;
+        for _ in 0..=self.tokens.len() {
+            if current == ancestor {
+                return true;
+            }
+            match self.head_of(current) {
+                Some(head) => current = head,
+                None => return false,
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,69 @@
+//! Syllables and metre of Greek verse: `syllabify` divides a word
+//! (ἄν-θρω-πος) and tells the quantity of each syllable, `scan` fits a
+//! line to the dactylic hexameter or the iambic trimeter and finds its
+//! caesurae:
+//!
+//! ```text
+//! ἄνδρα μοι ἔννεπε, μοῦσα, πολύτροπον, ὃς μάλα πολλὰ
+//! –∪∪|–∪∪|–∪∪|–∪∪|–∪∪|––    trochaic caesura after μοῦσα, bucolic after πολύτροπον
+//! ```
+//!
+//! Synizesis (Πηληϊάδεω with -εω one syllable) is not modelled, so lines
+//! that need it do not scan.
+
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+pub mod scan;
+pub mod syllable;
+
+pub use scan::{scan, Caesura, CaesuraKind, Foot, FootKind, Meter, Scansion};
+pub use syllable::{syllabify, Quantity, Syllable};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn texts(syllables: &[Syllable]) -> Vec<&str> {
+        syllables.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_syllabify() {
+        assert_eq!(texts(&syllabify("ἄνθρωπος")), ["ἄν", "θρω", "πος"]);
+        assert_eq!(texts(&syllabify("θάλασσα")), ["θά", "λασ", "σα"]);
+        assert_eq!(texts(&syllabify("ἄστρον")), ["ἄ", "στρον"]);
+        assert_eq!(texts(&syllabify("παιδεύω")), ["παι", "δεύ", "ω"]);
+        // A diaeresis parts the vowels
+        assert_eq!(texts(&syllabify("ἀϊδής")), ["ἀ", "ϊ", "δής"]);
+
+        let quantities: Vec<Quantity> = syllabify("ἄνθρωπος").iter().map(|s| s.quantity).collect();
+        assert_eq!(quantities, [Quantity::Long, Quantity::Long, Quantity::Short]);
+        // Stop and liquid: either
+        assert_eq!(syllabify("πατρός")[0].quantity, Quantity::Common);
+        assert_eq!(syllabify("δόξα")[0].quantity, Quantity::Long);
+    }
+
+    #[test]
+    fn test_hexameter() {
+        let scansion = scan("ἄνδρα μοι ἔννεπε, μοῦσα, πολύτροπον, ὃς μάλα πολλὰ", Meter::Hexameter).unwrap();
+        assert_eq!(scansion.pattern(), "–∪∪|–∪∪|–∪∪|–∪∪|–∪∪|––");
+        let kinds: Vec<CaesuraKind> = scansion.caesurae.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, [CaesuraKind::Trochaic, CaesuraKind::Bucolic]);
+        assert_eq!(scansion.syllables[scansion.caesurae[0].after].text, "σα");
+
+        assert!(scan("ὁ λόγος", Meter::Hexameter).is_none());
+    }
+
+    #[test]
+    fn test_trimeter() {
+        // Sophocles, Oedipus Tyrannus 1
+        let scansion = scan("ὦ τέκνα, Κάδμου τοῦ πάλαι νέα τροφή", Meter::IambicTrimeter).unwrap();
+        assert_eq!(scansion.pattern(), "––|∪–|––|∪–|∪–|∪–");
+        assert_eq!(scansion.caesurae.iter().map(|c| c.kind).collect::<Vec<_>>(), [CaesuraKind::Penthemimeral]);
+        assert_eq!(Meter::from_tag("iambic-trimeter"), Some(Meter::IambicTrimeter));
+    }
+}
@@ -0,0 +1,245 @@
+//! Fitting a line's syllables to a metre. Each foot of the metre lists the
+//! shapes it may take, tried in order; common syllables take whichever
+//! quantity their place calls for.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::syllable::{divide, letters, Quantity, Syllable};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Meter {
+    /// Dactylic hexameter: five dactyls or spondees and a final – ×
+    Hexameter,
+    /// Iambic trimeter: three metra × – ∪ –, a long resolved into two
+    /// shorts anywhere but at the end, and an anapaest in the first foot
+    IambicTrimeter,
+}
+
+impl Meter {
+    pub const ALL: [Meter; 2] = [Meter::Hexameter, Meter::IambicTrimeter];
+
+    /// "hexameter" or "iambic-trimeter".
+    pub fn tag(self) -> &'static str {
+        match self {
+            Meter::Hexameter => "hexameter",
+            Meter::IambicTrimeter => "iambic-trimeter",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Meter> {
+        Meter::ALL.into_iter().find(|meter| meter.tag() == tag)
+    }
+
+    /// The shapes of each foot, most usual first.
+    fn feet(self) -> Vec<&'static [&'static [Slot]]> {
+        use Slot::{Anceps as X, Long as L, Short as S};
+        match self {
+            Meter::Hexameter => {
+                let mut feet: Vec<&'static [&'static [Slot]]> = vec![&[&[L, S, S], &[L, L]]; 5];
+                feet.push(&[&[L, X]]);
+                feet
+            }
+            Meter::IambicTrimeter => vec![
+                &[&[X, L], &[X, S, S], &[S, S, L]],
+                &[&[S, L], &[S, S, S]],
+                &[&[X, L], &[X, S, S]],
+                &[&[S, L], &[S, S, S]],
+                &[&[X, L], &[X, S, S]],
+                &[&[S, X]],
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Long,
+    Short,
+    Anceps,
+}
+
+impl Slot {
+    fn admits(self, quantity: Quantity) -> bool {
+        match self {
+            Slot::Long => quantity != Quantity::Short,
+            Slot::Short => quantity != Quantity::Long,
+            Slot::Anceps => true,
+        }
+    }
+
+    /// The quantity a syllable of `quantity` has in this slot.
+    fn realize(self, quantity: Quantity) -> Quantity {
+        match (self, quantity) {
+            (Slot::Long, _) => Quantity::Long,
+            (Slot::Short, _) => Quantity::Short,
+            (Slot::Anceps, Quantity::Common) => Quantity::Long,
+            (Slot::Anceps, quantity) => quantity,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FootKind {
+    Dactyl,
+    Spondee,
+    Trochee,
+    Iamb,
+    Pyrrhic,
+    Tribrach,
+    Anapaest,
+}
+
+impl FootKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            FootKind::Dactyl => "dactyl",
+            FootKind::Spondee => "spondee",
+            FootKind::Trochee => "trochee",
+            FootKind::Iamb => "iamb",
+            FootKind::Pyrrhic => "pyrrhic",
+            FootKind::Tribrach => "tribrach",
+            FootKind::Anapaest => "anapaest",
+        }
+    }
+
+    fn of(quantities: &[Quantity]) -> FootKind {
+        use Quantity::{Long as L, Short as S};
+        match quantities {
+            [L, S, S] => FootKind::Dactyl,
+            [L, L] => FootKind::Spondee,
+            [L, S] => FootKind::Trochee,
+            [S, L] => FootKind::Iamb,
+            [S, S] => FootKind::Pyrrhic,
+            [S, S, S] => FootKind::Tribrach,
+            _ => FootKind::Anapaest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Foot {
+    pub kind: FootKind,
+    /// Indices into `Scansion::syllables`
+    pub syllables: Range<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaesuraKind {
+    /// After the first syllable of the third foot
+    Penthemimeral,
+    /// After the second syllable of a dactylic third foot (hexameter)
+    Trochaic,
+    /// After the first syllable of the fourth foot
+    Hephthemimeral,
+    /// Word end after a dactylic fourth foot (hexameter)
+    Bucolic,
+}
+
+impl CaesuraKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            CaesuraKind::Penthemimeral => "penthemimeral",
+            CaesuraKind::Trochaic => "trochaic",
+            CaesuraKind::Hephthemimeral => "hephthemimeral",
+            CaesuraKind::Bucolic => "bucolic",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Caesura {
+    pub kind: CaesuraKind,
+    /// The syllable the word ends with
+    pub after: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scansion {
+    pub meter: Meter,
+    /// Quantities as scanned: long or short, never common
+    pub syllables: Vec<Syllable>,
+    pub feet: Vec<Foot>,
+    pub caesurae: Vec<Caesura>,
+}
+
+impl Scansion {
+    /// Feet as – and ∪, divided by |: "–∪∪|––|–∪∪|––|–∪∪|––".
+    pub fn pattern(&self) -> String {
+        let feet: Vec<String> = self.feet.iter()
+            .map(|foot| self.syllables[foot.syllables.clone()].iter().map(|s| s.quantity.symbol()).collect())
+            .collect();
+        feet.join("|")
+    }
+}
+
+/// The syllables of a line of verse, with the quantities the words give
+/// them before any metre is fitted.
+pub fn syllables(line: &str) -> Vec<Syllable> {
+    divide(&letters(line.split_whitespace()), true)
+}
+
+/// `line` scanned as `meter`, or None if its syllables fit no shape of it.
+pub fn scan(line: &str, meter: Meter) -> Option<Scansion> {
+    let mut syllables = syllables(line);
+    let feet = meter.feet();
+    let mut shapes = Vec::with_capacity(feet.len());
+    if !fit(&syllables, &feet, &mut shapes) {
+        return None;
+    }
+
+    let mut scanned = Vec::with_capacity(feet.len());
+    let mut start = 0;
+    for shape in shapes {
+        for (syllable, slot) in syllables[start..start + shape.len()].iter_mut().zip(shape) {
+            syllable.quantity = slot.realize(syllable.quantity);
+        }
+        let range = start..start + shape.len();
+        scanned.push(Foot { kind: FootKind::of(&syllables[range.clone()].iter().map(|s| s.quantity).collect::<Vec<_>>()), syllables: range });
+        start += shape.len();
+    }
+    let caesurae = caesurae(&syllables, &scanned, meter);
+    Some(Scansion { meter, syllables, feet: scanned, caesurae })
+}
+
+/// Fits `syllables` to `feet`, pushing the shape chosen for each foot.
+fn fit(syllables: &[Syllable], feet: &[&'static [&'static [Slot]]], shapes: &mut Vec<&'static [Slot]>) -> bool {
+    let Some((foot, rest)) = feet.split_first() else { return syllables.is_empty() };
+    for &shape in foot.iter() {
+        let fits = syllables.len() >= shape.len()
+            && shape.iter().zip(syllables).all(|(slot, syllable)| slot.admits(syllable.quantity));
+        if fits {
+            shapes.push(shape);
+            if fit(&syllables[shape.len()..], rest, shapes) {
+                return true;
+            }
+            shapes.pop();
+        }
+    }
+    false
+}
+
+fn caesurae(syllables: &[Syllable], feet: &[Foot], meter: Meter) -> Vec<Caesura> {
+    let word_ends = |after: usize| syllables.get(after + 1).is_some_and(|next| next.word != syllables[after].word);
+    let mut found = Vec::new();
+    let mut mark = |kind, after| {
+        if word_ends(after) {
+            found.push(Caesura { kind, after });
+        }
+    };
+    let hexameter = meter == Meter::Hexameter;
+    if let Some(third) = feet.get(2) {
+        mark(CaesuraKind::Penthemimeral, third.syllables.start);
+        if hexameter && third.kind == FootKind::Dactyl {
+            mark(CaesuraKind::Trochaic, third.syllables.start + 1);
+        }
+    }
+    if let Some(fourth) = feet.get(3) {
+        mark(CaesuraKind::Hephthemimeral, fourth.syllables.start);
+        if hexameter && fourth.kind == FootKind::Dactyl {
+            mark(CaesuraKind::Bucolic, fourth.syllables.end - 1);
+        }
+    }
+    found
+}
@@ -0,0 +1,223 @@
+//! Syllable division and quantity. A consonant between vowels begins the
+//! next syllable, as does a cluster that can begin a word (ἄ-στρον,
+//! τέ-κνον); other clusters are split after their first consonant
+//! (ἄν-θρω-πος, θά-λασ-σα). A syllable is long by nature (η, ω, a
+//! diphthong, a circumflex or iota subscript) or by position (its vowel
+//! followed by two consonants, ζ ξ ψ counting as two).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use unicode_normalization::UnicodeNormalization;
+
+const CIRCUMFLEX: char = '\u{0342}';
+const MACRON: char = '\u{0304}';
+const BREVE: char = '\u{0306}';
+const DIAERESIS: char = '\u{0308}';
+const IOTA_SUBSCRIPT: char = '\u{0345}';
+
+/// Clusters of two or three consonants a Greek word can begin with.
+const INITIAL_CLUSTERS: &[&str] = &[
+    "βδ", "βλ", "βρ", "γλ", "γν", "γρ", "δμ", "δρ", "θλ", "θν", "θρ", "κλ", "κμ", "κν", "κρ", "κτ", "μν", "πλ", "πν", "πρ",
+    "πτ", "σβ", "σθ", "σκ", "σμ", "σπ", "στ", "σφ", "σχ", "τλ", "τμ", "τρ", "φθ", "φλ", "φρ", "χθ", "χλ", "χν", "χρ",
+    "σκλ", "σκρ", "σπλ", "σπρ", "στρ", "σφρ",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quantity {
+    Long,
+    Short,
+    /// Either, as the verse needs: α, ι or υ of unmarked length, a short
+    /// vowel before a stop and a liquid (πατρός), a long one in hiatus
+    Common,
+}
+
+impl Quantity {
+    /// "long", "short" or "common".
+    pub fn name(self) -> &'static str {
+        match self {
+            Quantity::Long => "long",
+            Quantity::Short => "short",
+            Quantity::Common => "common",
+        }
+    }
+
+    /// – or ∪; ⏓ for common.
+    pub fn symbol(self) -> char {
+        match self {
+            Quantity::Long => '–',
+            Quantity::Short => '∪',
+            Quantity::Common => '⏓',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Syllable {
+    pub text: String,
+    pub quantity: Quantity,
+    /// Index of the word the syllable's vowel belongs to
+    pub word: usize,
+}
+
+/// A Greek letter with the marks after it.
+#[derive(Debug, Clone)]
+pub(crate) struct Letter {
+    written: char,
+    /// Lowercase, σ for ς
+    base: char,
+    marks: Vec<char>,
+    word: usize,
+}
+
+impl Letter {
+    fn is_vowel(&self) -> bool {
+        matches!(self.base, 'α' | 'ε' | 'η' | 'ι' | 'ο' | 'υ' | 'ω')
+    }
+
+    /// Counts as two consonants
+    fn is_double(&self) -> bool {
+        matches!(self.base, 'ζ' | 'ξ' | 'ψ')
+    }
+
+    fn is_stop(&self) -> bool {
+        matches!(self.base, 'β' | 'γ' | 'δ' | 'θ' | 'κ' | 'π' | 'τ' | 'φ' | 'χ')
+    }
+
+    fn is_liquid(&self) -> bool {
+        matches!(self.base, 'λ' | 'μ' | 'ν' | 'ρ')
+    }
+}
+
+fn greek_base(c: char) -> Option<char> {
+    match c.to_lowercase().next()? {
+        'ς' => Some('σ'),
+        lower @ 'α'..='ω' => Some(lower),
+        _ => None,
+    }
+}
+
+/// The Greek letters of `words` in order, numbered by word. Anything else
+/// (punctuation, the apostrophe of an elision) is dropped.
+pub(crate) fn letters<'a>(words: impl IntoIterator<Item = &'a str>) -> Vec<Letter> {
+    let mut letters: Vec<Letter> = Vec::new();
+    for (word, text) in words.into_iter().enumerate() {
+        let mut in_letter = false;
+        for c in text.nfd() {
+            if let Some(base) = greek_base(c) {
+                letters.push(Letter { written: c, base, marks: Vec::new(), word });
+                in_letter = true;
+            } else if in_letter && ('\u{0300}'..='\u{036f}').contains(&c) {
+                if let Some(letter) = letters.last_mut() {
+                    letter.marks.push(c);
+                }
+            } else {
+                in_letter = false;
+            }
+        }
+    }
+    letters
+}
+
+/// Vowels and diphthongs, as `first..=last` letter indices.
+fn nuclei(letters: &[Letter]) -> Vec<(usize, usize)> {
+    let mut nuclei = Vec::new();
+    let mut i = 0;
+    while i < letters.len() {
+        if !letters[i].is_vowel() {
+            i += 1;
+            continue;
+        }
+        let next = letters.get(i + 1).filter(|n| n.word == letters[i].word && !n.marks.contains(&DIAERESIS));
+        let diphthong = matches!(
+            (letters[i].base, next.map(|n| n.base)),
+            ('α' | 'ε' | 'ο' | 'υ', Some('ι')) | ('α' | 'ε' | 'η' | 'ο' | 'ω', Some('υ'))
+        );
+        let last = if diphthong { i + 1 } else { i };
+        nuclei.push((i, last));
+        i = last + 1;
+    }
+    nuclei
+}
+
+fn nature(letters: &[Letter], (first, last): (usize, usize)) -> Quantity {
+    let vowel = &letters[first];
+    if first != last || matches!(vowel.base, 'η' | 'ω') {
+        return Quantity::Long;
+    }
+    if vowel.marks.iter().any(|&m| matches!(m, CIRCUMFLEX | MACRON | IOTA_SUBSCRIPT)) {
+        return Quantity::Long;
+    }
+    if vowel.marks.contains(&BREVE) || matches!(vowel.base, 'ε' | 'ο') {
+        return Quantity::Short;
+    }
+    Quantity::Common
+}
+
+/// Where the consonants `run` between two vowels of one word divide:
+/// how many stay with the first.
+fn split(run: &[Letter]) -> usize {
+    if run.len() <= 1 {
+        return 0;
+    }
+    let cluster: String = run.iter().map(|l| l.base).collect();
+    if INITIAL_CLUSTERS.contains(&cluster.as_str()) {
+        0
+    } else {
+        1
+    }
+}
+
+/// The syllables of `letters`. Word ends divide syllables too: a word's
+/// final consonants stay with it, and the letters of a word without a
+/// vowel (δ’ before a word) go with the next syllable. With `verse`, the
+/// words run on: a vowel before two consonants in the next word is long
+/// by position, and a long one at the end of a word before another vowel
+/// may be shortened (correption), so it is common.
+pub(crate) fn divide(letters: &[Letter], verse: bool) -> Vec<Syllable> {
+    let nuclei = nuclei(letters);
+    let mut syllables = Vec::with_capacity(nuclei.len());
+    let mut start = 0;
+    for (k, &(first, last)) in nuclei.iter().enumerate() {
+        let word = letters[first].word;
+        let next = nuclei.get(k + 1).map_or(letters.len(), |&(next, _)| next);
+        let run = &letters[last + 1..next];
+        let end = match nuclei.get(k + 1) {
+            None => letters.len(),
+            Some(_) => match run.iter().position(|l| l.word != word) {
+                Some(boundary) => last + 1 + boundary,
+                None => last + 1 + split(run),
+            },
+        };
+
+        // Consonants that count for position: the word's own, and with
+        // `verse` those before the next vowel
+        let counted = if verse { run } else { &run[..run.iter().position(|l| l.word != word).unwrap_or(run.len())] };
+        let weight: usize = counted.iter().map(|l| if l.is_double() { 2 } else { 1 }).sum();
+        let muta_cum_liquida = counted.len() == 2
+            && counted[0].is_stop()
+            && counted[1].is_liquid()
+            && counted[0].word == counted[1].word;
+        let natural = nature(letters, (first, last));
+        let quantity = if natural == Quantity::Long {
+            let hiatus = verse && run.is_empty() && letters.get(last + 1).is_some_and(|l| l.word != word);
+            if hiatus { Quantity::Common } else { Quantity::Long }
+        } else if weight >= 2 && !muta_cum_liquida {
+            Quantity::Long
+        } else if weight >= 2 {
+            Quantity::Common
+        } else {
+            natural
+        };
+
+        let text = letters[start..end].iter().flat_map(|l| core::iter::once(l.written).chain(l.marks.iter().copied())).nfc().collect();
+        syllables.push(Syllable { text, quantity, word });
+        start = end;
+    }
+    syllables
+}
+
+/// The syllables of `word` (ἄν-θρω-πος). The quantity of the last one is
+/// what the word alone shows; in verse the next word may lengthen it.
+pub fn syllabify(word: &str) -> Vec<Syllable> {
+    divide(&letters([word]), false)
+}
@@ -0,0 +1,178 @@
+//! Accent placement for generated forms. Paradigm endings are stored without
+//! accents, so where the accent of an inflected form falls is computed here:
+//! verbs draw it back as far as the final syllable allows (recessive), nouns
+//! and adjectives keep it on the syllable the lemma has it on (persistent).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use logos_protocol::MorphFlags;
+use unicode_normalization::UnicodeNormalization;
+
+const GRAVE: char = '\u{0300}';
+const ACUTE: char = '\u{0301}';
+const CIRCUMFLEX: char = '\u{0342}';
+const MACRON: char = '\u{0304}';
+const DIAERESIS: char = '\u{0308}';
+const IOTA_SUBSCRIPT: char = '\u{0345}';
+
+fn is_accent(c: char) -> bool {
+    matches!(c, GRAVE | ACUTE | CIRCUMFLEX)
+}
+
+fn is_mark(c: char) -> bool {
+    ('\u{0300}'..='\u{036f}').contains(&c)
+}
+
+/// True for a Greek vowel, with or without diacritics.
+pub fn is_vowel(c: char) -> bool {
+    let base = c.nfd().next().unwrap_or(c);
+    matches!(base.to_lowercase().next(), Some('α' | 'ε' | 'η' | 'ι' | 'ο' | 'υ' | 'ω'))
+}
+
+/// A base letter and the combining marks after it.
+struct Letter {
+    base: char,
+    marks: Vec<char>,
+}
+
+/// A syllable's vowel or diphthong, as a range of letters.
+struct Nucleus {
+    first: usize,
+    last: usize,
+    long: bool,
+}
+
+fn letters(word: &str) -> Vec<Letter> {
+    let mut letters: Vec<Letter> = Vec::new();
+    for c in word.nfd() {
+        match letters.last_mut() {
+            Some(letter) if is_mark(c) => letter.marks.push(c),
+            _ => letters.push(Letter { base: c, marks: Vec::new() }),
+        }
+    }
+    letters
+}
+
+fn nuclei(letters: &[Letter]) -> Vec<Nucleus> {
+    let mut nuclei = Vec::new();
+    let mut i = 0;
+    while i < letters.len() {
+        if !is_vowel(letters[i].base) {
+            i += 1;
+            continue;
+        }
+        let base = letters[i].base.to_lowercase().next().unwrap_or(letters[i].base);
+        let next = letters.get(i + 1).filter(|n| !n.marks.contains(&DIAERESIS));
+        let second = next.and_then(|n| n.base.to_lowercase().next());
+        let diphthong = matches!(
+            (base, second),
+            ('α' | 'ε' | 'ο' | 'υ', Some('ι')) | ('α' | 'ε' | 'η' | 'ο' | 'ω', Some('υ'))
+        );
+        if diphthong {
+            // Final -αι and -οι count as short for accentuation
+            let final_short = i + 2 == letters.len() && matches!(base, 'α' | 'ο') && second == Some('ι');
+            nuclei.push(Nucleus { first: i, last: i + 1, long: !final_short });
+            i += 2;
+        } else {
+            let marks = &letters[i].marks;
+            let long = matches!(base, 'η' | 'ω') || marks.iter().any(|&m| matches!(m, CIRCUMFLEX | MACRON | IOTA_SUBSCRIPT));
+            nuclei.push(Nucleus { first: i, last: i, long });
+            i += 1;
+        }
+    }
+    nuclei
+}
+
+fn render(letters: &[Letter]) -> String {
+    letters.iter().flat_map(|l| core::iter::once(l.base).chain(l.marks.iter().copied())).nfc().collect()
+}
+
+/// `word` without acute, grave or circumflex accents; breathings, diaereses
+/// and iota subscripts stay.
+pub fn strip_accents(word: &str) -> String {
+    word.nfd().filter(|&c| !is_accent(c)).nfc().collect()
+}
+
+/// `word` as written with one accent, a grave turned acute (a grave is an
+/// oxytone's accent before another word). `None` without an accent, or with
+/// two (the second one thrown back by an enclitic).
+pub fn single_accent(word: &str) -> Option<String> {
+    let decomposed: Vec<char> = word.nfd().collect();
+    if decomposed.iter().filter(|&&c| is_accent(c)).count() != 1 {
+        return None;
+    }
+    Some(decomposed.into_iter().map(|c| if c == GRAVE { ACUTE } else { c }).nfc().collect())
+}
+
+/// Number of syllables (vowels and diphthongs) in `word`.
+pub fn syllables(word: &str) -> usize {
+    nuclei(&letters(word)).len()
+}
+
+/// `word` with its accents replaced by `accent` on syllable `at` (counted
+/// from the start), on the second vowel of a diphthong.
+fn place(word: &str, at: usize, accent: char) -> String {
+    let mut letters = letters(&strip_accents(word));
+    let nuclei = nuclei(&letters);
+    if let Some(nucleus) = nuclei.get(at) {
+        letters[nucleus.last].marks.push(accent);
+    }
+    render(&letters)
+}
+
+/// The accent a long penult takes before a short ultima is the circumflex
+/// (σωτῆρα); anywhere else in this position it is the acute.
+fn penult_accent(nuclei: &[Nucleus]) -> char {
+    let n = nuclei.len();
+    if n >= 2 && nuclei[n - 2].long && !nuclei[n - 1].long { CIRCUMFLEX } else { ACUTE }
+}
+
+/// Recessive accent, as on finite verbs: the antepenult when the ultima is
+/// short, else the penult (ἔλεγον, λέγουσι, λέγω).
+pub fn recessive(word: &str) -> String {
+    let stripped = strip_accents(word);
+    let nuclei = nuclei(&letters(&stripped));
+    let n = nuclei.len();
+    match n {
+        0 => stripped,
+        1 => place(&stripped, 0, if nuclei[0].long { CIRCUMFLEX } else { ACUTE }),
+        _ if n >= 3 && !nuclei[n - 1].long => place(&stripped, n - 3, ACUTE),
+        _ => place(&stripped, n - 2, penult_accent(&nuclei)),
+    }
+}
+
+/// Persistent accent, as on nouns and adjectives: on the syllable `lemma`
+/// has it on, as far as the length of the ultima allows (ἄνθρωπος,
+/// ἀνθρώπου). A long accented ultima of the genitive or dative takes the
+/// circumflex (ποταμοῦ). A lemma without an accent falls back to `recessive`.
+pub fn persistent(word: &str, lemma: &str, flags: MorphFlags) -> String {
+    let lemma_letters = letters(lemma);
+    let accented = nuclei(&lemma_letters).into_iter().enumerate().find_map(|(k, nucleus)| {
+        let marks = lemma_letters[nucleus.first..=nucleus.last].iter().flat_map(|l| l.marks.iter());
+        marks.copied().find(|&m| is_accent(m)).map(|accent| (k, accent))
+    });
+    let Some((k, lemma_accent)) = accented else {
+        return recessive(word);
+    };
+
+    let stripped = strip_accents(word);
+    let nuclei = nuclei(&letters(&stripped));
+    let n = nuclei.len();
+    if n == 0 {
+        return stripped;
+    }
+    let ultima_long = nuclei[n - 1].long;
+    let earliest = if ultima_long { n.saturating_sub(2) } else { n.saturating_sub(3) };
+    let at = k.min(n - 1).max(earliest);
+
+    let accent = if at + 1 == n {
+        let oblique = flags.intersects(MorphFlags::GENITIVE | MorphFlags::DATIVE);
+        if ultima_long && (oblique || lemma_accent == CIRCUMFLEX) { CIRCUMFLEX } else { ACUTE }
+    } else if at + 2 == n {
+        penult_accent(&nuclei)
+    } else {
+        ACUTE
+    };
+    place(&stripped, at, accent)
+}
@@ -6,10 +6,12 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod accent;
+
 use alloc::string::String;
-use alloc::format;
 
-use logos_protocol::{Lemma, Paradigm, MorphFlags};
+use logos_protocol::{LanguageProfile, Lemma, Paradigm, MorphFlags};
+use unicode_normalization::UnicodeNormalization;
 
 use core::fmt;
 
@@ -39,21 +41,95 @@ pub fn generate(
     // assuming we deserialized or are using the unarchived version.
     // If using Archived version, the signature would change. 
     // For Phase 3 MVP, we work with the standard structs.
+    inflect(&lemma.text, paradigm.endings.iter().map(|(bits, suffix)| (*bits, suffix.as_str())), flags)
+}
 
-    for (rule_flags, suffix) in &paradigm.endings {
+/// `generate` over any stem and ending table, e.g. one read from an archive.
+pub fn inflect<'a>(
+    stem: &str,
+    endings: impl IntoIterator<Item = (u32, &'a str)>,
+    flags: MorphFlags,
+) -> Result<String, MorphError> {
+    for (rule_flags, suffix) in endings {
         // Exact match check
-        if *rule_flags == flags.bits() {
-            return Ok(format!("{}{}", lemma.text, suffix));
+        if rule_flags == flags.bits() {
+            return Ok(format!("{}{}", stem, suffix));
         }
     }
 
     Err(MorphError::FormNotFound(flags))
 }
 
+/// How generated forms write the iota of ᾳ, ῃ, ῳ. Dictionaries store the
+/// subscript.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IotaStyle {
+    /// ᾠδή
+    #[default]
+    Subscript,
+    /// ὠιδή, as in inscriptions and some editions
+    Adscript,
+    /// ωδή, as Modern Greek spells it
+    Omitted,
+}
+
+/// Spelling choices for generated forms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GenerateOptions {
+    /// Add the movable ν before a word beginning with a vowel (λέγουσιν
+    /// αὐτῷ, but λέγουσι τοῦτο), to dative plurals and third-person forms
+    /// in -σι and third-person singulars in -ε.
+    pub movable_nu: bool,
+    pub iota: IotaStyle,
+}
+
+impl GenerateOptions {
+    /// The spelling conventions of `profile`: Modern Greek drops the iota
+    /// subscript.
+    pub fn for_profile(profile: LanguageProfile) -> Self {
+        let iota = match profile {
+            LanguageProfile::Ancient => IotaStyle::Subscript,
+            LanguageProfile::Modern => IotaStyle::Omitted,
+        };
+        Self { iota, ..Self::default() }
+    }
+}
+
+/// `form` with its iota subscripts written in `style`.
+pub fn write_iota(form: &str, style: IotaStyle) -> String {
+    const SUBSCRIPT: char = '\u{0345}';
+    match style {
+        IotaStyle::Subscript => form.into(),
+        IotaStyle::Adscript => form.nfd().map(|c| if c == SUBSCRIPT { 'ι' } else { c }).nfc().collect(),
+        IotaStyle::Omitted => form.nfd().filter(|&c| c != SUBSCRIPT).nfc().collect(),
+    }
+}
+
+/// `inflect` for a form followed by `next` (the next word, if any).
+pub fn inflect_before<'a>(
+    stem: &str,
+    endings: impl IntoIterator<Item = (u32, &'a str)>,
+    flags: MorphFlags,
+    next: Option<&str>,
+    options: &GenerateOptions,
+) -> Result<String, MorphError> {
+    let form = write_iota(&inflect(stem, endings, flags)?, options.iota);
+    let before_vowel = next.and_then(|w| w.chars().next()).is_some_and(accent::is_vowel);
+    if !options.movable_nu || !before_vowel {
+        return Ok(form);
+    }
+    let plain = accent::strip_accents(&form);
+    let third_person = flags.contains(MorphFlags::THIRD_PERSON | MorphFlags::SINGULAR) && plain.ends_with('ε');
+    if plain.ends_with("σι") || third_person {
+        return Ok(format!("{}ν", form));
+    }
+    Ok(form)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use logos_protocol::{LemmaId, Gender, ParadigmId};
+    use logos_protocol::{Dialect, LemmaId, Gender, ParadigmId, PartOfSpeech, Period};
     use proptest::prelude::*;
 
     proptest! {
@@ -62,7 +138,13 @@ mod tests {
             let lemma = Lemma { 
                 id: LemmaId(1), 
                 text: stem.clone(), 
-                gender: Gender::Neuter 
+                gender: Gender::Neuter,
+                pos: PartOfSpeech::Noun,
+                urn: None,
+                gloss: None,
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                paradigm: None,
             };
             
             // Arbitrary flags
@@ -70,6 +152,8 @@ mod tests {
             
             let paradigm = Paradigm {
                 id: ParadigmId(1),
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
                 endings: vec![(flags.bits(), suffix.clone())]
             };
             
@@ -77,4 +161,58 @@ mod tests {
             assert_eq!(result.unwrap(), format!("{}{}", stem, suffix));
         }
     }
+
+    #[test]
+    fn test_movable_nu() {
+        let endings = [
+            ((MorphFlags::VERB | MorphFlags::THIRD_PERSON | MorphFlags::PLURAL).bits(), "ουσι"),
+            ((MorphFlags::VERB | MorphFlags::THIRD_PERSON | MorphFlags::SINGULAR).bits(), "ε"),
+            ((MorphFlags::VERB | MorphFlags::FIRST_PERSON | MorphFlags::SINGULAR).bits(), "ον"),
+        ];
+        let options = GenerateOptions { movable_nu: true, ..Default::default() };
+        let form = |flags: MorphFlags, next, options: &GenerateOptions| inflect_before("λέγ", endings, MorphFlags::VERB | flags, next, options).unwrap();
+
+        let plural = MorphFlags::THIRD_PERSON | MorphFlags::PLURAL;
+        assert_eq!(form(plural, Some("αὐτῷ"), &options), "λέγουσιν");
+        assert_eq!(form(plural, Some("τοῦτο"), &options), "λέγουσι");
+        assert_eq!(form(plural, None, &options), "λέγουσι");
+        assert_eq!(form(plural, Some("αὐτῷ"), &GenerateOptions::default()), "λέγουσι");
+        assert_eq!(form(MorphFlags::THIRD_PERSON | MorphFlags::SINGULAR, Some("ἐκεῖ"), &options), "λέγεν");
+        assert_eq!(form(MorphFlags::FIRST_PERSON | MorphFlags::SINGULAR, Some("ἐκεῖ"), &options), "λέγον");
+    }
+
+    #[test]
+    fn test_iota_style() {
+        assert_eq!(write_iota("ᾠδῇ", IotaStyle::Subscript), "ᾠδῇ");
+        assert_eq!(write_iota("ᾠδῇ", IotaStyle::Adscript), "ὠιδῆι");
+        assert_eq!(write_iota("ᾠδῇ", IotaStyle::Omitted), "ὠδῆ");
+
+        let endings = [(MorphFlags::DATIVE.bits(), "ῇ")];
+        let modern = GenerateOptions::for_profile(LanguageProfile::Modern);
+        assert_eq!(inflect_before("ᾠδ", endings, MorphFlags::DATIVE, None, &modern).unwrap(), "ὠδῆ");
+        let ancient = GenerateOptions::for_profile(LanguageProfile::Ancient);
+        assert_eq!(inflect_before("ᾠδ", endings, MorphFlags::DATIVE, None, &ancient).unwrap(), "ᾠδῇ");
+    }
+
+    #[test]
+    fn test_accentuation() {
+        use accent::{persistent, recessive};
+
+        assert_eq!(recessive("ἐλεγον"), "ἔλεγον");
+        assert_eq!(recessive("λεγουσι"), "λέγουσι");
+        assert_eq!(recessive("λεγω"), "λέγω");
+        assert_eq!(recessive("παιδευουσι"), "παιδεύουσι");
+
+        let nom = MorphFlags::NOMINATIVE;
+        let gen = MorphFlags::GENITIVE;
+        assert_eq!(persistent("ἀνθρωπος", "ἄνθρωπος", nom), "ἄνθρωπος");
+        assert_eq!(persistent("ἀνθρωπου", "ἄνθρωπος", gen), "ἀνθρώπου");
+        assert_eq!(persistent("ἀνθρωποι", "ἄνθρωπος", nom), "ἄνθρωποι");
+        assert_eq!(persistent("δωρα", "δῶρον", nom), "δῶρα");
+        assert_eq!(persistent("δωρου", "δῶρον", gen), "δώρου");
+        assert_eq!(persistent("ποταμου", "ποταμός", gen), "ποταμοῦ");
+        assert_eq!(persistent("ποταμον", "ποταμός", MorphFlags::ACCUSATIVE), "ποταμόν");
+        // No accent on the lemma (a bare stem): recessive
+        assert_eq!(persistent("ἀνθρωπος", "ἀνθρωπ", nom), "ἄνθρωπος");
+    }
 }
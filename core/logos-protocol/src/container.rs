@@ -0,0 +1,76 @@
+//! Optional compressed wrapper around a dictionary archive:
+//!
+//! ```text
+//! "LGSD" | version: u8 | codec: u8 | reserved: u16 | raw_len: u32 LE | payload
+//! ```
+//!
+//! Plain rkyv archives never start with the magic, so readers can accept
+//! either form transparently.
+
+/// Leading bytes of a container.
+pub const MAGIC: [u8; 4] = *b"LGSD";
+pub const CONTAINER_VERSION: u8 = 1;
+pub const HEADER_LEN: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Codec {
+    /// zlib-wrapped DEFLATE (the Adler-32 trailer catches corruption)
+    Zlib = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContainerHeader {
+    pub codec: Codec,
+    /// Size of the decompressed archive
+    pub raw_len: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerError {
+    Truncated,
+    UnsupportedVersion(u8),
+    UnknownCodec(u8),
+}
+
+impl core::fmt::Display for ContainerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ContainerError::Truncated => write!(f, "container header is truncated"),
+            ContainerError::UnsupportedVersion(v) => write!(f, "unsupported container version {}", v),
+            ContainerError::UnknownCodec(c) => write!(f, "unknown container codec {}", c),
+        }
+    }
+}
+
+/// True if `data` starts like a container (it may still be truncated).
+pub fn is_container(data: &[u8]) -> bool {
+    data.len() >= MAGIC.len() && data[..MAGIC.len()] == MAGIC
+}
+
+impl ContainerHeader {
+    /// Parses the header of a buffer that `is_container`.
+    pub fn parse(data: &[u8]) -> Result<Self, ContainerError> {
+        if data.len() < HEADER_LEN {
+            return Err(ContainerError::Truncated);
+        }
+        if data[4] != CONTAINER_VERSION {
+            return Err(ContainerError::UnsupportedVersion(data[4]));
+        }
+        let codec = match data[5] {
+            1 => Codec::Zlib,
+            other => return Err(ContainerError::UnknownCodec(other)),
+        };
+        let raw_len = u32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+        Ok(Self { codec, raw_len })
+    }
+
+    pub fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut out = [0u8; HEADER_LEN];
+        out[..4].copy_from_slice(&MAGIC);
+        out[4] = CONTAINER_VERSION;
+        out[5] = self.codec as u8;
+        out[8..].copy_from_slice(&self.raw_len.to_le_bytes());
+        out
+    }
+}
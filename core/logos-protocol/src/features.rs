@@ -0,0 +1,400 @@
+//! Typed view of `MorphFlags`: one optional value per grammatical category,
+//! for code that asks "which case?" instead of testing bits, and for
+//! messages that spell the features out ("gen.sg.masc"), compact tags
+//! ("N-GSM") and Universal Dependencies FEATS ("Case=Gen|Gender=Masc|Number=Sing").
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize as SerdeDeserialize, Serialize as SerdeSerialize};
+
+use crate::morphology::{Case, Degree, Gender, Mood, MorphFlags, Number, Person, Tense, Voice};
+
+/// Categories whose values each have a flag of their own.
+macro_rules! flag_categories {
+    ($($category:ident $ud:literal { $($value:ident => $flag:ident, $abbreviation:literal, $ud_value:literal, $letter:literal;)* })*) => {$(
+        impl $category {
+            /// Every flag of the category.
+            pub const MASK: MorphFlags = MorphFlags::empty()$(.union(MorphFlags::$flag))*;
+
+            /// The value `flags` mark; None when they mark none or several.
+            pub fn from_flags(flags: MorphFlags) -> Option<Self> {
+                match flags & Self::MASK {
+                    $(f if f == MorphFlags::$flag => Some($category::$value),)*
+                    _ => None,
+                }
+            }
+
+            pub fn flags(self) -> MorphFlags {
+                match self {
+                    $($category::$value => MorphFlags::$flag,)*
+                }
+            }
+
+            pub fn abbreviation(self) -> &'static str {
+                match self {
+                    $($category::$value => $abbreviation,)*
+                }
+            }
+
+            /// UD feature and value.
+            pub fn ud(self) -> (&'static str, &'static str) {
+                match self {
+                    $($category::$value => ($ud, $ud_value),)*
+                }
+            }
+
+            /// Letter in a compact tag.
+            pub fn letter(self) -> char {
+                match self {
+                    $($category::$value => $letter,)*
+                }
+            }
+        }
+    )*};
+}
+
+flag_categories! {
+    Case "Case" {
+        Nominative => NOMINATIVE, "nom", "Nom", 'N';
+        Genitive => GENITIVE, "gen", "Gen", 'G';
+        Dative => DATIVE, "dat", "Dat", 'D';
+        Accusative => ACCUSATIVE, "acc", "Acc", 'A';
+        Vocative => VOCATIVE, "voc", "Voc", 'V';
+    }
+    Gender "Gender" {
+        Masculine => MASCULINE, "masc", "Masc", 'M';
+        Feminine => FEMININE, "fem", "Fem", 'F';
+        Neuter => NEUTER, "neut", "Neut", 'N';
+    }
+    Number "Number" {
+        Singular => SINGULAR, "sg", "Sing", 'S';
+        Plural => PLURAL, "pl", "Plur", 'P';
+    }
+    Person "Person" {
+        First => FIRST_PERSON, "1", "1", '1';
+        Second => SECOND_PERSON, "2", "2", '2';
+        Third => THIRD_PERSON, "3", "3", '3';
+    }
+    Voice "Voice" {
+        Active => ACTIVE, "act", "Act", 'A';
+        Passive => PASSIVE, "pass", "Pass", 'P';
+    }
+}
+
+/// The flags know three times; the other tenses map onto the nearest.
+impl Tense {
+    pub const MASK: MorphFlags = MorphFlags::PRESENT.union(MorphFlags::PAST).union(MorphFlags::FUTURE);
+
+    pub fn from_flags(flags: MorphFlags) -> Option<Self> {
+        match flags & Self::MASK {
+            f if f == MorphFlags::PRESENT => Some(Tense::Present),
+            f if f == MorphFlags::PAST => Some(Tense::Past),
+            f if f == MorphFlags::FUTURE => Some(Tense::Future),
+            _ => None,
+        }
+    }
+
+    pub fn flags(self) -> MorphFlags {
+        match self {
+            Tense::Present | Tense::Perfect => MorphFlags::PRESENT,
+            Tense::Future | Tense::FuturePerfect => MorphFlags::FUTURE,
+            Tense::Aorist | Tense::Imperfect | Tense::Pluperfect | Tense::Past => MorphFlags::PAST,
+        }
+    }
+
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Tense::Present => "pres",
+            Tense::Future => "fut",
+            Tense::Aorist => "aor",
+            Tense::Imperfect => "impf",
+            Tense::Perfect => "perf",
+            Tense::Pluperfect => "plup",
+            Tense::FuturePerfect => "futperf",
+            Tense::Past => "past",
+        }
+    }
+
+    /// UD tense; the aspect of the tenses that mark one comes from `aspect`.
+    pub fn ud(self) -> (&'static str, &'static str) {
+        match self {
+            Tense::Present | Tense::Perfect => ("Tense", "Pres"),
+            Tense::Future | Tense::FuturePerfect => ("Tense", "Fut"),
+            Tense::Aorist | Tense::Imperfect | Tense::Past => ("Tense", "Past"),
+            Tense::Pluperfect => ("Tense", "Pqp"),
+        }
+    }
+
+    /// UD `Aspect` value.
+    pub fn aspect(self) -> Option<&'static str> {
+        match self {
+            Tense::Imperfect => Some("Imp"),
+            Tense::Aorist | Tense::Perfect | Tense::FuturePerfect => Some("Perf"),
+            _ => None,
+        }
+    }
+
+    /// `X` for a past of unmarked aspect.
+    pub fn letter(self) -> char {
+        match self {
+            Tense::Present => 'P',
+            Tense::Future => 'F',
+            Tense::Aorist => 'A',
+            Tense::Imperfect => 'I',
+            Tense::Perfect => 'R',
+            Tense::Pluperfect => 'L',
+            Tense::FuturePerfect => 'T',
+            Tense::Past => 'X',
+        }
+    }
+}
+
+/// Only the non-finite moods have flags; finite forms carry none.
+impl Mood {
+    pub const MASK: MorphFlags = MorphFlags::PARTICIPLE.union(MorphFlags::INFINITIVE);
+
+    pub fn from_flags(flags: MorphFlags) -> Option<Self> {
+        match flags & Self::MASK {
+            f if f == MorphFlags::PARTICIPLE => Some(Mood::Participle),
+            f if f == MorphFlags::INFINITIVE => Some(Mood::Infinitive),
+            _ => None,
+        }
+    }
+
+    pub fn flags(self) -> MorphFlags {
+        match self {
+            Mood::Participle => MorphFlags::PARTICIPLE,
+            Mood::Infinitive => MorphFlags::INFINITIVE,
+            _ => MorphFlags::empty(),
+        }
+    }
+
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Mood::Indicative => "ind",
+            Mood::Subjunctive => "subj",
+            Mood::Imperative => "imp",
+            Mood::Participle => "part",
+            Mood::Infinitive => "inf",
+            Mood::Optative => "opt",
+        }
+    }
+
+    /// UD `VerbForm` of the non-finite moods, `Mood` of the others.
+    pub fn ud(self) -> (&'static str, &'static str) {
+        match self {
+            Mood::Indicative => ("Mood", "Ind"),
+            Mood::Subjunctive => ("Mood", "Sub"),
+            Mood::Imperative => ("Mood", "Imp"),
+            Mood::Optative => ("Mood", "Opt"),
+            Mood::Participle => ("VerbForm", "Part"),
+            Mood::Infinitive => ("VerbForm", "Inf"),
+        }
+    }
+
+    pub fn letter(self) -> char {
+        match self {
+            Mood::Indicative => 'I',
+            Mood::Subjunctive => 'S',
+            Mood::Imperative => 'M',
+            Mood::Participle => 'P',
+            Mood::Infinitive => 'N',
+            Mood::Optative => 'O',
+        }
+    }
+}
+
+impl Degree {
+    pub const MASK: MorphFlags = MorphFlags::COMPARATIVE.union(MorphFlags::SUPERLATIVE);
+
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Degree::Positive => "pos",
+            Degree::Comparative => "comp",
+            Degree::Superlative => "superl",
+        }
+    }
+
+    pub fn ud(self) -> (&'static str, &'static str) {
+        match self {
+            Degree::Positive => ("Degree", "Pos"),
+            Degree::Comparative => ("Degree", "Cmp"),
+            Degree::Superlative => ("Degree", "Sup"),
+        }
+    }
+
+    pub fn letter(self) -> char {
+        match self {
+            Degree::Positive => 'P',
+            Degree::Comparative => 'C',
+            Degree::Superlative => 'S',
+        }
+    }
+}
+
+/// The inflectional features of one analysis. A category is None when the
+/// flags mark no value for it, or several. Part-of-speech hints are not
+/// features and do not survive `to_flags`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+pub struct MorphFeatures {
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub case: Option<Case>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub person: Option<Person>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub number: Option<Number>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub gender: Option<Gender>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub tense: Option<Tense>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub mood: Option<Mood>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub voice: Option<Voice>,
+    /// Comparative or superlative; the positive is unmarked
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub degree: Option<Degree>,
+}
+
+impl MorphFeatures {
+    pub fn to_flags(self) -> MorphFlags {
+        let mut flags = MorphFlags::empty();
+        flags |= self.case.map_or(MorphFlags::empty(), Case::flags);
+        flags |= self.person.map_or(MorphFlags::empty(), Person::flags);
+        flags |= self.number.map_or(MorphFlags::empty(), Number::flags);
+        flags |= self.gender.map_or(MorphFlags::empty(), Gender::flags);
+        flags |= self.tense.map_or(MorphFlags::empty(), Tense::flags);
+        flags |= self.mood.map_or(MorphFlags::empty(), Mood::flags);
+        flags |= self.voice.map_or(MorphFlags::empty(), Voice::flags);
+        flags | self.degree.map_or(MorphFlags::empty(), Degree::flags)
+    }
+
+    /// Abbreviations of the values present, in display order.
+    pub fn abbreviations(self) -> impl Iterator<Item = &'static str> {
+        [
+            self.case.map(Case::abbreviation),
+            self.person.map(Person::abbreviation),
+            self.number.map(Number::abbreviation),
+            self.gender.map(Gender::abbreviation),
+            self.tense.map(Tense::abbreviation),
+            self.mood.map(Mood::abbreviation),
+            self.voice.map(Voice::abbreviation),
+            self.degree.map(Degree::abbreviation),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Universal Dependencies FEATS: `Feature=Value` pairs in alphabetical
+    /// order, joined by `|`; `_` when there are none.
+    pub fn ud_feats(self) -> String {
+        let mut feats: Vec<(&str, &str)> = [
+            self.case.map(Case::ud),
+            self.degree.map(Degree::ud),
+            self.gender.map(Gender::ud),
+            self.mood.map(Mood::ud),
+            self.number.map(Number::ud),
+            self.person.map(Person::ud),
+            self.tense.map(Tense::ud),
+            self.tense.and_then(Tense::aspect).map(|aspect| ("Aspect", aspect)),
+            self.voice.map(Voice::ud),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if feats.is_empty() {
+            return String::from("_");
+        }
+        feats.sort();
+        let feats: Vec<String> = feats.iter().map(|(feature, value)| alloc::format!("{}={}", feature, value)).collect();
+        feats.join("|")
+    }
+}
+
+impl From<MorphFlags> for MorphFeatures {
+    fn from(flags: MorphFlags) -> Self {
+        MorphFeatures {
+            case: Case::from_flags(flags),
+            person: Person::from_flags(flags),
+            number: Number::from_flags(flags),
+            gender: Gender::from_flags(flags),
+            tense: Tense::from_flags(flags),
+            mood: Mood::from_flags(flags),
+            voice: Voice::from_flags(flags),
+            degree: Some(Degree::from_flags(flags)).filter(|&d| d != Degree::Positive),
+        }
+    }
+}
+
+impl From<MorphFeatures> for MorphFlags {
+    fn from(features: MorphFeatures) -> Self {
+        features.to_flags()
+    }
+}
+
+/// Dot-separated abbreviations: "gen.sg.masc", "3.sg.pres.act".
+impl fmt::Display for MorphFeatures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, abbreviation) in self.abbreviations().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            f.write_str(abbreviation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compact parsing code in the style of Robinson's: part of speech, then
+/// the feature letters, dash-separated. Nominals give case, number and
+/// gender ("N-GSM", "T-NPF", pronouns with their person: "P-1GS") and a
+/// degree ("A-GSM-C"); verbs give tense, voice and mood, then person and
+/// number ("V-PA-3S") or, for participles, case, number and gender
+/// ("V-PAP-GSM"). Categories the flags leave open are skipped.
+pub fn compact_tag(flags: MorphFlags) -> String {
+    let features = MorphFeatures::from(flags);
+    let letters = |values: &[Option<char>]| -> String { values.iter().flatten().collect() };
+    let nominal = letters(&[features.case.map(Case::letter), features.number.map(Number::letter), features.gender.map(Gender::letter)]);
+
+    let verbal = flags.contains(MorphFlags::VERB) || features.mood.is_some();
+    let parts: [String; 3] = if verbal {
+        let ending = match features.mood {
+            Some(Mood::Participle) => nominal,
+            _ => letters(&[features.person.map(Person::letter), features.number.map(Number::letter)]),
+        };
+        let stem = letters(&[features.tense.map(Tense::letter), features.voice.map(Voice::letter), features.mood.map(Mood::letter)]);
+        [String::from("V"), stem, ending]
+    } else {
+        let pos = if flags.contains(MorphFlags::ARTICLE) {
+            "T"
+        } else if flags.contains(MorphFlags::RELATIVE) {
+            "R"
+        } else if flags.contains(MorphFlags::REFLEXIVE) {
+            "F"
+        } else if flags.contains(MorphFlags::PRONOUN) {
+            "P"
+        } else if flags.contains(MorphFlags::ADJECTIVE) {
+            "A"
+        } else if flags.contains(MorphFlags::NOUN) {
+            "N"
+        } else if flags.contains(MorphFlags::PREPOSITION) {
+            "PREP"
+        } else if flags.contains(MorphFlags::CONJUNCTION) {
+            "CONJ"
+        } else if flags.contains(MorphFlags::NUMERAL) {
+            "A-NUI"
+        } else {
+            "X"
+        };
+        let mut body = letters(&[features.person.map(Person::letter)]);
+        body.push_str(&nominal);
+        let degree = features.degree.map(|d| String::from(d.letter())).unwrap_or_default();
+        [String::from(pos), body, degree]
+    };
+    let parts: Vec<&str> = parts.iter().map(String::as_str).filter(|part| !part.is_empty()).collect();
+    parts.join("-")
+}
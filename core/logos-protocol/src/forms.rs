@@ -0,0 +1,122 @@
+//! Pre-expanded surface forms: every stem plus ending a dictionary licenses,
+//! worked out when the archive is built and stored in a hash table, so
+//! analysing a word is one probe instead of a scan over every lemma and
+//! paradigm. Optional: an archive without the table is scanned as before.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use rkyv::{Archive, Serialize};
+
+use crate::ids::LemmaId;
+use crate::morphology::{Dialect, Period};
+use crate::pool::{ArchivedDictionary, PoolError, PoolStr, StringPool};
+
+/// One analysis of a surface form, as the builder hands it in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfaceForm {
+    /// Stem plus ending
+    pub form: String,
+    pub lemma: LemmaId,
+    /// MorphFlags bits
+    pub flags: u32,
+    /// Bytes of `form` that are the ending
+    pub ending_len: u8,
+    /// Usage of the lemma and paradigm combined
+    pub dialects: Dialect,
+    pub periods: Period,
+}
+
+#[derive(Debug, Clone, Archive, Serialize)]
+#[archive(check_bytes, archived = "ArchivedSurfaceForm")]
+pub struct PooledSurfaceForm {
+    pub form: PoolStr,
+    pub lemma: LemmaId,
+    pub flags: u32,
+    pub ending_len: u8,
+    /// `Dialect` bits
+    pub dialects: u8,
+    /// `Period` bits
+    pub periods: u8,
+}
+
+/// 64-bit FNV-1a, the table's hash.
+fn hash(form: &str) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in form.bytes() {
+        h ^= b as u64;
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    h
+}
+
+/// Interns `forms` and lays out the table: entries grouped by form (each
+/// group in the order given, which lookups keep), and an open-addressing
+/// slot array at most half full, each slot the position of a group's first
+/// entry plus one (0 for empty).
+pub(crate) fn build_table(pool: &mut StringPool, forms: &[SurfaceForm]) -> Result<(Vec<PooledSurfaceForm>, Vec<u32>), PoolError> {
+    if forms.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let mut order: Vec<usize> = (0..forms.len()).collect();
+    order.sort_by(|&a, &b| forms[a].form.cmp(&forms[b].form));
+
+    let mut entries = Vec::with_capacity(forms.len());
+    let mut groups = Vec::new();
+    for (i, &at) in order.iter().enumerate() {
+        let form = &forms[at];
+        if i == 0 || forms[order[i - 1]].form != form.form {
+            groups.push((hash(&form.form), entries.len() as u32));
+        }
+        entries.push(PooledSurfaceForm {
+            form: pool.intern(&form.form)?,
+            lemma: form.lemma,
+            flags: form.flags,
+            ending_len: form.ending_len,
+            dialects: form.dialects.bits(),
+            periods: form.periods.bits(),
+        });
+    }
+
+    let mut slots = alloc::vec![0u32; (groups.len() * 2).next_power_of_two()];
+    let mask = slots.len() - 1;
+    for (h, first) in groups {
+        let mut slot = h as usize & mask;
+        while slots[slot] != 0 {
+            slot = (slot + 1) & mask;
+        }
+        slots[slot] = first + 1;
+    }
+    Ok((entries, slots))
+}
+
+impl ArchivedDictionary {
+    /// Whether the archive carries a surface-form table.
+    pub fn has_surface_forms(&self) -> bool {
+        !self.form_slots.is_empty()
+    }
+
+    /// The analyses of `form` in the surface-form table, in dictionary
+    /// order; empty when the form is not listed or there is no table.
+    pub fn surface_entries(&self, form: &str) -> &[ArchivedSurfaceForm] {
+        if self.form_slots.is_empty() {
+            return &[];
+        }
+        let mask = self.form_slots.len() - 1;
+        let mut slot = hash(form) as usize & mask;
+        // The table is never full, but a damaged one must not loop forever
+        for _ in 0..self.form_slots.len() {
+            let first = self.form_slots[slot];
+            if first == 0 {
+                return &[];
+            }
+            let start = first as usize - 1;
+            if let Some(entry) = self.forms.get(start).filter(|e| self.str(&e.form) == form) {
+                let len = self.forms[start..].iter().take_while(|e| e.form == entry.form).count();
+                return &self.forms[start..start + len];
+            }
+            slot = (slot + 1) & mask;
+        }
+        &[]
+    }
+}
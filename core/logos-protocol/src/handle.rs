@@ -0,0 +1,111 @@
+//! Shared ownership of a dictionary archive. Validation runs once, when the
+//! handle is made; clones share the bytes, so any number of engines (one per
+//! worker or request) can read the same dictionary without copying it.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use core::fmt;
+
+use rkyv::AlignedVec;
+
+use crate::ids::LemmaId;
+use crate::meta::IntegrityError;
+use crate::model::Dictionary;
+use crate::pool::{ArchivedDictionary, PoolError};
+
+/// Alignment the archive needs to be read in place.
+const ARCHIVE_ALIGN: usize = 16;
+
+enum Storage {
+    Owned(AlignedVec),
+    /// Kept as given (e.g. a memory-mapped file)
+    Shared(Box<dyn AsRef<[u8]> + Send + Sync>),
+}
+
+impl Storage {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Storage::Owned(data) => data,
+            Storage::Shared(data) => (**data).as_ref(),
+        }
+    }
+}
+
+/// A validated, reference-counted dictionary archive.
+#[derive(Clone)]
+pub struct DictionaryHandle(Arc<Storage>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandleError {
+    /// The bytes are not a `Dictionary` archive
+    Archive(String),
+    Pool(PoolError),
+    Integrity(IntegrityError),
+    /// A lemma ID past `LemmaId::LOCAL_MASK`, which would run into the IDs
+    /// of other loaded dictionaries once namespaced
+    LemmaIdOutOfRange(LemmaId),
+}
+
+impl fmt::Display for HandleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandleError::Archive(e) => write!(f, "{}", e),
+            HandleError::Pool(e) => write!(f, "{}", e),
+            HandleError::Integrity(e) => write!(f, "{}", e),
+            HandleError::LemmaIdOutOfRange(id) => {
+                write!(f, "lemma ID {} is past {}, the largest a dictionary may use", id.0, LemmaId::LOCAL_MASK)
+            }
+        }
+    }
+}
+
+impl DictionaryHandle {
+    /// Validates an archive (not a compressed container) and takes it over.
+    pub fn new(data: AlignedVec) -> Result<Self, HandleError> {
+        check(&data)?;
+        Ok(Self(Arc::new(Storage::Owned(data))))
+    }
+
+    /// Like `new`, but keeps `buffer` instead of copying it when it is
+    /// suitably aligned (memory maps are page-aligned).
+    pub fn from_buffer<B>(buffer: B) -> Result<Self, HandleError>
+    where
+        B: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        let data = buffer.as_ref();
+        if data.as_ptr().align_offset(ARCHIVE_ALIGN) != 0 {
+            let mut copy = AlignedVec::with_capacity(data.len());
+            copy.extend_from_slice(data);
+            return Self::new(copy);
+        }
+        check(data)?;
+        Ok(Self(Arc::new(Storage::Shared(Box::new(buffer)))))
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        self.0.bytes()
+    }
+
+    pub fn archived(&self) -> &ArchivedDictionary {
+        // SAFETY: the bytes were validated with check_archived_root when the
+        // handle was made and are never mutated afterwards.
+        unsafe { rkyv::archived_root::<Dictionary>(self.bytes()) }
+    }
+
+    /// True when both handles share the same bytes.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+fn check(data: &[u8]) -> Result<(), HandleError> {
+    let archived = rkyv::check_archived_root::<Dictionary>(data).map_err(|e| HandleError::Archive(e.to_string()))?;
+    archived.validate_pool().map_err(HandleError::Pool)?;
+    archived.verify_integrity().map_err(HandleError::Integrity)?;
+    let mut ids = archived.lemmas.iter().map(|l| l.id.0).chain(archived.pronouns.iter().map(|p| p.lemma.0));
+    match ids.find(|&id| id > LemmaId::LOCAL_MASK) {
+        Some(id) => Err(HandleError::LemmaIdOutOfRange(LemmaId(id))),
+        None => Ok(()),
+    }
+}
@@ -36,3 +36,26 @@ define_id!(LemmaId, "Unique identifier for a Lemma (dictionary headword).");
 define_id!(ParadigmId, "Unique identifier for an inflectional paradigm.");
 define_id!(TokenId, "Unique identifier for a parsed token in a sentence.");
 define_id!(SentenceId, "Unique identifier for a processed sentence.");
+define_id!(ClauseId, "Unique identifier for a clause, across all sentences of a text.");
+
+impl LemmaId {
+    /// Bits reserved for the dictionary-local part of a namespaced ID.
+    /// The top 8 bits select the dictionary (0 = base dictionary).
+    pub const NAMESPACE_SHIFT: u32 = 24;
+    pub const LOCAL_MASK: u32 = (1 << Self::NAMESPACE_SHIFT) - 1;
+
+    /// Builds an ID that cannot collide with lemmas of other loaded
+    /// dictionaries. Bits of `local` past `LOCAL_MASK` are dropped, so
+    /// `DictionaryHandle` refuses dictionaries with such IDs.
+    pub const fn namespaced(namespace: u8, local: u32) -> Self {
+        Self(((namespace as u32) << Self::NAMESPACE_SHIFT) | (local & Self::LOCAL_MASK))
+    }
+
+    pub const fn namespace(self) -> u8 {
+        (self.0 >> Self::NAMESPACE_SHIFT) as u8
+    }
+
+    pub const fn local(self) -> u32 {
+        self.0 & Self::LOCAL_MASK
+    }
+}
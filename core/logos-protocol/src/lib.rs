@@ -10,15 +10,34 @@ pub mod ids;
 pub mod morphology;
 
 // Re-export core types for convenience
-pub use ids::{LemmaId, TokenId, SentenceId, ParadigmId};
+pub use ids::{LemmaId, TokenId, SentenceId, ClauseId, ParadigmId};
 pub use morphology::*;
 
 pub mod model;
 pub use model::*;
 
+pub mod container;
+pub mod features;
+pub mod forms;
+pub mod handle;
+pub mod meta;
+pub mod messages;
+pub mod pool;
+pub mod stats;
+pub mod view;
+pub use features::{compact_tag, MorphFeatures};
+pub use forms::{ArchivedSurfaceForm, SurfaceForm};
+pub use handle::{DictionaryHandle, HandleError};
+pub use meta::BuildInfo;
+pub use messages::{Locale, MessageArgs};
+pub use view::{DictionaryView, SurfaceMatch};
+pub use stats::DictionaryStats;
+pub use pool::{ArchivedDictionary, ArchivedLemma, ArchivedParadigm, ArchivedPronounParadigm, DictionaryArchive, PoolStr, StringPool};
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
     use rkyv::{to_bytes, from_bytes};
 
     #[test]
@@ -46,10 +65,238 @@ mod tests {
         assert_eq!(original, deserialized);
     }
     
+    #[test]
+    fn test_lemma_id_namespacing() {
+        // Base dictionary IDs are unchanged by namespace 0
+        assert_eq!(LemmaId::namespaced(0, 42), LemmaId(42));
+
+        let supplement = LemmaId::namespaced(2, 42);
+        assert_ne!(supplement, LemmaId(42));
+        assert_eq!(supplement.namespace(), 2);
+        assert_eq!(supplement.local(), 42);
+    }
+
+    #[test]
+    fn test_dictionary_string_pool() {
+        let lemma = |id, text: &str| Lemma { id: LemmaId(id), text: text.into(), gender: Gender::Masculine, pos: PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None };
+        let dict = Dictionary {
+            version: 3,
+            lemmas: alloc::vec![lemma(1, "λόγ"), lemma(2, "άνθρωπ"), lemma(3, "λόγ")],
+            paradigms: alloc::vec![Paradigm {
+                id: ParadigmId(1),
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                endings: alloc::vec![(1, "ος".into()), (2, "ου".into()), (4, "ος".into())],
+            }],
+            ..Default::default()
+        };
+
+        // Repeated stems and endings are stored once
+        let (archive, unique) = DictionaryArchive::build(&dict).expect("Pool limits");
+        assert_eq!(unique, 4);
+        assert_eq!(archive.pool, "λόγάνθρωποςου");
+        assert_eq!(archive.pool_ends, [6, 18, 22, 26]);
+
+        let bytes = to_bytes::<_, 1024>(&dict).expect("Failed to serialize Dictionary");
+        let archived = rkyv::check_archived_root::<Dictionary>(&bytes).expect("Archive should validate");
+        assert!(archived.validate_pool().is_ok());
+        assert_eq!(archived.str(&archived.lemmas[2].text), "λόγ");
+        assert_eq!(archived.str(&archived.paradigms[0].endings[2].1), "ος");
+
+        let restored: Dictionary = from_bytes(&bytes).expect("Failed to deserialize Dictionary");
+        assert_eq!(restored.lemmas[1].text, "άνθρωπ");
+        assert_eq!(restored.paradigms[0].endings[1], (2, "ου".into()));
+
+        // A reference is an index into the string ends
+        assert_eq!(core::mem::size_of::<PoolStr>(), 4);
+
+        // Ends that split a character are caught after the byte check
+        let mut broken = archive.clone();
+        broken.pool_ends[0] = 1;
+        let bytes = to_bytes::<_, 1024>(&broken).expect("Failed to serialize archive");
+        let archived = rkyv::check_archived_root::<DictionaryArchive>(&bytes).expect("Archive should validate");
+        assert_eq!(archived.validate_pool(), Err(pool::PoolError::BadEnds { index: 0 }));
+    }
+
+    #[test]
+    fn test_long_gloss() {
+        // Far past the 255 bytes a one-byte length held
+        let gloss = "word, speech, discourse; reason, account; ".repeat(40);
+        let dict = Dictionary {
+            version: 1,
+            lemmas: alloc::vec![Lemma { id: LemmaId(1), text: "λόγ".into(), gender: Gender::Masculine, pos: PartOfSpeech::Noun, urn: None, gloss: Some(gloss.clone()), dialects: Dialect::empty(), periods: Period::empty(), paradigm: None }],
+            ..Default::default()
+        };
+        let bytes = to_bytes::<_, 4096>(&dict).expect("Failed to serialize Dictionary");
+        let archived = rkyv::check_archived_root::<Dictionary>(&bytes).expect("Archive should validate");
+        assert!(archived.validate_pool().is_ok());
+        assert_eq!(archived.lemmas[0].gloss.as_ref().map(|g| archived.str(g)), Some(gloss.as_str()));
+
+        let restored: Dictionary = from_bytes(&bytes).expect("Failed to deserialize Dictionary");
+        assert_eq!(restored.lemmas[0].gloss, Some(gloss));
+    }
+
+    #[test]
+    fn test_lemma_lookups() {
+        let lemma = |id, text: &str| Lemma { id: LemmaId(id), text: text.into(), gender: Gender::Masculine, pos: PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None };
+        let dict = Dictionary {
+            version: 1,
+            lemmas: alloc::vec![lemma(42, "ἀνθρωπ"), lemma(7, "λόγ"), lemma(3, "ἀνθρωπίν"), lemma(9, "λόγ"), lemma(1, "ἀγαθ")],
+            ..Default::default()
+        };
+        let bytes = to_bytes::<_, 1024>(&dict).expect("Failed to serialize Dictionary");
+        let archived = rkyv::check_archived_root::<Dictionary>(&bytes).expect("Archive should validate");
+
+        // The archive's index answers like a scan of the authoring form
+        fn ids(found: alloc::vec::Vec<view::LemmaView>) -> alloc::vec::Vec<u32> {
+            found.into_iter().map(|l| l.id.0).collect()
+        }
+        assert_eq!(archived.lemma(LemmaId(42)).map(|l| l.text), Some("ἀνθρωπ"));
+        assert_eq!(dict.lemma(LemmaId(42)).map(|l| l.text), Some("ἀνθρωπ"));
+        assert!(archived.lemma(LemmaId(5)).is_none());
+        assert_eq!(ids(archived.find_exact("λόγ")), [7, 9]);
+        assert_eq!(ids(dict.find_exact("λόγ")), [7, 9]);
+        assert_eq!(ids(archived.find_prefix("ἀνθρωπ", 10)), [42, 3]);
+        assert_eq!(ids(dict.find_prefix("ἀνθρωπ", 10)), [42, 3]);
+        assert_eq!(ids(archived.find_prefix("", 2)), ids(dict.find_prefix("", 2)));
+        assert!(archived.find_prefix("ξ", 10).is_empty());
+    }
+
+    #[test]
+    fn test_tag_transitions() {
+        let noun_acc = (MorphFlags::NOUN | MorphFlags::ACCUSATIVE | MorphFlags::PLURAL).tag();
+        assert_eq!(noun_acc, (MorphFlags::NOUN | MorphFlags::ACCUSATIVE).bits());
+
+        let t = |from, to, weight| TagTransition { from, to, weight };
+        let dict = Dictionary {
+            version: 1,
+            transitions: alloc::vec![t(5, 1, -20), t(TagTransition::START, 5, -10), t(1, 5, -30)],
+            ..Default::default()
+        };
+        let bytes = to_bytes::<_, 256>(&dict).expect("Failed to serialize Dictionary");
+        let archived = rkyv::check_archived_root::<Dictionary>(&bytes).expect("Archive should validate");
+
+        // Looked up by (from, to) whatever the authoring order
+        assert_eq!(archived.transition(TagTransition::START, 5), Some(-10));
+        assert_eq!(archived.transition(5, 1), Some(-20));
+        assert_eq!(archived.transition(1, 2), None);
+        assert!(archived.verify_integrity().is_ok());
+    }
+
+    #[test]
+    fn test_dictionary_stats() {
+        let lemma = |id, text: &str, pos| Lemma { id: LemmaId(id), text: text.into(), gender: Gender::Masculine, pos, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None };
+        let paradigm = |id, endings: &[&str]| Paradigm {
+            id: ParadigmId(id),
+            dialects: Dialect::empty(),
+            periods: Period::empty(),
+            endings: endings.iter().enumerate().map(|(i, e)| (1 << i, (*e).into())).collect(),
+        };
+        let dict = Dictionary {
+            version: 1,
+            lemmas: alloc::vec![lemma(1, "λόγ", PartOfSpeech::Noun), lemma(2, "ἵππ", PartOfSpeech::Noun), lemma(3, "λέγ", PartOfSpeech::Verb)],
+            paradigms: alloc::vec![paradigm(1, &["ος", "ου", "ον"]), paradigm(2, &["ω", "ει", "ον"])],
+            ..Default::default()
+        };
+        let (archive, _) = DictionaryArchive::build(&dict).unwrap();
+        let bytes = to_bytes::<_, 1024>(&archive).unwrap();
+        let stats = rkyv::check_archived_root::<Dictionary>(&bytes).unwrap().stats();
+
+        assert_eq!(stats.lemmas, 3);
+        assert_eq!(stats.lemmas_by_pos, alloc::vec![
+            stats::PosCount { pos: PartOfSpeech::Noun, lemmas: 2 },
+            stats::PosCount { pos: PartOfSpeech::Verb, lemmas: 1 },
+        ]);
+        assert_eq!(stats.paradigm_sizes, alloc::vec![stats::ParadigmSize { endings: 3, paradigms: 2 }]);
+        assert_eq!(stats.suffixes.len(), 5);
+        assert_eq!((stats.suffixes[0].suffix.as_str(), stats.suffixes[0].cells), ("ον", 2));
+    }
+
+    #[test]
+    fn test_surface_form_table() {
+        use alloc::{string::String, vec::Vec};
+        let form = |text: String, lemma, flags| SurfaceForm { form: text, lemma: LemmaId(lemma), flags, ending_len: 2, dialects: Dialect::empty(), periods: Period::empty() };
+        // Enough forms for the probes to collide, and a form with two analyses
+        let mut forms: Vec<SurfaceForm> = (0..300).map(|i| form(alloc::format!("λόγ{}ος", i), i, 1)).collect();
+        forms.push(form("λόγον".into(), 7, 4));
+        forms.push(form("λόγον".into(), 3, 8));
+        let dict = Dictionary { version: 1, ..Default::default() };
+        let (archive, _) = DictionaryArchive::build_with_forms(&dict, &forms).unwrap();
+        let bytes = to_bytes::<_, 1024>(&archive).unwrap();
+        let archived = rkyv::check_archived_root::<Dictionary>(&bytes).unwrap();
+        archived.validate_pool().unwrap();
+
+        assert!(archived.has_surface_forms());
+        for i in 0..300 {
+            let found = archived.surface_entries(&alloc::format!("λόγ{}ος", i));
+            assert_eq!(found.iter().map(|f| f.lemma.0).collect::<Vec<_>>(), alloc::vec![i]);
+        }
+        // Analyses of one form keep the order they were given in
+        let found = archived.surface_forms("λόγον").unwrap();
+        assert_eq!(found.iter().map(|f| (f.lemma.0, f.flags)).collect::<Vec<_>>(), alloc::vec![(7, 4), (3, 8)]);
+        assert_eq!(found[0].ending_len, 2);
+        assert!(archived.surface_entries("λόγος").is_empty());
+        assert_eq!(archived.stats().surface_forms, 302);
+        // Without a table, lookups fall back to scanning
+        assert!(dict.surface_forms("λόγον").is_none());
+    }
+
+    #[test]
+    fn test_degree_flags() {
+        assert_eq!(Degree::from_flags(MorphFlags::ADJECTIVE), Degree::Positive);
+        assert_eq!(Degree::from_flags(MorphFlags::ADJECTIVE | MorphFlags::COMPARATIVE), Degree::Comparative);
+        for degree in [Degree::Positive, Degree::Comparative, Degree::Superlative] {
+            assert_eq!(Degree::from_flags(degree.flags()), degree);
+        }
+    }
+
+    #[test]
+    fn test_morph_features() {
+        let flags = MorphFlags::NOUN | MorphFlags::GENITIVE | MorphFlags::SINGULAR | MorphFlags::MASCULINE;
+        let features = MorphFeatures::from(flags);
+        assert_eq!(features.case, Some(Case::Genitive));
+        assert_eq!(features.number, Some(Number::Singular));
+        assert_eq!(features.gender, Some(Gender::Masculine));
+        assert_eq!(features.person, None);
+        assert_eq!(features.to_string(), "gen.sg.masc");
+        // The part-of-speech hint is not a feature
+        assert_eq!(features.to_flags(), flags - MorphFlags::NOUN);
+
+        let verb = MorphFlags::VERB | MorphFlags::THIRD_PERSON | MorphFlags::SINGULAR | MorphFlags::PRESENT | MorphFlags::ACTIVE;
+        assert_eq!(MorphFeatures::from(verb).to_string(), "3.sg.pres.act");
+        assert_eq!(MorphFlags::from(MorphFeatures::from(verb)), verb - MorphFlags::VERB);
+
+        // Two cases at once name neither
+        assert_eq!(Case::from_flags(MorphFlags::NOMINATIVE | MorphFlags::ACCUSATIVE), None);
+        assert_eq!(Tense::Aorist.flags(), MorphFlags::PAST);
+        assert_eq!(MorphFeatures::default().to_string(), "");
+
+        assert_eq!(compact_tag(flags), "N-GSM");
+        assert_eq!(features.ud_feats(), "Case=Gen|Gender=Masc|Number=Sing");
+        assert_eq!(compact_tag(verb), "V-PA-3S");
+        assert_eq!(MorphFeatures::from(verb).ud_feats(), "Number=Sing|Person=3|Tense=Pres|Voice=Act");
+        let participle = MorphFlags::PARTICIPLE | MorphFlags::PRESENT | MorphFlags::ACTIVE | MorphFlags::GENITIVE | MorphFlags::SINGULAR | MorphFlags::MASCULINE;
+        assert_eq!(compact_tag(participle), "V-PAP-GSM");
+        assert_eq!(compact_tag(MorphFlags::ADJECTIVE | MorphFlags::COMPARATIVE | MorphFlags::NOMINATIVE | MorphFlags::PLURAL | MorphFlags::NEUTER), "A-NPN-C");
+        assert_eq!(compact_tag(MorphFlags::PRONOUN | MorphFlags::FIRST_PERSON | MorphFlags::DATIVE | MorphFlags::SINGULAR), "P-1DS");
+        assert_eq!(MorphFeatures::default().ud_feats(), "_");
+    }
+
     #[test]
     fn test_id_layout() {
         // Verify Zero-Cost abstraction: LemmaId(u32) should be exactly 4 bytes
         assert_eq!(core::mem::size_of::<LemmaId>(), 4);
         assert_eq!(core::mem::size_of::<Option<LemmaId>>(), 8); // u32 + tag (padding)
     }
+
+    #[test]
+    fn test_messages() {
+        use messages::render;
+        let args = [("source", "pl".to_string()), ("target", "sg".to_string())];
+        assert_eq!(render(Locale::English, "agreement.number", &args), "Number mismatch: pl vs sg");
+        assert_eq!(render(Locale::Greek, "agreement.number", &args), "Ασυμφωνία αριθμού: pl έναντι sg");
+        // Missing parameters stay as written; unknown codes render as the code
+        assert_eq!(render(Locale::English, "agreement.person", &args[..1]), "Person mismatch: pl vs {target}");
+        assert_eq!(render(Locale::Greek, "style.vocative", &args), "style.vocative");
+    }
 }
@@ -0,0 +1,162 @@
+//! Message catalogs of the diagnostics: one template per diagnostic code
+//! and locale, with `{name}` parameters filled in from the finding:
+//!
+//! ```text
+//! agreement.number    en  Number mismatch: {source} vs {target}
+//!                     el  Ασυμφωνία αριθμού: {source} έναντι {target}
+//! ```
+//!
+//! Findings carry their parameters (`MessageArgs`) next to the English
+//! message, so callers can render them again in the reader's locale.
+//! Parameters no template names are left out; a `{name}` without a
+//! parameter is kept as written.
+//!
+//! A third catalog links codes to grammar topics (`explanation`), so
+//! teaching interfaces can point from an error to its lesson.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize as SerdeDeserialize, Serialize as SerdeSerialize};
+
+/// Parameters of a message template, by name.
+pub type MessageArgs = Vec<(&'static str, String)>;
+
+/// Language of diagnostic messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+pub enum Locale {
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "en"))]
+    English,
+    /// Modern Greek
+    #[cfg_attr(feature = "serde", serde(rename = "el"))]
+    Greek,
+}
+
+impl Locale {
+    /// BCP 47 tag: "en" or "el".
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Greek => "el",
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::English => ENGLISH,
+            Locale::Greek => GREEK,
+        }
+    }
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("agreement.number", "Number mismatch: {source} vs {target}"),
+    ("agreement.person", "Person mismatch: {source} vs {target}"),
+    ("agreement.determiner", "Agreement Mismatch (Det-Noun): {source} vs {target}"),
+    ("agreement.reflexive-person", "Reflexive person mismatch: {source} vs {target}"),
+    ("agreement.reflexive-number", "Reflexive number mismatch: {source} vs {target}"),
+    (
+        "definiteness.predicative",
+        "Predicative position: '{adjective}' stands outside the article group of '{noun}' and reads as \"{noun} is {adjective}\"; place it after the article for an attribute",
+    ),
+    (
+        "definiteness.missing-article",
+        "Missing article: '{noun}' refers back to '{earlier}', and a known referent usually takes the article",
+    ),
+    (
+        "discourse.no-antecedent",
+        "No antecedent: '{anaphor}' ({anaphor_features}) agrees with no noun before it; the nearest, '{noun}', is {noun_features}",
+    ),
+    ("semantic.constraint", "Constraint Violation: Object '{object}' does not satisfy requirement of '{verb}'."),
+    ("semantic.preposition", "{preposition} expects {article} {expected}-like argument, got '{argument}'"),
+    ("semantic.animacy", "{verb} expects {article} {expected} {role}, got '{argument}' ({animacy})"),
+    ("anachronism.period", "Anachronism: '{word}' is a {periods} form in a text of {text_periods}"),
+    ("accent.placement", "{word} should be {expected} ({rule} accent)"),
+];
+
+const GREEK: &[(&str, &str)] = &[
+    ("agreement.number", "Ασυμφωνία αριθμού: {source} έναντι {target}"),
+    ("agreement.person", "Ασυμφωνία προσώπου: {source} έναντι {target}"),
+    ("agreement.determiner", "Ασυμφωνία άρθρου και ουσιαστικού: {source} έναντι {target}"),
+    ("agreement.reflexive-person", "Ασυμφωνία προσώπου αυτοπαθούς αντωνυμίας: {source} έναντι {target}"),
+    ("agreement.reflexive-number", "Ασυμφωνία αριθμού αυτοπαθούς αντωνυμίας: {source} έναντι {target}"),
+    (
+        "definiteness.predicative",
+        "Κατηγορηματική θέση: το «{adjective}» βρίσκεται έξω από τον έναρθρο όρο του «{noun}» και διαβάζεται «{noun} είναι {adjective}»· για επιθετικό προσδιορισμό, βάλτε το μετά το άρθρο",
+    ),
+    (
+        "definiteness.missing-article",
+        "Λείπει το άρθρο: το «{noun}» αναφέρεται στο «{earlier}», και ό,τι είναι ήδη γνωστό παίρνει συνήθως άρθρο",
+    ),
+    (
+        "discourse.no-antecedent",
+        "Χωρίς προηγούμενο όρο: το «{anaphor}» ({anaphor_features}) δεν συμφωνεί με κανένα ουσιαστικό πριν από αυτό· το πλησιέστερο, «{noun}», είναι {noun_features}",
+    ),
+    ("semantic.constraint", "Παραβίαση περιορισμού: το αντικείμενο «{object}» δεν ικανοποιεί την απαίτηση του «{verb}»."),
+    ("semantic.preposition", "Η πρόθεση {preposition} θέλει όρισμα του τύπου {choices}, όχι «{argument}»"),
+    ("semantic.animacy", "Το {verb} θέλει {role} του τύπου {choices}, όχι «{argument}» ({animacy})"),
+    ("anachronism.period", "Αναχρονισμός: το «{word}» είναι τύπος της περιόδου {periods} σε κείμενο της περιόδου {text_periods}"),
+    ("accent.placement", "Το {word} τονίζεται {expected} ({rule})"),
+];
+
+/// Grammar topic of each code: a section of Smyth's *Greek Grammar*
+/// ("smyth-949") or a topic slug of our own.
+const EXPLANATIONS: &[(&str, &str)] = &[
+    ("agreement.number", "smyth-949"),
+    ("agreement.person", "smyth-949"),
+    ("agreement.determiner", "smyth-1020"),
+    ("agreement.reflexive-person", "smyth-1218"),
+    ("agreement.reflexive-number", "smyth-1218"),
+    ("definiteness.predicative", "smyth-1168"),
+    ("definiteness.missing-article", "smyth-1120"),
+    ("discourse.no-antecedent", "pronoun-antecedent"),
+    ("semantic.constraint", "verb-arguments"),
+    ("semantic.preposition", "smyth-1636"),
+    ("semantic.animacy", "verb-arguments"),
+    ("anachronism.period", "dialects-and-periods"),
+    ("accent.placement", "smyth-159"),
+];
+
+/// The grammar topic explaining findings of `code`, if one is catalogued.
+pub fn explanation(code: &str) -> Option<&'static str> {
+    EXPLANATIONS.iter().find(|(c, _)| *c == code).map(|(_, topic)| *topic)
+}
+
+/// The template of `code` in `locale`, or the English one when the locale
+/// has none. None for codes of no catalog (custom `ValidationSystem`s).
+pub fn template(locale: Locale, code: &str) -> Option<&'static str> {
+    let find = |catalog: &'static [(&str, &'static str)]| catalog.iter().find(|(c, _)| *c == code).map(|(_, t)| *t);
+    find(locale.catalog()).or_else(|| find(ENGLISH))
+}
+
+/// The message of `code` in `locale` with `args` filled in; the code
+/// itself when no catalog has it.
+pub fn render(locale: Locale, code: &str, args: &[(&str, String)]) -> String {
+    let Some(template) = template(locale, code) else { return String::from(code) };
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        message.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            let (_, value) = args.iter().find(|(name, _)| *name == &after[..close])?;
+            Some((value, close))
+        });
+        match value {
+            Some((value, close)) => {
+                message.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = after;
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
@@ -0,0 +1,216 @@
+//! Build metadata archived alongside every dictionary, so a deployment can
+//! tell which lexicon it is running and detect a damaged one.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use rkyv::{Archive, Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize as SerdeDeserialize, Serialize as SerdeSerialize};
+
+use crate::model::Dictionary;
+use crate::morphology::{Gender, NameKind, PartOfSpeech, PronounClass};
+use crate::pool::ArchivedDictionary;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+pub struct BuildInfo {
+    /// `ContentHash` of the lemmas, paradigms, pronouns and names
+    pub content_hash: u64,
+    /// Unix seconds; 0 when the builder did not record it
+    pub built_at: u64,
+    /// Input files the archive was compiled from
+    pub sources: Vec<String>,
+    pub lemma_count: u32,
+    pub paradigm_count: u32,
+}
+
+/// 64-bit FNV-1a over the lexicon content. Strings are length-prefixed so
+/// moving bytes between neighbouring fields changes the hash.
+pub struct ContentHash(u64);
+
+impl Default for ContentHash {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl ContentHash {
+    fn bytes(&mut self, data: &[u8]) {
+        for b in data {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn str(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.bytes(s.as_bytes());
+    }
+
+    pub fn lemma(&mut self, id: u32, text: &str, gender: Gender, pos: PartOfSpeech, urn: Option<&str>, gloss: Option<&str>) {
+        self.u32(id);
+        self.str(text);
+        self.bytes(&[gender as u8, pos as u8]);
+        for optional in [urn, gloss] {
+            match optional {
+                Some(s) => {
+                    self.bytes(&[1]);
+                    self.str(s);
+                }
+                None => self.bytes(&[0]),
+            }
+        }
+    }
+
+    pub fn paradigm(&mut self, id: u32, endings: usize) {
+        self.u32(id);
+        self.u32(endings as u32);
+    }
+
+    /// Usage tags (`Dialect` and `Period` bits) of the lemma or paradigm just hashed.
+    pub fn usage(&mut self, dialects: u8, periods: u8) {
+        self.bytes(&[dialects, periods]);
+    }
+
+    pub fn ending(&mut self, flags: u32, ending: &str) {
+        self.u32(flags);
+        self.str(ending);
+    }
+
+    pub fn pronoun(&mut self, lemma: u32, class: PronounClass, forms: usize) {
+        self.u32(lemma);
+        self.bytes(&[class as u8]);
+        self.u32(forms as u32);
+    }
+
+    pub fn name(&mut self, lemma: u32, kind: NameKind) {
+        self.u32(lemma);
+        self.bytes(&[kind as u8]);
+    }
+
+    pub fn transition(&mut self, from: u32, to: u32, weight: i32) {
+        self.u32(from);
+        self.u32(to);
+        self.bytes(&weight.to_le_bytes());
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl BuildInfo {
+    /// Hash and counts of `dict`; `built_at` and `sources` are left for the builder.
+    pub fn describe(dict: &Dictionary) -> Self {
+        let mut hash = ContentHash::default();
+        hash.u32(dict.version);
+        for l in &dict.lemmas {
+            hash.lemma(l.id.0, &l.text, l.gender, l.pos, l.urn.as_deref(), l.gloss.as_deref());
+            hash.usage(l.dialects.bits(), l.periods.bits());
+        }
+        for p in &dict.paradigms {
+            hash.paradigm(p.id.0, p.endings.len());
+            hash.usage(p.dialects.bits(), p.periods.bits());
+            for (flags, ending) in &p.endings {
+                hash.ending(*flags, ending);
+            }
+        }
+        for p in &dict.pronouns {
+            hash.pronoun(p.lemma.0, p.class, p.forms.len());
+            for (flags, form) in &p.forms {
+                hash.ending(*flags, form);
+            }
+        }
+        for n in &dict.names {
+            hash.name(n.lemma.0, n.kind);
+        }
+        // In archive order, so the hash survives the builder's sort
+        let mut transitions: Vec<_> = dict.transitions.iter().map(|t| (t.from, t.to, t.weight)).collect();
+        transitions.sort_unstable();
+        for (from, to, weight) in transitions {
+            hash.transition(from, to, weight);
+        }
+        Self {
+            content_hash: hash.finish(),
+            lemma_count: dict.lemmas.len() as u32,
+            paradigm_count: dict.paradigms.len() as u32,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityError {
+    CountMismatch { lemmas: u32, paradigms: u32 },
+    HashMismatch { expected: u64, actual: u64 },
+}
+
+impl fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntegrityError::CountMismatch { lemmas, paradigms } => {
+                write!(f, "header declares {} lemmas and {} paradigms, archive differs", lemmas, paradigms)
+            }
+            IntegrityError::HashMismatch { expected, actual } => {
+                write!(f, "content hash {:016x} does not match the header ({:016x})", actual, expected)
+            }
+        }
+    }
+}
+
+impl ArchivedDictionary {
+    /// Recomputes `BuildInfo::content_hash` from the archived content.
+    pub fn content_hash(&self) -> u64 {
+        let mut hash = ContentHash::default();
+        hash.u32(self.version);
+        for l in self.lemmas.iter() {
+            let gender: Gender = l.gender.deserialize(&mut rkyv::Infallible).unwrap_or(Gender::Masculine);
+            let pos: PartOfSpeech = l.pos.deserialize(&mut rkyv::Infallible).unwrap_or(PartOfSpeech::Noun);
+            hash.lemma(l.id.0, self.str(&l.text), gender, pos, l.urn.as_ref().map(|urn| self.str(urn)), l.gloss.as_ref().map(|gloss| self.str(gloss)));
+            hash.usage(l.dialects, l.periods);
+        }
+        for p in self.paradigms.iter() {
+            hash.paradigm(p.id.0, p.endings.len());
+            hash.usage(p.dialects, p.periods);
+            for (flags, ending) in p.endings.iter() {
+                hash.ending(*flags, self.str(ending));
+            }
+        }
+        for p in self.pronouns.iter() {
+            let class: PronounClass = p.class.deserialize(&mut rkyv::Infallible).unwrap_or(PronounClass::Personal);
+            hash.pronoun(p.lemma.0, class, p.forms.len());
+            for (flags, form) in p.forms.iter() {
+                hash.ending(*flags, self.str(form));
+            }
+        }
+        for n in self.names.iter() {
+            let kind: NameKind = n.kind.deserialize(&mut rkyv::Infallible).unwrap_or(NameKind::Person);
+            hash.name(n.lemma.0, kind);
+        }
+        for t in self.transitions.iter() {
+            hash.transition(t.from, t.to, t.weight);
+        }
+        hash.finish()
+    }
+
+    /// Checks the content against `info`; run after `validate_pool`.
+    pub fn verify_integrity(&self) -> Result<(), IntegrityError> {
+        let info = &self.info;
+        if info.lemma_count as usize != self.lemmas.len() || info.paradigm_count as usize != self.paradigms.len() {
+            return Err(IntegrityError::CountMismatch { lemmas: info.lemma_count, paradigms: info.paradigm_count });
+        }
+        let actual = self.content_hash();
+        if actual != info.content_hash {
+            return Err(IntegrityError::HashMismatch { expected: info.content_hash, actual });
+        }
+        Ok(())
+    }
+}
@@ -1,38 +1,135 @@
 use rkyv::{Archive, Deserialize, Serialize};
 use crate::ids::{LemmaId, ParadigmId};
-use crate::morphology::{Gender, PartOfSpeech};
+use crate::morphology::{Dialect, Gender, Period, NameKind, PartOfSpeech, PronounClass};
 use alloc::string::String;
 use alloc::vec::Vec;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize as SerdeDeserialize, Serialize as SerdeSerialize};
 
-#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
-#[archive(check_bytes)]
 pub struct Lemma {
     pub id: LemmaId,
     pub text: String,
     pub gender: Gender,
     pub pos: PartOfSpeech,
-    // Future: pub paradigm_id: ParadigmId,
+    /// Link to an external resource: a CITE/CTS URN, an LSJ entry, a
+    /// Perseus lexicon page (e.g. "urn:cite2:hmt:lsj.chicago_md:n63275")
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub urn: Option<String>,
+    /// Short English meaning for autocompletion and tooltips ("word, reason")
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub gloss: Option<String>,
+    /// Dialects the lemma is used in; empty for all
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dialects: Dialect,
+    /// Periods the lemma is attested in; empty for all
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub periods: Period,
+    /// The paradigm the lemma inflects by; None to pair it with every
+    /// paradigm (indeclinables, and dictionaries that name none)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub paradigm: Option<ParadigmId>,
 }
 
-#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+impl Lemma {
+    /// Whether forms of the lemma may take the endings of `paradigm`.
+    pub fn inflects_by(&self, paradigm: ParadigmId) -> bool {
+        self.paradigm.is_none_or(|own| own == paradigm)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
-#[archive(check_bytes)]
 pub struct Paradigm {
     pub id: ParadigmId,
+    /// Dialects whose endings these are (Ionic -ης for Attic -ας); empty for all
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dialects: Dialect,
+    /// Periods whose endings these are (a Koine set); empty for all
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub periods: Period,
     pub endings: Vec<(u32, String)>,
 }
 
-#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+/// Pronouns inflect suppletively (ἐγώ, ἐμοῦ, μοι), so instead of endings
+/// their paradigm lists every full form of one lemma.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+pub struct PronounParadigm {
+    pub lemma: LemmaId,
+    pub class: PronounClass,
+    /// (MorphFlags bits, full form); `class.flags()` is added on lookup
+    pub forms: Vec<(u32, String)>,
+}
+
+/// Gazetteer entry: marks a `ProperNoun` lemma (inflected through the
+/// ordinary paradigms) as a known name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+pub struct NameEntry {
+    pub lemma: LemmaId,
+    pub kind: NameKind,
+}
+
+/// Weight of tag `to` directly following tag `from`, for choosing among a
+/// word's analyses by context. Tags are `MorphFlags::tag` bits, with
+/// `TagTransition::START` before the first word of a sentence; weights are
+/// log-probabilities scaled by 1000 (higher is likelier).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
 #[archive(check_bytes)]
+pub struct TagTransition {
+    pub from: u32,
+    pub to: u32,
+    pub weight: i32,
+}
+
+impl TagTransition {
+    pub const START: u32 = 0;
+}
+
+/// Authoring form of the lexicon. Archived (rkyv) with its strings pooled;
+/// see `pool::DictionaryArchive`.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
 pub struct Dictionary {
     pub version: u32,
     pub lemmas: Vec<Lemma>,
     pub paradigms: Vec<Paradigm>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pronouns: Vec<PronounParadigm>,
+    /// Gazetteer of proper names
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub names: Vec<NameEntry>,
+    /// Tag bigram weights for context disambiguation
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub transitions: Vec<TagTransition>,
+}
+
+/// One learned weight of the transition parser: a hashed feature paired
+/// with a parser action.
+#[derive(Debug, Clone, Copy, PartialEq, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+pub struct FeatureWeight {
+    pub feature: u64,
+    pub action: u16,
+    pub weight: f32,
+}
+
+/// Weights fitted on a treebank: the transition parser's feature weights
+/// and tag bigrams for context disambiguation (which take precedence over
+/// the dictionaries' own).
+#[derive(Debug, Clone, Default, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+pub struct ParserModel {
+    pub version: u32,
+    pub features: Vec<FeatureWeight>,
+    pub transitions: Vec<TagTransition>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
@@ -60,4 +157,119 @@ pub struct SemanticEdge {
 pub struct SemanticNetwork {
     pub version: u32,
     pub edges: Vec<SemanticEdge>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub prepositions: Vec<PrepositionRole>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub animacy: Vec<AnimacyMark>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub animacy_constraints: Vec<AnimacyConstraint>,
+}
+
+/// The animacy scale, lowest first: a human outranks an animal, which
+/// outranks a thing, which outranks an abstraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+#[repr(u8)]
+pub enum Animacy {
+    Abstract = 0,   // ἀρετή
+    Inanimate = 1,  // λίθος
+    Animal = 2,     // ἵππος
+    Human = 3,      // ἄνθρωπος
+}
+
+impl Animacy {
+    pub const ALL: [Animacy; 4] = [Animacy::Abstract, Animacy::Inanimate, Animacy::Animal, Animacy::Human];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Animacy::Abstract => "abstract",
+            Animacy::Inanimate => "inanimate",
+            Animacy::Animal => "animal",
+            Animacy::Human => "human",
+        }
+    }
+}
+
+/// The animacy of a concept and, through IsA, of every kind of it: marking
+/// ἄνθρωπος human and ζῷον animal covers the lemmas under them, and a
+/// nearer mark wins over a farther one.
+#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+pub struct AnimacyMark {
+    pub concept: LemmaId,
+    pub animacy: Animacy,
+}
+
+/// Which argument of a verb an `AnimacyConstraint` is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+#[repr(u8)]
+pub enum AnimacyRole {
+    Subject = 0,     // λέγει ὁ ἄνθρωπος
+    Experiencer = 1, // the indirect object of δοκεῖ μοι, ἀρέσκει τῷ ἀνδρί
+}
+
+/// The least animate argument a verb takes in a role.
+#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+pub struct AnimacyConstraint {
+    pub verb: LemmaId,
+    pub role: AnimacyRole,
+    pub minimum: Animacy,
+}
+
+/// What the argument of a preposition stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+#[repr(u8)]
+pub enum SemanticRole {
+    Location = 0,     // ἐν τῇ πόλει
+    Destination = 1,  // εἰς τὴν πόλιν
+    Source = 2,       // ἐκ τῆς πόλεως
+    Time = 3,         // ἐν τῇ νυκτί
+    Instrument = 4,
+    Agent = 5,        // ὑπὸ τοῦ ἀνθρώπου
+    Cause = 6,
+    Companion = 7,    // μετὰ τῶν φίλων
+}
+
+impl SemanticRole {
+    pub const ALL: [SemanticRole; 8] = [
+        SemanticRole::Location, SemanticRole::Destination, SemanticRole::Source, SemanticRole::Time,
+        SemanticRole::Instrument, SemanticRole::Agent, SemanticRole::Cause, SemanticRole::Companion,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            SemanticRole::Location => "location",
+            SemanticRole::Destination => "destination",
+            SemanticRole::Source => "source",
+            SemanticRole::Time => "time",
+            SemanticRole::Instrument => "instrument",
+            SemanticRole::Agent => "agent",
+            SemanticRole::Cause => "cause",
+            SemanticRole::Companion => "companion",
+        }
+    }
+}
+
+/// A role a preposition gives its argument, and the concepts that fit it:
+/// the argument must be (IsA) or have (HasAttribute) one of `concepts`.
+/// A preposition with several roles (ἐν: location or time) lists one each,
+/// and an argument fitting any of them is accepted.
+#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+pub struct PrepositionRole {
+    pub preposition: LemmaId,
+    /// MorphFlags case bits of the argument the role applies to (ἐπί with
+    /// the genitive, dative or accusative); 0 for any case
+    pub cases: u32,
+    pub role: SemanticRole,
+    pub concepts: Vec<LemmaId>,
 }
@@ -14,6 +14,7 @@ pub enum Case {
     Genitive = 1,
     Accusative = 2,
     Vocative = 3,
+    Dative = 4,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
@@ -66,6 +67,8 @@ pub enum Tense {
     Perfect = 4,
     Pluperfect = 5,
     FuturePerfect = 6,
+    /// A past tense of unmarked aspect, as `MorphFlags::PAST` records it
+    Past = 7,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
@@ -81,6 +84,37 @@ pub enum Mood {
     Optative = 5,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+#[repr(u8)]
+pub enum Degree {
+    Positive = 0,
+    Comparative = 1, // -τερος
+    Superlative = 2, // -τατος
+}
+
+impl Degree {
+    /// Positive is the absence of both degree flags.
+    pub fn from_flags(flags: MorphFlags) -> Self {
+        if flags.contains(MorphFlags::SUPERLATIVE) {
+            Degree::Superlative
+        } else if flags.contains(MorphFlags::COMPARATIVE) {
+            Degree::Comparative
+        } else {
+            Degree::Positive
+        }
+    }
+
+    pub fn flags(self) -> MorphFlags {
+        match self {
+            Degree::Positive => MorphFlags::empty(),
+            Degree::Comparative => MorphFlags::COMPARATIVE,
+            Degree::Superlative => MorphFlags::SUPERLATIVE,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
 #[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
 #[archive(check_bytes)]
@@ -96,6 +130,43 @@ pub enum PartOfSpeech {
     Pronoun = 7,
     Particle = 8,
     Numeral = 9,
+    ProperNoun = 10,
+}
+
+/// What a gazetteer name refers to (see `NameEntry`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+#[repr(u8)]
+pub enum NameKind {
+    Person = 0, // Σωκράτης
+    Place = 1,  // Ἀθῆναι
+    People = 2, // Ἕλληνες
+    Deity = 3,  // Ζεύς
+}
+
+/// Subclass of a pronoun lemma (see `PronounParadigm`). Person rides in
+/// each form's flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+#[archive(check_bytes)]
+#[repr(u8)]
+pub enum PronounClass {
+    Personal = 0,     // ἐγώ, σύ
+    Demonstrative = 1, // οὗτος, ἐκεῖνος
+    Reflexive = 2,    // ἐμαυτοῦ, ἑαυτοῦ
+    Possessive = 3,   // ἐμός, σός (inflects and attaches like an adjective)
+}
+
+impl PronounClass {
+    /// Flags every form of this class carries besides its own.
+    pub fn flags(self) -> MorphFlags {
+        match self {
+            PronounClass::Personal | PronounClass::Demonstrative => MorphFlags::PRONOUN,
+            PronounClass::Reflexive => MorphFlags::PRONOUN | MorphFlags::REFLEXIVE,
+            PronounClass::Possessive => MorphFlags::PRONOUN | MorphFlags::ADJECTIVE,
+        }
+    }
 }
 
 bitflags! {
@@ -144,6 +215,136 @@ bitflags! {
         const INFINITIVE = 33554432;
         const VERB = 67108864;
         const RELATIVE = 134217728;
+        const REFLEXIVE = 268435456;
+
+        // Degree (Bits 29-30; Positive = neither)
+        const COMPARATIVE = 536870912;
+        const SUPERLATIVE = 1073741824;
+
+        // Digits / alphabetic numerals (Bit 31; indeclinable, no Case)
+        const NUMERAL = 2147483648;
+    }
+}
+
+impl MorphFlags {
+    /// Part of speech and case: what context disambiguation scores.
+    pub const TAG: MorphFlags = MorphFlags::NOMINATIVE
+        .union(MorphFlags::GENITIVE)
+        .union(MorphFlags::DATIVE)
+        .union(MorphFlags::ACCUSATIVE)
+        .union(MorphFlags::VOCATIVE)
+        .union(MorphFlags::PREPOSITION)
+        .union(MorphFlags::ARTICLE)
+        .union(MorphFlags::ADJECTIVE)
+        .union(MorphFlags::NOUN)
+        .union(MorphFlags::CONJUNCTION)
+        .union(MorphFlags::PRONOUN)
+        .union(MorphFlags::PARTICIPLE)
+        .union(MorphFlags::INFINITIVE)
+        .union(MorphFlags::VERB)
+        .union(MorphFlags::NUMERAL);
+
+    /// Bits of the disambiguation tag (see `TagTransition`).
+    pub fn tag(self) -> u32 {
+        self.intersection(Self::TAG).bits()
+    }
+}
+
+bitflags! {
+    /// Dialects a lemma or paradigm belongs to; empty means common to all.
+    /// Archived as its bits (`u8`).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    #[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+    pub struct Dialect: u8 {
+        const ATTIC = 1;
+        const IONIC = 1 << 1;
+        const DORIC = 1 << 2;
+        const KOINE = 1 << 3;
+    }
+}
+
+bitflags! {
+    /// Periods a lemma or paradigm is attested in; empty means all. A Koine
+    /// paradigm set (no optative, -οσαν imperfects) is tagged `KOINE`.
+    /// Archived as its bits (`u8`).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    #[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+    pub struct Period: u8 {
+        const ARCHAIC = 1;
+        const CLASSICAL = 1 << 1;
+        const KOINE = 1 << 2;
+        const BYZANTINE = 1 << 3;
+    }
+}
+
+/// Tag sets where empty means "unmarked", shared by `Dialect` and `Period`.
+macro_rules! usage_tags {
+    ($($tags:ident),*) => {$(
+        impl $tags {
+            /// Tags of a form built from a lemma of `self` and a paradigm of
+            /// `other`; None when they share none.
+            pub fn combine(self, other: $tags) -> Option<$tags> {
+                match (self.is_empty(), other.is_empty()) {
+                    (true, _) => Some(other),
+                    (_, true) => Some(self),
+                    _ => Some(self & other).filter(|t| !t.is_empty()),
+                }
+            }
+
+            /// Whether a form with these tags suits a reader of `wanted`
+            /// (empty on either side suits everyone).
+            pub fn suits(self, wanted: $tags) -> bool {
+                self.is_empty() || wanted.is_empty() || self.intersects(wanted)
+            }
+        }
+    )*};
+}
+
+usage_tags!(Dialect, Period);
+
+/// Which Greek is analyzed. Selects the part of the flag schema the
+/// analyzer accepts and the syntax heuristics it applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+pub enum LanguageProfile {
+    #[default]
+    Ancient,
+    /// No dative case and no infinitive; indirect objects are genitives
+    /// (Δίνω του Πέτρου) or σε-phrases
+    Modern,
+}
+
+impl LanguageProfile {
+    /// Flags the language does not use; analyses carrying them are dropped.
+    pub const fn missing(self) -> MorphFlags {
+        match self {
+            LanguageProfile::Ancient => MorphFlags::empty(),
+            LanguageProfile::Modern => MorphFlags::DATIVE.union(MorphFlags::INFINITIVE),
+        }
+    }
+
+    /// Case inventory.
+    pub const fn cases(self) -> MorphFlags {
+        MorphFlags::NOMINATIVE
+            .union(MorphFlags::GENITIVE)
+            .union(MorphFlags::DATIVE)
+            .union(MorphFlags::ACCUSATIVE)
+            .union(MorphFlags::VOCATIVE)
+            .difference(self.missing())
+    }
+
+    /// Tenses and non-finite forms.
+    pub const fn verbal_forms(self) -> MorphFlags {
+        MorphFlags::PRESENT
+            .union(MorphFlags::PAST)
+            .union(MorphFlags::FUTURE)
+            .union(MorphFlags::PARTICIPLE)
+            .union(MorphFlags::INFINITIVE)
+            .difference(self.missing())
+    }
+
+    pub fn admits(self, flags: MorphFlags) -> bool {
+        !flags.intersects(self.missing())
     }
 }
 
@@ -0,0 +1,358 @@
+//! Archive layout of `Dictionary`: every lemma stem and paradigm ending is
+//! stored once in a shared byte arena and referenced by its index there.
+//!
+//! `Dictionary` keeps plain `String`s for authoring (JSON, tests); its rkyv
+//! impls below intern them on the way out, so `Archived<Dictionary>` is the
+//! pooled `ArchivedDictionary`. Read strings back with `ArchivedDictionary::str`.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use rkyv::ser::Serializer;
+use rkyv::{Archive, Deserialize, Fallible, Serialize};
+
+use crate::forms::{build_table, PooledSurfaceForm, SurfaceForm};
+use crate::ids::{LemmaId, ParadigmId};
+use crate::meta::BuildInfo;
+use crate::model::{Dictionary, Lemma, NameEntry, Paradigm, PronounParadigm, TagTransition};
+use crate::morphology::{Dialect, Gender, PartOfSpeech, Period, PronounClass};
+
+/// A string stored in `DictionaryArchive::pool`, by its index in
+/// `DictionaryArchive::pool_ends`. Each copy of a string costs these 4
+/// bytes, and each distinct string 4 more for its end, where an archived
+/// `String` takes 8 bytes per copy (plus its bytes past 8).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Archive, Serialize, Deserialize)]
+#[archive(as = "PoolStr")]
+#[repr(transparent)]
+pub struct PoolStr(u32);
+
+// Every bit pattern is a well-formed reference; `validate_pool` checks it
+// against the pool itself.
+impl<C: ?Sized> rkyv::bytecheck::CheckBytes<C> for PoolStr {
+    type Error = core::convert::Infallible;
+
+    unsafe fn check_bytes<'a>(value: *const Self, _context: &mut C) -> Result<&'a Self, Self::Error> {
+        Ok(&*value)
+    }
+}
+
+impl PoolStr {
+    /// Bytes a pool holds at most, all strings together
+    pub const MAX_BYTES: usize = u32::MAX as usize;
+
+    pub fn new(index: usize) -> Option<Self> {
+        u32::try_from(index).ok().map(Self)
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Deduplicating builder for the byte arena.
+#[derive(Debug, Default)]
+pub struct StringPool {
+    bytes: String,
+    /// End of each distinct string in `bytes`, in the order interned
+    ends: Vec<u32>,
+    index: BTreeMap<String, PoolStr>,
+}
+
+impl StringPool {
+    pub fn intern(&mut self, s: &str) -> Result<PoolStr, PoolError> {
+        if let Some(existing) = self.index.get(s) {
+            return Ok(*existing);
+        }
+        let too_large = PoolError::TooLarge { offset: self.bytes.len(), len: s.len() };
+        let end = u32::try_from(self.bytes.len() + s.len()).map_err(|_| too_large)?;
+        let entry = PoolStr::new(self.ends.len()).ok_or(too_large)?;
+        self.bytes.push_str(s);
+        self.ends.push(end);
+        self.index.insert(s.to_string(), entry);
+        Ok(entry)
+    }
+
+    /// Distinct strings interned so far.
+    pub fn unique(&self) -> usize {
+        self.index.len()
+    }
+
+    /// The arena and the end of each string in it.
+    pub fn into_parts(self) -> (String, Vec<u32>) {
+        (self.bytes, self.ends)
+    }
+}
+
+#[derive(Debug, Clone, Archive, Serialize)]
+#[archive(check_bytes, archived = "ArchivedLemma")]
+pub struct PooledLemma {
+    pub id: LemmaId,
+    pub text: PoolStr,
+    pub gender: Gender,
+    pub pos: PartOfSpeech,
+    pub urn: Option<PoolStr>,
+    pub gloss: Option<PoolStr>,
+    /// `Dialect` bits
+    pub dialects: u8,
+    /// `Period` bits
+    pub periods: u8,
+    pub paradigm: Option<ParadigmId>,
+}
+
+#[derive(Debug, Clone, Archive, Serialize)]
+#[archive(check_bytes, archived = "ArchivedParadigm")]
+pub struct PooledParadigm {
+    pub id: ParadigmId,
+    /// `Dialect` bits
+    pub dialects: u8,
+    /// `Period` bits
+    pub periods: u8,
+    pub endings: Vec<(u32, PoolStr)>,
+}
+
+#[derive(Debug, Clone, Archive, Serialize)]
+#[archive(check_bytes, archived = "ArchivedPronounParadigm")]
+pub struct PooledPronoun {
+    pub lemma: LemmaId,
+    pub class: PronounClass,
+    pub forms: Vec<(u32, PoolStr)>,
+}
+
+/// What actually gets written for a `Dictionary`.
+#[derive(Debug, Clone, Archive, Serialize)]
+#[archive(check_bytes, archived = "ArchivedDictionary")]
+pub struct DictionaryArchive {
+    pub version: u32,
+    pub info: BuildInfo,
+    pub lemmas: Vec<PooledLemma>,
+    pub paradigms: Vec<PooledParadigm>,
+    pub pronouns: Vec<PooledPronoun>,
+    pub names: Vec<NameEntry>,
+    /// Sorted by (from, to) for `ArchivedDictionary::transition`
+    pub transitions: Vec<TagTransition>,
+    pub pool: String,
+    /// End of each string in `pool`, which starts where the previous one
+    /// ends; `PoolStr`s index it
+    pub pool_ends: Vec<u32>,
+    /// Positions in `lemmas`, ordered by text (homographs in dictionary
+    /// order): exact and prefix lookups
+    pub by_text: Vec<u32>,
+    /// Positions in `lemmas`, ordered by ID
+    pub by_id: Vec<u32>,
+    /// Pre-expanded surface forms, grouped by form; empty unless built with
+    /// `build_with_forms`
+    pub forms: Vec<PooledSurfaceForm>,
+    /// Hash slots into `forms` (see `ArchivedDictionary::surface_entries`)
+    pub form_slots: Vec<u32>,
+}
+
+impl DictionaryArchive {
+    /// Interns every string of `dict` and records its hash and counts in
+    /// `info` (the builder fills in the rest); also returns how many distinct strings remain.
+    pub fn build(dict: &Dictionary) -> Result<(Self, usize), PoolError> {
+        Self::build_with_forms(dict, &[])
+    }
+
+    /// `build`, plus a surface-form table of `forms` (which must list each
+    /// form's analyses in the order a scan of `dict` finds them).
+    pub fn build_with_forms(dict: &Dictionary, forms: &[SurfaceForm]) -> Result<(Self, usize), PoolError> {
+        let mut pool = StringPool::default();
+        let mut lemmas = Vec::with_capacity(dict.lemmas.len());
+        for l in &dict.lemmas {
+            let urn = l.urn.as_deref().map(|urn| pool.intern(urn)).transpose()?;
+            let gloss = l.gloss.as_deref().map(|gloss| pool.intern(gloss)).transpose()?;
+            lemmas.push(PooledLemma { id: l.id, text: pool.intern(&l.text)?, gender: l.gender, pos: l.pos, urn, gloss, dialects: l.dialects.bits(), periods: l.periods.bits(), paradigm: l.paradigm });
+        }
+        let mut paradigms = Vec::with_capacity(dict.paradigms.len());
+        for p in &dict.paradigms {
+            let mut endings = Vec::with_capacity(p.endings.len());
+            for (flags, ending) in &p.endings {
+                endings.push((*flags, pool.intern(ending)?));
+            }
+            paradigms.push(PooledParadigm { id: p.id, dialects: p.dialects.bits(), periods: p.periods.bits(), endings });
+        }
+        let mut pronouns = Vec::with_capacity(dict.pronouns.len());
+        for p in &dict.pronouns {
+            let mut forms = Vec::with_capacity(p.forms.len());
+            for (flags, form) in &p.forms {
+                forms.push((*flags, pool.intern(form)?));
+            }
+            pronouns.push(PooledPronoun { lemma: p.lemma, class: p.class, forms });
+        }
+        let (forms, form_slots) = build_table(&mut pool, forms)?;
+
+        let mut transitions = dict.transitions.clone();
+        transitions.sort_by_key(|t| (t.from, t.to, t.weight));
+
+        let mut by_text: Vec<u32> = (0..dict.lemmas.len() as u32).collect();
+        by_text.sort_by(|&a, &b| dict.lemmas[a as usize].text.cmp(&dict.lemmas[b as usize].text));
+        let mut by_id: Vec<u32> = (0..dict.lemmas.len() as u32).collect();
+        by_id.sort_by_key(|&i| dict.lemmas[i as usize].id);
+
+        let unique = pool.unique();
+        let (pool, pool_ends) = pool.into_parts();
+        Ok((Self { version: dict.version, info: BuildInfo::describe(dict), lemmas, paradigms, pronouns, names: dict.names.clone(), transitions, pool, pool_ends, by_text, by_id, forms, form_slots }, unique))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolError {
+    /// A string that would take the pool past `PoolStr::MAX_BYTES`
+    TooLarge { offset: usize, len: usize },
+    /// A reference past the strings the pool holds
+    OutOfRange { index: usize, strings: usize },
+    /// String ends that go backwards, past the pool or split a character
+    BadEnds { index: usize },
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::TooLarge { offset, len } => {
+                write!(f, "string of {} bytes at pool offset {} exceeds the pool limits", len, offset)
+            }
+            PoolError::OutOfRange { index, strings } => {
+                write!(f, "pooled string {} is outside the pool of {} strings", index, strings)
+            }
+            PoolError::BadEnds { index } => {
+                write!(f, "pooled string {} ends outside the pool or splits a character", index)
+            }
+        }
+    }
+}
+
+impl ArchivedDictionary {
+    /// The pooled string, or "" for an out-of-range reference
+    /// (`validate_pool` rules those out up front).
+    pub fn str(&self, s: &PoolStr) -> &str {
+        let index = s.index();
+        let Some(&end) = self.pool_ends.get(index) else { return "" };
+        let start = index.checked_sub(1).map_or(0, |previous| self.pool_ends[previous]);
+        self.pool.as_str().get(start as usize..end as usize).unwrap_or("")
+    }
+
+    /// Weight of the tag bigram `from` -> `to`, if the dictionary lists it.
+    pub fn transition(&self, from: u32, to: u32) -> Option<i32> {
+        self.transitions
+            .binary_search_by_key(&(from, to), |t| (t.from, t.to))
+            .ok()
+            .map(|i| self.transitions[i].weight)
+    }
+
+    /// Byte-level validation (`check_archived_root`) cannot see into pool
+    /// references; call this once after it.
+    pub fn validate_pool(&self) -> Result<(), PoolError> {
+        let pool = self.pool.as_str();
+        let mut start = 0;
+        for (index, &end) in self.pool_ends.iter().enumerate() {
+            let end = end as usize;
+            if end < start || !pool.is_char_boundary(end) {
+                return Err(PoolError::BadEnds { index });
+            }
+            start = end;
+        }
+        let strings = self.pool_ends.len();
+        let check = |s: &PoolStr| {
+            if s.index() < strings { Ok(()) } else { Err(PoolError::OutOfRange { index: s.index(), strings }) }
+        };
+        for lemma in self.lemmas.iter() {
+            check(&lemma.text)?;
+            if let Some(urn) = lemma.urn.as_ref() {
+                check(urn)?;
+            }
+            if let Some(gloss) = lemma.gloss.as_ref() {
+                check(gloss)?;
+            }
+        }
+        for paradigm in self.paradigms.iter() {
+            for (_, ending) in paradigm.endings.iter() {
+                check(ending)?;
+            }
+        }
+        for pronoun in self.pronouns.iter() {
+            for (_, form) in pronoun.forms.iter() {
+                check(form)?;
+            }
+        }
+        for form in self.forms.iter() {
+            check(&form.form)?;
+        }
+        Ok(())
+    }
+}
+
+/// Carries the interned form from `serialize` to `resolve`.
+pub struct DictionaryResolver {
+    archive: DictionaryArchive,
+    inner: DictionaryArchiveResolver,
+}
+
+impl Archive for Dictionary {
+    type Archived = ArchivedDictionary;
+    type Resolver = DictionaryResolver;
+
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        resolver.archive.resolve(pos, resolver.inner, out);
+    }
+}
+
+impl<S: Serializer + ?Sized> Serialize<S> for Dictionary
+where
+    DictionaryArchive: Serialize<S>,
+{
+    /// Panics if the strings pass 4 GiB, more than the archive's 32-bit
+    /// relative pointers reach anyway; use `DictionaryArchive::build` to
+    /// handle that as an error.
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let (archive, _) = DictionaryArchive::build(self).expect("dictionary exceeds string pool limits");
+        let inner = archive.serialize(serializer)?;
+        Ok(DictionaryResolver { archive, inner })
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Dictionary, D> for ArchivedDictionary {
+    fn deserialize(&self, deserializer: &mut D) -> Result<Dictionary, D::Error> {
+        let mut lemmas = Vec::with_capacity(self.lemmas.len());
+        for l in self.lemmas.iter() {
+            lemmas.push(Lemma {
+                id: l.id.deserialize(deserializer)?,
+                text: self.str(&l.text).to_string(),
+                gender: l.gender.deserialize(deserializer)?,
+                pos: l.pos.deserialize(deserializer)?,
+                urn: l.urn.as_ref().map(|urn| self.str(urn).to_string()),
+                gloss: l.gloss.as_ref().map(|gloss| self.str(gloss).to_string()),
+                dialects: Dialect::from_bits_truncate(l.dialects),
+                periods: Period::from_bits_truncate(l.periods),
+                paradigm: l.paradigm.as_ref().map(|p| ParadigmId(p.0)),
+            });
+        }
+        let paradigms = self.paradigms.iter().map(|p| Paradigm {
+            id: ParadigmId(p.id.0),
+            dialects: Dialect::from_bits_truncate(p.dialects),
+            periods: Period::from_bits_truncate(p.periods),
+            endings: p.endings.iter().map(|(flags, ending)| (*flags, self.str(ending).to_string())).collect(),
+        }).collect();
+        let mut pronouns = Vec::with_capacity(self.pronouns.len());
+        for p in self.pronouns.iter() {
+            pronouns.push(PronounParadigm {
+                lemma: p.lemma.deserialize(deserializer)?,
+                class: p.class.deserialize(deserializer)?,
+                forms: p.forms.iter().map(|(flags, form)| (*flags, self.str(form).to_string())).collect(),
+            });
+        }
+
+        let mut names = Vec::with_capacity(self.names.len());
+        for name in self.names.iter() {
+            names.push(name.deserialize(deserializer)?);
+        }
+
+        let mut transitions = Vec::with_capacity(self.transitions.len());
+        for t in self.transitions.iter() {
+            transitions.push(t.deserialize(deserializer)?);
+        }
+
+        Ok(Dictionary { version: self.version, lemmas, paradigms, pronouns, names, transitions })
+    }
+}
@@ -0,0 +1,100 @@
+//! Summary of what an archive holds, for tools and UIs that want to show a
+//! lexicon's coverage without reading it lemma by lemma.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize as SerdeDeserialize, Serialize as SerdeSerialize};
+
+use crate::morphology::PartOfSpeech;
+use crate::pool::ArchivedDictionary;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+pub struct DictionaryStats {
+    pub lemmas: u32,
+    /// Lemmas per part of speech, most frequent first
+    pub lemmas_by_pos: Vec<PosCount>,
+    pub paradigms: u32,
+    /// How many paradigms have each number of endings, smallest first
+    pub paradigm_sizes: Vec<ParadigmSize>,
+    /// Every distinct ending with the number of paradigm cells spelled with
+    /// it, most frequent first (the zero ending is "")
+    pub suffixes: Vec<SuffixCount>,
+    pub pronoun_forms: u32,
+    pub names: u32,
+    pub transitions: u32,
+    /// Entries of the pre-expanded surface-form table (0 without one)
+    pub surface_forms: u32,
+    /// Size of the shared string pool
+    pub pool_bytes: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+pub struct PosCount {
+    pub pos: PartOfSpeech,
+    pub lemmas: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+pub struct ParadigmSize {
+    pub endings: u32,
+    pub paradigms: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(SerdeDeserialize, SerdeSerialize))]
+pub struct SuffixCount {
+    pub suffix: String,
+    pub cells: u32,
+}
+
+impl ArchivedDictionary {
+    /// Counts over the whole archive; one pass over the lemmas and paradigms.
+    pub fn stats(&self) -> DictionaryStats {
+        let mut lemmas_by_pos: Vec<PosCount> = Vec::new();
+        for lemma in self.lemmas.iter() {
+            let pos: PartOfSpeech = rkyv::Deserialize::deserialize(&lemma.pos, &mut rkyv::Infallible).unwrap_or(PartOfSpeech::Noun);
+            match lemmas_by_pos.iter_mut().find(|c| c.pos == pos) {
+                Some(count) => count.lemmas += 1,
+                None => lemmas_by_pos.push(PosCount { pos, lemmas: 1 }),
+            }
+        }
+        lemmas_by_pos.sort_by_key(|c| core::cmp::Reverse(c.lemmas));
+
+        let mut paradigm_sizes: Vec<ParadigmSize> = Vec::new();
+        let mut suffixes: Vec<SuffixCount> = Vec::new();
+        for paradigm in self.paradigms.iter() {
+            let endings = paradigm.endings.len() as u32;
+            match paradigm_sizes.iter_mut().find(|s| s.endings == endings) {
+                Some(size) => size.paradigms += 1,
+                None => paradigm_sizes.push(ParadigmSize { endings, paradigms: 1 }),
+            }
+            for (_, ending) in paradigm.endings.iter() {
+                let suffix = self.str(ending);
+                match suffixes.iter_mut().find(|s| s.suffix == suffix) {
+                    Some(count) => count.cells += 1,
+                    None => suffixes.push(SuffixCount { suffix: suffix.to_string(), cells: 1 }),
+                }
+            }
+        }
+        paradigm_sizes.sort_by_key(|s| s.endings);
+        suffixes.sort_by(|a, b| b.cells.cmp(&a.cells).then_with(|| a.suffix.cmp(&b.suffix)));
+
+        DictionaryStats {
+            lemmas: self.lemmas.len() as u32,
+            lemmas_by_pos,
+            paradigms: self.paradigms.len() as u32,
+            paradigm_sizes,
+            suffixes,
+            pronoun_forms: self.pronouns.iter().map(|p| p.forms.len() as u32).sum(),
+            names: self.names.len() as u32,
+            transitions: self.transitions.len() as u32,
+            surface_forms: self.forms.len() as u32,
+            pool_bytes: self.pool.len() as u32,
+        }
+    }
+}
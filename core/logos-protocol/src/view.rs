@@ -0,0 +1,197 @@
+//! Read access shared by the authoring `Dictionary` and the archived
+//! `ArchivedDictionary`, so lookups can run on either: a dictionary built in
+//! code (tests, tools) needs no rkyv round trip first.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::ids::{LemmaId, ParadigmId};
+use crate::model::Dictionary;
+use crate::morphology::{Dialect, Period, PronounClass};
+use crate::pool::{ArchivedDictionary, ArchivedLemma, PoolStr};
+
+/// A lemma as lookups see it.
+#[derive(Debug, Clone, Copy)]
+pub struct LemmaView<'a> {
+    pub id: LemmaId,
+    pub text: &'a str,
+    pub dialects: Dialect,
+    pub periods: Period,
+    /// None when the lemma may take any paradigm
+    pub paradigm: Option<ParadigmId>,
+}
+
+impl LemmaView<'_> {
+    /// Whether forms of the lemma may take the endings of `paradigm`.
+    pub fn inflects_by(&self, paradigm: ParadigmId) -> bool {
+        self.paradigm.is_none_or(|own| own == paradigm)
+    }
+}
+
+/// A paradigm's endings, without the plumbing of either storage.
+#[derive(Clone, Copy)]
+pub struct ParadigmView<'a> {
+    pub id: ParadigmId,
+    pub dialects: Dialect,
+    pub periods: Period,
+    pub endings: Forms<'a>,
+}
+
+#[derive(Clone, Copy)]
+pub struct PronounView<'a> {
+    pub lemma: LemmaId,
+    pub class: PronounClass,
+    pub forms: Forms<'a>,
+}
+
+/// One analysis listed in a surface-form table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SurfaceMatch {
+    pub lemma: LemmaId,
+    /// MorphFlags bits
+    pub flags: u32,
+    /// Bytes of the form that are the ending; the rest is the stem
+    pub ending_len: usize,
+    pub dialects: Dialect,
+    pub periods: Period,
+}
+
+/// (MorphFlags bits, text) pairs: paradigm endings or pronoun forms.
+#[derive(Clone, Copy)]
+pub enum Forms<'a> {
+    Plain(&'a [(u32, String)]),
+    Pooled(&'a [(u32, PoolStr)], &'a ArchivedDictionary),
+}
+
+impl<'a> Forms<'a> {
+    pub fn iter(self) -> impl Iterator<Item = (u32, &'a str)> {
+        let len = match self {
+            Forms::Plain(forms) => forms.len(),
+            Forms::Pooled(forms, _) => forms.len(),
+        };
+        (0..len).map(move |i| match self {
+            Forms::Plain(forms) => (forms[i].0, forms[i].1.as_str()),
+            Forms::Pooled(forms, dict) => (forms[i].0, dict.str(&forms[i].1)),
+        })
+    }
+}
+
+/// What the lexer and the morphology read from a dictionary. The lookups
+/// scan every lemma unless the storage has an index (archives do).
+pub trait DictionaryView {
+    fn lemmas(&self) -> impl Iterator<Item = LemmaView<'_>>;
+    fn paradigms(&self) -> impl Iterator<Item = ParadigmView<'_>>;
+    fn pronouns(&self) -> impl Iterator<Item = PronounView<'_>>;
+
+    /// The lemma with this (dictionary-local) ID.
+    fn lemma(&self, id: LemmaId) -> Option<LemmaView<'_>> {
+        self.lemmas().find(|l| l.id == id)
+    }
+
+    /// Lemmas whose text is exactly `text` (homographs included), in
+    /// dictionary order.
+    fn find_exact(&self, text: &str) -> Vec<LemmaView<'_>> {
+        self.lemmas().filter(|l| l.text == text).collect()
+    }
+
+    /// Up to `limit` lemmas whose text starts with `prefix`, in text order.
+    fn find_prefix(&self, prefix: &str, limit: usize) -> Vec<LemmaView<'_>> {
+        let mut found: Vec<LemmaView> = self.lemmas().filter(|l| l.text.starts_with(prefix)).collect();
+        found.sort_by(|a, b| a.text.cmp(b.text));
+        found.truncate(limit);
+        found
+    }
+
+    /// Stem-plus-ending analyses of `form` from a pre-expanded surface-form
+    /// table, in dictionary order; None when the storage has no table, and
+    /// the caller scans the lemmas and paradigms instead.
+    fn surface_forms(&self, _form: &str) -> Option<Vec<SurfaceMatch>> {
+        None
+    }
+}
+
+impl DictionaryView for Dictionary {
+    fn lemmas(&self) -> impl Iterator<Item = LemmaView<'_>> {
+        self.lemmas.iter().map(|l| LemmaView { id: l.id, text: &l.text, dialects: l.dialects, periods: l.periods, paradigm: l.paradigm })
+    }
+
+    fn paradigms(&self) -> impl Iterator<Item = ParadigmView<'_>> {
+        self.paradigms.iter().map(|p| ParadigmView { id: p.id, dialects: p.dialects, periods: p.periods, endings: Forms::Plain(&p.endings) })
+    }
+
+    fn pronouns(&self) -> impl Iterator<Item = PronounView<'_>> {
+        self.pronouns.iter().map(|p| PronounView { lemma: p.lemma, class: p.class, forms: Forms::Plain(&p.forms) })
+    }
+}
+
+impl ArchivedDictionary {
+    /// The archived lemma with this (dictionary-local) ID, through the index.
+    pub fn lemma_by_id(&self, id: LemmaId) -> Option<&ArchivedLemma> {
+        let at = self.by_id.binary_search_by_key(&id.0, |&i| self.lemmas.get(i as usize).map_or(u32::MAX, |l| l.id.0)).ok()?;
+        self.lemmas.get(self.by_id[at] as usize)
+    }
+
+    fn lemma_view(&self, l: &ArchivedLemma) -> LemmaView<'_> {
+        LemmaView {
+            id: LemmaId(l.id.0),
+            text: self.str(&l.text),
+            dialects: Dialect::from_bits_truncate(l.dialects),
+            periods: Period::from_bits_truncate(l.periods),
+            paradigm: l.paradigm.as_ref().map(|p| ParadigmId(p.0)),
+        }
+    }
+
+    /// Lemmas in text order from the first whose text is not below `text`.
+    fn lemmas_from<'a>(&'a self, text: &str) -> impl Iterator<Item = LemmaView<'a>> {
+        let start = self.by_text.partition_point(|&i| self.lemmas.get(i as usize).is_some_and(|l| self.str(&l.text) < text));
+        self.by_text[start..].iter().filter_map(|&i| self.lemmas.get(i as usize)).map(|l| self.lemma_view(l))
+    }
+}
+
+impl DictionaryView for ArchivedDictionary {
+    fn lemmas(&self) -> impl Iterator<Item = LemmaView<'_>> {
+        self.lemmas.iter().map(move |l| self.lemma_view(l))
+    }
+
+    fn lemma(&self, id: LemmaId) -> Option<LemmaView<'_>> {
+        self.lemma_by_id(id).map(|l| self.lemma_view(l))
+    }
+
+    fn find_exact(&self, text: &str) -> Vec<LemmaView<'_>> {
+        self.lemmas_from(text).take_while(|l| l.text == text).collect()
+    }
+
+    fn find_prefix(&self, prefix: &str, limit: usize) -> Vec<LemmaView<'_>> {
+        self.lemmas_from(prefix).take_while(|l| l.text.starts_with(prefix)).take(limit).collect()
+    }
+
+    fn surface_forms(&self, form: &str) -> Option<Vec<SurfaceMatch>> {
+        if !self.has_surface_forms() {
+            return None;
+        }
+        Some(self.surface_entries(form).iter().map(|f| SurfaceMatch {
+            lemma: LemmaId(f.lemma.0),
+            flags: f.flags,
+            ending_len: f.ending_len as usize,
+            dialects: Dialect::from_bits_truncate(f.dialects),
+            periods: Period::from_bits_truncate(f.periods),
+        }).collect())
+    }
+
+    fn paradigms(&self) -> impl Iterator<Item = ParadigmView<'_>> {
+        self.paradigms.iter().map(move |p| ParadigmView {
+            id: ParadigmId(p.id.0),
+            dialects: Dialect::from_bits_truncate(p.dialects),
+            periods: Period::from_bits_truncate(p.periods),
+            endings: Forms::Pooled(&p.endings, self),
+        })
+    }
+
+    fn pronouns(&self) -> impl Iterator<Item = PronounView<'_>> {
+        self.pronouns.iter().map(move |p| PronounView {
+            lemma: LemmaId(p.lemma.0),
+            class: rkyv::Deserialize::deserialize(&p.class, &mut rkyv::Infallible).unwrap_or(PronounClass::Personal),
+            forms: Forms::Pooled(&p.forms, self),
+        })
+    }
+}
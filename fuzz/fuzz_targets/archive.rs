@@ -0,0 +1,25 @@
+//! Feeds arbitrary bytes to the checked loaders (dictionaries, compressed
+//! containers, semantic networks, parser models); whatever they accept must
+//! then be safe to read zero-copy.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use logos_engine::LogosEngine;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(engine) = LogosEngine::from_bytes(data) {
+        let _ = engine.dictionary_info();
+        let _ = engine.analyze_core("ὁ ἄνθρωπος λέγει.");
+    }
+
+    let mut engine = LogosEngine::from_bytes(&empty_dictionary()).unwrap();
+    if engine.load_semantics_bytes(data).is_ok() | engine.load_model_bytes(data).is_ok() {
+        let _ = engine.analyze_core("ὁ ἄνθρωπος λέγει.");
+    }
+    let _ = engine.add_dictionary_bytes(data);
+});
+
+fn empty_dictionary() -> rkyv::AlignedVec {
+    rkyv::to_bytes::<_, 256>(&logos_protocol::Dictionary::default()).unwrap()
+}
@@ -0,0 +1,42 @@
+//! Resolves arbitrary words against the golden-test dictionary, under every
+//! combination of the matching options the first byte selects.
+
+#![no_main]
+
+use std::sync::LazyLock;
+
+use libfuzzer_sys::fuzz_target;
+use logos_parser::analysis::analyze_token_with;
+use logos_parser::morphology::MatchOptions;
+use logos_parser::token::TokenKind;
+use logos_parser::Lexer;
+use logos_protocol::{Dictionary, DictionaryHandle, LanguageProfile};
+
+static DICTIONARY: LazyLock<DictionaryHandle> = LazyLock::new(|| {
+    let dictionary: Dictionary = serde_json::from_str(include_str!("../../tests/golden/dictionary.json")).unwrap();
+    DictionaryHandle::new(rkyv::to_bytes::<_, 1024>(&dictionary).unwrap()).unwrap()
+});
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&mode, rest)) = data.split_first() else { return };
+    let Ok(word) = std::str::from_utf8(rest) else { return };
+    let dict = DICTIONARY.archived();
+    let options = MatchOptions {
+        accent_insensitive: mode & 1 != 0,
+        restrict_dialect: mode & 2 != 0,
+        restrict_period: mode & 4 != 0,
+        profile: if mode & 8 != 0 { LanguageProfile::Modern } else { LanguageProfile::Ancient },
+        ..Default::default()
+    };
+
+    let analysis = analyze_token_with(dict, word, None, &options);
+    assert!((0.0..=1.0).contains(&analysis.confidence));
+    if let Some(id) = analysis.lemma_id {
+        analyze_token_with(dict, word, Some(id.0), &options);
+    }
+    for token in Lexer::new(dict).tokenize(word) {
+        if let TokenKind::Word(id) = token.kind {
+            analyze_token_with(dict, &token.text, Some(id.0), &options);
+        }
+    }
+});
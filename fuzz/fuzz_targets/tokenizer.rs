@@ -0,0 +1,29 @@
+//! Spans are computed by pointer arithmetic on the input; check that every
+//! one lands on char boundaries, in order, and covers the token's own text.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use logos_parser::parser::{parse_with_config, parse_with_spans, RawToken};
+use logos_parser::token::Span;
+use logos_parser::TokenizerConfig;
+
+fn check(input: &str, tokens: &[(Span, RawToken<'_>)]) {
+    let mut last = 0;
+    for (span, token) in tokens {
+        assert!(last <= span.start && span.start < span.end && span.end <= input.len(), "{:?} after {}", span, last);
+        let text = input.get(span.start..span.end).expect("span off a char boundary");
+        if let RawToken::Word(word) | RawToken::Hyphenated(word) = token {
+            assert_eq!(text, *word);
+        }
+        last = span.end;
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else { return };
+    check(input, &parse_with_spans(input));
+
+    let config = TokenizerConfig { rejoin_hyphens: true, keep_latin: false, punctuation: vec!['.', ',', ';', '·', '-'], ..Default::default() };
+    check(input, &parse_with_config(input, &config));
+});
@@ -0,0 +1,62 @@
+//! Analysis of a book-length text (about 100,000 words). Compare
+//! `cargo bench -p logos-engine` with `cargo bench -p logos-engine --features parallel`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use logos_engine::{AnalysisOptions, LogosEngine};
+use logos_protocol::{Dialect, Dictionary, Gender, Lemma, LemmaId, MorphFlags, Paradigm, ParadigmId, PartOfSpeech, Period};
+
+const SENTENCES: usize = 20_000;
+
+fn dictionary() -> Vec<u8> {
+    let dict = Dictionary {
+        version: 1,
+        lemmas: vec![
+            Lemma { id: LemmaId(1), text: "λόγ".to_string(), gender: Gender::Masculine, pos: PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None },
+            Lemma { id: LemmaId(2), text: "λέγ".to_string(), gender: Gender::Masculine, pos: PartOfSpeech::Verb, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None },
+            Lemma { id: LemmaId(3), text: "ἀνθρώπ".to_string(), gender: Gender::Masculine, pos: PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None },
+        ],
+        paradigms: vec![Paradigm {
+            id: ParadigmId(1),
+            dialects: Dialect::empty(),
+            periods: Period::empty(),
+            endings: vec![
+                ((MorphFlags::NOUN | MorphFlags::NOMINATIVE | MorphFlags::SINGULAR).bits(), "ος".to_string()),
+                ((MorphFlags::NOUN | MorphFlags::ACCUSATIVE | MorphFlags::SINGULAR).bits(), "ον".to_string()),
+                ((MorphFlags::NOUN | MorphFlags::NOMINATIVE | MorphFlags::PLURAL).bits(), "οι".to_string()),
+                ((MorphFlags::VERB | MorphFlags::THIRD_PERSON | MorphFlags::SINGULAR).bits(), "ει".to_string()),
+                ((MorphFlags::VERB | MorphFlags::THIRD_PERSON | MorphFlags::PLURAL).bits(), "ουσι".to_string()),
+            ],
+        }],
+        ..Default::default()
+    };
+    rkyv::to_bytes::<_, 1024>(&dict).unwrap().to_vec()
+}
+
+/// Five-word sentences cycling through a few patterns, with unknown words mixed in.
+fn book() -> String {
+    let patterns = [
+        "ὁ ἄνθρωπος λέγει λόγον καλῶς.",
+        "οἱ λόγοι λέγουσι τὸν ἄνθρωπον;",
+        "ἄνθρωπος λόγον λέγει ἐν ἀγορᾷ.",
+        "λόγοι καὶ ἄνθρωποι λέγουσι πολλά.",
+    ];
+    (0..SENTENCES).map(|i| patterns[i % patterns.len()]).collect::<Vec<_>>().join(" ")
+}
+
+fn bench_book(c: &mut Criterion) {
+    let engine = LogosEngine::from_bytes(&dictionary()).unwrap();
+    let text = book();
+    let options = AnalysisOptions::default();
+
+    let mut group = c.benchmark_group("book");
+    group.sample_size(10);
+    group.bench_function("analyze", |b| b.iter(|| engine.analyze_with_options(&text, &options).unwrap()));
+    group.bench_function("analyze_uncached", |b| {
+        engine.set_cache_capacity(0);
+        b.iter(|| engine.analyze_with_options(&text, &options).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_book);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! Tokens aligned with the text they came from (see
+//! `LogosEngine::align_core`): each token's byte span in the original
+//! input, the form it was looked up as, and the lemma found, so standoff
+//! annotations (TEI and the like) can point into the original file however
+//! the engine respelled the words.
+
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+use crate::{AnalysisReport, TokenSpan};
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct TokenAlignment {
+    /// Index of the token in the report's `tokens`
+    pub token: usize,
+    /// `[start, end]` byte span in the original input
+    pub span: [usize; 2],
+    /// `[start, end]` of each half of a word rejoined across a line break;
+    /// empty for every other token
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub fragments: Vec<[usize; 2]>,
+    /// The input's text at `span`, as written
+    pub original: String,
+    /// The form compared with the dictionary, after every normalization
+    /// the lookup made (`TokenDebug::normalizations`)
+    pub normalized: String,
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub lemma_id: Option<u32>,
+    /// Headword of the resolved lemma
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub lemma: Option<String>,
+}
+
+impl TokenAlignment {
+    /// Whether byte `offset` of the input falls inside the token (inside
+    /// one of its halves, for a rejoined word).
+    pub fn contains(&self, offset: usize) -> bool {
+        let within = |[start, end]: [usize; 2]| start <= offset && offset < end;
+        if self.fragments.is_empty() { within(self.span) } else { self.fragments.iter().copied().any(within) }
+    }
+}
+
+/// The tokens of `report`, analyzed from `input` with `normalizations` on,
+/// matched with their `spans` in it.
+pub(crate) fn align(input: &str, report: &AnalysisReport, spans: &[TokenSpan]) -> Vec<TokenAlignment> {
+    report.tokens.iter().zip(spans).enumerate().map(|(i, (token, span))| TokenAlignment {
+        token: i,
+        span: [span.start, span.end],
+        fragments: span.fragments.clone(),
+        original: input[span.start..span.end].to_string(),
+        normalized: token.normalizations.last().map_or_else(|| token.text.clone(), |n| n.to.clone()),
+        lemma_id: token.lemma_id,
+        lemma: token.lemma.clone(),
+    }).collect()
+}
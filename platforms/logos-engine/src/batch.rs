@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+use crate::{options::AnalysisOptions, AnalysisReport, EngineError};
+
+/// Corpus-level counters aggregated over every analyzed text.
+#[derive(Debug, Default, Clone, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct BatchStats {
+    pub texts: usize,
+    /// Blank texts, with nothing to analyze
+    pub skipped: usize,
+    /// Texts the engine failed on (see `failures`)
+    pub failed: usize,
+    /// Word tokens (punctuation and editorial sigla excluded)
+    pub tokens: usize,
+    pub unknown_tokens: usize,
+    pub unknown_rate: f64,
+    pub syntax_errors: usize,
+    pub semantic_errors: usize,
+}
+
+impl BatchStats {
+    pub fn record(&mut self, report: &AnalysisReport) {
+        self.texts += 1;
+        for token in &report.tokens {
+            if matches!(token.kind.as_str(), "Punctuation" | "Editorial" | "Foreign") {
+                continue;
+            }
+            self.tokens += 1;
+            if token.kind == "Unknown" {
+                self.unknown_tokens += 1;
+            }
+        }
+        self.syntax_errors += report.syntax_errors.len();
+        self.semantic_errors += report.semantic_errors.len();
+        self.unknown_rate = if self.tokens == 0 {
+            0.0
+        } else {
+            self.unknown_tokens as f64 / self.tokens as f64
+        };
+    }
+}
+
+/// Report for one input text; `index` points back into the submitted list
+/// (skipped and failed texts have no entry).
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct BatchItem {
+    pub index: usize,
+    pub report: AnalysisReport,
+}
+
+/// Why the text at `index` could not be analyzed.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct BatchFailure {
+    pub index: usize,
+    /// `EngineError::kind`
+    pub kind: String,
+    pub message: String,
+}
+
+impl BatchFailure {
+    pub fn new(index: usize, error: &EngineError) -> Self {
+        Self { index, kind: error.kind().to_string(), message: error.to_string() }
+    }
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct BatchReport {
+    pub reports: Vec<BatchItem>,
+    pub failures: Vec<BatchFailure>,
+    pub stats: BatchStats,
+}
+
+/// One slice of a resumable batch. `stats` is cumulative since `start_batch`.
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct BatchChunk {
+    pub reports: Vec<BatchItem>,
+    pub failures: Vec<BatchFailure>,
+    pub stats: BatchStats,
+    pub remaining: usize,
+    pub done: bool,
+}
+
+/// Pending work of a batch started with `start_batch`.
+pub(crate) struct BatchState {
+    pub pending: VecDeque<String>,
+    pub next_index: usize,
+    pub options: AnalysisOptions,
+    pub stats: BatchStats,
+}
@@ -0,0 +1,75 @@
+use thiserror::Error;
+
+/// Failure categories surfaced by the engine.
+/// The category name is prefixed to the message so callers on the far side of
+/// a string-only boundary (JS, C, HTTP) can still branch on it.
+#[derive(Debug, Error)]
+pub enum EngineError {
+    #[error("InvalidDictionary: {0}")]
+    InvalidDictionary(String),
+    /// Bytes given to `load_semantics_bytes` that are no `SemanticNetwork`
+    #[error("InvalidSemantics: {0}")]
+    InvalidSemantics(String),
+    /// Bytes given to `load_model_bytes` that are no `ParserModel`
+    #[error("InvalidModel: {0}")]
+    InvalidModel(String),
+    #[error("SerializationFailed: {0}")]
+    SerializationFailed(String),
+    #[error("EmptyInput: nothing to analyze")]
+    EmptyInput,
+    #[error("InvalidOptions: {0}")]
+    InvalidOptions(String),
+    #[error("NoBatch: call start_batch before next_chunk")]
+    NoBatch,
+    #[error("InvalidCorpus: {0}")]
+    InvalidCorpus(String),
+    /// A stage this build was compiled without (see `LogosEngine::stages`)
+    #[error("Unavailable: built without the `{0}` stage")]
+    Unavailable(&'static str),
+    #[error(transparent)]
+    Pipeline(#[from] PipelineError),
+}
+
+/// A stage of the analysis whose output does not fit its input. `analyze`
+/// drops that output and lists the error in
+/// `AnalysisReport::pipeline_errors`, returning what the other stages
+/// produced; stricter callers get it as `EngineError::Pipeline`. Indices are
+/// the sentence's own.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PipelineError {
+    #[error("DependencyOutOfRange: arc from {head} to {dependent} in a sentence of {len} words")]
+    DependencyOutOfRange { head: usize, dependent: usize, len: usize },
+    #[error("ElidedOutOfRange: restored verb {index} (repeating {verb:?}) in a sentence of {len} tokens")]
+    ElidedOutOfRange { index: usize, verb: Option<usize>, len: usize },
+    #[error("PlacementOutOfRange: adjective {adjective} of noun {noun} in a sentence of {len} tokens")]
+    PlacementOutOfRange { adjective: usize, noun: usize, len: usize },
+}
+
+impl PipelineError {
+    /// Stable category identifier (matches the message prefix).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PipelineError::DependencyOutOfRange { .. } => "DependencyOutOfRange",
+            PipelineError::ElidedOutOfRange { .. } => "ElidedOutOfRange",
+            PipelineError::PlacementOutOfRange { .. } => "PlacementOutOfRange",
+        }
+    }
+}
+
+impl EngineError {
+    /// Stable category identifier (matches the message prefix).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EngineError::InvalidDictionary(_) => "InvalidDictionary",
+            EngineError::InvalidSemantics(_) => "InvalidSemantics",
+            EngineError::InvalidModel(_) => "InvalidModel",
+            EngineError::SerializationFailed(_) => "SerializationFailed",
+            EngineError::EmptyInput => "EmptyInput",
+            EngineError::InvalidOptions(_) => "InvalidOptions",
+            EngineError::NoBatch => "NoBatch",
+            EngineError::InvalidCorpus(_) => "InvalidCorpus",
+            EngineError::Unavailable(_) => "Unavailable",
+            EngineError::Pipeline(e) => e.kind(),
+        }
+    }
+}
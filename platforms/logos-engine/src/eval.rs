@@ -0,0 +1,372 @@
+//! Scoring against gold treebanks (PROIEL, Perseus UD) in CoNLL-U.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+use logos_protocol::MorphFlags;
+
+use crate::export::attachment;
+use crate::{AnalysisReport, EngineError};
+
+/// One word line of a CoNLL-U file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldToken {
+    pub form: String,
+    pub lemma: String,
+    pub upos: String,
+    /// Treebank-specific tag; the 9-character positional tag in Perseus data
+    pub xpos: String,
+    /// Raw FEATS column (`Case=Nom|Number=Sing`), `_` when empty
+    pub feats: String,
+    /// 1-based, 0 for the root
+    pub head: usize,
+    pub deprel: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GoldSentence {
+    /// `# sent_id` comment, if present
+    pub id: Option<String>,
+    pub tokens: Vec<GoldToken>,
+}
+
+impl GoldSentence {
+    /// The word forms joined by spaces, as handed to the engine.
+    pub fn text(&self) -> String {
+        self.tokens.iter().map(|t| t.form.as_str()).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Reads the sentences of a CoNLL-U document. Multiword-token ranges (`1-2`)
+/// and empty nodes (`1.1`) are skipped; their words are listed separately.
+pub fn read_conllu(text: &str) -> Result<Vec<GoldSentence>, EngineError> {
+    let mut sentences = Vec::new();
+    let mut current = GoldSentence::default();
+
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            if !current.tokens.is_empty() {
+                sentences.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            if let Some((key, value)) = comment.split_once('=') {
+                if key.trim() == "sent_id" {
+                    current.id = Some(value.trim().to_string());
+                }
+            }
+            continue;
+        }
+
+        let columns: Vec<&str> = line.split('\t').collect();
+        if columns.len() != 10 {
+            return Err(EngineError::InvalidCorpus(format!("line {}: expected 10 columns, found {}", n + 1, columns.len())));
+        }
+        if columns[0].contains(['-', '.']) {
+            continue;
+        }
+        let head = columns[6]
+            .parse()
+            .map_err(|_| EngineError::InvalidCorpus(format!("line {}: HEAD {:?} is not a number", n + 1, columns[6])))?;
+        current.tokens.push(GoldToken {
+            form: columns[1].to_string(),
+            lemma: columns[2].to_string(),
+            upos: columns[3].to_string(),
+            xpos: columns[4].to_string(),
+            feats: columns[5].to_string(),
+            head,
+            deprel: columns[7].to_string(),
+        });
+    }
+    if !current.tokens.is_empty() {
+        sentences.push(current);
+    }
+    Ok(sentences)
+}
+
+/// Attachment scores of the parser against a gold treebank
+/// (see `LogosEngine::evaluate_parser_core`). Punctuation is not scored.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ParserEval {
+    /// Sentences scored
+    pub sentences: usize,
+    /// Sentences the engine tokenized differently from the gold, left out
+    pub skipped: usize,
+    /// Scored tokens
+    pub tokens: usize,
+    /// Percentage of tokens with the gold head
+    pub uas: f64,
+    /// Percentage of tokens with the gold head and relation
+    pub las: f64,
+    /// Per gold relation, most frequent first
+    pub relations: Vec<RelationScore>,
+}
+
+/// Relations are compared without their subtype (`obl:agent` counts as `obl`).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct RelationScore {
+    pub deprel: String,
+    /// Gold tokens with this relation
+    pub gold: usize,
+    pub uas: f64,
+    pub las: f64,
+}
+
+fn main_relation(deprel: &str) -> &str {
+    deprel.split(':').next().unwrap_or(deprel)
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+/// Running totals while a treebank is scored.
+#[derive(Default)]
+pub(crate) struct ParserTally {
+    sentences: usize,
+    skipped: usize,
+    /// relation -> (gold, right head, right head and relation)
+    relations: BTreeMap<String, (usize, usize, usize)>,
+}
+
+impl ParserTally {
+    pub(crate) fn add(&mut self, gold: &GoldSentence, report: &AnalysisReport) {
+        if report.tokens.len() != gold.tokens.len() {
+            self.skipped += 1;
+            return;
+        }
+        self.sentences += 1;
+
+        for (i, expected) in gold.tokens.iter().enumerate() {
+            if expected.upos == "PUNCT" {
+                continue;
+            }
+            let (head, deprel) = attachment(report, i);
+            let relation = main_relation(&expected.deprel);
+            let entry = self.relations.entry(relation.to_string()).or_default();
+            entry.0 += 1;
+            if head == expected.head {
+                entry.1 += 1;
+                if main_relation(deprel) == relation {
+                    entry.2 += 1;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> ParserEval {
+        let (mut tokens, mut attached, mut labeled) = (0, 0, 0);
+        let mut relations = Vec::with_capacity(self.relations.len());
+        for (deprel, (gold, right_head, right_label)) in self.relations {
+            tokens += gold;
+            attached += right_head;
+            labeled += right_label;
+            relations.push(RelationScore { deprel, gold, uas: percent(right_head, gold), las: percent(right_label, gold) });
+        }
+        relations.sort_by_key(|r| std::cmp::Reverse(r.gold));
+
+        ParserEval {
+            sentences: self.sentences,
+            skipped: self.skipped,
+            tokens,
+            uas: percent(attached, tokens),
+            las: percent(labeled, tokens),
+            relations,
+        }
+    }
+}
+
+/// Accuracy of `analyze_token` against gold tags
+/// (see `LogosEngine::evaluate_morphology_core`). Punctuation is not scored.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct MorphologyEval {
+    pub sentences: usize,
+    /// Scored tokens
+    pub tokens: usize,
+    /// Tokens no dictionary resolved; their features count as wrong
+    pub unknown: usize,
+    /// In the order of `FEATURES`
+    pub features: Vec<FeatureScore>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct FeatureScore {
+    /// UD feature name (`Case`, `Number`, ...)
+    pub feature: String,
+    /// Tokens whose gold tag carries the feature
+    pub scored: usize,
+    pub correct: usize,
+    pub accuracy: f64,
+    /// Non-empty cells of the gold × predicted matrix; `_` stands for a
+    /// prediction without the feature
+    pub confusion: Vec<Confusion>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct Confusion {
+    pub gold: String,
+    pub predicted: String,
+    pub count: usize,
+}
+
+/// A feature `MorphFlags` can express, with its UD values and the slot it
+/// takes in a Perseus positional tag.
+struct Feature {
+    name: &'static str,
+    values: &'static [(&'static str, MorphFlags)],
+    slot: usize,
+    /// Positional codes per UD value; codes missing here are not scored
+    codes: &'static [(char, &'static str)],
+}
+
+const FEATURES: [Feature; 6] = [
+    Feature {
+        name: "Case",
+        values: &[
+            ("Nom", MorphFlags::NOMINATIVE),
+            ("Gen", MorphFlags::GENITIVE),
+            ("Dat", MorphFlags::DATIVE),
+            ("Acc", MorphFlags::ACCUSATIVE),
+            ("Voc", MorphFlags::VOCATIVE),
+        ],
+        slot: 7,
+        codes: &[('n', "Nom"), ('g', "Gen"), ('d', "Dat"), ('a', "Acc"), ('v', "Voc")],
+    },
+    Feature {
+        name: "Number",
+        values: &[("Sing", MorphFlags::SINGULAR), ("Plur", MorphFlags::PLURAL)],
+        slot: 2,
+        codes: &[('s', "Sing"), ('p', "Plur")],
+    },
+    Feature {
+        name: "Gender",
+        values: &[("Masc", MorphFlags::MASCULINE), ("Fem", MorphFlags::FEMININE), ("Neut", MorphFlags::NEUTER)],
+        slot: 6,
+        codes: &[('m', "Masc"), ('f', "Fem"), ('n', "Neut")],
+    },
+    Feature {
+        name: "Person",
+        values: &[("1", MorphFlags::FIRST_PERSON), ("2", MorphFlags::SECOND_PERSON), ("3", MorphFlags::THIRD_PERSON)],
+        slot: 1,
+        codes: &[('1', "1"), ('2', "2"), ('3', "3")],
+    },
+    // Imperfect, aorist, perfect and pluperfect all fall under PAST
+    Feature {
+        name: "Tense",
+        values: &[("Pres", MorphFlags::PRESENT), ("Past", MorphFlags::PAST), ("Fut", MorphFlags::FUTURE)],
+        slot: 3,
+        codes: &[('p', "Pres"), ('i', "Past"), ('a', "Past"), ('r', "Past"), ('l', "Past"), ('f', "Fut"), ('t', "Fut")],
+    },
+    // Middle forms have no flag of their own and are not scored
+    Feature {
+        name: "Voice",
+        values: &[("Act", MorphFlags::ACTIVE), ("Pass", MorphFlags::PASSIVE)],
+        slot: 5,
+        codes: &[('a', "Act"), ('p', "Pass"), ('e', "Pass")],
+    },
+];
+
+impl Feature {
+    /// The gold value, from FEATS or else from a Perseus positional tag.
+    fn gold(&self, token: &GoldToken) -> Option<&'static str> {
+        if token.feats != "_" {
+            let value = token.feats.split('|').find_map(|f| f.strip_prefix(self.name)?.strip_prefix('='))?;
+            let value = match (self.name, value) {
+                ("Tense", "Pqp" | "Imp") => "Past",
+                _ => value,
+            };
+            return self.values.iter().find(|(name, _)| *name == value).map(|(name, _)| *name);
+        }
+        let code = token.xpos.chars().nth(self.slot).filter(|_| token.xpos.chars().count() == 9)?;
+        self.codes.iter().find(|(c, _)| *c == code).map(|(_, name)| *name)
+    }
+
+    /// The predicted value, `_` when the flags carry none of it.
+    fn predicted(&self, flags: MorphFlags) -> &'static str {
+        self.values.iter().find(|(_, flag)| flags.contains(*flag)).map_or("_", |(name, _)| *name)
+    }
+}
+
+/// The gold tag of `token` as `MorphFlags`: part of speech from UPOS plus
+/// every feature `FEATURES` knows, for training on gold morphology.
+pub fn gold_flags(token: &GoldToken) -> MorphFlags {
+    let pos = match token.upos.as_str() {
+        "NOUN" | "PROPN" => MorphFlags::NOUN,
+        "ADJ" => MorphFlags::ADJECTIVE,
+        "DET" => MorphFlags::ARTICLE,
+        "ADP" => MorphFlags::PREPOSITION,
+        "CCONJ" | "SCONJ" => MorphFlags::CONJUNCTION,
+        "PRON" => MorphFlags::PRONOUN,
+        "NUM" => MorphFlags::NUMERAL,
+        "VERB" | "AUX" => match token.feats.split('|').find_map(|f| f.strip_prefix("VerbForm=")) {
+            Some("Part") => MorphFlags::PARTICIPLE,
+            Some("Inf") => MorphFlags::INFINITIVE,
+            _ => MorphFlags::VERB,
+        },
+        _ => MorphFlags::empty(),
+    };
+    FEATURES.iter().fold(pos, |flags, feature| {
+        let value = feature.gold(token);
+        flags | feature.values.iter().find(|(name, _)| Some(*name) == value).map_or(MorphFlags::empty(), |(_, flag)| *flag)
+    })
+}
+
+/// Running totals while a corpus is scored for morphology.
+#[derive(Default)]
+pub(crate) struct MorphologyTally {
+    tokens: usize,
+    unknown: usize,
+    /// Per feature: (gold, predicted) -> count
+    cells: [BTreeMap<(&'static str, &'static str), usize>; FEATURES.len()],
+}
+
+impl MorphologyTally {
+    /// `flags` is None when the token did not resolve to a lemma.
+    pub(crate) fn add(&mut self, gold: &GoldToken, flags: Option<MorphFlags>) {
+        if gold.upos == "PUNCT" {
+            return;
+        }
+        self.tokens += 1;
+        if flags.is_none() {
+            self.unknown += 1;
+        }
+        for (feature, cells) in FEATURES.iter().zip(self.cells.iter_mut()) {
+            let Some(expected) = feature.gold(gold) else { continue };
+            let predicted = flags.map_or("_", |flags| feature.predicted(flags));
+            *cells.entry((expected, predicted)).or_default() += 1;
+        }
+    }
+
+    pub(crate) fn finish(self, sentences: usize) -> MorphologyEval {
+        let features = FEATURES.iter().zip(self.cells).map(|(feature, cells)| {
+            let scored = cells.values().sum();
+            let correct = cells.iter().filter(|((gold, predicted), _)| gold == predicted).map(|(_, n)| n).sum();
+            FeatureScore {
+                feature: feature.name.to_string(),
+                scored,
+                correct,
+                accuracy: percent(correct, scored),
+                confusion: cells
+                    .into_iter()
+                    .map(|((gold, predicted), count)| Confusion { gold: gold.to_string(), predicted: predicted.to_string(), count })
+                    .collect(),
+            }
+        }).collect();
+
+        MorphologyEval { sentences, tokens: self.tokens, unknown: self.unknown, features }
+    }
+}
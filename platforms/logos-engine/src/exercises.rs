@@ -0,0 +1,201 @@
+//! Drills built from an analyzed sentence (see
+//! `LogosEngine::exercises_core`): a verb blanked out to be filled in, and
+//! words to put in the passive or the plural. Every answer key is generated
+//! from the word's own paradigm, so it can be checked by comparison.
+//!
+//! Forms a student types for a lemma are checked with feedback on what is
+//! off (see `LogosEngine::check_form_core`): the accents, the ending, or
+//! the stem itself.
+
+use std::borrow::Cow;
+
+use logos_parser::normalize::{fold_accents, fold_case, iota_subscript};
+use logos_protocol::MorphFlags;
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{AnalysisReport, MorphologyFormat, TokenSpan};
+
+/// What stands in a cloze prompt for the word to supply.
+pub const BLANK: &str = "_____";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+#[serde(rename_all = "snake_case")]
+pub enum ExerciseKind {
+    /// Supply the blanked-out verb from its lemma and morphology
+    Cloze,
+    /// Put an active verb in the passive
+    Passive,
+    /// Put a singular word in the plural
+    Plural,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct Exercise {
+    pub kind: ExerciseKind,
+    /// Index of the word in the sentence's tokens
+    pub token: usize,
+    /// `[start, end]` byte span of the word in the sentence
+    pub span: [usize; 2],
+    /// The sentence, the word replaced by `BLANK` in a cloze
+    pub prompt: String,
+    /// The word as the sentence has it
+    pub word: String,
+    /// Headword of the word's lemma
+    pub lemma: String,
+    /// Morphology of the form asked for, in `AnalysisOptions::morphology_format`
+    pub morphology: String,
+    /// Accepted answers
+    pub answers: Vec<String>,
+}
+
+impl Exercise {
+    /// Whether `response` is one of the `answers` (surrounding space aside).
+    pub fn check(&self, response: &str) -> bool {
+        self.answers.iter().any(|answer| answer == response.trim())
+    }
+}
+
+/// The drills of a sentence whose tokens (`report.tokens`, `spans`) have
+/// the chosen analyses `flags`. `inflect` gives the form of token `i`'s
+/// lemma with other flags, as its paradigm spells it.
+pub(crate) fn drills(
+    sentence: &str,
+    report: &AnalysisReport,
+    spans: &[TokenSpan],
+    flags: &[MorphFlags],
+    format: MorphologyFormat,
+    inflect: impl Fn(usize, MorphFlags) -> Option<String>,
+) -> Vec<Exercise> {
+    let mut exercises = Vec::new();
+    for (i, ((token, span), &flags)) in report.tokens.iter().zip(spans).zip(flags).enumerate() {
+        let Some(lemma) = &token.lemma else { continue };
+        let drill = |kind, target: MorphFlags, prompt: String, answer: String| Exercise {
+            kind,
+            token: i,
+            span: [span.start, span.end],
+            prompt,
+            word: token.text.clone(),
+            lemma: lemma.clone(),
+            morphology: format.render(target),
+            answers: vec![answer],
+        };
+
+        let finite = flags.contains(MorphFlags::VERB) && !flags.intersects(MorphFlags::PARTICIPLE | MorphFlags::INFINITIVE);
+        // Only forms the paradigm gives back are asked for
+        if finite && inflect(i, flags).as_deref() == Some(token.text.as_str()) {
+            let prompt = format!("{}{}{}", &sentence[..span.start], BLANK, &sentence[span.end..]);
+            exercises.push(drill(ExerciseKind::Cloze, flags, prompt, token.text.clone()));
+        }
+        if finite && flags.contains(MorphFlags::ACTIVE) {
+            let target = flags.difference(MorphFlags::ACTIVE) | MorphFlags::PASSIVE;
+            if let Some(form) = inflect(i, target) {
+                exercises.push(drill(ExerciseKind::Passive, target, sentence.to_string(), form));
+            }
+        }
+        if flags.contains(MorphFlags::SINGULAR) {
+            let target = flags.difference(MorphFlags::SINGULAR) | MorphFlags::PLURAL;
+            if let Some(form) = inflect(i, target) {
+                exercises.push(drill(ExerciseKind::Plural, target, sentence.to_string(), form));
+            }
+        }
+    }
+    exercises
+}
+
+/// How a student's answer compares with the form asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+#[serde(rename_all = "snake_case")]
+pub enum FormVerdict {
+    /// One of the accepted forms
+    Correct,
+    /// An accepted form but for its accents or breathings
+    WrongAccent,
+    /// The lemma's stem with another ending
+    WrongEnding,
+    /// Not built on the lemma's stem
+    WrongStem,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct FormCheck {
+    pub verdict: FormVerdict,
+    /// The accepted form nearest the answer
+    pub expected: String,
+    /// Every form the lemma's paradigms give for the morphology asked for
+    pub accepted: Vec<String>,
+    /// Stem of `expected`; empty for pronouns, whose forms are listed whole
+    pub stem: String,
+    /// Morphology of the answer when it is another form of the lemma, in
+    /// `AnalysisOptions::morphology_format`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub found: Option<String>,
+}
+
+impl FormCheck {
+    /// Whether the answer is one of the `accepted` forms.
+    pub fn is_correct(&self) -> bool {
+        self.verdict == FormVerdict::Correct
+    }
+}
+
+/// `text` as compared: composed, lowercase, iota adscripts written as
+/// subscripts.
+fn spelled(text: &str) -> String {
+    let composed: String = text.trim().nfc().collect();
+    fold_case(&iota_subscript(&composed)).into_owned()
+}
+
+/// `spelled` without accents and breathings.
+fn plain(text: &str) -> String {
+    let spelled = spelled(text);
+    match fold_accents(&spelled) {
+        Cow::Borrowed(_) => spelled,
+        Cow::Owned(folded) => folded,
+    }
+}
+
+/// Checks `answer` against the `accepted` (stem, form) pairs. `paradigm`
+/// lists (morphology, form) of every form of the lemma, to name the one
+/// given instead. None when nothing is accepted.
+pub(crate) fn check_form<'a>(
+    answer: &str,
+    accepted: &[(String, String)],
+    paradigm: impl IntoIterator<Item = (&'a str, &'a str)> + Clone,
+) -> Option<FormCheck> {
+    let (answer_spelled, answer_plain) = (spelled(answer), plain(answer));
+    let exact = accepted.iter().find(|(_, form)| spelled(form) == answer_spelled);
+    let unaccented = || accepted.iter().find(|(_, form)| plain(form) == answer_plain);
+    let (verdict, (stem, expected)) = if let Some(hit) = exact {
+        (FormVerdict::Correct, hit)
+    } else if let Some(hit) = unaccented() {
+        (FormVerdict::WrongAccent, hit)
+    } else if let Some(hit) = accepted.iter().find(|(stem, _)| !stem.is_empty() && answer_plain.starts_with(&plain(stem))) {
+        (FormVerdict::WrongEnding, hit)
+    } else {
+        let found = paradigm.clone().into_iter().any(|(_, form)| plain(form) == answer_plain);
+        // A pronoun's other forms share no stem with it but are still its own
+        (if found { FormVerdict::WrongEnding } else { FormVerdict::WrongStem }, accepted.first()?)
+    };
+
+    let found = (verdict != FormVerdict::Correct).then(|| {
+        let forms = || paradigm.clone().into_iter();
+        forms().find(|(_, form)| spelled(form) == answer_spelled)
+            .or_else(|| forms().find(|(_, form)| plain(form) == answer_plain))
+            .map(|(morphology, _)| morphology.to_string())
+    }).flatten();
+    Some(FormCheck {
+        verdict,
+        expected: expected.clone(),
+        accepted: accepted.iter().map(|(_, form)| form.clone()).collect(),
+        stem: stem.clone(),
+        found,
+    })
+}
@@ -0,0 +1,202 @@
+//! Serializers for analysis reports in formats consumed outside the web UI.
+
+use std::fmt::Write;
+
+use logos_protocol::{compact_tag, PartOfSpeech};
+
+use crate::report::{AnalysisReport, TokenDebug};
+use crate::{EngineError, MorphologyFormat};
+
+/// Renders a report as one CoNLL-U sentence block (terminated by a blank line).
+/// Words without a head attach to the root (HEAD 0), and punctuation
+/// without one to the nearest of those words. Restored verbs are
+/// not written: one of their dependents is promoted to take their place,
+/// as UD does for ellipsis.
+pub fn to_conllu(report: &AnalysisReport) -> String {
+    let mut out = String::new();
+    let text: Vec<&str> = report.tokens.iter().map(|t| t.text.as_str()).collect();
+    let _ = writeln!(out, "# text = {}", text.join(" "));
+
+    for (i, token) in report.tokens.iter().enumerate() {
+        let (head, deprel) = attachment(report, i);
+        let _ = writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t_\t_\t{}\t{}\t_\t_",
+            i + 1,
+            token.text,
+            token.lemma.as_deref().unwrap_or("_"),
+            upos(token),
+            head,
+            deprel,
+        );
+    }
+    out.push('\n');
+    out
+}
+
+/// Renders a report as one TEI `<s>` element with the XML id `id`: words
+/// as `<w>` with `@lemma` and their morphology tag in `@ana` ("N-GSM"),
+/// punctuation and sigla as `<pc>`, and the dependencies as a standoff
+/// `<linkGrp>` of UD relations, each `<link>` pointing at its head (the
+/// `<s>` itself for the root) and its dependent. Words are numbered
+/// `<id>.w1`, `<id>.w2`, … Morphology written as flag names (the default
+/// format) is given as its tag, the other formats as they are. `id` must
+/// be an XML name (a letter or `_`, then letters, digits, `_`, `-` or `.`),
+/// else the call fails with `InvalidOptions`.
+pub fn to_tei(report: &AnalysisReport, id: &str) -> Result<String, EngineError> {
+    if !is_ncname(id) {
+        return Err(EngineError::InvalidOptions(format!("'{}' is not an XML name", id)));
+    }
+    let mut out = String::new();
+    let _ = writeln!(out, "<s xml:id=\"{}\">", id);
+    for (i, token) in report.tokens.iter().enumerate() {
+        let element = if is_punct(token) { "pc" } else { "w" };
+        let _ = write!(out, "  <{} xml:id=\"{}.w{}\"", element, id, i + 1);
+        if let Some(lemma) = &token.lemma {
+            let _ = write!(out, " lemma=\"{}\"", escape(lemma));
+        }
+        if let Some(tag) = tag(token) {
+            let _ = write!(out, " ana=\"{}\"", escape(&tag));
+        }
+        let _ = writeln!(out, ">{}</{}>", escape(&token.text), element);
+    }
+
+    let _ = writeln!(out, "  <linkGrp type=\"UD-SYN\" targFunc=\"head argument\">");
+    for i in 0..report.tokens.len() {
+        let (head, deprel) = attachment(report, i);
+        let head = if head == 0 { id.to_string() } else { format!("{}.w{}", id, head) };
+        let _ = writeln!(out, "    <link ana=\"ud-syn:{}\" target=\"#{} #{}.w{}\"/>", deprel, head, id, i + 1);
+    }
+    out.push_str("  </linkGrp>\n</s>\n");
+    Ok(out)
+}
+
+/// Whether `id` can stand as an `xml:id`: an XML name without a colon.
+fn is_ncname(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// The token's morphology as `@ana` gives it; None when it has none.
+fn tag(token: &TokenDebug) -> Option<String> {
+    match MorphologyFormat::parse_flags(&token.morphology) {
+        Some(flags) if flags.is_empty() => None,
+        Some(flags) => Some(compact_tag(flags)),
+        None if token.morphology == "_" => None,
+        None => Some(token.morphology.clone()),
+    }
+}
+
+/// `text` with the characters XML reserves written as entities.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 1-based HEAD and UD DEPREL of token `i`, as written by `to_conllu`.
+pub(crate) fn attachment(report: &AnalysisReport, i: usize) -> (usize, &'static str) {
+    let token = &report.tokens[i];
+    match report.dependencies.iter().find(|d| d.dependent == i) {
+        Some(d) if d.head >= report.tokens.len() => promoted(report, d.head, i),
+        Some(d) => (d.head + 1, deprel(&d.role, token)),
+        None if is_punct(token) => (nearest_root(report, i).map_or(0, |j| j + 1), "punct"),
+        None => (0, "root"),
+    }
+}
+
+/// The word closest to token `i` that attaches to the root, the one before
+/// it on a tie (a full stop closes the sentence before it); None when the
+/// report has no such word.
+fn nearest_root(report: &AnalysisReport, i: usize) -> Option<usize> {
+    let len = report.tokens.len();
+    let is_root = |j: usize| !is_punct(&report.tokens[j]) && attachment(report, j).0 == 0;
+    (1..len).flat_map(|d| [i.checked_sub(d), Some(i + d).filter(|&j| j < len)])
+        .flatten()
+        .find(|&j| is_root(j))
+}
+
+/// Attachment of token `i`, a dependent of the restored verb `verb`: the
+/// predicate of a copula (else its first dependent) takes the verb's place
+/// as the root; the first dependent of a gapped verb is a `conj` of the verb
+/// it repeats, and the others its `orphan`s.
+fn promoted(report: &AnalysisReport, verb: usize, i: usize) -> (usize, &'static str) {
+    let dependents: Vec<_> = report.dependencies.iter()
+        .filter(|d| d.head == verb && d.dependent < report.tokens.len())
+        .collect();
+    let antecedent = report.reconstructed.get(verb - report.tokens.len()).and_then(|r| r.antecedent);
+    let Some(first) = dependents.iter()
+        .find(|d| antecedent.is_none() && d.role == "Complement")
+        .or(dependents.iter().min_by_key(|d| d.dependent))
+    else {
+        return (0, "root");
+    };
+    match antecedent {
+        _ if first.dependent != i => {
+            let role = dependents.iter().find(|d| d.dependent == i).map_or("", |d| d.role.as_str());
+            (first.dependent + 1, if antecedent.is_some() { "orphan" } else { deprel(role, &report.tokens[i]) })
+        }
+        Some(verb) => (verb + 1, "conj"),
+        None => (0, "root"),
+    }
+}
+
+pub(crate) fn is_punct(token: &TokenDebug) -> bool {
+    token.kind == "Punctuation" || token.kind == "Editorial"
+}
+
+fn upos(token: &TokenDebug) -> &'static str {
+    if is_punct(token) {
+        return "PUNCT";
+    }
+    if token.kind == "Numeral" {
+        return "NUM";
+    }
+    match token.pos {
+        Some(PartOfSpeech::Noun) => "NOUN",
+        Some(PartOfSpeech::Adjective) => "ADJ",
+        Some(PartOfSpeech::Verb) => "VERB",
+        Some(PartOfSpeech::Adverb) => "ADV",
+        Some(PartOfSpeech::Article) => "DET",
+        Some(PartOfSpeech::Preposition) => "ADP",
+        Some(PartOfSpeech::Conjunction) => "CCONJ",
+        Some(PartOfSpeech::Pronoun) => "PRON",
+        Some(PartOfSpeech::Particle) => "PART",
+        Some(PartOfSpeech::Numeral) => "NUM",
+        Some(PartOfSpeech::ProperNoun) => "PROPN",
+        None => "X",
+    }
+}
+
+/// Maps the parser's SyntaxRole names onto Universal Dependencies relations.
+fn deprel(role: &str, token: &TokenDebug) -> &'static str {
+    match role {
+        "Subject" => "nsubj",
+        "Object" => "obj",
+        "IndirectObject" => "iobj",
+        "Modifier" => match token.pos {
+            Some(PartOfSpeech::Article) => "det",
+            Some(PartOfSpeech::Adjective) => "amod",
+            _ => "nmod",
+        },
+        "Root" => "root",
+        "PrepositionArg" => "pobj",
+        "Coordinator" => "cc",
+        "Conjunct" => "conj",
+        "PassiveAgent" => "obl:agent",
+        "AbsoluteClause" => "advcl:abs",
+        "Complement" => "xcomp",
+        "RelativeClause" => "acl:relcl",
+        "Comparison" => "obl",
+        _ => "dep",
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(feature = "syntax")]
+use std::collections::BTreeMap;
+
+#[cfg(feature = "syntax")]
+use logos_parser::syntax::Dependency;
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+/// The full ambiguity of an analysis, for external decoders
+/// (see `LogosEngine::analyze_lattice_core`): every licensed analysis per
+/// token and every dependency some reading of the sentence produces.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct Lattice {
+    pub tokens: Vec<LatticeToken>,
+    /// Sorted by dependent, then by descending score
+    pub arcs: Vec<LatticeArc>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct LatticeToken {
+    pub text: String,
+    pub kind: String,
+    /// Empty for punctuation, numerals and editorial sigla
+    pub analyses: Vec<LatticeAnalysis>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct LatticeAnalysis {
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub lemma_id: Option<u32>,
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub lemma: Option<String>,
+    pub morphology: String,
+    /// Raw `MorphFlags` bits
+    pub flags: u32,
+    /// The analysis the collapsed report picked
+    pub chosen: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct LatticeArc {
+    pub head: usize,
+    pub dependent: usize,
+    pub role: String,
+    /// Share (0..1) of the parses containing the arc, over the chosen
+    /// reading and every reading that swaps one token's analysis
+    pub score: f64,
+    /// Part of the collapsed report's tree
+    pub chosen: bool,
+}
+
+/// Counts arcs over the parses of every reading; `best` is the chosen tree.
+#[cfg(feature = "syntax")]
+#[derive(Default)]
+pub(crate) struct ArcTally {
+    parses: usize,
+    /// (dependent, head, role) -> parses containing it
+    counts: BTreeMap<(usize, usize, String), usize>,
+}
+
+#[cfg(feature = "syntax")]
+impl ArcTally {
+    pub(crate) fn add(&mut self, deps: Vec<Dependency>, offset: usize) {
+        self.parses += 1;
+        for dep in deps {
+            let key = (dep.dependent_index + offset, dep.head_index + offset, format!("{:?}", dep.role));
+            *self.counts.entry(key).or_default() += 1;
+        }
+    }
+
+    /// Arcs of this sentence, merged into `arcs`; the tally is reset.
+    pub(crate) fn drain(&mut self, best: &[(usize, usize, String)], arcs: &mut Vec<LatticeArc>) {
+        let parses = self.parses.max(1) as f64;
+        let mut sentence: Vec<LatticeArc> = std::mem::take(&mut self.counts)
+            .into_iter()
+            .map(|((dependent, head, role), n)| {
+                let chosen = best.iter().any(|(d, h, r)| *d == dependent && *h == head && *r == role);
+                LatticeArc { head, dependent, role, score: n as f64 / parses, chosen }
+            })
+            .collect();
+        sentence.sort_by(|a, b| a.dependent.cmp(&b.dependent).then(b.score.total_cmp(&a.score)));
+        arcs.extend(sentence);
+        self.parses = 0;
+    }
+}
@@ -0,0 +1,87 @@
+use logos_protocol::{DictionaryStats, Gender, NameKind, PartOfSpeech};
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+/// A dictionary entry that licenses a looked-up word form.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct LemmaMatch {
+    /// Namespaced id (see `LemmaId::namespace`)
+    pub lemma_id: u32,
+    pub headword: String,
+    #[cfg_attr(feature = "tsify", tsify(type = "string"))]
+    pub pos: PartOfSpeech,
+    #[cfg_attr(feature = "tsify", tsify(type = "string"))]
+    pub gender: Gender,
+    /// Index of the dictionary in the stack (0 = base)
+    pub dictionary: u8,
+    /// Gazetteer classification, for listed names
+    #[cfg_attr(feature = "tsify", tsify(optional, type = "string"))]
+    pub name: Option<NameKind>,
+    /// External link of the lemma (see `Lemma::urn`)
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub urn: Option<String>,
+    /// Flags of the form as analyzed
+    pub morphology: String,
+    pub stem: String,
+}
+
+/// A lemma as listed, for lookups by ID or headword prefix.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct LemmaEntry {
+    /// Namespaced id (see `LemmaId::namespace`)
+    pub lemma_id: u32,
+    pub headword: String,
+    #[cfg_attr(feature = "tsify", tsify(type = "string"))]
+    pub pos: PartOfSpeech,
+    #[cfg_attr(feature = "tsify", tsify(type = "string"))]
+    pub gender: Gender,
+    /// Index of the dictionary in the stack (0 = base)
+    pub dictionary: u8,
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub urn: Option<String>,
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub gloss: Option<String>,
+}
+
+/// Every form a paradigm generates for one stem.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ParadigmTable {
+    pub lemma_id: u32,
+    pub paradigm_id: u32,
+    pub stem: String,
+    pub forms: Vec<ParadigmForm>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ParadigmForm {
+    pub morphology: String,
+    /// Raw `MorphFlags` bits of `morphology`
+    pub flags: u32,
+    pub form: String,
+}
+
+/// Build metadata of one loaded dictionary (see `logos_protocol::BuildInfo`).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct DictionaryInfo {
+    /// Index in the stack (0 = base), i.e. its LemmaId namespace
+    pub dictionary: u8,
+    pub version: u32,
+    /// 16 hex digits; a string because JS numbers cannot hold a u64
+    pub content_hash: String,
+    /// Unix seconds; 0 if unknown
+    pub built_at: u64,
+    pub sources: Vec<String>,
+    pub lemma_count: u32,
+    pub paradigm_count: u32,
+    /// Size of the decompressed archive
+    pub bytes: usize,
+    /// Lemmas by part of speech, paradigm sizes and the suffix inventory
+    #[cfg_attr(feature = "tsify", tsify(type = "{ lemmas: number; lemmas_by_pos: { pos: string; lemmas: number }[]; paradigms: number; paradigm_sizes: { endings: number; paradigms: number }[]; suffixes: { suffix: string; cells: number }[]; pronoun_forms: number; names: number; transitions: number; surface_forms: number; pool_bytes: number }"))]
+    pub stats: DictionaryStats,
+}
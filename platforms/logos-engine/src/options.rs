@@ -0,0 +1,192 @@
+use serde::Deserialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+#[cfg(feature = "ecs")]
+use logos_ecs::systems::definiteness::Strictness;
+use logos_parser::morphology::MatchOptions;
+use logos_parser::TokenizerConfig;
+use logos_protocol::{compact_tag, Dialect, Locale, MorphFeatures, MorphFlags, Period};
+
+use crate::suppress::IgnoreRule;
+
+/// Per-call knobs for `LogosEngine::analyze`, passed from JS as a plain object.
+/// Every field is optional; omitted fields fall back to the full pipeline.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+#[serde(default)]
+pub struct AnalysisOptions {
+    /// Dependency parser to run. `none` stops after morphology.
+    pub parser: ParserKind,
+    /// Skip the semantic solver even when a graph is loaded.
+    pub skip_semantics: bool,
+    /// Diagnostic families to report.
+    pub diagnostics: Vec<DiagnosticKind>,
+    /// Findings to leave out, by `id` or by code and span.
+    pub ignore: Vec<IgnoreRule>,
+    /// Opener of the line comments read for `logos-ignore` directives
+    /// (`// logos-ignore agreement.number`); empty to read none.
+    pub ignore_comment: String,
+    /// Language of diagnostic messages: `"en"` (default) or `"el"`
+    /// (Modern Greek).
+    #[cfg_attr(feature = "tsify", tsify(type = "\"en\" | \"el\""))]
+    pub locale: Locale,
+    /// How eagerly `definiteness` diagnostics flag article use.
+    #[cfg(feature = "ecs")]
+    #[cfg_attr(feature = "tsify", tsify(type = "\"lenient\" | \"standard\" | \"strict\""))]
+    pub definiteness: Strictness,
+    /// Match word forms with accents and breathings ignored.
+    pub accent_insensitive: bool,
+    /// Dialects to read the text as (e.g. `"IONIC"` for Herodotus,
+    /// `"ATTIC | KOINE"`); their forms win over other dialects' homographs.
+    #[cfg_attr(feature = "tsify", tsify(type = "string"))]
+    pub dialects: Dialect,
+    /// Only accept forms of `dialects` (or unmarked ones) instead of preferring them.
+    pub restrict_dialect: bool,
+    /// Periods the text was written in (`"KOINE"` for the New Testament);
+    /// their forms win, and `anachronism` diagnostics flag the others.
+    #[cfg_attr(feature = "tsify", tsify(type = "string"))]
+    pub periods: Period,
+    /// Only accept forms of `periods` (or unmarked ones), e.g. to analyze with
+    /// the Koine paradigm sets alone.
+    pub restrict_period: bool,
+    /// Pick each word's analysis by the tags of its neighbours, using the
+    /// dictionaries' tag bigrams (a no-op without them).
+    pub disambiguate: bool,
+    /// Extra analyses reported per token beyond the chosen one.
+    pub max_alternatives: usize,
+    /// Close dictionary forms reported per unknown word (0 for none). Each
+    /// unknown word costs a scan of the dictionaries.
+    pub max_suggestions: usize,
+    /// How `morphology` strings are written.
+    pub morphology_format: MorphologyFormat,
+    /// Attach per-stage timings and resource sizes as `report.perf`.
+    pub collect_perf: bool,
+    /// Attach the final ECS world as `report.world`, for caching and diffing.
+    pub snapshot_world: bool,
+    /// Attach word-order statistics and hyperbata as `report.style`.
+    pub style: bool,
+    /// Fill in each token's `debug`: how its analysis was matched.
+    pub debug: bool,
+    /// Fill in each token's `normalizations`: how its spelling was changed
+    /// to look it up.
+    pub normalizations: bool,
+    /// Word characters, punctuation and hyphen rejoining of the tokenizer.
+    #[cfg_attr(feature = "tsify", tsify(type = "{ scripts?: (\"greek\" | \"latin\" | \"other\")[], keep_latin?: boolean, rejoin_hyphens?: boolean, punctuation?: string[] }"))]
+    pub tokenizer: TokenizerConfig,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            parser: ParserKind::Greedy,
+            skip_semantics: false,
+            diagnostics: vec![DiagnosticKind::Agreement, DiagnosticKind::Semantic, DiagnosticKind::Anachronism],
+            ignore: Vec::new(),
+            ignore_comment: "//".to_string(),
+            locale: Locale::English,
+            #[cfg(feature = "ecs")]
+            definiteness: Strictness::Standard,
+            accent_insensitive: false,
+            dialects: Dialect::empty(),
+            restrict_dialect: false,
+            periods: Period::empty(),
+            restrict_period: false,
+            disambiguate: true,
+            max_alternatives: 0,
+            max_suggestions: 0,
+            morphology_format: MorphologyFormat::Flags,
+            collect_perf: false,
+            snapshot_world: false,
+            style: false,
+            debug: false,
+            normalizations: false,
+            tokenizer: TokenizerConfig::default(),
+        }
+    }
+}
+
+impl AnalysisOptions {
+    /// The language profile is the engine's (`LogosEngine::with_profile`),
+    /// left at Ancient here.
+    pub fn match_options(&self) -> MatchOptions {
+        MatchOptions {
+            accent_insensitive: self.accent_insensitive,
+            dialects: self.dialects,
+            restrict_dialect: self.restrict_dialect,
+            periods: self.periods,
+            restrict_period: self.restrict_period,
+            ..Default::default()
+        }
+    }
+
+    pub fn reports(&self, kind: DiagnosticKind) -> bool {
+        self.diagnostics.contains(&kind)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+#[serde(rename_all = "snake_case")]
+pub enum ParserKind {
+    Greedy,
+    /// Learned weights; requires `LogosEngine::load_model_bytes`
+    Transition,
+    None,
+}
+
+/// Spelling of the `morphology` strings of tokens, alternatives, lattices
+/// and paradigm tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+#[serde(rename_all = "snake_case")]
+pub enum MorphologyFormat {
+    /// Flag names, as `generate` accepts them ("NOUN | GENITIVE | SINGULAR")
+    Flags,
+    /// Compact parsing code ("N-GSM")
+    Tag,
+    /// Universal Dependencies FEATS ("Case=Gen|Number=Sing")
+    Ud,
+}
+
+impl MorphologyFormat {
+    /// `flags` in this format; "None" for no flags (`_` in UD).
+    pub fn render(self, flags: MorphFlags) -> String {
+        if flags.is_empty() {
+            return if self == MorphologyFormat::Ud { "_" } else { "None" }.to_string();
+        }
+        match self {
+            MorphologyFormat::Flags => flags.iter_names().map(|(name, _)| name).collect::<Vec<_>>().join(" | "),
+            MorphologyFormat::Tag => compact_tag(flags),
+            MorphologyFormat::Ud => MorphFeatures::from(flags).ud_feats(),
+        }
+    }
+
+    /// Flags written in the `Flags` format; None if a name is unknown.
+    pub fn parse_flags(text: &str) -> Option<MorphFlags> {
+        if text.trim() == "None" {
+            return Some(MorphFlags::empty());
+        }
+        text.split('|').map(|name| MorphFlags::from_name(name.trim())).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    Agreement,
+    Semantic,
+    /// Article use (missing articles, predicative position); off by default
+    Definiteness,
+    /// Pronouns and ὁ δέ agreeing with no noun in the last sentences, as
+    /// warnings; off by default
+    Discourse,
+    /// Forms marked for other periods than `AnalysisOptions::periods`
+    /// (a no-op without it)
+    Anachronism,
+    /// Accents placed other than the analysis calls for, as warnings (for
+    /// student compositions); off by default. Misaccented words only resolve
+    /// with `accent_insensitive` or an unaccented dictionary.
+    Accent,
+}
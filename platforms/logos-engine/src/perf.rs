@@ -0,0 +1,78 @@
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+/// Per-stage wall-clock timings (milliseconds) plus the sizes of the loaded
+/// resources, attached to the report when `AnalysisOptions::collect_perf` is set.
+#[derive(Debug, Default, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct PerfReport {
+    pub tokenize_ms: f64,
+    pub morphology_ms: f64,
+    pub syntax_ms: f64,
+    pub agreement_ms: f64,
+    pub semantics_ms: f64,
+    pub total_ms: f64,
+    pub dictionary_bytes: usize,
+    pub lemma_count: usize,
+    pub paradigm_count: usize,
+    pub graph_nodes: usize,
+    pub graph_edges: usize,
+    /// Morphology cache lookups while the call ran (engine-wide, so
+    /// concurrent calls are included)
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+}
+
+/// Counters of the engine's morphology cache (see `LogosEngine::cache_stats`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub capacity: usize,
+    /// Hits over lookups, 0 before the first lookup
+    pub hit_rate: f64,
+}
+
+/// Millisecond clock used for perf timings. Hosts without `std::time`
+/// (wasm32-unknown-unknown) install their own via `LogosEngine::set_clock`.
+pub type Clock = fn() -> f64;
+
+/// Default clock backed by `std::time::Instant`.
+pub fn system_clock() -> f64 {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+/// Lap timer that reads the clock only when enabled.
+pub struct PerfTimer {
+    clock: Option<Clock>,
+    started: f64,
+    last: f64,
+}
+
+impl PerfTimer {
+    pub fn new(enabled: bool, clock: Clock) -> Self {
+        let clock = enabled.then_some(clock);
+        let now = clock.map_or(0.0, |c| c());
+        Self { clock, started: now, last: now }
+    }
+
+    /// Milliseconds since the previous lap (or since construction).
+    pub fn lap(&mut self) -> f64 {
+        let Some(clock) = self.clock else { return 0.0 };
+        let now = clock();
+        let elapsed = now - self.last;
+        self.last = now;
+        elapsed
+    }
+
+    pub fn total(&self) -> f64 {
+        self.last - self.started
+    }
+}
@@ -0,0 +1,427 @@
+#[cfg(feature = "ecs")]
+use logos_ecs::snapshot::WorldSnapshot;
+#[cfg(feature = "ecs")]
+use logos_ecs::systems::agreement::{AgreementError, AgreementEvidence};
+#[cfg(feature = "ecs")]
+use logos_ecs::systems::Diagnostic;
+#[cfg(feature = "semantics")]
+use logos_solver::SemanticError;
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(feature = "ecs")]
+use logos_protocol::messages::explanation;
+use logos_protocol::{MorphFeatures, PartOfSpeech};
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+use crate::perf::PerfReport;
+use crate::style::StyleReport;
+
+/// The structured response sent back to callers (JSON-serializable).
+/// With the `tsify` feature, a matching interface lands in the generated .d.ts.
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct TokenDebug {
+    pub text: String,
+    // serde-wasm-bindgen serializes `None` as `undefined`, hence optional
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub lemma_id: Option<u32>,
+    /// Headword of the resolved lemma
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub lemma: Option<String>,
+    /// CITE/CTS URN or lexicon reference of the lemma, for hyperlinking
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub urn: Option<String>,
+    #[cfg_attr(feature = "tsify", tsify(optional, type = "string"))]
+    pub pos: Option<PartOfSpeech>,
+    pub kind: String,
+    /// Value of a `Numeral` token
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub value: Option<u32>,
+    /// Editorial status (Restored, Supplied, Deleted, Corrupt) from the
+    /// surrounding sigla of a critical edition
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub editorial: Option<String>,
+    /// "Attributive", "Predicative" or "Bare", for adjectives of a noun
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub position: Option<String>,
+    /// Line citation, when analyzed from a `TextSource`
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub citation: Option<String>,
+    pub morphology: String,
+    /// Case, number, gender etc. of the analysis, one field per category
+    /// present (values are the enum variant names)
+    #[cfg_attr(feature = "tsify", tsify(type = "{ case?: string; person?: string; number?: string; gender?: string; tense?: string; mood?: string; voice?: string; degree?: string }"))]
+    pub features: MorphFeatures,
+    /// Dialects the matched form is marked for, when it is
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub dialect: Option<String>,
+    /// Periods the matched form is marked for, when it is
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub period: Option<String>,
+    /// How sure the analysis is, 0 to 1: the certainty of the rule that
+    /// matched it, times the disambiguation model's probability of the choice
+    pub confidence: f32,
+    /// How the analysis was matched; only with `AnalysisOptions::debug`
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub debug: Option<String>,
+    /// Spelling changes made to look the word up, in order; only with
+    /// `AnalysisOptions::normalizations`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub normalizations: Vec<NormalizationDebug>,
+    /// Other licensed analyses (capped by `AnalysisOptions::max_alternatives`)
+    pub alternatives: Vec<AlternativeDebug>,
+    /// Spelling corrections of an unknown word, closest first (capped by
+    /// `AnalysisOptions::max_suggestions`)
+    pub suggestions: Vec<SpellingSuggestion>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct AlternativeDebug {
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub lemma_id: Option<u32>,
+    pub morphology: String,
+}
+
+/// One spelling change made to a word for lookup: "ΛΟΓΟΣ" case folded
+/// to "λογος", then accent stripped, and so on.
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct NormalizationDebug {
+    #[cfg_attr(feature = "tsify", tsify(type = "\"case_folding\" | \"nfc\" | \"final_sigma\" | \"iota_subscript\" | \"accent_stripping\" | \"movable_nu\""))]
+    pub kind: String,
+    /// The word before the change
+    pub from: String,
+    /// The word after it
+    pub to: String,
+}
+
+/// A dictionary form near a misspelled word.
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct SpellingSuggestion {
+    pub form: String,
+    pub lemma_id: u32,
+    /// Weighted edit distance: 1 per accent or breathing, more for letters
+    pub cost: u32,
+}
+
+/// One arc of the dependency tree; indices point into `AnalysisReport::tokens`,
+/// and from `tokens.len()` on into `AnalysisReport::reconstructed`.
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct DependencyDebug {
+    pub head: usize,
+    pub dependent: usize,
+    pub role: String,
+    /// Citation of the dependent's line
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub citation: Option<String>,
+    /// How sure the parser is of the arc, 0 to 1
+    pub confidence: f32,
+}
+
+/// The thematic role of a verb's argument, derived from its dependency role
+/// and the verb's voice; indices point into `AnalysisReport::tokens` (a
+/// restored verb's past its end, into `reconstructed`).
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ThematicRoleDebug {
+    pub predicate: usize,
+    pub argument: usize,
+    /// `agent`, `patient`, `recipient`, `instrument` or `experiencer`
+    pub role: String,
+}
+
+/// How far the dependency tree strays from projectivity (no crossing arcs),
+/// as Greek hyperbaton makes it do.
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ProjectivityDebug {
+    /// Pairs of crossing arcs, each given by its dependent; a sentence's
+    /// root stands for its arc from the root, so an arc spanning it crosses
+    pub crossings: Vec<[usize; 2]>,
+    /// Share of the arcs (roots' included) that cross no other; 1 for a
+    /// projective tree
+    pub projective_share: f32,
+}
+
+/// A verb the text leaves out, restored by the parser to head the words of
+/// its clause.
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ReconstructedDebug {
+    pub text: String,
+    /// `copula` (of a nominal sentence) or `gapped` (a δέ clause repeating
+    /// the μέν clause's verb)
+    pub kind: String,
+    pub morphology: String,
+    /// The token a gapped verb repeats
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub antecedent: Option<usize>,
+}
+
+/// A pronoun (or ὁ δέ) and the noun it refers back to, possibly in an
+/// earlier sentence; indices point into `AnalysisReport::tokens`.
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ReferenceDebug {
+    pub anaphor: usize,
+    pub antecedent: usize,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct AnalysisReport {
+    /// Always present, whatever stage after tokenization fails
+    pub tokens: Vec<TokenDebug>,
+    /// `PipelineError` messages of the stages that failed on part of the
+    /// input, with the sentence; the rest of the report holds what the other
+    /// stages produced
+    pub pipeline_errors: Vec<String>,
+    pub dependencies: Vec<DependencyDebug>,
+    pub thematic_roles: Vec<ThematicRoleDebug>,
+    /// Resolved antecedents of pronouns; unresolved ones are left out
+    pub references: Vec<ReferenceDebug>,
+    /// Elided verbs, numbered on from `tokens.len()` in `dependencies`
+    pub reconstructed: Vec<ReconstructedDebug>,
+    pub projectivity: ProjectivityDebug,
+    /// Agreement errors, then `definiteness` warnings when requested
+    pub syntax_errors: Vec<SerializableAgreementError>,
+    /// Violated semantic constraints, numbered and ignored like
+    /// `syntax_errors`
+    pub semantic_errors: Vec<SerializableAgreementError>,
+    pub debug_info: String,
+    /// Present only when `AnalysisOptions::collect_perf` is set
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub perf: Option<PerfReport>,
+    /// Present only when `AnalysisOptions::snapshot_world` is set
+    #[cfg(feature = "ecs")]
+    #[cfg_attr(feature = "tsify", tsify(optional, type = "unknown"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world: Option<WorldSnapshot>,
+    /// Present only when `AnalysisOptions::style` is set
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<StyleReport>,
+}
+
+/// How serious a diagnostic is; warnings do not make the text wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct SerializableAgreementError {
+    pub source: String,
+    pub target: String,
+    pub message: String,
+    /// Stable kind of the finding, e.g. "agreement.number" or "accent.placement"
+    pub code: String,
+    /// Index of the token it is reported on; past `tokens`, a word in
+    /// `reconstructed`
+    pub token: usize,
+    /// Stays the same when the text is edited elsewhere, so clients can
+    /// track or suppress the finding (see `number_diagnostics`)
+    pub id: String,
+    pub severity: Severity,
+    /// Citation of the `source` token's line
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub citation: Option<String>,
+    /// Grammar topic that explains the finding, e.g. "smyth-949" (a
+    /// section of Smyth's grammar) or "verb-arguments", for linking it to
+    /// a lesson (see `logos_protocol::messages::explanation`)
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub explanation_id: Option<String>,
+    /// Corrected form of the word that breaks agreement, if the dictionaries have it
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub suggestion: Option<String>,
+    /// How sure the diagnostic is, 0 to 1: no surer than the analyses and
+    /// arcs it rests on
+    pub confidence: f32,
+    /// Rule that reported it, e.g. "subject-verb"; only with
+    /// `AnalysisOptions::debug`
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// What the rule compared; only with `AnalysisOptions::debug`
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<EvidenceDebug>,
+}
+
+/// Why a rule reported a diagnostic.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EvidenceDebug {
+    /// The features of one category ("number", "person") on the two words
+    Features { category: String, source: MorphFeatures, target: MorphFeatures },
+    /// Headwords searched in the semantic graph, the argument's first, for
+    /// any of `sought`
+    Path { searched: Vec<String>, sought: Vec<String> },
+    /// The argument's animacy, marked on the headword `from`, below the
+    /// verb's `minimum`
+    Animacy { from: String, animacy: String, minimum: String },
+}
+
+/// The evidence in a line, for logs and plain-text output.
+impl fmt::Display for EvidenceDebug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Features { category, source, target } => write!(f, "{}: {} vs {}", category, source, target),
+            Self::Path { searched, sought } => write!(f, "searched {} for {}", searched.join(" → "), sought.join(", ")),
+            Self::Animacy { from, animacy, minimum } => write!(f, "{} (from {}), below {}", animacy, from, minimum),
+        }
+    }
+}
+
+#[cfg(feature = "ecs")]
+impl From<AgreementEvidence> for EvidenceDebug {
+    fn from(e: AgreementEvidence) -> Self {
+        Self::Features { category: e.category.to_string(), source: e.source.into(), target: e.target.into() }
+    }
+}
+
+#[cfg(feature = "ecs")]
+impl From<AgreementError> for SerializableAgreementError {
+    fn from(e: AgreementError) -> Self {
+        Self {
+            source: e.source,
+            target: e.target,
+            message: e.details,
+            code: e.code.to_string(),
+            token: 0,
+            id: String::new(),
+            severity: Severity::Error,
+            citation: None,
+            explanation_id: explanation(e.code).map(str::to_string),
+            suggestion: e.suggestion,
+            confidence: e.confidence,
+            rule: None,
+            evidence: None,
+        }
+    }
+}
+
+#[cfg(feature = "ecs")]
+impl From<Diagnostic> for SerializableAgreementError {
+    fn from(d: Diagnostic) -> Self {
+        Self {
+            source: d.source,
+            target: d.target,
+            message: d.message,
+            code: d.code.to_string(),
+            token: 0,
+            id: String::new(),
+            severity: Severity::Error,
+            citation: None,
+            explanation_id: explanation(d.code).map(str::to_string),
+            suggestion: None,
+            confidence: d.confidence,
+            rule: None,
+            evidence: None,
+        }
+    }
+}
+
+/// The argument is the `source` and its verb or preposition the `target`.
+#[cfg(feature = "semantics")]
+impl From<SemanticError> for SerializableAgreementError {
+    fn from(e: SemanticError) -> Self {
+        Self {
+            source: e.object_text,
+            target: e.verb_text,
+            message: e.message,
+            code: e.code.to_string(),
+            token: 0,
+            id: String::new(),
+            severity: Severity::Error,
+            citation: None,
+            explanation_id: explanation(e.code).map(str::to_string),
+            suggestion: None,
+            confidence: 1.0,
+            rule: None,
+            evidence: None,
+        }
+    }
+}
+
+/// Puts a report's diagnostics in text order, by sentence, token, code and
+/// message, and gives each its `id`: an FNV-1a hash of the code, the two
+/// words and the number of identical findings before it, as 16 hex digits.
+/// Token positions are left out, so edits elsewhere in the text keep it.
+pub(crate) fn number_diagnostics(errors: &mut [SerializableAgreementError], sentence_of: impl Fn(usize) -> usize) {
+    errors.sort_by(|a, b| {
+        (sentence_of(a.token), a.token, &a.code, &a.message).cmp(&(sentence_of(b.token), b.token, &b.code, &b.message))
+    });
+    let mut seen: HashMap<(&str, &str, &str), usize> = HashMap::new();
+    let ids: Vec<String> = errors.iter().map(|e| {
+        let occurrence = seen.entry((&e.code, &e.source, &e.target)).or_default();
+        *occurrence += 1;
+        let hash = fnv1a(&[e.code.as_bytes(), e.source.as_bytes(), e.target.as_bytes(), &occurrence.to_le_bytes()]);
+        format!("{:016x}", hash)
+    }).collect();
+    for (e, id) in errors.iter_mut().zip(ids) {
+        e.id = id;
+    }
+}
+
+/// 64-bit FNV-1a; a 0xFF byte, which UTF-8 never uses, ends each part.
+fn fnv1a(parts: &[&[u8]]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for part in parts {
+        for &byte in part.iter().chain(&[0xff]) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Which stages of the pipeline this build has (`LogosEngine::stages`).
+/// The cargo features `syntax`, `ecs` and `semantics` compile the others
+/// out; `analyze` then skips them and leaves their parts of the report empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct Stages {
+    /// Tokenization, morphology, disambiguation and the anachronism and
+    /// accent diagnostics; always present
+    pub morphology: bool,
+    /// Dependency parsing, with placements and restored verbs
+    pub syntax: bool,
+    /// Agreement, definiteness and discourse diagnostics, references,
+    /// projectivity, style and world snapshots
+    pub ecs: bool,
+    /// Semantic graphs, semantic diagnostics and thematic roles
+    pub semantics: bool,
+}
+
+/// A lexer token with its byte offsets into the input (no morphology).
+#[derive(Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct TokenSpan {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    /// `Word`, `Unknown`, `Punctuation`, `Numeral` or `Other`
+    pub kind: String,
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub lemma_id: Option<u32>,
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub value: Option<u32>,
+    /// `[start, end]` of each half of a word rejoined across a line break;
+    /// empty for every other token
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub fragments: Vec<[usize; 2]>,
+}
@@ -0,0 +1,81 @@
+//! Scansion of verse for `LogosEngine::scan_core`, over `logos-meter`.
+
+use logos_meter::{Meter, Syllable};
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ScannedLine {
+    /// 1-based line of the text
+    pub line: usize,
+    pub text: String,
+    /// Feet as – and ∪ ("–∪∪|––|…"); absent when the line fits no shape
+    /// of the metre
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub pattern: Option<String>,
+    /// Quantities as scanned, or as the words give them when the line does
+    /// not scan
+    pub syllables: Vec<ScannedSyllable>,
+    pub feet: Vec<ScannedFoot>,
+    pub caesurae: Vec<ScannedCaesura>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ScannedSyllable {
+    pub text: String,
+    /// Index of the word in the line
+    pub word: usize,
+    #[cfg_attr(feature = "tsify", tsify(type = "\"long\" | \"short\" | \"common\""))]
+    pub quantity: String,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ScannedFoot {
+    /// "dactyl", "spondee", "iamb", …
+    pub kind: String,
+    /// `[start, end)` indices into `syllables`
+    pub syllables: [usize; 2],
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct ScannedCaesura {
+    #[cfg_attr(feature = "tsify", tsify(type = "\"penthemimeral\" | \"trochaic\" | \"hephthemimeral\" | \"bucolic\""))]
+    pub kind: String,
+    /// Index of the syllable the word ends with
+    pub after: usize,
+}
+
+fn syllable(s: &Syllable) -> ScannedSyllable {
+    ScannedSyllable { text: s.text.clone(), word: s.word, quantity: s.quantity.name().to_string() }
+}
+
+/// Line `line` (1-based) of a text, `text`, scanned as `meter`.
+pub(crate) fn scan_line(line: usize, text: &str, meter: Meter) -> ScannedLine {
+    let Some(scansion) = logos_meter::scan(text, meter) else {
+        return ScannedLine {
+            line,
+            text: text.to_string(),
+            pattern: None,
+            syllables: logos_meter::scan::syllables(text).iter().map(syllable).collect(),
+            feet: Vec::new(),
+            caesurae: Vec::new(),
+        };
+    };
+    ScannedLine {
+        line,
+        text: text.to_string(),
+        pattern: Some(scansion.pattern()),
+        syllables: scansion.syllables.iter().map(syllable).collect(),
+        feet: scansion.feet.iter()
+            .map(|f| ScannedFoot { kind: f.kind.name().to_string(), syllables: [f.syllables.start, f.syllables.end] })
+            .collect(),
+        caesurae: scansion.caesurae.iter()
+            .map(|c| ScannedCaesura { kind: c.kind.name().to_string(), after: c.after })
+            .collect(),
+    }
+}
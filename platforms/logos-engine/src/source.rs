@@ -0,0 +1,60 @@
+use serde::Deserialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+use crate::EngineError;
+
+/// Input text with a citation per line (e.g. "Iliad 1.1"), so everything in
+/// the report can be keyed by passage.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct TextSource {
+    pub text: String,
+    /// In ascending `start` order; text before the first line is uncited
+    pub lines: Vec<SourceLine>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct SourceLine {
+    /// Byte offset of the line in `TextSource::text`
+    pub start: usize,
+    pub citation: String,
+}
+
+impl TextSource {
+    /// Cites each '\n'-separated line as `prefix` plus its number, counting
+    /// from `first`: `numbered(text, "Iliad 1.", 1)` gives "Iliad 1.1", "Iliad 1.2", ...
+    pub fn numbered(text: impl Into<String>, prefix: &str, first: u32) -> Self {
+        let text = text.into();
+        let starts = std::iter::once(0).chain(text.match_indices('\n').map(|(i, _)| i + 1));
+        let lines = starts
+            .zip(first..)
+            .map(|(start, n)| SourceLine { start, citation: format!("{}{}", prefix, n) })
+            .collect();
+        Self { text, lines }
+    }
+
+    /// Citation of the line containing byte `offset`.
+    pub fn citation_at(&self, offset: usize) -> Option<&str> {
+        let line = self.lines.partition_point(|l| l.start <= offset).checked_sub(1)?;
+        Some(self.lines[line].citation.as_str())
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), EngineError> {
+        let mut previous = None;
+        for line in &self.lines {
+            if line.start > self.text.len() || !self.text.is_char_boundary(line.start) {
+                return Err(EngineError::InvalidOptions(format!(
+                    "Line '{}' starts at byte {}, outside the text or inside a character",
+                    line.citation, line.start
+                )));
+            }
+            if previous.is_some_and(|p| p > line.start) {
+                return Err(EngineError::InvalidOptions(format!("Line '{}' is out of order", line.citation)));
+            }
+            previous = Some(line.start);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use logos_protocol::container::{is_container, Codec, ContainerHeader, HEADER_LEN, MAGIC};
+use miniz_oxide::inflate::stream::{inflate, InflateState};
+use miniz_oxide::{DataFormat, MZError, MZFlush, MZStatus};
+use rkyv::AlignedVec;
+
+use crate::EngineError;
+
+/// Largest archive a container may declare; bounds the up-front allocation.
+const MAX_RAW_LEN: u32 = 512 * 1024 * 1024;
+
+/// Accepts dictionary bytes in arbitrary chunks (e.g. straight off a fetch
+/// stream), inflating compressed containers as they arrive so the compressed
+/// download is never buffered whole. Plain archives are accepted as well.
+pub struct DictionaryStream {
+    state: State,
+}
+
+enum State {
+    /// Too few bytes yet to tell a container from a plain archive
+    Sniffing(Vec<u8>),
+    Plain(AlignedVec),
+    Inflating {
+        inflater: Box<InflateState>,
+        out: AlignedVec,
+        written: usize,
+        done: bool,
+    },
+}
+
+fn invalid(message: impl Into<String>) -> EngineError {
+    EngineError::InvalidDictionary(message.into())
+}
+
+impl Default for DictionaryStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DictionaryStream {
+    pub fn new() -> Self {
+        Self { state: State::Sniffing(Vec::new()) }
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), EngineError> {
+        match &mut self.state {
+            State::Sniffing(buf) => {
+                buf.extend_from_slice(chunk);
+                if buf.len() >= MAGIC.len() && !is_container(buf) {
+                    self.state = State::Plain(crate::aligned(buf));
+                } else if buf.len() >= HEADER_LEN {
+                    let buf = std::mem::take(buf);
+                    self.start_inflating(&buf[..HEADER_LEN])?;
+                    return self.push(&buf[HEADER_LEN..]);
+                }
+                Ok(())
+            }
+            State::Plain(data) => {
+                data.extend_from_slice(chunk);
+                Ok(())
+            }
+            State::Inflating { inflater, out, written, done } => {
+                let mut input = chunk;
+                while !input.is_empty() {
+                    if *done {
+                        return Err(invalid("Trailing bytes after the compressed payload"));
+                    }
+                    let result = inflate(inflater, input, &mut out[*written..], MZFlush::None);
+                    input = &input[result.bytes_consumed..];
+                    *written += result.bytes_written;
+                    match result.status {
+                        Ok(MZStatus::StreamEnd) => *done = true,
+                        Ok(_) => {}
+                        // No room left although input remains
+                        Err(MZError::Buf) => return Err(invalid("Payload is larger than its declared size")),
+                        Err(e) => return Err(invalid(format!("Corrupt compressed payload ({:?})", e))),
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn start_inflating(&mut self, header: &[u8]) -> Result<(), EngineError> {
+        let header = ContainerHeader::parse(header).map_err(|e| invalid(e.to_string()))?;
+        if header.raw_len > MAX_RAW_LEN {
+            return Err(invalid(format!("Declared size {} exceeds the {} byte limit", header.raw_len, MAX_RAW_LEN)));
+        }
+        let format = match header.codec {
+            Codec::Zlib => DataFormat::Zlib,
+        };
+
+        let mut out = AlignedVec::with_capacity(header.raw_len as usize);
+        out.resize(header.raw_len as usize, 0);
+        self.state = State::Inflating { inflater: InflateState::new_boxed(format), out, written: 0, done: false };
+        Ok(())
+    }
+
+    /// The complete, decompressed (but not yet validated) archive.
+    pub fn finish(self) -> Result<AlignedVec, EngineError> {
+        match self.state {
+            State::Sniffing(buf) if is_container(&buf) => Err(invalid("Container header is truncated")),
+            State::Sniffing(buf) => Ok(crate::aligned(&buf)),
+            State::Plain(data) => Ok(data),
+            State::Inflating { done: false, .. } => Err(invalid("Compressed payload is truncated")),
+            State::Inflating { out, written, .. } if written != out.len() => {
+                Err(invalid(format!("Payload inflated to {} bytes, header declared {}", written, out.len())))
+            }
+            State::Inflating { out, .. } => Ok(out),
+        }
+    }
+}
+
+/// One-shot form of `DictionaryStream`.
+pub fn decode_dictionary(data: &[u8]) -> Result<AlignedVec, EngineError> {
+    let mut stream = DictionaryStream::new();
+    stream.push(data)?;
+    stream.finish()
+}
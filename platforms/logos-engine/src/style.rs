@@ -0,0 +1,83 @@
+#[cfg(feature = "ecs")]
+use std::collections::HashMap;
+
+#[cfg(feature = "ecs")]
+use logos_ecs::components::DependencyRole;
+#[cfg(feature = "ecs")]
+use logos_ecs::LogosWorld;
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+/// Word-order statistics of one text, attached to the report when
+/// `AnalysisOptions::style` is set. Token positions index `report.tokens`.
+#[derive(Debug, Default, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct StyleReport {
+    /// Clause patterns, most frequent first
+    pub word_order: Vec<OrderCount>,
+    pub hyperbata: Vec<Hyperbaton>,
+}
+
+/// How often clauses put verb (V), subject (S) and object (O) in one order,
+/// e.g. "SOV", or "VS" for a clause without an object.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct OrderCount {
+    pub pattern: String,
+    pub count: usize,
+}
+
+/// A modifier split from its head by words that belong elsewhere
+/// (ἀγαθὸν ἔχει ἄνθρωπον).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct Hyperbaton {
+    pub modifier: usize,
+    pub head: usize,
+    /// Tokens in between that are not part of the head's phrase
+    pub intruders: usize,
+}
+
+/// Every token with a subject or object counts as a clause head; clauses
+/// with neither are left out.
+#[cfg(feature = "ecs")]
+pub(crate) fn analyze_style(world: &LogosWorld) -> StyleReport {
+    let tokens = world.tokens();
+    let position: HashMap<_, _> = tokens.iter().enumerate().map(|(i, t)| (*t, i)).collect();
+
+    let mut word_order: Vec<OrderCount> = Vec::new();
+    for (v, &verb) in tokens.iter().enumerate() {
+        let children = world.children_of(verb);
+        let first = |role| children.iter().find(|c| world.dependency(**c).is_some_and(|(_, r)| r == role)).and_then(|c| position.get(c).copied());
+        let mut slots: Vec<(usize, char)> = [(first(DependencyRole::Subject), 'S'), (first(DependencyRole::Object), 'O')]
+            .into_iter()
+            .filter_map(|(p, label)| p.map(|p| (p, label)))
+            .collect();
+        if slots.is_empty() {
+            continue;
+        }
+        slots.push((v, 'V'));
+        slots.sort();
+
+        let pattern: String = slots.into_iter().map(|(_, label)| label).collect();
+        match word_order.iter_mut().find(|o| o.pattern == pattern) {
+            Some(order) => order.count += 1,
+            None => word_order.push(OrderCount { pattern, count: 1 }),
+        }
+    }
+    word_order.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.pattern.cmp(&b.pattern)));
+
+    let mut hyperbata = Vec::new();
+    for (m, &modifier) in tokens.iter().enumerate() {
+        let Some((head, DependencyRole::Modifier)) = world.dependency(modifier) else { continue };
+        let Some(&h) = position.get(&head) else { continue };
+        let phrase = world.subtree(head);
+        let intruders = tokens[m.min(h) + 1..m.max(h)].iter().filter(|t| !phrase.contains(t)).count();
+        if intruders > 0 {
+            hyperbata.push(Hyperbaton { modifier: m, head: h, intruders });
+        }
+    }
+
+    StyleReport { word_order, hyperbata }
+}
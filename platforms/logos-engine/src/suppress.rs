@@ -0,0 +1,112 @@
+//! Diagnostics a teacher or editor has accepted (poetic agreement, an
+//! anacoluthon): `AnalysisOptions::ignore` rules, and directives in line
+//! comments of the text itself:
+//!
+//! ```text
+//! ἡ πληθὺς λέγουσι // logos-ignore agreement.number
+//! // logos-ignore-next-line
+//! ὁ δὲ ταῦτα λέγει.
+//! ```
+//!
+//! A directive without codes ignores every finding of its line; codes are
+//! full (`agreement.number`) or a family (`agreement`).
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use serde::Deserialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+/// Ignores the findings of its own line
+const IGNORE: &str = "logos-ignore";
+/// `IGNORE` with this suffix ignores those of the next line
+const NEXT_LINE: &str = "-next-line";
+
+/// Findings to leave out of a report. Every field given must match, so
+/// `code` and `span` together accept one kind of finding in one passage; a
+/// rule without any field matches nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+#[serde(default)]
+pub struct IgnoreRule {
+    /// A diagnostic's `id`
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub id: Option<String>,
+    /// A diagnostic `code` ("agreement.number") or family ("agreement")
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub code: Option<String>,
+    /// Byte range `[start, end)` of the input the finding's token starts in
+    #[cfg_attr(feature = "tsify", tsify(optional))]
+    pub span: Option<[usize; 2]>,
+}
+
+impl IgnoreRule {
+    /// Whether the rule covers a finding of `code` on the token starting at
+    /// byte `offset`. Findings without an ID (the semantic ones) only match
+    /// rules without one.
+    pub fn matches(&self, id: Option<&str>, code: &str, offset: usize) -> bool {
+        if self.id.is_none() && self.code.is_none() && self.span.is_none() {
+            return false;
+        }
+        self.id.as_deref().is_none_or(|wanted| id == Some(wanted))
+            && self.code.as_deref().is_none_or(|wanted| {
+                code.strip_prefix(wanted).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            })
+            && self.span.is_none_or(|[start, end]| (start..end).contains(&offset))
+    }
+}
+
+/// The ignore directives of `input`, in line comments opened by `comment`,
+/// as rules spanning the lines they cover. The text returned has the
+/// directives blanked out with spaces, so its byte offsets are those of
+/// `input`; other comments are left as they are. An empty `comment` reads
+/// no directives.
+pub(crate) fn read_directives<'a>(input: &'a str, comment: &str) -> (Cow<'a, str>, Vec<IgnoreRule>) {
+    let mut rules = Vec::new();
+    if comment.is_empty() || !input.contains(IGNORE) {
+        return (Cow::Borrowed(input), rules);
+    }
+
+    let mut lines: Vec<Range<usize>> = Vec::new();
+    let mut start = 0;
+    for line in input.split('\n') {
+        lines.push(start..start + line.len());
+        start += line.len() + 1;
+    }
+
+    let mut blanked = Vec::new();
+    for (n, line) in lines.iter().enumerate() {
+        let text = &input[line.clone()];
+        let directive = text.match_indices(comment).find_map(|(at, _)| {
+            let rest = text[at + comment.len()..].trim_start().strip_prefix(IGNORE)?;
+            let (next_line, codes) = match rest.strip_prefix(NEXT_LINE) {
+                Some(codes) => (true, codes),
+                None => (false, rest),
+            };
+            // "logos-ignored" is no directive
+            (codes.is_empty() || codes.starts_with(char::is_whitespace)).then_some((at, next_line, codes))
+        });
+        let Some((at, next_line, codes)) = directive else { continue };
+        blanked.push(line.start + at..line.end);
+
+        let target = if next_line { lines.get(n + 1) } else { Some(line) };
+        let Some(target) = target else { continue };
+        let span = Some([target.start, target.end]);
+        let mut codes = codes.split_whitespace().peekable();
+        if codes.peek().is_none() {
+            rules.push(IgnoreRule { span, ..Default::default() });
+        }
+        rules.extend(codes.map(|code| IgnoreRule { code: Some(code.to_string()), span, ..Default::default() }));
+    }
+
+    if blanked.is_empty() {
+        return (Cow::Borrowed(input), rules);
+    }
+    let mut bytes = input.as_bytes().to_vec();
+    for range in blanked {
+        bytes[range].fill(b' ');
+    }
+    // Each blanked range runs from the comment to the end of its line
+    (Cow::Owned(String::from_utf8(bytes).expect("whole characters blanked")), rules)
+}
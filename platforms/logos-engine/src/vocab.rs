@@ -0,0 +1,95 @@
+use serde::Serialize;
+#[cfg(feature = "tsify")]
+use tsify::Tsify;
+
+use crate::AnalysisReport;
+
+/// Lemma counts for which `VocabReport::coverage` is reported.
+pub const COVERAGE_STEPS: [usize; 5] = [10, 50, 100, 500, 1000];
+
+/// Frequency profile of a text, for gauging reading difficulty and building
+/// vocabulary lists (see `LogosEngine::analyze_vocabulary_core`).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct VocabReport {
+    /// Word tokens (punctuation, numerals and editorial sigla excluded)
+    pub tokens: usize,
+    /// Most frequent first
+    pub lemmas: Vec<LemmaFrequency>,
+    pub coverage: Vec<Coverage>,
+    /// Forms no dictionary resolves to a lemma, most frequent first
+    pub unknown: Vec<UnknownWord>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct LemmaFrequency {
+    pub lemma_id: u32,
+    pub lemma: String,
+    pub count: usize,
+    /// Distinct forms seen, in order of appearance
+    pub forms: Vec<String>,
+}
+
+/// Share of the text's tokens that the `top` most frequent lemmas cover.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct Coverage {
+    pub top: usize,
+    pub percent: f64,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "tsify", derive(Tsify))]
+pub struct UnknownWord {
+    pub form: String,
+    pub count: usize,
+}
+
+impl VocabReport {
+    pub(crate) fn from_report(report: &AnalysisReport) -> Self {
+        let mut tokens = 0;
+        let mut lemmas: Vec<LemmaFrequency> = Vec::new();
+        let mut unknown: Vec<UnknownWord> = Vec::new();
+
+        for token in &report.tokens {
+            if matches!(token.kind.as_str(), "Punctuation" | "Numeral" | "Editorial" | "Foreign") {
+                continue;
+            }
+            tokens += 1;
+            let Some(lemma_id) = token.lemma_id else {
+                match unknown.iter_mut().find(|u| u.form == token.text) {
+                    Some(word) => word.count += 1,
+                    None => unknown.push(UnknownWord { form: token.text.clone(), count: 1 }),
+                }
+                continue;
+            };
+            match lemmas.iter_mut().find(|l| l.lemma_id == lemma_id) {
+                Some(entry) => {
+                    entry.count += 1;
+                    if !entry.forms.contains(&token.text) {
+                        entry.forms.push(token.text.clone());
+                    }
+                }
+                None => lemmas.push(LemmaFrequency {
+                    lemma_id,
+                    lemma: token.lemma.clone().unwrap_or_else(|| token.text.clone()),
+                    count: 1,
+                    forms: vec![token.text.clone()],
+                }),
+            }
+        }
+
+        // Stable sorts keep first appearance as the tie-breaker
+        lemmas.sort_by_key(|l| std::cmp::Reverse(l.count));
+        unknown.sort_by_key(|u| std::cmp::Reverse(u.count));
+
+        let coverage = COVERAGE_STEPS.iter().map(|&top| {
+            let covered: usize = lemmas.iter().take(top).map(|l| l.count).sum();
+            let percent = if tokens == 0 { 0.0 } else { covered as f64 * 100.0 / tokens as f64 };
+            Coverage { top, percent }
+        }).collect();
+
+        Self { tokens, lemmas, coverage, unknown }
+    }
+}
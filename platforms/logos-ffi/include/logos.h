@@ -0,0 +1,42 @@
+/*
+ * logos.h — C ABI for the Logos analysis engine (crate `logos-ffi`).
+ *
+ * Fallible calls return NULL (pointers) or -1 (status codes); the reason is
+ * available from logos_last_error() on the same thread until the next call.
+ * A panic inside the library fails the call the same way, with a message
+ * starting "Panic:"; it never unwinds into the caller.
+ * Strings returned by the library must be released with logos_string_free().
+ * An engine may be shared across threads for logos_analyze(), but must not be
+ * mutated (add_dictionary / load_semantics) concurrently.
+ */
+#ifndef LOGOS_H
+#define LOGOS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct LogosEngine LogosEngine;
+
+const char *logos_version(void);
+const char *logos_last_error(void);
+
+LogosEngine *logos_engine_new(const uint8_t *data, size_t len);
+void logos_engine_free(LogosEngine *engine);
+
+int32_t logos_engine_add_dictionary(LogosEngine *engine, const uint8_t *data, size_t len);
+int32_t logos_engine_load_semantics(LogosEngine *engine, const uint8_t *data, size_t len);
+
+/* Returns the AnalysisReport as JSON; options_json may be NULL. */
+char *logos_analyze(const LogosEngine *engine, const char *text, const char *options_json);
+
+void logos_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LOGOS_H */
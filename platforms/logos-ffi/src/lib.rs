@@ -0,0 +1,258 @@
+//! Stable C ABI over `logos-engine` (see `include/logos.h`), for embedding in
+//! iOS/Android apps and other runtimes without going through WASM.
+//!
+//! Conventions:
+//! * Fallible calls return NULL (pointers) or -1 (status codes) and record a
+//!   message retrievable with `logos_last_error` on the same thread.
+//! * A panic never unwinds into the caller: it fails the call the same way,
+//!   with a message starting "Panic:".
+//! * Strings handed out by the library must be released with `logos_string_free`.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+use logos_engine::{AnalysisOptions, EngineError, LogosEngine};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(message: String) {
+    // Interior NULs cannot cross the C boundary; truncate at the first one
+    let message = CString::new(message).unwrap_or_else(|e| {
+        let end = e.nul_position();
+        CString::new(&e.into_vec()[..end]).unwrap_or_default()
+    });
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+fn clear_error() {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+}
+
+fn status(result: Result<(), EngineError>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            set_error(e.to_string());
+            -1
+        }
+    }
+}
+
+/// Runs the body of an entry point, returning `failed` (its error sentinel)
+/// with the message recorded if it panics.
+fn guard<T>(failed: T, body: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        set_error(format!("Panic: {}", panic_message(payload.as_ref())));
+        failed
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => payload.downcast_ref::<String>().map_or("unknown cause", String::as_str),
+    }
+}
+
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if data.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len)
+    }
+}
+
+unsafe fn text<'a>(s: *const c_char, what: &str) -> Result<&'a str, EngineError> {
+    if s.is_null() {
+        return Err(EngineError::InvalidOptions(format!("{} is NULL", what)));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| EngineError::InvalidOptions(format!("{} is not UTF-8: {}", what, e)))
+}
+
+/// Library version as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn logos_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Message for the last failed call on this thread, or NULL. Valid until the
+/// next library call on the same thread.
+#[no_mangle]
+pub extern "C" fn logos_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| slot.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Creates an engine from a compiled dictionary. The bytes are copied.
+///
+/// # Safety
+/// `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn logos_engine_new(data: *const u8, len: usize) -> *mut LogosEngine {
+    clear_error();
+    guard(ptr::null_mut(), || match LogosEngine::from_bytes(bytes(data, len)) {
+        Ok(engine) => Box::into_raw(Box::new(engine)),
+        Err(e) => {
+            set_error(e.to_string());
+            ptr::null_mut()
+        }
+    })
+}
+
+/// # Safety
+/// `engine` must come from `logos_engine_new` (or be NULL) and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn logos_engine_free(engine: *mut LogosEngine) {
+    if !engine.is_null() {
+        guard((), || drop(Box::from_raw(engine)));
+    }
+}
+
+/// Stacks an extra dictionary on top of the loaded ones. Returns 0 or -1.
+///
+/// # Safety
+/// `engine` must be a live engine; `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn logos_engine_add_dictionary(engine: *mut LogosEngine, data: *const u8, len: usize) -> i32 {
+    clear_error();
+    guard(-1, || match engine.as_mut() {
+        Some(engine) => status(engine.add_dictionary_bytes(bytes(data, len))),
+        None => status(Err(EngineError::InvalidOptions("engine is NULL".to_string()))),
+    })
+}
+
+/// Loads a compiled semantic network. Returns 0 or -1.
+///
+/// # Safety
+/// `engine` must be a live engine; `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn logos_engine_load_semantics(engine: *mut LogosEngine, data: *const u8, len: usize) -> i32 {
+    clear_error();
+    guard(-1, || match engine.as_mut() {
+        Some(engine) => status(engine.load_semantics_bytes(bytes(data, len))),
+        None => status(Err(EngineError::InvalidOptions("engine is NULL".to_string()))),
+    })
+}
+
+/// Analyzes `text` and returns the report as a JSON string (free it with
+/// `logos_string_free`), or NULL on error. `options_json` may be NULL; otherwise
+/// it is an `AnalysisOptions` object, e.g. `{"accent_insensitive": true}`.
+///
+/// # Safety
+/// `engine` must be a live engine; `text` and `options_json` (if not NULL) must
+/// be NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn logos_analyze(
+    engine: *const LogosEngine,
+    text: *const c_char,
+    options_json: *const c_char,
+) -> *mut c_char {
+    clear_error();
+    guard(ptr::null_mut(), || match analyze(engine, text, options_json) {
+        Ok(json) => json.into_raw(),
+        Err(e) => {
+            set_error(e.to_string());
+            ptr::null_mut()
+        }
+    })
+}
+
+unsafe fn analyze(
+    engine: *const LogosEngine,
+    input: *const c_char,
+    options_json: *const c_char,
+) -> Result<CString, EngineError> {
+    let engine = engine.as_ref().ok_or_else(|| EngineError::InvalidOptions("engine is NULL".to_string()))?;
+    let input = text(input, "text")?;
+    let options = if options_json.is_null() {
+        AnalysisOptions::default()
+    } else {
+        serde_json::from_str(text(options_json, "options")?)
+            .map_err(|e| EngineError::InvalidOptions(e.to_string()))?
+    };
+
+    let report = engine.analyze_with_options(input, &options)?;
+    let json = serde_json::to_vec(&report).map_err(|e| EngineError::SerializationFailed(e.to_string()))?;
+    // serde_json escapes control characters, so the output never contains NUL
+    CString::new(json).map_err(|e| EngineError::SerializationFailed(e.to_string()))
+}
+
+/// Releases a string returned by this library. NULL is ignored.
+///
+/// # Safety
+/// `s` must come from this library and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn logos_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        guard((), || drop(CString::from_raw(s)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logos_protocol::{Dialect, Dictionary, Gender, Lemma, LemmaId, MorphFlags, Paradigm, ParadigmId, PartOfSpeech, Period};
+
+    fn last_error() -> String {
+        let err = logos_last_error();
+        assert!(!err.is_null(), "an error should be recorded");
+        unsafe { CStr::from_ptr(err) }.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_c_abi_roundtrip() {
+        let dict = Dictionary {
+            version: 1,
+            lemmas: vec![Lemma { id: LemmaId(1), text: "λόγ".to_string(), gender: Gender::Masculine, pos: PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None }],
+            paradigms: vec![Paradigm {
+                id: ParadigmId(1),
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                endings: vec![((MorphFlags::NOMINATIVE | MorphFlags::SINGULAR).bits(), "ος".to_string())],
+            }],
+            ..Default::default()
+        };
+        let bytes = rkyv::to_bytes::<_, 1024>(&dict).unwrap();
+
+        unsafe {
+            let engine = logos_engine_new(bytes.as_ptr(), bytes.len());
+            assert!(!engine.is_null());
+            assert!(logos_last_error().is_null());
+
+            let json = logos_analyze(engine, c"λόγος".as_ptr(), ptr::null());
+            assert!(!json.is_null());
+            let report: serde_json::Value = serde_json::from_slice(CStr::from_ptr(json).to_bytes()).unwrap();
+            assert_eq!(report["tokens"][0]["lemma_id"], 1);
+            logos_string_free(json);
+
+            // Errors surface as NULL plus a message
+            assert!(logos_analyze(engine, c"   ".as_ptr(), ptr::null()).is_null());
+            assert!(last_error().starts_with("EmptyInput"));
+            assert!(logos_analyze(engine, c"λόγος".as_ptr(), c"{\"parser\": 1}".as_ptr()).is_null());
+            assert!(last_error().starts_with("InvalidOptions"));
+            assert_eq!(logos_engine_add_dictionary(engine, [1u8, 2, 3].as_ptr(), 3), -1);
+            assert!(last_error().starts_with("InvalidDictionary"));
+
+            logos_engine_free(engine);
+            assert!(logos_engine_new(ptr::null(), 0).is_null());
+        }
+        assert!(!logos_version().is_null());
+    }
+
+    #[test]
+    fn test_panics_stay_inside() {
+        let status = guard(-1, || -> i32 { panic!("index out of range") });
+        assert_eq!(status, -1);
+        assert_eq!(last_error(), "Panic: index out of range");
+        let json = guard(ptr::null_mut(), || -> *mut c_char { panic!("{} tokens", 3) });
+        assert!(json.is_null());
+        assert_eq!(last_error(), "Panic: 3 tokens");
+    }
+}
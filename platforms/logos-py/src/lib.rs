@@ -0,0 +1,92 @@
+//! Python bindings (`import logos`). Reports come back as plain dicts and
+//! lists with the same shape as the JSON the WASM and HTTP adapters emit.
+
+use logos_engine::{AnalysisOptions, EngineError, LogosEngine};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pythonize::{depythonize, pythonize};
+use serde::Serialize;
+
+create_exception!(logos, LogosError, PyException, "Raised for any engine failure; the message starts with the error kind.");
+
+fn to_py_err(e: EngineError) -> PyErr {
+    LogosError::new_err(e.to_string())
+}
+
+fn to_py<'py, T: Serialize>(py: Python<'py>, value: &T) -> PyResult<Bound<'py, PyAny>> {
+    pythonize(py, value).map_err(|e| LogosError::new_err(format!("SerializationFailed: {}", e)))
+}
+
+fn parse_options(options: Option<&Bound<'_, PyAny>>) -> PyResult<AnalysisOptions> {
+    match options {
+        None => Ok(AnalysisOptions::default()),
+        Some(obj) if obj.is_none() => Ok(AnalysisOptions::default()),
+        Some(obj) => depythonize(obj).map_err(|e| LogosError::new_err(format!("InvalidOptions: {}", e))),
+    }
+}
+
+#[pyclass(name = "Engine", module = "logos")]
+pub struct Engine {
+    inner: LogosEngine,
+}
+
+#[pymethods]
+impl Engine {
+    #[new]
+    fn new(dict_bytes: &[u8]) -> PyResult<Self> {
+        Ok(Self { inner: LogosEngine::from_bytes(dict_bytes).map_err(to_py_err)? })
+    }
+
+    fn add_dictionary(&mut self, dict_bytes: &[u8]) -> PyResult<()> {
+        self.inner.add_dictionary_bytes(dict_bytes).map_err(to_py_err)
+    }
+
+    fn replace_dictionary(&mut self, dict_bytes: &[u8]) -> PyResult<()> {
+        self.inner.replace_dictionary_bytes(dict_bytes).map_err(to_py_err)
+    }
+
+    fn load_semantics(&mut self, data: &[u8]) -> PyResult<()> {
+        self.inner.load_semantics_bytes(data).map_err(to_py_err)
+    }
+
+    /// `analyze(text, options=None) -> dict`; `options` takes the same keys as
+    /// `AnalysisOptions` (e.g. `{"accent_insensitive": True}`).
+    #[pyo3(signature = (text, options=None))]
+    fn analyze<'py>(&self, py: Python<'py>, text: &str, options: Option<&Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyAny>> {
+        let options = parse_options(options)?;
+        let report = self.inner.analyze_with_options(text, &options).map_err(to_py_err)?;
+        to_py(py, &report)
+    }
+
+    #[pyo3(signature = (texts, options=None))]
+    fn analyze_batch<'py>(&self, py: Python<'py>, texts: Vec<String>, options: Option<&Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyAny>> {
+        let options = parse_options(options)?;
+        to_py(py, &self.inner.analyze_batch_core(&texts, &options))
+    }
+
+    fn tokenize<'py>(&self, py: Python<'py>, text: &str) -> PyResult<Bound<'py, PyAny>> {
+        to_py(py, &self.inner.tokenize_core(text))
+    }
+
+    /// Dictionary entries a word form resolves to.
+    #[pyo3(signature = (word, options=None))]
+    fn lookup<'py>(&self, py: Python<'py>, word: &str, options: Option<&Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyAny>> {
+        let options = parse_options(options)?;
+        to_py(py, &self.inner.lookup_core(word, &options))
+    }
+
+    /// Paradigm tables for any form of a lemma.
+    #[pyo3(signature = (lemma, options=None))]
+    fn decline<'py>(&self, py: Python<'py>, lemma: &str, options: Option<&Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyAny>> {
+        let options = parse_options(options)?;
+        to_py(py, &self.inner.decline_core(lemma, &options))
+    }
+}
+
+#[pymodule]
+fn logos(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Engine>()?;
+    m.add("LogosError", m.py().get_type::<LogosError>())?;
+    Ok(())
+}
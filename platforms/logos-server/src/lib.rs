@@ -0,0 +1,153 @@
+//! HTTP adapter over `logos-engine`. One engine is loaded at startup and
+//! shared read-only by every request.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use logos_engine::{AnalysisOptions, AnalysisReport, EngineError, LogosEngine, ParadigmForm, TokenSpan};
+use logos_protocol::MorphFlags;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct AnalyzeRequest {
+    pub text: String,
+    #[serde(default)]
+    pub options: AnalysisOptions,
+}
+
+#[derive(Deserialize)]
+pub struct TokenizeRequest {
+    pub text: String,
+}
+
+#[derive(Deserialize)]
+pub struct GenerateRequest {
+    /// Any form of the lemma to inflect
+    pub word: String,
+    /// Features every generated form must carry, e.g. `"GENITIVE | PLURAL"`.
+    /// Omitted: the full paradigm.
+    #[serde(default = "MorphFlags::empty")]
+    pub morphology: MorphFlags,
+    #[serde(default)]
+    pub options: AnalysisOptions,
+}
+
+/// Error body: `{ "kind": "EmptyInput", "message": "..." }`.
+#[derive(Serialize)]
+struct ErrorBody {
+    kind: &'static str,
+    message: String,
+}
+
+pub struct ApiError(EngineError);
+
+impl From<EngineError> for ApiError {
+    fn from(e: EngineError) -> Self {
+        Self(e)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self.0 {
+            EngineError::EmptyInput | EngineError::InvalidOptions(_) | EngineError::InvalidCorpus(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let body = ErrorBody { kind: self.0.kind(), message: self.0.to_string() };
+        (status, Json(body)).into_response()
+    }
+}
+
+pub fn router(engine: Arc<LogosEngine>) -> Router {
+    Router::new()
+        .route("/analyze", post(analyze))
+        .route("/tokenize", post(tokenize))
+        .route("/generate", post(generate))
+        .with_state(engine)
+}
+
+async fn analyze(
+    State(engine): State<Arc<LogosEngine>>,
+    Json(req): Json<AnalyzeRequest>,
+) -> Result<Json<AnalysisReport>, ApiError> {
+    Ok(Json(engine.analyze_with_options(&req.text, &req.options)?))
+}
+
+async fn tokenize(
+    State(engine): State<Arc<LogosEngine>>,
+    Json(req): Json<TokenizeRequest>,
+) -> Json<Vec<TokenSpan>> {
+    Json(engine.tokenize_core(&req.text))
+}
+
+async fn generate(
+    State(engine): State<Arc<LogosEngine>>,
+    Json(req): Json<GenerateRequest>,
+) -> Json<Vec<ParadigmForm>> {
+    Json(engine.generate_core(&req.word, req.morphology, &req.options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use logos_protocol::{Dialect, Dictionary, Gender, Lemma, LemmaId, Paradigm, ParadigmId, PartOfSpeech, Period};
+    use tower::ServiceExt;
+
+    fn engine() -> Arc<LogosEngine> {
+        let dict = Dictionary {
+            version: 1,
+            lemmas: vec![Lemma { id: LemmaId(1), text: "λόγ".to_string(), gender: Gender::Masculine, pos: PartOfSpeech::Noun, urn: None, gloss: None, dialects: Dialect::empty(), periods: Period::empty(), paradigm: None }],
+            paradigms: vec![Paradigm {
+                id: ParadigmId(1),
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                endings: vec![
+                    ((MorphFlags::NOMINATIVE | MorphFlags::SINGULAR).bits(), "ος".to_string()),
+                    ((MorphFlags::GENITIVE | MorphFlags::SINGULAR).bits(), "ου".to_string()),
+                    ((MorphFlags::GENITIVE | MorphFlags::PLURAL).bits(), "ων".to_string()),
+                ],
+            }],
+            ..Default::default()
+        };
+        let bytes = rkyv::to_bytes::<_, 1024>(&dict).unwrap();
+        Arc::new(LogosEngine::from_bytes(&bytes).unwrap())
+    }
+
+    async fn post_json(path: &str, body: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::post(path)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router(engine()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_endpoints() {
+        let (status, report) = post_json("/analyze", r#"{"text": "λόγος."}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(report["tokens"][0]["lemma_id"], 1);
+
+        let (_, tokens) = post_json("/tokenize", r#"{"text": "λόγος."}"#).await;
+        assert_eq!(tokens[1]["kind"], "Punctuation");
+        assert_eq!(tokens[1]["start"], "λόγος".len());
+
+        let (_, forms) = post_json("/generate", r#"{"word": "λόγος", "morphology": "GENITIVE"}"#).await;
+        let forms: Vec<&str> = forms.as_array().unwrap().iter().map(|f| f["form"].as_str().unwrap()).collect();
+        assert_eq!(forms, vec!["λόγου", "λόγων"]);
+
+        let (status, error) = post_json("/analyze", r#"{"text": "   "}"#).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error["kind"], "EmptyInput");
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs::{self, File};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use clap::Parser;
+use logos_engine::LogosEngine;
+use memmap2::Mmap;
+
+#[derive(Parser)]
+#[command(author, version, about = "Serves the Logos analyzer over HTTP")]
+struct Cli {
+    /// Compiled dictionary (.rkyv), memory-mapped for the server's lifetime
+    #[arg(short, long, value_name = "FILE")]
+    dict: PathBuf,
+
+    /// Compiled semantic network (.rkyv)
+    #[arg(short, long, value_name = "FILE")]
+    semantics: Option<PathBuf>,
+
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    addr: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    // 1. Map the dictionary once; the engine validates it and keeps the mapping
+    let file = File::open(&cli.dict).with_context(|| format!("opening {:?}", cli.dict))?;
+    // SAFETY: the file must not be truncated while mapped; dictionaries are
+    // build artifacts that are replaced, not edited in place.
+    let map = unsafe { Mmap::map(&file) }.with_context(|| format!("mapping {:?}", cli.dict))?;
+    let mut engine = LogosEngine::from_buffer(map).with_context(|| format!("loading {:?}", cli.dict))?;
+
+    // 2. The semantic graph is rebuilt in memory anyway, so a plain read suffices
+    if let Some(path) = &cli.semantics {
+        let data = fs::read(path).with_context(|| format!("reading {:?}", path))?;
+        engine.load_semantics_bytes(&data).with_context(|| format!("loading {:?}", path))?;
+    }
+
+    // 3. Serve
+    let app = logos_server::router(Arc::new(engine));
+    let listener = tokio::net::TcpListener::bind(cli.addr).await?;
+    println!("🚀 Listening on http://{}", cli.addr);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async { let _ = tokio::signal::ctrl_c().await; })
+        .await?;
+    Ok(())
+}
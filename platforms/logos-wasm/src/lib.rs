@@ -1,370 +1,320 @@
 use wasm_bindgen::prelude::*;
-use logos_protocol::Dictionary;
-use logos_parser::Lexer;
-use logos_ecs::{LogosWorld, systems::agreement::AgreementError};
-use logos_solver::{SemanticGraph, validate_semantics};
 use serde::Serialize;
 
+// The pipeline lives in logos-engine; this crate only adapts it to JS.
+pub use logos_engine;
+pub use logos_engine::{
+    translit, AnalysisOptions, AnalysisReport, BatchChunk, BatchReport, EngineError, LanguageProfile, Meter, MorphologyFormat, TextSource, TokenDebug,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ENGINE_ERROR: &'static str = r#"
+/** Category prefix of every `Error.message` thrown by LogosEngine. */
+export type EngineErrorKind = "InvalidDictionary" | "InvalidSemantics" | "InvalidModel" | "SerializationFailed" | "EmptyInput" | "InvalidOptions" | "NoBatch" | "InvalidCorpus" | "Unavailable";
+"#;
+
 #[wasm_bindgen]
 pub fn init_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
-/// The structured response sent back to JavaScript/React
-#[derive(Serialize)]
-pub struct TokenDebug {
-    pub text: String,
-    pub lemma_id: Option<u32>,
-    pub kind: String,
-    pub morphology: String,
-    pub debug: String,
-}
-
-#[derive(Serialize)]
-pub struct AnalysisReport {
-    pub tokens: Vec<TokenDebug>,
-    pub syntax_errors: Vec<SerializableAgreementError>,
-    pub semantic_errors: Vec<String>,
-    pub debug_info: String,
-}
-
-#[derive(Serialize)]
-pub struct SerializableAgreementError {
-    pub source: String,
-    pub target: String,
-    pub message: String,
+/// Greek text in Latin letters, or Latin text back in Greek, in `scheme`.
+#[wasm_bindgen]
+pub fn transliterate(
+    text: &str,
+    #[wasm_bindgen(unchecked_param_type = "\"ala-lc\" | \"iso-843\" | \"scholarly\"")] scheme: &str,
+) -> Result<String, JsError> {
+    let scheme = translit::Scheme::from_tag(scheme)
+        .ok_or_else(|| EngineError::InvalidOptions(format!("unknown transliteration scheme `{}`", scheme)))?;
+    Ok(translit::transliterate(text, scheme))
 }
 
-impl From<AgreementError> for SerializableAgreementError {
-    fn from(e: AgreementError) -> Self {
-        Self {
-            source: e.source,
-            target: e.target,
-            message: e.details,
-        }
-    }
+// std::time::Instant panics on wasm32-unknown-unknown; use the host clock instead.
+// `performance` is available on both the window and worker globals.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
 }
 
 /// The Engine Instance running in the Browser
 #[wasm_bindgen]
 pub struct LogosEngine {
-    // We own the raw binary of the dictionary (loaded via fetch() in JS)
-    data: Vec<u8>,
-    // Optional loaded Semantic Graph
-    semantic_graph: Option<SemanticGraph>,
+    inner: logos_engine::LogosEngine,
 }
 
 #[wasm_bindgen]
 impl LogosEngine {
+    /// Accepts a plain archive or a compressed container.
     #[wasm_bindgen(constructor)]
-    pub fn new(data: Vec<u8>) -> Self {
-        // In a production app, we would validate the RKYV archive here using rkyv::check_archived_root
-        Self { 
-            data,
-            semantic_graph: None,
-        }
+    pub fn new(data: Vec<u8>) -> Result<LogosEngine, JsError> {
+        Ok(Self::wrap(logos_engine::LogosEngine::from_bytes(&data)?))
+    }
+
+    /// Like the constructor, for `"ancient"` or `"modern"` Greek.
+    pub fn with_profile(data: Vec<u8>, profile: &str) -> Result<LogosEngine, JsError> {
+        let profile = match profile {
+            "ancient" => LanguageProfile::Ancient,
+            "modern" => LanguageProfile::Modern,
+            other => return Err(EngineError::InvalidOptions(format!("unknown language profile `{}`", other)).into()),
+        };
+        Ok(Self::wrap(logos_engine::LogosEngine::from_bytes(&data)?.with_profile(profile)))
+    }
+
+    /// Appends a supplementary dictionary (e.g. proper names) consulted after
+    /// the ones already loaded. Its lemma IDs are namespaced by load position.
+    pub fn add_dictionary(&mut self, data: Vec<u8>) -> Result<(), JsError> {
+        Ok(self.inner.add_dictionary_bytes(&data)?)
+    }
+
+    /// Swaps the whole dictionary stack for a single new base dictionary.
+    pub fn replace_dictionary(&mut self, data: Vec<u8>) -> Result<(), JsError> {
+        Ok(self.inner.replace_dictionary_bytes(&data)?)
+    }
+
+    pub fn load_semantics(&mut self, data: Vec<u8>) -> Result<(), JsError> {
+        Ok(self.inner.load_semantics_bytes(&data)?)
+    }
+
+    /// Loads a trained `ParserModel` (from `logos-trainer`), enabling
+    /// `parser: "transition"` and its disambiguation weights.
+    pub fn load_model(&mut self, data: Vec<u8>) -> Result<(), JsError> {
+        Ok(self.inner.load_model_bytes(&data)?)
     }
 
-    pub fn load_semantics(&mut self, data: Vec<u8>) {
-        // Assume trusted input for now, consistent with Dictionary loading
-        let archived = unsafe { rkyv::archived_root::<logos_protocol::SemanticNetwork>(&data) };
-        self.semantic_graph = Some(SemanticGraph::from_archived(archived));
+    /// The pipeline stages this build was compiled with; `load_semantics`
+    /// fails with "Unavailable" in a build without `semantics`.
+    #[wasm_bindgen(unchecked_return_type = "Stages")]
+    pub fn stages() -> Result<JsValue, JsError> {
+        to_js(&logos_engine::LogosEngine::stages())
     }
 
     /// The Main Loop: Text -> Lexer -> ECS -> Solver -> JSON
-    pub fn analyze(&self, input: &str) -> JsValue {
-        let report = self.analyze_core(input);
-        serde_wasm_bindgen::to_value(&report).unwrap()
+    /// `options` may be `undefined` to run the full pipeline.
+    #[wasm_bindgen(unchecked_return_type = "AnalysisReport")]
+    pub fn analyze(
+        &self,
+        input: &str,
+        #[wasm_bindgen(unchecked_param_type = "AnalysisOptions | undefined")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let report = self.inner.analyze_with_options(input, &parse_options(options)?)?;
+        to_js(&report)
     }
-}
 
-impl LogosEngine {
-    /// Pure Rust analysis (No WASM dependencies in return type)
-    pub fn analyze_core(&self, input: &str) -> AnalysisReport {
-        // 1. Zero-Copy Load of Dictionary
-        let dict = unsafe { rkyv::archived_root::<Dictionary>(&self.data) };
-
-        // 2. Lexical Analysis (Text -> Tokens)
-        let lexer = Lexer::new(dict);
-        let tokens = lexer.tokenize(input);
-        
-        // 3. Morphology Resolution (Unified Pipeline)
-        struct AnalyzedToken<'a> {
-            text: &'a str,
-            analysis: logos_parser::morphology::MorphAnalysis,
-        }
-
-        let analyzed_tokens: Vec<AnalyzedToken> = tokens.iter().map(|t| {
-            // Check for Punctuation first to avoid unnecessary dictionary lookup
-            if let logos_parser::token::TokenKind::Punctuation(_) = t.kind {
-                 return AnalyzedToken {
-                    text: &t.text,
-                    analysis: logos_parser::morphology::MorphAnalysis {
-                        flags: logos_protocol::MorphFlags::empty(),
-                        lemma_id: None,
-                        debug_msg: "Punctuation".to_string(),
-                        stem: String::new(),
-                        kind: "Punctuation".to_string(),
-                    }
-                };
-            }
-
-            let mut known_id = None;
-            if let logos_parser::token::TokenKind::Word(id) = t.kind {
-                known_id = Some(id.0);
-            }
-
-            let analysis = logos_parser::morphology::resolve_morphology(dict, &t.text, known_id);
-            
-            AnalyzedToken {
-                text: &t.text,
-                analysis,
-            }
-        }).collect();
-
-        // 4. Transform for Output (TokenDebug)
-        let debug_tokens: Vec<TokenDebug> = analyzed_tokens.iter().map(|at| {
-             let morph_str = if at.analysis.flags.is_empty() {
-                 "None".to_string()
-             } else {
-                 format!("{:?}", at.analysis.flags)
-             };
-
-             TokenDebug {
-                text: at.text.to_string(),
-                lemma_id: at.analysis.lemma_id.map(|id| id.0),
-                kind: at.analysis.kind.clone(),
-                morphology: morph_str,
-                debug: at.analysis.debug_msg.clone(),
-            }
-        }).collect();
-
-        // 5. ECS Simulation (Tokens -> Entities)
-        let mut world = LogosWorld::new();
-        let mut entities = Vec::new();
-
-        for at in &analyzed_tokens {
-            entities.push(
-                world.add_token(
-                    at.text.to_string(), 
-                    at.analysis.lemma_id, 
-                    at.analysis.flags
-                )
-            );
-        }
-
-        // 6. Syntactic Parsing
-        // Construct MorphTokens for parser input
-        let parser_input: Vec<logos_parser::syntax::MorphToken> = analyzed_tokens.iter().map(|at| {
-            logos_parser::syntax::MorphToken {
-                text: at.text,
-                flags: at.analysis.flags,
-            }
-        }).collect();
-
-        let dependencies = logos_parser::syntax::parse_greedy(&parser_input);
-        
-        for dep in dependencies {
-            if dep.dependent_index < entities.len() && dep.head_index < entities.len() {
-                let child_entity = entities[dep.dependent_index];
-                let head_entity = entities[dep.head_index];
-                
-                use logos_parser::syntax::SyntaxRole;
-                use logos_ecs::components::DependencyRole;
-
-                let role = match dep.role {
-                    SyntaxRole::Subject => DependencyRole::Subject,
-                    SyntaxRole::Object => DependencyRole::Object,
-                    SyntaxRole::Modifier => DependencyRole::Modifier,
-                    SyntaxRole::Root => DependencyRole::Root,
-                    SyntaxRole::PrepositionArg => DependencyRole::PrepositionArg,
-                    SyntaxRole::IndirectObject => DependencyRole::IndirectObject,
-                    SyntaxRole::Coordinator => DependencyRole::Coordinator,
-                    SyntaxRole::Conjunct => DependencyRole::Conjunct,
-                    SyntaxRole::PassiveAgent => DependencyRole::PassiveAgent,
-                    SyntaxRole::AbsoluteClause => DependencyRole::AbsoluteClause,
-                    SyntaxRole::Complement => DependencyRole::Complement,
-                    SyntaxRole::RelativeClause => DependencyRole::RelativeClause,
-                    SyntaxRole::None => continue,
-                };
-                
-                world.set_dependency(child_entity, head_entity, role);
-            }
-        }
-
-        let syntax_errors_raw = world.validate();
-        let syntax_errors: Vec<SerializableAgreementError> = syntax_errors_raw
-            .into_iter()
-            .map(|e| e.into())
-            .collect();
-
-        // 7. Semantic Validation (Meaning)
-        let default_graph = SemanticGraph::new();
-        let graph = self.semantic_graph.as_ref().unwrap_or(&default_graph);
-        let semantic_errors_raw = validate_semantics(&world, graph);
-        let semantic_errors: Vec<String> = semantic_errors_raw
-            .into_iter()
-            .map(|e| e.message)
-            .collect();
-
-        AnalysisReport {
-            tokens: debug_tokens,
-            syntax_errors,
-            semantic_errors,
-            debug_info: format!("Lemmas: {}, Paradigms: {}", dict.lemmas.len(), dict.paradigms.len()),
-        }
+    /// Analyzes a text whose lines carry citations (e.g. "Iliad 1.1"); the
+    /// report tags tokens, dependencies and diagnostics with them.
+    #[wasm_bindgen(unchecked_return_type = "AnalysisReport")]
+    pub fn analyze_source(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "TextSource")] source: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "AnalysisOptions | undefined")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let source: TextSource = serde_wasm_bindgen::from_value(source)
+            .map_err(|e| EngineError::InvalidOptions(e.to_string()))?;
+        let report = self.inner.analyze_source(&source, &parse_options(options)?)?;
+        to_js(&report)
+    }
+
+    /// Lemmas by frequency, top-N coverage and unknown words, for gauging
+    /// reading difficulty and building vocabulary lists.
+    #[wasm_bindgen(unchecked_return_type = "VocabReport")]
+    pub fn analyze_vocabulary(
+        &self,
+        input: &str,
+        #[wasm_bindgen(unchecked_param_type = "AnalysisOptions | undefined")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let report = self.inner.analyze_vocabulary_core(input, &parse_options(options)?)?;
+        to_js(&report)
+    }
+
+    /// Cloze, passive and plural drills on a sentence, with answer keys
+    /// generated from the words' paradigms.
+    #[wasm_bindgen(unchecked_return_type = "Exercise[]")]
+    pub fn exercises(
+        &self,
+        sentence: &str,
+        #[wasm_bindgen(unchecked_param_type = "AnalysisOptions | undefined")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let exercises = self.inner.exercises_core(sentence, &parse_options(options)?)?;
+        to_js(&exercises)
+    }
+
+    /// Checks a student's answer for the form of a lemma with `flags`
+    /// (flag names, "GENITIVE | PLURAL"): correct, or off in its accent,
+    /// ending or stem. Undefined when the lemma has no such form.
+    #[wasm_bindgen(unchecked_return_type = "FormCheck | undefined")]
+    pub fn check_form(
+        &self,
+        lemma_id: u32,
+        flags: &str,
+        answer: &str,
+        #[wasm_bindgen(unchecked_param_type = "AnalysisOptions | undefined")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let flags = MorphologyFormat::parse_flags(flags)
+            .ok_or_else(|| EngineError::InvalidOptions(format!("unknown morphology `{}`", flags)))?;
+        let check = self.inner.check_form_core(lemma_id, flags, answer, &parse_options(options)?);
+        to_js(&check)
+    }
+
+    /// Syllables, feet and caesurae of each line of verse in `text`.
+    #[wasm_bindgen(unchecked_return_type = "ScannedLine[]")]
+    pub fn scan(
+        &self,
+        text: &str,
+        #[wasm_bindgen(unchecked_param_type = "\"hexameter\" | \"iambic-trimeter\"")] meter: &str,
+    ) -> Result<JsValue, JsError> {
+        let meter = Meter::from_tag(meter)
+            .ok_or_else(|| EngineError::InvalidOptions(format!("unknown meter `{}`", meter)))?;
+        to_js(&self.inner.scan_core(text, meter))
+    }
+
+    /// Each token's byte span in `text`, the form it was looked up as and
+    /// its lemma, for standoff annotation of the original text.
+    #[wasm_bindgen(unchecked_return_type = "TokenAlignment[]")]
+    pub fn align(
+        &self,
+        text: &str,
+        #[wasm_bindgen(unchecked_param_type = "AnalysisOptions | undefined")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let alignment = self.inner.align_core(text, &parse_options(options)?)?;
+        to_js(&alignment)
+    }
+
+    /// Every analysis per token and every candidate dependency with its
+    /// score, for tools that do their own decoding.
+    #[wasm_bindgen(unchecked_return_type = "Lattice")]
+    pub fn analyze_lattice(
+        &self,
+        input: &str,
+        #[wasm_bindgen(unchecked_param_type = "AnalysisOptions | undefined")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let lattice = self.inner.analyze_lattice_core(input, &parse_options(options)?)?;
+        to_js(&lattice)
+    }
+
+    /// Hit/miss counters and fill of the morphology cache, which keeps
+    /// resolved word forms across calls.
+    #[wasm_bindgen(unchecked_return_type = "CacheStats")]
+    pub fn cache_stats(&self) -> Result<JsValue, JsError> {
+        to_js(&self.inner.cache_stats())
+    }
+
+    pub fn clear_cache(&self) {
+        self.inner.clear_cache();
+    }
+
+    /// Word forms kept across calls; 0 turns the cache off.
+    pub fn set_cache_capacity(&self, capacity: usize) {
+        self.inner.set_cache_capacity(capacity);
+    }
+
+    /// Build metadata of each loaded dictionary (hash, build time, sources,
+    /// counts), to confirm the deployed lexicon version, with its `stats`
+    /// (lemmas by part of speech, paradigm sizes, suffix inventory).
+    #[wasm_bindgen(unchecked_return_type = "DictionaryInfo[]")]
+    pub fn dictionary_info(&self) -> Result<JsValue, JsError> {
+        to_js(&self.inner.dictionary_info())
+    }
+
+    /// The lemma with this (namespaced) ID, or undefined.
+    #[wasm_bindgen(unchecked_return_type = "LemmaEntry | undefined")]
+    pub fn lemma(&self, id: u32) -> Result<JsValue, JsError> {
+        to_js(&self.inner.lemma_core(id))
+    }
+
+    /// Up to `limit` lemmas whose headword starts with `prefix`, in
+    /// headword order, for autocompletion.
+    #[wasm_bindgen(unchecked_return_type = "LemmaEntry[]")]
+    pub fn find_lemmas(&self, prefix: &str, limit: usize) -> Result<JsValue, JsError> {
+        to_js(&self.inner.find_lemmas_core(prefix, limit))
+    }
+
+    /// Up to `limit` completions for a typed prefix, with glosses: exact
+    /// matches first, then ones that differ only in accents, breathings or case.
+    #[wasm_bindgen(unchecked_return_type = "LemmaEntry[]")]
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Result<JsValue, JsError> {
+        to_js(&self.inner.suggest_core(prefix, limit))
+    }
+
+    /// Analyzes every text in one call and aggregates corpus statistics.
+    #[wasm_bindgen(unchecked_return_type = "BatchReport")]
+    pub fn analyze_batch(
+        &self,
+        texts: Vec<String>,
+        #[wasm_bindgen(unchecked_param_type = "AnalysisOptions | undefined")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let report = self.inner.analyze_batch_core(&texts, &parse_options(options)?);
+        to_js(&report)
+    }
+
+    /// Queues texts for incremental processing via `next_chunk`, so a Web Worker
+    /// can yield between chunks. Replaces any batch still in progress.
+    pub fn start_batch(
+        &mut self,
+        texts: Vec<String>,
+        #[wasm_bindgen(unchecked_param_type = "AnalysisOptions | undefined")] options: JsValue,
+    ) -> Result<(), JsError> {
+        self.inner.start_batch_core(texts, parse_options(options)?);
+        Ok(())
+    }
+
+    /// Analyzes up to `max_texts` queued texts.
+    #[wasm_bindgen(unchecked_return_type = "BatchChunk")]
+    pub fn next_chunk(&mut self, max_texts: usize) -> Result<JsValue, JsError> {
+        let chunk = self.inner.next_chunk_core(max_texts)?;
+        to_js(&chunk)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use logos_protocol::{Dictionary, Lemma, Paradigm, Gender, LemmaId, PartOfSpeech, ParadigmId};
-    use rkyv::to_bytes;
-
-    #[test]
-    fn test_robust_morphology_resolution() {
-        // 1. Setup Mock Dictionary
-        // Lemma: "άνθρωπος" (Full word stored, stem: άνθρωπ)
-        // Paradigm: 
-        //  - "ος" -> Nom|Sg|Masc
-        //  - "ου" -> Gen|Sg|Masc
-        let lemma = Lemma {
-            id: LemmaId(1),
-            text: "άνθρωπος".to_string(),
-            gender: Gender::Masculine,
-            pos: PartOfSpeech::Noun,
-        };
+impl LogosEngine {
+    #[allow(unused_mut)]
+    fn wrap(mut inner: logos_engine::LogosEngine) -> Self {
+        #[cfg(target_arch = "wasm32")]
+        inner.set_clock(performance_now);
+        Self { inner }
+    }
 
-        let paradigm = Paradigm {
-            id: ParadigmId(1),
-            endings: vec![
-                ((logos_protocol::MorphFlags::NOMINATIVE | logos_protocol::MorphFlags::SINGULAR | logos_protocol::MorphFlags::MASCULINE).bits(), "ος".to_string()),
-                ((logos_protocol::MorphFlags::GENITIVE | logos_protocol::MorphFlags::SINGULAR | logos_protocol::MorphFlags::MASCULINE).bits(), "ου".to_string())
-            ],
-        };
+    /// The wrapped native engine, for Rust callers embedding the WASM type.
+    pub fn core(&self) -> &logos_engine::LogosEngine {
+        &self.inner
+    }
+}
 
-        let dict = Dictionary {
-            version: 1,
-            lemmas: vec![lemma],
-            paradigms: vec![paradigm],
-        };
+/// Builds a LogosEngine from a dictionary delivered in chunks, inflating
+/// compressed containers as they arrive:
+/// `for await (const chunk of response.body) loader.push(chunk);`
+#[wasm_bindgen]
+pub struct DictionaryLoader {
+    inner: logos_engine::DictionaryStream,
+}
 
-        // Serialize to bytes (simulating loading dict.rkyv)
-        let bytes = to_bytes::<_, 256>(&dict).unwrap();
-        let archived = unsafe { rkyv::archived_root::<Dictionary>(&bytes) };
-
-        // Case 1: "άνθρωπος" (Nom Sg)
-        let analysis = logos_parser::morphology::resolve_morphology(archived, "άνθρωπος", Some(1));
-        assert_ne!(analysis.kind, "Unknown", "Should resolve 'άνθρωπος'");
-        assert!(format!("{:?}", analysis.flags).contains("NOMINATIVE"), "Should be Nominative");
-        assert!(analysis.debug_msg.contains("Matched!"), "Debug should indicate match");
-
-        // Case 2: "άνθρωπου" (Gen Sg)
-        let analysis = logos_parser::morphology::resolve_morphology(archived, "άνθρωπου", Some(1));
-        assert_ne!(analysis.kind, "Unknown", "Should resolve 'άνθρωπου'");
-        assert!(format!("{:?}", analysis.flags).contains("GENITIVE"), "Should be Genitive");
-
-        // Case 3: "άλογο" (Mismatch)
-        // We pass None to simulate that Lexer didn't match it (or we are verifying scratch lookup)
-        let analysis = logos_parser::morphology::resolve_morphology(archived, "άλογο", None);
-        assert_eq!(analysis.kind, "Unknown", "Should NOT resolve 'άλογο'");
+#[wasm_bindgen]
+impl DictionaryLoader {
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> DictionaryLoader {
+        Self { inner: logos_engine::DictionaryStream::new() }
     }
 
-    #[test]
-    fn test_integration_with_real_dict() {
-        use std::fs;
-        use std::path::PathBuf;
-
-        // Locate the dictionary file relative to the crate root
-        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        path.push("www");
-        path.push("dict_v10.rkyv");
-
-        if !path.exists() {
-            println!("Skipping integration test: Dictionary not found at {:?}", path);
-            return;
-        }
-
-        println!("Loading dictionary from {:?}", path);
-        let data = fs::read(path).expect("Failed to read dictionary file");
-        
-        // Initialize Engine
-        let engine = LogosEngine::new(data);
-        
-        // Run Analysis: "απάνθρωπος" (Cruel/Inhuman)
-        let report = engine.analyze_core("απάνθρωπος");
-        
-        // Verify Results
-        let token = &report.tokens[0];
-        assert_eq!(token.text, "απάνθρωπος");
-        
-        // We expect it to be resolved now with the robust matching
-        assert_ne!(token.kind, "Unknown", "Should be resolved as Word (or Recovered)");
-        assert_ne!(token.morphology, "None", "Morphology should be resolved");
-        
-        println!("Successfully analyzed '{}'", token.text);
-        println!("Debug Info: {}", token.debug);
-        println!("Morphology: {}", token.morphology);
-
-        // Run Analysis: "xyznonsense" (Unknown)
-        let report_unknown = engine.analyze_core("xyznonsense");
-        let token_unknown = &report_unknown.tokens[0];
-        assert_eq!(token_unknown.text, "xyznonsense");
-        assert_eq!(token_unknown.kind, "Unknown", "Should be Unknown");
-        assert_eq!(token_unknown.morphology, "None", "Morphology should be None");
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), JsError> {
+        Ok(self.inner.push(chunk)?)
     }
 
-    #[test]
-    fn test_full_sentence_analysis() {
-        use std::fs;
-        use std::path::PathBuf;
-
-        // Load Dictionary
-        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        path.push("www");
-        path.push("dict_v10.rkyv");
-        if !path.exists() { return; }
-        let data = fs::read(path).expect("Failed to read dictionary file");
-        let engine = LogosEngine::new(data);
-
-        // Analyze: "Ο απάνθρωπος." (The cruel [one].)
-        let report = engine.analyze_core("Ο απάνθρωπος.");
-        
-        assert_eq!(report.tokens.len(), 3, "Should have 3 tokens");
-        
-        let t1 = &report.tokens[1]; // "απάνθρωπος"
-        assert_eq!(t1.text, "απάνθρωπος");
-        assert_ne!(t1.kind, "Unknown", "Middle word should be resolved");
-        
-        let t2 = &report.tokens[2]; // "."
-        assert_eq!(t2.text, ".");
-        assert_eq!(t2.kind, "Punctuation", "Dot should be punctuation");
+    /// Consumes the loader.
+    pub fn finish(self) -> Result<LogosEngine, JsError> {
+        Ok(LogosEngine::wrap(logos_engine::LogosEngine::from_stream(self.inner)?))
     }
+}
 
-    #[test]
-    fn test_syntax_and_semantics_pipeline() {
-        use std::fs;
-        use std::path::PathBuf;
-
-        // Load Dictionary
-        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        path.push("www");
-        path.push("dict_v10.rkyv");
-        if !path.exists() { return; }
-        let data = fs::read(path).expect("Failed to read dictionary file");
-        let engine = LogosEngine::new(data);
-
-        // Analyze: "Ο απάνθρωπος" (2 tokens exactly to trigger mock syntax)
-        let report = engine.analyze_core("Ο απάνθρωπος");
-        
-        assert_eq!(report.tokens.len(), 2, "Should have 2 tokens");
-
-        // Verify Syntax/Semantics fields exist (Pipeline RAN)
-        println!("Syntax Errors: {:?}", report.syntax_errors.len());
-        println!("Semantic Errors: {:?}", report.semantic_errors.len());
-        
-        // Ensure we didn't crash during ECS/Solver steps
-        assert!(report.debug_info.contains("Lemmas:"), "Debug info should be present");
+fn parse_options(options: JsValue) -> Result<AnalysisOptions, EngineError> {
+    if options.is_undefined() || options.is_null() {
+        return Ok(AnalysisOptions::default());
     }
+    serde_wasm_bindgen::from_value(options).map_err(|e| EngineError::InvalidOptions(e.to_string()))
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value)
+        .map_err(|e| EngineError::SerializationFailed(e.to_string()).into())
 }
@@ -0,0 +1,167 @@
+//! Golden-file regression tests of the full pipeline: a curated suite of
+//! sentences is analyzed against a fixture dictionary and each report, as
+//! pretty-printed JSON, is compared with a checked-in snapshot. Blessing
+//! rewrites the snapshots to the current output, so a reviewer sees every
+//! behavior change of the classifier or parser as a snapshot diff.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use logos_engine::{AnalysisOptions, AnalysisReport, LogosEngine};
+use logos_protocol::Dictionary;
+use serde::Deserialize;
+
+pub const SUITE_FILE: &str = "suite.json";
+pub const DICTIONARY_FILE: &str = "dictionary.json";
+pub const SNAPSHOT_DIR: &str = "snapshots";
+
+/// One sentence of the suite, analyzed with its own options.
+#[derive(Debug, Deserialize)]
+pub struct Case {
+    /// Also the snapshot's file name, `<name>.json`
+    pub name: String,
+    pub text: String,
+    #[serde(default)]
+    pub options: AnalysisOptions,
+}
+
+/// How a case's report compares with its snapshot.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Matched,
+    /// First differing line (1-based) with the snapshot's and the report's text
+    Changed { line: usize, expected: String, actual: String },
+    /// No snapshot yet
+    Missing,
+    /// A snapshot with no case left in the suite
+    Stale,
+    /// Snapshot written (or, for a stale one, removed) in bless mode
+    Blessed,
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Changed { .. } | Outcome::Missing | Outcome::Stale)
+    }
+}
+
+/// A suite directory: `suite.json`, `dictionary.json` and `snapshots/`.
+pub struct Suite {
+    dir: PathBuf,
+    pub cases: Vec<Case>,
+    engine: LogosEngine,
+}
+
+impl Suite {
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let path = dir.join(SUITE_FILE);
+        let text = fs::read_to_string(&path).with_context(|| format!("reading {:?}", path))?;
+        let cases: Vec<Case> = serde_json::from_str(&text).with_context(|| format!("parsing {:?}", path))?;
+        for (i, case) in cases.iter().enumerate() {
+            anyhow::ensure!(!cases[..i].iter().any(|c| c.name == case.name), "{:?}: duplicate case {:?}", path, case.name);
+        }
+
+        let path = dir.join(DICTIONARY_FILE);
+        let text = fs::read_to_string(&path).with_context(|| format!("reading {:?}", path))?;
+        let dictionary: Dictionary = serde_json::from_str(&text).with_context(|| format!("parsing {:?}", path))?;
+        let bytes = rkyv::to_bytes::<_, 1024>(&dictionary).context("serializing the dictionary")?;
+        let engine = LogosEngine::from_bytes(&bytes)?;
+
+        Ok(Self { dir: dir.to_path_buf(), cases, engine })
+    }
+
+    pub fn snapshot_path(&self, name: &str) -> PathBuf {
+        self.dir.join(SNAPSHOT_DIR).join(format!("{}.json", name))
+    }
+
+    /// The report of a case, as it is stored in its snapshot.
+    pub fn render(&self, case: &Case) -> anyhow::Result<String> {
+        let report = self.engine.analyze_with_options(&case.text, &case.options).with_context(|| format!("analyzing case {:?}", case.name))?;
+        render(&report)
+    }
+
+    /// Compares every case whose name contains `filter` with its snapshot, or
+    /// with `bless` writes the snapshots that differ. Stale snapshots are only
+    /// looked for (and removed when blessing) when the whole suite runs.
+    pub fn run(&self, filter: Option<&str>, bless: bool) -> anyhow::Result<Vec<(String, Outcome)>> {
+        let mut outcomes = Vec::new();
+        if bless {
+            fs::create_dir_all(self.dir.join(SNAPSHOT_DIR))?;
+        }
+        for case in self.cases.iter().filter(|c| filter.is_none_or(|f| c.name.contains(f))) {
+            let actual = self.render(case)?;
+            let path = self.snapshot_path(&case.name);
+            let outcome = match fs::read_to_string(&path) {
+                Ok(expected) => compare(&expected, &actual),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Outcome::Missing,
+                Err(e) => return Err(e).with_context(|| format!("reading {:?}", path)),
+            };
+            let outcome = if bless && outcome != Outcome::Matched {
+                fs::write(&path, &actual).with_context(|| format!("writing {:?}", path))?;
+                Outcome::Blessed
+            } else {
+                outcome
+            };
+            outcomes.push((case.name.clone(), outcome));
+        }
+        if filter.is_none() {
+            for name in self.stale()? {
+                if bless {
+                    let path = self.snapshot_path(&name);
+                    fs::remove_file(&path).with_context(|| format!("removing {:?}", path))?;
+                    outcomes.push((name, Outcome::Blessed));
+                } else {
+                    outcomes.push((name, Outcome::Stale));
+                }
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Names of the snapshots no case writes.
+    fn stale(&self) -> anyhow::Result<Vec<String>> {
+        let dir = self.dir.join(SNAPSHOT_DIR);
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).with_context(|| format!("reading {:?}", dir)),
+        };
+        let mut stale = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+            if path.extension().is_some_and(|e| e == "json") && !self.cases.iter().any(|c| c.name == name) {
+                stale.push(name.to_string());
+            }
+        }
+        stale.sort();
+        Ok(stale)
+    }
+}
+
+/// Pretty-printed JSON with a final newline, so snapshots diff line by line.
+pub fn render(report: &AnalysisReport) -> anyhow::Result<String> {
+    let mut text = serde_json::to_string_pretty(report)?;
+    text.push('\n');
+    Ok(text)
+}
+
+fn compare(expected: &str, actual: &str) -> Outcome {
+    if expected == actual {
+        return Outcome::Matched;
+    }
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line += 1,
+            // differs only in line endings or the final newline
+            (None, None) => return Outcome::Changed { line, expected: String::new(), actual: String::new() },
+            (e, a) => {
+                return Outcome::Changed { line, expected: e.unwrap_or("<end of file>").to_string(), actual: a.unwrap_or("<end of file>").to_string() }
+            }
+        }
+    }
+}
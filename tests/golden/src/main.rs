@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use logos_golden::{Outcome, Suite};
+
+#[derive(Parser)]
+#[command(about = "Checks full-pipeline reports against their golden snapshots")]
+struct Cli {
+    /// Rewrite the snapshots that differ from the current output (and remove stale ones)
+    #[arg(long)]
+    bless: bool,
+    /// Only run the cases whose name contains this
+    #[arg(long)]
+    filter: Option<String>,
+    /// Suite directory holding suite.json, dictionary.json and snapshots/
+    #[arg(long, default_value = env!("CARGO_MANIFEST_DIR"))]
+    dir: PathBuf,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let cli = Cli::parse();
+    let suite = Suite::load(&cli.dir)?;
+    let outcomes = suite.run(cli.filter.as_deref(), cli.bless)?;
+
+    let mut failures = 0;
+    for (name, outcome) in &outcomes {
+        match outcome {
+            Outcome::Matched => println!("ok       {}", name),
+            Outcome::Blessed => println!("blessed  {}", name),
+            Outcome::Missing => println!("MISSING  {}", name),
+            Outcome::Stale => println!("STALE    {}", name),
+            Outcome::Changed { line, expected, actual } => {
+                println!("CHANGED  {} (line {})", name, line);
+                println!("  - {}", expected.trim_start());
+                println!("  + {}", actual.trim_start());
+            }
+        }
+        if outcome.is_failure() {
+            failures += 1;
+        }
+    }
+    println!("{} cases, {} failing", outcomes.len(), failures);
+    if failures > 0 {
+        println!("rerun with --bless to accept the new output");
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,18 @@
+use std::path::Path;
+
+use logos_golden::Suite;
+
+/// Set `LOGOS_BLESS` to rewrite the snapshots instead.
+#[test]
+fn reports_match_snapshots() {
+    let suite = Suite::load(Path::new(env!("CARGO_MANIFEST_DIR"))).unwrap();
+    let bless = std::env::var_os("LOGOS_BLESS").is_some();
+    let failures: Vec<String> = suite
+        .run(None, bless)
+        .unwrap()
+        .into_iter()
+        .filter(|(_, outcome)| outcome.is_failure())
+        .map(|(name, outcome)| format!("{}: {:?}", name, outcome))
+        .collect();
+    assert!(failures.is_empty(), "{}\nrun `cargo run -p logos-golden -- --bless` to accept", failures.join("\n"));
+}
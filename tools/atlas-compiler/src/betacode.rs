@@ -0,0 +1,135 @@
+//! Beta Code (the ASCII transcription of Greek used by the TLG and Perseus)
+//! to Unicode: `lo/gos` -> λόγος, `*)aqh=nai` -> Ἀθῆναι.
+
+use unicode_normalization::UnicodeNormalization;
+
+fn letter(c: char) -> Option<char> {
+    Some(match c.to_ascii_lowercase() {
+        'a' => 'α',
+        'b' => 'β',
+        'g' => 'γ',
+        'd' => 'δ',
+        'e' => 'ε',
+        'z' => 'ζ',
+        'h' => 'η',
+        'q' => 'θ',
+        'i' => 'ι',
+        'k' => 'κ',
+        'l' => 'λ',
+        'm' => 'μ',
+        'n' => 'ν',
+        'c' => 'ξ',
+        'o' => 'ο',
+        'p' => 'π',
+        'r' => 'ρ',
+        's' => 'σ',
+        't' => 'τ',
+        'u' => 'υ',
+        'f' => 'φ',
+        'x' => 'χ',
+        'y' => 'ψ',
+        'w' => 'ω',
+        'v' => 'ϝ',
+        _ => return None,
+    })
+}
+
+fn mark(c: char) -> Option<char> {
+    Some(match c {
+        ')' => '\u{0313}',
+        '(' => '\u{0314}',
+        '/' => '\u{0301}',
+        '\\' => '\u{0300}',
+        '=' => '\u{0342}',
+        '+' => '\u{0308}',
+        '|' => '\u{0345}',
+        _ => return None,
+    })
+}
+
+/// `text` in Unicode Greek (NFC). Text already in Greek script is returned
+/// as it is, so mixed sources can go through unchanged.
+pub fn to_greek(text: &str) -> String {
+    if text.chars().any(|c| ('\u{0370}'..='\u{03ff}').contains(&c) || ('\u{1f00}'..='\u{1fff}').contains(&c)) {
+        return text.nfc().collect();
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '*' {
+            // Capital: its marks come before the letter
+            let mut marks = Vec::new();
+            i += 1;
+            while let Some(m) = chars.get(i).and_then(|&c| mark(c)) {
+                marks.push(m);
+                i += 1;
+            }
+            if let Some(l) = chars.get(i).and_then(|&c| letter(c)) {
+                out.extend(l.to_uppercase());
+                i += 1;
+            }
+            out.extend(marks);
+            continue;
+        }
+        if let Some(l) = letter(c) {
+            i += 1;
+            if l == 'σ' {
+                // s1 medial, s2 final, s3 lunate; otherwise by position
+                match chars.get(i) {
+                    Some('1') => i += 1,
+                    Some('2') => {
+                        out.push('ς');
+                        i += 1;
+                        continue;
+                    }
+                    Some('3') => {
+                        out.push('ϲ');
+                        i += 1;
+                        continue;
+                    }
+                    next if !next.is_some_and(|&n| letter(n).is_some() || mark(n).is_some()) => {
+                        out.push('ς');
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+            out.push(l);
+            continue;
+        }
+        match mark(c) {
+            Some(m) => out.push(m),
+            None => out.push(match c {
+                ':' => '·',
+                '\'' => '’',
+                c => c,
+            }),
+        }
+        i += 1;
+    }
+    out.nfc().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_greek;
+
+    #[test]
+    fn test_to_greek() {
+        // Breathings and accents, on small letters and before capitals
+        assert_eq!(to_greek("lo/gos"), "λόγος");
+        assert_eq!(to_greek("*)aqh=nai"), "Ἀθῆναι");
+        assert_eq!(to_greek("o(do\\s"), "ὁδὸς");
+        assert_eq!(to_greek("pai+s"), "παϊς");
+        // Iota subscript
+        assert_eq!(to_greek("w)|dh/"), "ᾠδή");
+        // Final sigma by position, or as written
+        assert_eq!(to_greek("sofo/s, sofoi/"), "σοφός, σοφοί");
+        assert_eq!(to_greek("s2s1s3"), "ςσϲ");
+        // Punctuation, and text already in Greek
+        assert_eq!(to_greek("a:b'"), "α·β’");
+        assert_eq!(to_greek("λόγος"), "λόγος");
+    }
+}
@@ -0,0 +1,32 @@
+//! Every form a dictionary licenses, for archives that carry a surface-form
+//! table (`--expand-forms`). Lookups pair a lemma with its own paradigm, or
+//! with any paradigm if it names none, so the table has lemmas × endings
+//! entries at most: fast to probe, but it can outweigh the rest of the
+//! archive.
+
+use logos_protocol::{Dictionary, MorphFlags, SurfaceForm};
+
+/// Stem plus ending for each lemma and paradigm that share a dialect and
+/// period, in the order a scan of the dictionary finds them.
+pub fn surface_forms(dict: &Dictionary) -> anyhow::Result<Vec<SurfaceForm>> {
+    let mut forms = Vec::new();
+    for lemma in &dict.lemmas {
+        for paradigm in dict.paradigms.iter().filter(|p| lemma.inflects_by(p.id)) {
+            let Some(dialects) = lemma.dialects.combine(paradigm.dialects) else { continue };
+            let Some(periods) = lemma.periods.combine(paradigm.periods) else { continue };
+            for (bits, ending) in &paradigm.endings {
+                let flags = MorphFlags::from_bits_retain(*bits);
+                let form = logos_morph::inflect(&lemma.text, [(*bits, ending.as_str())], flags)?;
+                forms.push(SurfaceForm {
+                    form,
+                    lemma: lemma.id,
+                    flags: *bits,
+                    ending_len: u8::try_from(ending.len())?,
+                    dialects,
+                    periods,
+                });
+            }
+        }
+    }
+    Ok(forms)
+}
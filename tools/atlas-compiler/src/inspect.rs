@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use logos_protocol::container::{is_container, ContainerHeader, HEADER_LEN};
+use logos_protocol::{BuildInfo, DictionaryHandle, DictionaryStats};
+use rkyv::AlignedVec;
+use serde::Serialize;
+
+/// Suffixes listed in the text summary; `--json` lists them all.
+const SUFFIXES_SHOWN: usize = 20;
+
+#[derive(clap::Args)]
+pub struct InspectArgs {
+    /// Compiled dictionary (.rkyv, plain or compressed)
+    #[arg(value_name = "FILE")]
+    input: PathBuf,
+
+    /// Print the summary as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    version: u32,
+    info: BuildInfo,
+    stats: DictionaryStats,
+}
+
+pub fn run(args: &InspectArgs) -> anyhow::Result<()> {
+    let data = fs::read(&args.input).with_context(|| format!("reading {:?}", args.input))?;
+    let data = if is_container(&data) {
+        let header = ContainerHeader::parse(&data).map_err(|e| anyhow::anyhow!("{}", e))?;
+        let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&data[HEADER_LEN..])
+            .map_err(|e| anyhow::anyhow!("inflating {:?}: {:?}", args.input, e.status))?;
+        anyhow::ensure!(raw.len() == header.raw_len as usize, "{:?} inflated to {} bytes, header declared {}", args.input, raw.len(), header.raw_len);
+        raw
+    } else {
+        data
+    };
+    let mut aligned = AlignedVec::with_capacity(data.len());
+    aligned.extend_from_slice(&data);
+    let handle = DictionaryHandle::new(aligned).map_err(|e| anyhow::anyhow!("{:?}: {}", args.input, e))?;
+
+    let archived = handle.archived();
+    let summary = Summary {
+        version: archived.version,
+        info: rkyv::Deserialize::deserialize(&archived.info, &mut rkyv::Infallible)?,
+        stats: archived.stats(),
+    };
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+    } else {
+        print_summary(&summary);
+    }
+    Ok(())
+}
+
+fn print_summary(summary: &Summary) {
+    let (info, stats) = (&summary.info, &summary.stats);
+    println!("📦 Dictionary version {}, content hash {:016x}", summary.version, info.content_hash);
+    if !info.sources.is_empty() {
+        println!("   built at {} from {}", info.built_at, info.sources.join(", "));
+    }
+    println!("📚 {} lemmas", stats.lemmas);
+    for count in &stats.lemmas_by_pos {
+        println!("   {:<14} {}", format!("{:?}", count.pos), count.lemmas);
+    }
+    println!("🧩 {} paradigms", stats.paradigms);
+    for size in &stats.paradigm_sizes {
+        println!("   {:>3} endings: {}", size.endings, size.paradigms);
+    }
+    println!("🔚 {} distinct suffixes (most frequent first)", stats.suffixes.len());
+    for suffix in stats.suffixes.iter().take(SUFFIXES_SHOWN) {
+        let shown = if suffix.suffix.is_empty() { "∅" } else { suffix.suffix.as_str() };
+        println!("   -{:<10} {}", shown, suffix.cells);
+    }
+    println!(
+        "   {} pronoun forms, {} names, {} tag transitions, {} pooled string bytes",
+        stats.pronoun_forms, stats.names, stats.transitions, stats.pool_bytes
+    );
+    if stats.surface_forms > 0 {
+        println!("🧮 {} pre-expanded surface forms", stats.surface_forms);
+    }
+}
@@ -1,39 +1,336 @@
-use clap::Parser;
+mod betacode;
+mod expand;
+mod inspect;
+mod registry;
+mod report;
+mod semantics;
+mod tabular;
+mod tei;
+mod templates;
+mod watch;
+mod wordnet;
+
+use clap::{Parser, Subcommand, ValueEnum};
 use std::fs;
-use std::path::PathBuf;
-use logos_protocol::{Dictionary};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use logos_protocol::{BuildInfo, Dictionary, DictionaryArchive, DictionaryHandle, SemanticNetwork, SurfaceForm};
+use logos_protocol::container::{Codec, ContainerHeader};
 use rkyv::ser::{serializers::AllocSerializer, Serializer};
+use rkyv::AlignedVec;
+
+use registry::Registry;
 
 #[derive(Parser)]
 #[command(author, version, about = "Compiles JSON dictionary to rkyv binary")]
+#[command(group = clap::ArgGroup::new("stable").args(["registry", "hash_ids"]))]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 struct Cli {
-    #[arg(short, long, value_name = "FILE")]
-    input: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(short, long, value_name = "FILE", required = true)]
+    input: Option<PathBuf>,
+
+    #[arg(short, long, value_name = "FILE", required = true)]
+    output: Option<PathBuf>,
+
+    /// Format of `--input`: a `Dictionary` as JSON, or a lexicon table
+    /// (columns headword, pos, gender, paradigm, gloss) whose paradigms
+    /// come from `--paradigms`
+    #[arg(long, value_enum, default_value_t = InputFormat::Json)]
+    format: InputFormat,
+
+    /// Paradigm definitions for a CSV/TSV lexicon, in the same format
+    /// (columns paradigm, morphology, ending)
+    #[arg(long, value_name = "FILE")]
+    paradigms: Vec<PathBuf>,
+
+    /// Wrap the archive in a zlib-compressed container (smaller download;
+    /// inflated by the engine on load)
+    #[arg(long)]
+    compress: bool,
+
+    /// Keep lemma IDs stable across rebuilds: take them from this registry
+    /// (JSON, created if missing) and register new lemmas in it
+    #[arg(long, value_name = "FILE")]
+    registry: Option<PathBuf>,
+
+    /// Keep lemma IDs stable across rebuilds by deriving them from a hash of
+    /// each lemma's part of speech and text
+    #[arg(long, conflicts_with = "registry")]
+    hash_ids: bool,
+
+    /// Semantics files (SemanticNetwork JSON) that must only reference
+    /// lemmas of this build; fails otherwise
+    #[arg(long, value_name = "FILE", requires = "stable")]
+    check_semantics: Vec<PathBuf>,
+
+    /// Semantic relations written between headwords (`apple isa food;
+    /// eat requires edible`), resolved against this build's lemmas
+    #[arg(long, value_name = "FILE", requires = "semantics_output")]
+    semantics: Vec<PathBuf>,
+
+    /// Where the network compiled from `--semantics` is written (rkyv, for
+    /// `load_semantics_bytes`)
+    #[arg(long, value_name = "FILE", requires = "semantics")]
+    semantics_output: Option<PathBuf>,
 
-    #[arg(short, long, value_name = "FILE")]
-    output: PathBuf,
+    /// Store every lemma's forms under every paradigm in a hash table, so a
+    /// word is analysed with one probe instead of a scan (a larger archive
+    /// for less work in the browser)
+    #[arg(long)]
+    expand_forms: bool,
+
+    /// Keep running and recompile whenever the input (or a paradigm or
+    /// semantics file) changes, printing which lemmas and paradigms changed.
+    /// Each rebuild writes the whole archive again; only unchanged expanded
+    /// forms are reused, and an unchanged dictionary is not written at all
+    #[arg(long)]
+    watch: bool,
+
+    /// Compile twice and fail unless both builds are byte-identical
+    #[arg(long)]
+    verify_reproducible: bool,
+
+    /// Also write the size report printed after compiling as JSON
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    Json,
+    Csv,
+    Tsv,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Summarize a compiled dictionary: build metadata, lemmas by part of
+    /// speech, paradigm sizes and the suffix inventory
+    Inspect(inspect::InspectArgs),
+    /// Extract headwords, parts of speech and short glosses from TEI lexica
+    /// (the Perseus LSJ XML) into Dictionary JSON
+    ImportTei(tei::ImportArgs),
+    /// Turn WordNet hypernyms and attributes of synsets mapped to Greek
+    /// lemmas (Open Multilingual Wordnet) into a semantics file
+    ImportWordnet(wordnet::ImportArgs),
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    match &cli.command {
+        Some(Command::Inspect(args)) => return inspect::run(args),
+        Some(Command::ImportTei(args)) => return tei::run(args),
+        Some(Command::ImportWordnet(args)) => return wordnet::run(args),
+        None => {}
+    }
+    // Both are required without a subcommand
+    let (Some(input), Some(output)) = (&cli.input, &cli.output) else {
+        unreachable!("clap requires --input and --output");
+    };
 
-    println!("📖 Reading JSON from {:?}...", cli.input);
-    let input_data = fs::read_to_string(&cli.input)?;
+    let mut session = Session { registry: cli.registry.as_deref().map(Registry::load).transpose()?, previous: None, forms: Vec::new() };
+    build(&cli, input, output, &mut session)?;
+    if cli.watch {
+        watch::run(&cli, input, output, &mut session)?;
+    }
+    Ok(())
+}
+
+/// What a run keeps from one build to the next (`--watch` builds many).
+struct Session {
+    registry: Option<Registry>,
+    /// The last dictionary built, to tell what a rebuild changed
+    previous: Option<Dictionary>,
+    /// Surface forms expanded from `previous`, reused while its lemmas and
+    /// paradigms stay the same
+    forms: Vec<SurfaceForm>,
+}
 
-    // 2. Deserialize JSON to Rust Structs
-    // Ensure logos-protocol types derive Deserialize (from serde)
-    let dict: Dictionary = serde_json::from_str(&input_data)?;
+/// Compiles `input` into `output`. A rebuild whose content did not change
+/// writes nothing.
+fn build(cli: &Cli, input: &Path, output: &Path, session: &mut Session) -> anyhow::Result<()> {
+    // Kept only once the build succeeded, so a failed run registers nothing
+    let mut registry = session.registry.clone();
+    let dict = load(cli, input, registry.as_mut())?;
+
+    // Semantics rely on the stable IDs
+    for path in &cli.check_semantics {
+        let network: SemanticNetwork = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let problems = registry::check_semantics(&network, &dict, registry.as_ref());
+        for (edge, id, why) in &problems {
+            println!("❌ {:?} edge {}: lemma {}: {}", path, edge, id, why);
+        }
+        if !problems.is_empty() {
+            anyhow::bail!("{:?} references {} unknown lemma IDs", path, problems.len());
+        }
+        println!("🔗 {:?}: all {} edges reference stable IDs", path, network.edges.len());
+    }
+
+    if let Some(path) = &cli.semantics_output {
+        let network = semantics::compile(&cli.semantics, &dict)?;
+        semantics::save(&network, path)?;
+        println!("🔗 {} semantic edges, {} preposition roles and {} animacy marks written to {:?}",
+            network.edges.len(), network.prepositions.len(), network.animacy.len() + network.animacy_constraints.len(), path);
+    }
+
+    let previous = session.previous.as_ref();
+    if previous.is_some_and(|p| BuildInfo::describe(p).content_hash == BuildInfo::describe(&dict).content_hash) {
+        println!("💤 No content changes; {:?} left as it is", output);
+        return Ok(());
+    }
+    let forms = if !cli.expand_forms {
+        Vec::new()
+    } else if previous.is_some_and(|p| p.lemmas == dict.lemmas && p.paradigms == dict.paradigms) {
+        println!("🧮 Reusing {} surface forms (lemmas and paradigms unchanged)", session.forms.len());
+        session.forms.clone()
+    } else {
+        let forms = expand::surface_forms(&dict)?;
+        println!("🧮 Expanded {} surface forms", forms.len());
+        forms
+    };
+
+    let built = compile(cli, input, &dict, &forms)?;
+    if cli.verify_reproducible {
+        println!("🔁 Compiling again to check the output is reproducible...");
+        let again = load(cli, input, session.registry.clone().as_mut())?;
+        let forms = if cli.expand_forms { expand::surface_forms(&again)? } else { Vec::new() };
+        let again = compile(cli, input, &again, &forms)?;
+        let (first, second) = (registry::fnv(&built.bytes), registry::fnv(&again.bytes));
+        if built.bytes != again.bytes {
+            let at = built.bytes.iter().zip(&again.bytes).position(|(a, b)| a != b).unwrap_or(built.bytes.len().min(again.bytes.len()));
+            anyhow::bail!("the two builds differ (hashes {:016x} and {:016x}, first at byte {})", first, second, at);
+        }
+        println!("🔁 Both builds are byte-identical ({:016x})", first);
+    }
+
+    // 5. Write Binary
+    fs::write(output, &built.bytes)?;
+    if let (Some(registry), Some(path)) = (&registry, &cli.registry) {
+        registry.save(path)?;
+    }
+    session.registry = registry;
+
+    if let Some(previous) = previous {
+        watch::print_changes(previous, &dict);
+    }
+    // Stats are read back from the archive, as a loader would see it
+    let stats = DictionaryHandle::new(built.aligned).map_err(|e| anyhow::anyhow!("{}", e))?.archived().stats();
+    let report = report::Report::new(&dict, &built.archive, stats, built.archive_bytes, built.bytes.len());
+    report.print();
+    if let Some(path) = &cli.report {
+        report.save(path)?;
+        println!("📝 Report written to {:?}", path);
+    }
+
+    println!("✅ Success! Binary written to {:?}", output);
+    session.previous = Some(dict);
+    session.forms = forms;
+    Ok(())
+}
+
+/// Reads the input, gives lemmas their stable IDs when asked to, and puts
+/// every collection in canonical order.
+fn load(cli: &Cli, input: &Path, registry: Option<&mut Registry>) -> anyhow::Result<Dictionary> {
+    let mut dict: Dictionary = match cli.format {
+        InputFormat::Json => {
+            println!("📖 Reading JSON from {:?}...", input);
+            let input_data = fs::read_to_string(input)?;
+            let mut source: serde_json::Value = serde_json::from_str(&input_data)?;
+            let expanded = templates::expand(&mut source)?;
+            if expanded > 0 {
+                println!("🧬 Expanded {} paradigms from templates", expanded);
+            }
+
+            // 2. Deserialize JSON to Rust Structs
+            // Ensure logos-protocol types derive Deserialize (from serde)
+            serde_json::from_value(source)?
+        }
+        InputFormat::Csv | InputFormat::Tsv => {
+            anyhow::ensure!(!cli.paradigms.is_empty(), "a {} lexicon needs --paradigms", if cli.format == InputFormat::Csv { "CSV" } else { "TSV" });
+            println!("📖 Reading lexicon table {:?} with {} paradigm files...", input, cli.paradigms.len());
+            let delimiter = if cli.format == InputFormat::Csv { tabular::Delimiter::Comma } else { tabular::Delimiter::Tab };
+            tabular::read(input, &cli.paradigms, delimiter)?
+        }
+    };
 
     println!("⚙️  Compiling Dictionary version {} with {} lemmas...", dict.version, dict.lemmas.len());
 
-    // 3. Serialize to RKYV
+    // Stable lemma IDs
+    if let Some(registry) = registry {
+        let added = registry.assign(&mut dict)?;
+        println!("🪪 Lemma IDs from the registry ({} newly registered)", added);
+    } else if cli.hash_ids {
+        let collisions = registry::assign_hashed(&mut dict)?;
+        println!("🪪 Lemma IDs from content hashes ({} collisions probed)", collisions);
+    }
+
+    // Canonical order, so the archive depends on the content rather than on
+    // how the source happened to list it. Sorts are stable: endings keep
+    // their order (the citation form first), as do homographs.
+    dict.lemmas.sort_by_key(|l| l.id);
+    dict.paradigms.sort_by_key(|p| p.id);
+    dict.pronouns.sort_by_key(|p| p.lemma);
+    dict.names.sort_by_key(|n| (n.lemma, n.kind as u8));
+    dict.transitions.sort_by_key(|t| (t.from, t.to, t.weight));
+    Ok(dict)
+}
+
+/// An archive and the bytes to write for it.
+struct Built {
+    archive: DictionaryArchive,
+    aligned: AlignedVec,
+    archive_bytes: usize,
+    /// What gets written: the archive, or its compressed container
+    bytes: Vec<u8>,
+}
+
+fn compile(cli: &Cli, input: &Path, dict: &Dictionary, forms: &[SurfaceForm]) -> anyhow::Result<Built> {
+    // 3. Intern stems and endings into the shared string pool
+    let (mut archive, unique) = DictionaryArchive::build_with_forms(dict, forms).map_err(|e| anyhow::anyhow!("{}", e))?;
+    println!("🧵 Pooled {} distinct strings into {} bytes", unique, archive.pool.len());
+
+    // Build metadata (the content hash was computed by `build`)
+    let mut sources = vec![input];
+    sources.extend(cli.paradigms.iter().map(PathBuf::as_path));
+    archive.info.built_at = build_timestamp(&sources)?;
+    archive.info.sources = sources.iter().map(|source| source_name(source)).collect();
+    println!("🔖 Content hash {:016x}", archive.info.content_hash);
+
+    // 4. Serialize to RKYV (same layout as serializing `dict` directly)
     let mut serializer = AllocSerializer::<256>::default();
-    serializer.serialize_value(&dict).expect("Failed to rkyv serialize");
-    let bytes = serializer.into_serializer().into_inner();
+    serializer.serialize_value(&archive).expect("Failed to rkyv serialize");
+    let aligned = serializer.into_serializer().into_inner();
+    let mut bytes = aligned.to_vec();
+    let archive_bytes = bytes.len();
 
-    // 4. Write Binary
-    fs::write(&cli.output, bytes)?;
+    if cli.compress {
+        let raw_len = u32::try_from(bytes.len())?;
+        let mut container = ContainerHeader { codec: Codec::Zlib, raw_len }.to_bytes().to_vec();
+        container.extend(miniz_oxide::deflate::compress_to_vec_zlib(&bytes, 9));
+        println!("🗜️  Compressed {} -> {} bytes", bytes.len(), container.len());
+        bytes = container;
+    }
+    Ok(Built { archive, aligned, archive_bytes, bytes })
+}
 
-    println!("✅ Success! Binary written to {:?}", cli.output);
-    Ok(())
+/// Honors SOURCE_DATE_EPOCH; otherwise the time the newest source was last
+/// modified, so compiling unchanged sources again gives identical archives.
+fn build_timestamp(sources: &[&Path]) -> anyhow::Result<u64> {
+    if let Ok(epoch) = std::env::var("SOURCE_DATE_EPOCH") {
+        return Ok(epoch.trim().parse()?);
+    }
+    let mut newest = 0;
+    for source in sources {
+        let modified = fs::metadata(source)?.modified()?;
+        newest = newest.max(modified.duration_since(UNIX_EPOCH)?.as_secs());
+    }
+    Ok(newest)
+}
+
+/// File name only; build-machine directories are not worth shipping.
+fn source_name(path: &Path) -> String {
+    path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned()
 }
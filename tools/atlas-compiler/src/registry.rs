@@ -0,0 +1,264 @@
+//! Stable lemma IDs. The pipeline numbers lemmas afresh on every run, so a
+//! rebuild after adding one lemma can shift the rest; saved `SemanticNetwork`
+//! files and cached analyses then point at the wrong words. Here IDs come
+//! from a registry file kept between builds, or from a hash of the lemma.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use logos_protocol::{Dictionary, Lemma, LemmaId, SemanticNetwork};
+use serde::{Deserialize, Serialize};
+
+/// Lemma keys and the IDs they were given. IDs of lemmas since removed stay
+/// reserved, so they are never handed to a different word.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Registry {
+    /// Next ID to hand out
+    pub next_id: u32,
+    /// `key` -> ID
+    pub lemmas: BTreeMap<String, u32>,
+}
+
+impl Registry {
+    /// An empty registry when `path` does not exist yet.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self { next_id: 1, lemmas: BTreeMap::new() });
+        }
+        let text = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+        serde_json::from_str(&text).with_context(|| format!("parsing {:?}", path))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let text = serde_json::to_string_pretty(self)?;
+        fs::write(path, text + "\n").with_context(|| format!("writing {:?}", path))
+    }
+
+    /// Gives every lemma of `dict` its registered ID, registering new ones.
+    /// Returns how many were new.
+    pub fn assign(&mut self, dict: &mut Dictionary) -> anyhow::Result<usize> {
+        let keys = keys(&dict.lemmas);
+        let mut ids = HashMap::new();
+        let mut added = 0;
+        for (lemma, key) in dict.lemmas.iter().zip(keys) {
+            let id = match self.lemmas.get(&key) {
+                Some(&id) => id,
+                None => {
+                    let id = self.next_id;
+                    if id > LemmaId::LOCAL_MASK {
+                        bail!("the registry ran out of lemma IDs ({} max)", LemmaId::LOCAL_MASK);
+                    }
+                    self.next_id += 1;
+                    self.lemmas.insert(key, id);
+                    added += 1;
+                    id
+                }
+            };
+            ids.insert(lemma.id, LemmaId(id));
+        }
+        renumber(dict, &ids)?;
+        Ok(added)
+    }
+
+    /// True for IDs the registry gave out, including retired ones.
+    fn contains(&self, id: u32) -> bool {
+        self.lemmas.values().any(|&v| v == id)
+    }
+}
+
+/// Derives each lemma's ID from a hash of its key, so IDs survive rebuilds
+/// without a registry. Colliding keys are probed to the next free ID in key
+/// order; returns how many collided (their IDs may move when lemmas are added).
+pub fn assign_hashed(dict: &mut Dictionary) -> anyhow::Result<usize> {
+    let mut keyed: Vec<(String, LemmaId)> = keys(&dict.lemmas).into_iter()
+        .zip(dict.lemmas.iter().map(|l| l.id))
+        .collect();
+    keyed.sort();
+
+    let mut taken = BTreeSet::new();
+    let mut ids = HashMap::new();
+    let mut collisions = 0;
+    for (key, old) in keyed {
+        // 0 is left unused, like in the pipeline's numbering
+        let mut id = (fnv(key.as_bytes()) % LemmaId::LOCAL_MASK as u64) as u32 + 1;
+        if !taken.insert(id) {
+            collisions += 1;
+            while !taken.insert(id) {
+                id = id % LemmaId::LOCAL_MASK + 1;
+            }
+        }
+        ids.insert(old, LemmaId(id));
+    }
+    renumber(dict, &ids)?;
+    Ok(collisions)
+}
+
+/// Identity of a lemma across builds: part of speech and text, with a
+/// running number for homographs (`Noun:λόγ`, `Noun:λόγ#2`).
+pub fn keys(lemmas: &[Lemma]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    lemmas.iter()
+        .map(|l| {
+            let key = format!("{:?}:{}", l.pos, l.text);
+            let n = seen.entry(key.clone()).or_default();
+            *n += 1;
+            if *n == 1 { key } else { format!("{}#{}", key, n) }
+        })
+        .collect()
+}
+
+/// Rewrites lemma IDs and every reference to them.
+fn renumber(dict: &mut Dictionary, ids: &HashMap<LemmaId, LemmaId>) -> anyhow::Result<()> {
+    if ids.len() != dict.lemmas.len() {
+        bail!("the input gives several lemmas the same ID");
+    }
+    let map = |id: &mut LemmaId, what: &str| -> anyhow::Result<()> {
+        *id = *ids.get(id).with_context(|| format!("{} refers to lemma {}, which is not in the dictionary", what, id.0))?;
+        Ok(())
+    };
+    for lemma in &mut dict.lemmas {
+        lemma.id = ids[&lemma.id];
+    }
+    for pronoun in &mut dict.pronouns {
+        map(&mut pronoun.lemma, "a pronoun paradigm")?;
+    }
+    for name in &mut dict.names {
+        map(&mut name.lemma, "a gazetteer entry")?;
+    }
+    Ok(())
+}
+
+/// Lemma IDs of a semantics file that the stable numbering does not know:
+/// (edge index, ID, why). With a registry, retired lemmas are told apart
+/// from IDs it never gave out.
+pub fn check_semantics(network: &SemanticNetwork, dict: &Dictionary, registry: Option<&Registry>) -> Vec<(usize, u32, &'static str)> {
+    let present: BTreeSet<u32> = dict.lemmas.iter().map(|l| l.id.0).collect();
+    let mut problems = Vec::new();
+    for (i, edge) in network.edges.iter().enumerate() {
+        for id in [edge.from.0, edge.to.0] {
+            if present.contains(&id) {
+                continue;
+            }
+            let why = match registry {
+                Some(registry) if registry.contains(id) => "lemma was removed from the dictionary",
+                Some(_) => "ID was never registered",
+                None => "no lemma has this ID",
+            };
+            problems.push((i, id, why));
+        }
+    }
+    problems
+}
+
+pub fn fnv(data: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in data {
+        h ^= b as u64;
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logos_protocol::{Dialect, Gender, NameEntry, NameKind, PartOfSpeech, Period};
+
+    /// A dictionary of `words` numbered from 1 in order, as the pipeline
+    /// does, with the last one in the gazetteer.
+    fn dictionary(words: &[(PartOfSpeech, &str)]) -> Dictionary {
+        let lemmas: Vec<Lemma> = words.iter().zip(1..)
+            .map(|(&(pos, text), id)| Lemma {
+                id: LemmaId(id),
+                text: text.to_string(),
+                gender: Gender::Masculine,
+                pos,
+                urn: None,
+                gloss: None,
+                dialects: Dialect::empty(),
+                periods: Period::empty(),
+                paradigm: None,
+            })
+            .collect();
+        let names = vec![NameEntry { lemma: LemmaId(lemmas.len() as u32), kind: NameKind::Person }];
+        Dictionary { lemmas, names, ..Default::default() }
+    }
+
+    /// Key -> ID of every lemma.
+    fn ids(dict: &Dictionary) -> BTreeMap<String, u32> {
+        keys(&dict.lemmas).into_iter().zip(dict.lemmas.iter().map(|l| l.id.0)).collect()
+    }
+
+    const NOUN: PartOfSpeech = PartOfSpeech::Noun;
+    const VERB: PartOfSpeech = PartOfSpeech::Verb;
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry { next_id: 1, lemmas: BTreeMap::new() };
+        let mut first = dictionary(&[(NOUN, "λόγ"), (VERB, "λέγ"), (NOUN, "λόγ"), (NOUN, "Σωκράτ")]);
+        assert_eq!(registry.assign(&mut first).unwrap(), 4);
+        let before = ids(&first);
+        assert_eq!(before.keys().collect::<Vec<_>>(), ["Noun:Σωκράτ", "Noun:λόγ", "Noun:λόγ#2", "Verb:λέγ"]);
+
+        // Reordered, with a lemma added in front: the rest keep their IDs
+        let mut reordered = dictionary(&[(NOUN, "ἄνθρωπ"), (VERB, "λέγ"), (NOUN, "λόγ"), (NOUN, "λόγ"), (NOUN, "Σωκράτ")]);
+        assert_eq!(registry.assign(&mut reordered).unwrap(), 1);
+        let after = ids(&reordered);
+        assert!(before.iter().all(|(key, id)| after[key] == *id));
+        assert_eq!(after["Noun:ἄνθρωπ"], 5);
+        assert_eq!(reordered.names[0].lemma.0, before["Noun:Σωκράτ"]);
+
+        // Removed: the ID stays reserved, and comes back with the lemma
+        let mut removed = dictionary(&[(NOUN, "ἄνθρωπ"), (NOUN, "λόγ"), (NOUN, "λόγ"), (NOUN, "Σωκράτ")]);
+        assert_eq!(registry.assign(&mut removed).unwrap(), 0);
+        assert!(registry.contains(before["Verb:λέγ"]));
+        let mut added = dictionary(&[(NOUN, "ἄνθρωπ"), (NOUN, "λόγ"), (NOUN, "λόγ"), (NOUN, "θε"), (NOUN, "Σωκράτ")]);
+        assert_eq!(registry.assign(&mut added).unwrap(), 1);
+        assert_eq!(ids(&added)["Noun:θε"], 6);
+        let mut readded = dictionary(&[(VERB, "λέγ"), (NOUN, "Σωκράτ")]);
+        assert_eq!(registry.assign(&mut readded).unwrap(), 0);
+        assert_eq!(ids(&readded)["Verb:λέγ"], before["Verb:λέγ"]);
+
+        // A saved registry hands out the same IDs
+        let path = std::env::temp_dir().join(format!("atlas-registry-{}.json", std::process::id()));
+        registry.save(&path).unwrap();
+        let mut loaded = Registry::load(&path).unwrap();
+        let mut again = dictionary(&[(NOUN, "θε"), (NOUN, "Σωκράτ")]);
+        loaded.assign(&mut again).unwrap();
+        assert_eq!(ids(&again)["Noun:θε"], 6);
+        assert_eq!(loaded.next_id, 7);
+    }
+
+    #[test]
+    fn test_hashed_ids() {
+        let mut first = dictionary(&[(NOUN, "λόγ"), (VERB, "λέγ"), (NOUN, "λόγ"), (NOUN, "Σωκράτ")]);
+        assert_eq!(assign_hashed(&mut first).unwrap(), 0);
+        let before = ids(&first);
+        assert!(before.values().all(|&id| id != 0 && id <= LemmaId::LOCAL_MASK));
+
+        let mut changed = dictionary(&[(NOUN, "ἄνθρωπ"), (NOUN, "λόγ"), (NOUN, "λόγ"), (NOUN, "Σωκράτ"), (VERB, "λέγ")]);
+        assign_hashed(&mut changed).unwrap();
+        let after = ids(&changed);
+        assert!(before.iter().all(|(key, id)| after[key] == *id));
+        assert_eq!(changed.names[0].lemma.0, before["Verb:λέγ"]);
+    }
+
+    #[test]
+    fn test_check_semantics() {
+        let mut registry = Registry { next_id: 1, lemmas: BTreeMap::new() };
+        let mut dict = dictionary(&[(NOUN, "λόγ"), (VERB, "λέγ")]);
+        registry.assign(&mut dict).unwrap();
+        let mut dict = dictionary(&[(NOUN, "λόγ")]);
+        registry.assign(&mut dict).unwrap();
+
+        let edge = |from, to| logos_protocol::SemanticEdge { from: LemmaId(from), to: LemmaId(to), relation: logos_protocol::Relation::IsA };
+        let network = SemanticNetwork { version: 1, edges: vec![edge(1, 2), edge(1, 9)], prepositions: Vec::new(), animacy: Vec::new(), animacy_constraints: Vec::new() };
+        assert_eq!(check_semantics(&network, &dict, Some(&registry)), [
+            (0, 2, "lemma was removed from the dictionary"),
+            (1, 9, "ID was never registered"),
+        ]);
+        assert_eq!(check_semantics(&network, &dict, None)[0], (0, 2, "no lemma has this ID"));
+    }
+}
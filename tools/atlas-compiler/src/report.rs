@@ -0,0 +1,162 @@
+//! What a compiled dictionary is made of, printed after every build (and
+//! written as JSON with `--report`), so lexicon maintainers can see what
+//! the browser download is spent on.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use logos_protocol::{Dictionary, DictionaryArchive, DictionaryStats};
+use rkyv::ser::{serializers::AllocSerializer, Serializer};
+use serde::Serialize;
+
+/// Entries listed per "largest" table.
+const LARGEST_SHOWN: usize = 10;
+
+#[derive(Serialize)]
+pub struct Report {
+    pub stats: DictionaryStats,
+    /// Paradigm cells over all paradigms
+    pub endings: u32,
+    /// Size of the archive before compression
+    pub archive_bytes: usize,
+    /// Size of the file written (the container when compressed)
+    pub written_bytes: usize,
+    /// Archived size of each section on its own, largest first
+    pub sections: Vec<Share>,
+    /// Pool bytes by the kind of string that first brought them in, largest first
+    pub pool: Vec<Share>,
+    /// Paradigms with the most endings
+    pub largest_paradigms: Vec<Contributor>,
+    /// Lemmas with the longest glosses
+    pub longest_glosses: Vec<Contributor>,
+}
+
+#[derive(Serialize)]
+pub struct Share {
+    pub name: &'static str,
+    pub bytes: usize,
+}
+
+#[derive(Serialize)]
+pub struct Contributor {
+    pub id: u32,
+    /// Lemma text; empty for paradigms
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    /// Endings of a paradigm, bytes of a gloss
+    pub size: usize,
+}
+
+impl Report {
+    pub fn new(dict: &Dictionary, archive: &DictionaryArchive, stats: DictionaryStats, archive_bytes: usize, written_bytes: usize) -> Self {
+        let mut sections = vec![
+            Share { name: "lemmas", bytes: archived_len(&archive.lemmas) },
+            Share { name: "paradigms", bytes: archived_len(&archive.paradigms) },
+            Share { name: "pronouns", bytes: archived_len(&archive.pronouns) },
+            Share { name: "names", bytes: archived_len(&archive.names) },
+            Share { name: "transitions", bytes: archived_len(&archive.transitions) },
+            Share { name: "string pool", bytes: archived_len(&archive.pool) + archived_len(&archive.pool_ends) },
+            Share { name: "lemma index", bytes: archived_len(&archive.by_text) + archived_len(&archive.by_id) },
+            Share { name: "build info", bytes: archived_len(&archive.info) },
+            Share { name: "surface forms", bytes: archived_len(&archive.forms) + archived_len(&archive.form_slots) },
+        ];
+        sections.sort_by_key(|s| std::cmp::Reverse(s.bytes));
+
+        let mut largest_paradigms: Vec<Contributor> = dict.paradigms.iter()
+            .map(|p| Contributor { id: p.id.0, text: String::new(), size: p.endings.len() })
+            .collect();
+        largest_paradigms.sort_by_key(|c| std::cmp::Reverse(c.size));
+        largest_paradigms.truncate(LARGEST_SHOWN);
+
+        let mut longest_glosses: Vec<Contributor> = dict.lemmas.iter()
+            .filter_map(|l| Some(Contributor { id: l.id.0, text: l.text.clone(), size: l.gloss.as_ref()?.len() }))
+            .collect();
+        longest_glosses.sort_by_key(|c| std::cmp::Reverse(c.size));
+        longest_glosses.truncate(LARGEST_SHOWN);
+
+        Self {
+            endings: dict.paradigms.iter().map(|p| p.endings.len() as u32).sum(),
+            stats,
+            archive_bytes,
+            written_bytes,
+            sections,
+            pool: pool_shares(dict, archive.pool.len()),
+            largest_paradigms,
+            longest_glosses,
+        }
+    }
+
+    pub fn print(&self) {
+        println!("📊 {} lemmas, {} paradigms, {} endings; {} bytes archived, {} written",
+            self.stats.lemmas, self.stats.paradigms, self.endings, self.archive_bytes, self.written_bytes);
+        let by_pos: Vec<String> = self.stats.lemmas_by_pos.iter().map(|c| format!("{:?} {}", c.pos, c.lemmas)).collect();
+        if !by_pos.is_empty() {
+            println!("   by part of speech: {}", by_pos.join(", "));
+        }
+        for section in &self.sections {
+            println!("   {:<13} {:>10} bytes {:>5.1}%", section.name, section.bytes, percent(section.bytes, self.archive_bytes));
+        }
+        let pool_bytes = self.stats.pool_bytes as usize;
+        let pool: Vec<String> = self.pool.iter()
+            .filter(|s| s.bytes > 0)
+            .map(|s| format!("{} {:.1}%", s.name, percent(s.bytes, pool_bytes)))
+            .collect();
+        if !pool.is_empty() {
+            println!("   string pool: {}", pool.join(", "));
+        }
+        if let Some(largest) = self.largest_paradigms.first() {
+            println!("   largest paradigm: {} ({} endings)", largest.id, largest.size);
+        }
+        if let Some(longest) = self.longest_glosses.first() {
+            println!("   longest gloss: {} {} ({} bytes)", longest.id, longest.text, longest.size);
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let text = serde_json::to_string_pretty(self)?;
+        fs::write(path, text + "\n").with_context(|| format!("writing {:?}", path))
+    }
+}
+
+/// Bytes `value` takes when archived by itself.
+fn archived_len<T: rkyv::Serialize<AllocSerializer<256>>>(value: &T) -> usize {
+    let mut serializer = AllocSerializer::<256>::default();
+    match serializer.serialize_value(value) {
+        Ok(_) => serializer.into_serializer().into_inner().len(),
+        Err(_) => 0,
+    }
+}
+
+/// Pool bytes per kind of string, attributed the way `DictionaryArchive::build`
+/// interns them: a string shared by two kinds counts for the first. Surface
+/// forms, interned last, are what is left of `pool_bytes`.
+fn pool_shares(dict: &Dictionary, pool_bytes: usize) -> Vec<Share> {
+    let mut seen = HashSet::new();
+    let mut shares = [("stems", 0), ("urns", 0), ("glosses", 0), ("endings", 0), ("pronoun forms", 0), ("surface forms", 0)];
+    let mut add = |kind: usize, s: &str| {
+        if seen.insert(s.to_string()) {
+            shares[kind].1 += s.len();
+        }
+    };
+    for lemma in &dict.lemmas {
+        add(0, &lemma.text);
+        lemma.urn.iter().for_each(|urn| add(1, urn));
+        lemma.gloss.iter().for_each(|gloss| add(2, gloss));
+    }
+    for (_, ending) in dict.paradigms.iter().flat_map(|p| &p.endings) {
+        add(3, ending);
+    }
+    for (_, form) in dict.pronouns.iter().flat_map(|p| &p.forms) {
+        add(4, form);
+    }
+    shares[5].1 = pool_bytes.saturating_sub(shares[..5].iter().map(|(_, bytes)| bytes).sum::<usize>());
+    let mut shares: Vec<Share> = shares.into_iter().map(|(name, bytes)| Share { name, bytes }).collect();
+    shares.sort_by_key(|s| std::cmp::Reverse(s.bytes));
+    shares
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 { 0.0 } else { part as f64 * 100.0 / whole as f64 }
+}
@@ -0,0 +1,256 @@
+//! The semantics DSL: relations written between headwords instead of
+//! numeric lemma IDs, resolved against the dictionary being built.
+//!
+//! ```text
+//! # comments run to the end of the line
+//! eat requires edible; apple isa food
+//! food has edible
+//! Noun:λόγος isa concept   # a part of speech picks one of several homographs
+//! ἐν+dat location place; ἐν+dat time period
+//! εἰς destination place city
+//! ἄνθρωπος animacy human; ζῷον animacy animal
+//! λέγω subject human; δοκέω experiencer animal
+//! ```
+//!
+//! Statements are `<word> <relation> <word>`, separated by `;` or line
+//! breaks; relations are `isa` (or `is-a`), `requires` and `has`. A
+//! statement `<preposition>[+case] <role> <concept>...` gives a preposition
+//! (with an argument in that case) a semantic role, whose argument must be
+//! or have one of the concepts; roles are the `SemanticRole` names.
+//! `<word> animacy <level>` marks a concept (and its kinds) human, animal,
+//! inanimate or abstract, and `<verb> subject|experiencer <level>` sets the
+//! least animate subject or experiencer a verb takes.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use logos_protocol::{Animacy, AnimacyConstraint, AnimacyMark, AnimacyRole, Dictionary, Lemma, MorphFlags, PrepositionRole, Relation, SemanticEdge, SemanticNetwork, SemanticRole};
+use rkyv::ser::{serializers::AllocSerializer, Serializer};
+
+use crate::tabular::MAX_ERRORS_SHOWN;
+
+/// Compiles the DSL `sources` into one network over the lemmas of `dict`.
+/// Every unknown or ambiguous word and malformed statement is reported,
+/// with its file and line.
+pub fn compile(sources: &[PathBuf], dict: &Dictionary) -> anyhow::Result<SemanticNetwork> {
+    let (network, errors) = read(sources, dict)?;
+    if !errors.is_empty() {
+        for error in errors.iter().take(MAX_ERRORS_SHOWN) {
+            println!("❌ {}", error);
+        }
+        if errors.len() > MAX_ERRORS_SHOWN {
+            println!("   ... and {} more", errors.len() - MAX_ERRORS_SHOWN);
+        }
+        anyhow::bail!("{} bad semantic statements", errors.len());
+    }
+    Ok(network)
+}
+
+/// `compile`'s network, of the good statements, and a message per bad one.
+/// Only unreadable files are errors.
+fn read(sources: &[impl AsRef<Path>], dict: &Dictionary) -> anyhow::Result<(SemanticNetwork, Vec<String>)> {
+    let mut by_text: HashMap<&str, Vec<&Lemma>> = HashMap::new();
+    for lemma in &dict.lemmas {
+        by_text.entry(lemma.text.as_str()).or_default().push(lemma);
+    }
+
+    let mut edges: Vec<SemanticEdge> = Vec::new();
+    let mut prepositions = Vec::new();
+    let (mut animacy, mut animacy_constraints) = (Vec::new(), Vec::new());
+    let mut errors = Vec::new();
+    for path in sources {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            for statement in line.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+                match parse(statement, &by_text) {
+                    Ok(Statement::Role(role)) => prepositions.push(role),
+                    Ok(Statement::Animacy(mark)) => animacy.push(mark),
+                    Ok(Statement::AnimacyConstraint(constraint)) => animacy_constraints.push(constraint),
+                    Ok(Statement::Edge(edge)) => {
+                        let known = edges.iter().any(|e| e.from == edge.from && e.to == edge.to && e.relation == edge.relation);
+                        if !known {
+                            edges.push(edge);
+                        }
+                    }
+                    Err(e) => errors.push(format!("{}:{}: {}", path.display(), i + 1, e)),
+                }
+            }
+        }
+    }
+    Ok((SemanticNetwork { version: 1, edges, prepositions, animacy, animacy_constraints }, errors))
+}
+
+/// Writes `network` as the rkyv archive `load_semantics_bytes` reads.
+pub fn save(network: &SemanticNetwork, path: &Path) -> anyhow::Result<()> {
+    let mut serializer = AllocSerializer::<256>::default();
+    serializer.serialize_value(network)?;
+    fs::write(path, serializer.into_serializer().into_inner()).with_context(|| format!("writing {:?}", path))
+}
+
+enum Statement {
+    Edge(SemanticEdge),
+    Role(PrepositionRole),
+    Animacy(AnimacyMark),
+    AnimacyConstraint(AnimacyConstraint),
+}
+
+fn parse(statement: &str, by_text: &HashMap<&str, Vec<&Lemma>>) -> Result<Statement, String> {
+    let words: Vec<&str> = statement.split_whitespace().collect();
+    if let [preposition, role, concepts @ ..] = &words[..] {
+        if let Some(&role) = SemanticRole::ALL.iter().find(|r| r.name().eq_ignore_ascii_case(role)) {
+            return preposition_role(preposition, role, concepts, by_text).map(Statement::Role);
+        }
+    }
+    let [from, relation, to] = words[..] else {
+        return Err(format!("'{}': expected <word> <relation> <word>", statement));
+    };
+    let keyword = relation.to_lowercase();
+    if ["animacy", "subject", "experiencer"].contains(&keyword.as_str()) {
+        let level = *Animacy::ALL.iter().find(|a| a.name().eq_ignore_ascii_case(to))
+            .ok_or_else(|| format!("unknown animacy '{}' (expected human, animal, inanimate or abstract)", to))?;
+        let id = resolve(from, by_text)?.id;
+        return Ok(match keyword.as_str() {
+            "animacy" => Statement::Animacy(AnimacyMark { concept: id, animacy: level }),
+            "subject" => Statement::AnimacyConstraint(AnimacyConstraint { verb: id, role: AnimacyRole::Subject, minimum: level }),
+            _ => Statement::AnimacyConstraint(AnimacyConstraint { verb: id, role: AnimacyRole::Experiencer, minimum: level }),
+        });
+    }
+    let relation = match relation.to_lowercase().as_str() {
+        "isa" | "is-a" => Relation::IsA,
+        "requires" => Relation::RequiresAttribute,
+        "has" => Relation::HasAttribute,
+        _ => return Err(format!("unknown relation '{}' (expected isa, requires, has, animacy, subject or experiencer)", relation)),
+    };
+    Ok(Statement::Edge(SemanticEdge { from: resolve(from, by_text)?.id, to: resolve(to, by_text)?.id, relation }))
+}
+
+fn preposition_role(preposition: &str, role: SemanticRole, concepts: &[&str], by_text: &HashMap<&str, Vec<&Lemma>>) -> Result<PrepositionRole, String> {
+    if concepts.is_empty() {
+        return Err(format!("'{} {}': expected the concepts that fit the role", preposition, role.name()));
+    }
+    let (preposition, cases) = match preposition.rsplit_once('+') {
+        Some((preposition, case)) => (preposition, case_flags(case)?),
+        None => (preposition, MorphFlags::empty()),
+    };
+    Ok(PrepositionRole {
+        preposition: resolve(preposition, by_text)?.id,
+        cases: cases.bits(),
+        role,
+        concepts: concepts.iter().map(|c| resolve(c, by_text).map(|l| l.id)).collect::<Result<_, _>>()?,
+    })
+}
+
+fn case_flags(case: &str) -> Result<MorphFlags, String> {
+    match case.to_lowercase().as_str() {
+        "nom" => Ok(MorphFlags::NOMINATIVE),
+        "gen" => Ok(MorphFlags::GENITIVE),
+        "dat" => Ok(MorphFlags::DATIVE),
+        "acc" => Ok(MorphFlags::ACCUSATIVE),
+        "voc" => Ok(MorphFlags::VOCATIVE),
+        _ => Err(format!("unknown case '{}' (expected nom, gen, dat, acc or voc)", case)),
+    }
+}
+
+/// The lemma a word names: its headword, optionally prefixed with a part of
+/// speech (`Noun:apple`) when several lemmas share it.
+fn resolve<'a>(word: &str, by_text: &HashMap<&str, Vec<&'a Lemma>>) -> Result<&'a Lemma, String> {
+    let (pos, text) = match word.split_once(':') {
+        Some((pos, text)) => (Some(pos), text),
+        None => (None, word),
+    };
+    let candidates: Vec<&Lemma> = by_text.get(text).into_iter().flatten()
+        .filter(|l| pos.is_none_or(|pos| format!("{:?}", l.pos).eq_ignore_ascii_case(pos)))
+        .copied()
+        .collect();
+    match candidates[..] {
+        [lemma] => Ok(lemma),
+        [] if pos.is_some() && by_text.contains_key(text) => Err(format!("no {} '{}' in the dictionary", pos.unwrap_or_default(), text)),
+        [] => Err(format!("unknown word '{}'", text)),
+        _ if pos.is_some() => Err(format!("'{}' names {} lemmas of that part of speech", word, candidates.len())),
+        _ => {
+            let ids: Vec<String> = candidates.iter().map(|l| format!("{:?} {}", l.pos, l.id.0)).collect();
+            Err(format!("'{}' is ambiguous ({}); prefix a part of speech, e.g. {:?}:{}", text, ids.join(", "), candidates[0].pos, text))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logos_protocol::{Dialect, Gender, LemmaId, PartOfSpeech, Period};
+
+    fn lemma(id: u32, text: &str, pos: PartOfSpeech) -> Lemma {
+        Lemma {
+            id: LemmaId(id),
+            text: text.to_string(),
+            gender: Gender::Masculine,
+            pos,
+            urn: None,
+            gloss: None,
+            dialects: Dialect::empty(),
+            periods: Period::empty(),
+            paradigm: None,
+        }
+    }
+
+    fn dictionary() -> Dictionary {
+        Dictionary {
+            lemmas: vec![
+                lemma(1, "ἐσθίω", PartOfSpeech::Verb),
+                lemma(2, "ἔδεσμα", PartOfSpeech::Noun),
+                lemma(3, "μῆλον", PartOfSpeech::Noun),
+                lemma(4, "τροφή", PartOfSpeech::Noun),
+                lemma(5, "ἐν", PartOfSpeech::Preposition),
+                lemma(6, "τόπος", PartOfSpeech::Noun),
+                lemma(7, "ἄνθρωπος", PartOfSpeech::Noun),
+                lemma(8, "λέγω", PartOfSpeech::Verb),
+                lemma(9, "καλός", PartOfSpeech::Adjective),
+                lemma(10, "καλός", PartOfSpeech::Noun),
+            ],
+            ..Default::default()
+        }
+    }
+
+    /// Writes `contents` to a file of the temporary directory.
+    fn source(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("atlas-semantics-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_valid_file() {
+        let path = source("valid.txt", "# food\nἐσθίω requires ἔδεσμα; μῆλον isa τροφή\n\nτροφή has ἔδεσμα   # twice:\nτροφή HAS ἔδεσμα\nἐν+dat location τόπος\nἄνθρωπος animacy human; λέγω subject Human\nNoun:καλός is-a τροφή\n");
+        let (network, errors) = read(&[&path], &dictionary()).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+        let edges: Vec<(u32, u32, Relation)> = network.edges.iter().map(|e| (e.from.0, e.to.0, e.relation)).collect();
+        assert_eq!(edges, [(1, 2, Relation::RequiresAttribute), (3, 4, Relation::IsA), (4, 2, Relation::HasAttribute), (10, 4, Relation::IsA)]);
+        let role = &network.prepositions[0];
+        assert_eq!((role.preposition.0, role.cases, role.role, role.concepts.len()), (5, MorphFlags::DATIVE.bits(), SemanticRole::Location, 1));
+        assert_eq!((network.animacy[0].concept.0, network.animacy[0].animacy), (7, Animacy::Human));
+        let constraint = &network.animacy_constraints[0];
+        assert_eq!((constraint.verb.0, constraint.role, constraint.minimum), (8, AnimacyRole::Subject, Animacy::Human));
+    }
+
+    #[test]
+    fn test_errors() {
+        let path = source("bad.txt", "ἐσθίω requires ἔδεσμα\nἐσθίω requires ἄρτος; μῆλον isa\nμῆλον likes τροφή\n\nκαλός isa τροφή; Verb:καλός isa τροφή\nἐν+abl location τόπος; λέγω subject divine\n");
+        let (network, errors) = read(&[&path], &dictionary()).unwrap();
+        assert_eq!(network.edges.len(), 1);
+        let at = |line: usize, message: &str| format!("{}:{}: {}", path.display(), line, message);
+        assert_eq!(errors, [
+            at(2, "unknown word 'ἄρτος'"),
+            at(2, "'μῆλον isa': expected <word> <relation> <word>"),
+            at(3, "unknown relation 'likes' (expected isa, requires, has, animacy, subject or experiencer)"),
+            at(5, "'καλός' is ambiguous (Adjective 9, Noun 10); prefix a part of speech, e.g. Adjective:καλός"),
+            at(5, "no Verb 'καλός' in the dictionary"),
+            at(6, "unknown case 'abl' (expected nom, gen, dat, acc or voc)"),
+            at(6, "unknown animacy 'divine' (expected human, animal, inanimate or abstract)"),
+        ]);
+        assert!(read(&[source("missing.txt", "").with_extension("none")], &dictionary()).is_err());
+    }
+}
@@ -0,0 +1,337 @@
+//! CSV/TSV lexicons, for lexicons kept in a spreadsheet rather than written
+//! as nested JSON. Two kinds of table, each with a header row naming its
+//! columns (in any order; blank lines and lines starting with `#` are
+//! skipped):
+//!
+//! * Lexicon: `headword`, `pos`, `gender`, `paradigm`, and optionally
+//!   `gloss`. `pos` and `gender` are `PartOfSpeech` and `Gender` variant
+//!   names (`Noun`, `Feminine`); `gender` may be empty for words without
+//!   one. `paradigm` names a paradigm of the definition files, or is empty
+//!   for an indeclinable word. The stem stored is the headword minus the
+//!   first ending of its paradigm it ends with, so list the citation form's
+//!   ending first; lookups pair the lemma with that paradigm only.
+//! * Paradigm definitions: `paradigm`, `morphology`, `ending`, one row per
+//!   ending. `morphology` is MorphFlags names (`NOUN | GENITIVE | SINGULAR`)
+//!   or their bits as a number; an empty `ending` is the zero ending.
+//!
+//! CSV fields may be double-quoted (`""` for a quote inside one), and a
+//! quoted field may run over several lines; TSV fields are split on tabs as
+//! they are. Errors give the file, the row's first line and the column.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use logos_protocol::{Dialect, Dictionary, Gender, Lemma, LemmaId, MorphFlags, Paradigm, ParadigmId, PartOfSpeech, Period};
+use serde::de::{value, Deserialize, IntoDeserializer};
+
+const LEXICON_COLUMNS: [&str; 5] = ["headword", "pos", "gender", "paradigm", "gloss"];
+const PARADIGM_COLUMNS: [&str; 3] = ["paradigm", "morphology", "ending"];
+
+/// Errors listed before giving up, so one bad column does not flood the terminal.
+pub const MAX_ERRORS_SHOWN: usize = 50;
+
+/// Field separator of a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    Comma,
+    Tab,
+}
+
+/// A data row: its line number and fields by column name.
+struct Row {
+    line: usize,
+    fields: HashMap<&'static str, String>,
+}
+
+impl Row {
+    fn get(&self, column: &str) -> &str {
+        self.fields.get(column).map_or("", String::as_str)
+    }
+}
+
+/// Builds a dictionary from a lexicon table and the paradigm tables it
+/// refers to. Every malformed row is reported, with its file and line.
+pub fn read(lexicon: &Path, paradigm_files: &[impl AsRef<Path>], delimiter: Delimiter) -> anyhow::Result<Dictionary> {
+    let (dict, errors) = parse(lexicon, paradigm_files, delimiter)?;
+    if !errors.is_empty() {
+        for error in errors.iter().take(MAX_ERRORS_SHOWN) {
+            println!("❌ {}", error);
+        }
+        if errors.len() > MAX_ERRORS_SHOWN {
+            println!("   ... and {} more", errors.len() - MAX_ERRORS_SHOWN);
+        }
+        anyhow::bail!("{} malformed rows", errors.len());
+    }
+    Ok(dict)
+}
+
+/// `read`'s dictionary, of the well-formed rows, and a message per
+/// malformed one. Files that cannot be read or lack a header are errors.
+fn parse(lexicon: &Path, paradigm_files: &[impl AsRef<Path>], delimiter: Delimiter) -> anyhow::Result<(Dictionary, Vec<String>)> {
+    let mut errors = Vec::new();
+
+    // Paradigms get IDs in order of first appearance
+    let mut names: Vec<String> = Vec::new();
+    let mut paradigms: Vec<Paradigm> = Vec::new();
+    for path in paradigm_files {
+        let path = path.as_ref();
+        for row in rows(path, delimiter, &PARADIGM_COLUMNS, &PARADIGM_COLUMNS)? {
+            let at = |message: String| format!("{}:{}: {}", path.display(), row.line, message);
+            let name = row.get("paradigm");
+            if name.is_empty() {
+                errors.push(at("paradigm: empty name".to_string()));
+                continue;
+            }
+            let flags = match parse_flags(row.get("morphology")) {
+                Ok(flags) => flags,
+                Err(e) => {
+                    errors.push(at(e));
+                    continue;
+                }
+            };
+            let index = match names.iter().position(|n| n == name) {
+                Some(index) => index,
+                None => {
+                    names.push(name.to_string());
+                    paradigms.push(Paradigm {
+                        id: ParadigmId(names.len() as u32),
+                        dialects: Dialect::empty(),
+                        periods: Period::empty(),
+                        endings: Vec::new(),
+                    });
+                    names.len() - 1
+                }
+            };
+            paradigms[index].endings.push((flags.bits(), row.get("ending").to_string()));
+        }
+    }
+
+    let mut lemmas = Vec::new();
+    for row in rows(lexicon, delimiter, &LEXICON_COLUMNS, &LEXICON_COLUMNS[..4])? {
+        let id = LemmaId(lemmas.len() as u32 + 1);
+        match lemma(&row, id, &names, &paradigms) {
+            Ok(lemma) => lemmas.push(lemma),
+            Err(e) => errors.push(format!("{}:{}: {}", lexicon.display(), row.line, e)),
+        }
+    }
+
+    Ok((Dictionary { version: 1, lemmas, paradigms, ..Default::default() }, errors))
+}
+
+fn lemma(row: &Row, id: LemmaId, names: &[String], paradigms: &[Paradigm]) -> Result<Lemma, String> {
+    let headword = row.get("headword");
+    if headword.is_empty() {
+        return Err("headword: empty".to_string());
+    }
+    let pos: PartOfSpeech = variant(row.get("pos"), "pos")?;
+    let gender: Gender = match row.get("gender") {
+        "" => Gender::Masculine,
+        gender => variant(gender, "gender")?,
+    };
+    let gloss = Some(row.get("gloss")).filter(|g| !g.is_empty());
+
+    let (text, paradigm) = match row.get("paradigm") {
+        "" => (headword, None),
+        name => {
+            let index = names.iter().position(|n| n == name)
+                .ok_or_else(|| format!("paradigm: unknown paradigm '{}' (not in the paradigm files)", name))?;
+            let ending = paradigms[index].endings.iter()
+                .map(|(_, ending)| ending.as_str())
+                .find(|ending| headword.ends_with(ending))
+                .ok_or_else(|| format!("paradigm: '{}' ends in none of the endings of paradigm '{}'", headword, name))?;
+            (&headword[..headword.len() - ending.len()], Some(paradigms[index].id))
+        }
+    };
+    Ok(Lemma {
+        id,
+        text: text.to_string(),
+        gender,
+        pos,
+        urn: None,
+        gloss: gloss.map(str::to_string),
+        dialects: Dialect::empty(),
+        periods: Period::empty(),
+        paradigm,
+    })
+}
+
+/// A unit variant of `T` by name, with serde's list of the valid names on error.
+fn variant<'de, T: Deserialize<'de>>(name: &'de str, column: &str) -> Result<T, String> {
+    T::deserialize(IntoDeserializer::<value::Error>::into_deserializer(name)).map_err(|e| format!("{}: {}", column, e))
+}
+
+fn parse_flags(text: &str) -> Result<MorphFlags, String> {
+    let flags = match text.parse::<u32>() {
+        Ok(bits) => MorphFlags::from_bits(bits).ok_or_else(|| format!("morphology: {} sets unknown flag bits", bits))?,
+        Err(_) => bitflags::parser::from_str::<MorphFlags>(text).map_err(|_| {
+            let unknown = text.split('|').map(str::trim).find(|name| MorphFlags::from_name(name).is_none()).unwrap_or(text);
+            format!("morphology: unknown flag '{}'", unknown)
+        })?,
+    };
+    if flags.is_empty() {
+        return Err("morphology: empty".to_string());
+    }
+    Ok(flags)
+}
+
+/// The data rows of a table, checked against its header: every `required`
+/// column must be present, and nothing outside `known`.
+fn rows(path: &Path, delimiter: Delimiter, known: &[&'static str], required: &[&'static str]) -> anyhow::Result<Vec<Row>> {
+    let text = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+    let mut records = records(&text, delimiter).into_iter();
+
+    let (header_line, header) = records.next().with_context(|| format!("{}: no header row", path.display()))?;
+    let mut columns = Vec::new();
+    for name in header.map_err(|e| anyhow::anyhow!("{}:{}: {}", path.display(), header_line, e))? {
+        let column = known.iter().find(|&&k| k == name.to_lowercase())
+            .with_context(|| format!("{}:{}: unknown column '{}' (expected {})", path.display(), header_line, name, known.join(", ")))?;
+        columns.push(*column);
+    }
+    if let Some(missing) = required.iter().find(|r| !columns.contains(r)) {
+        anyhow::bail!("{}:{}: no '{}' column (columns: {})", path.display(), header_line, missing, required.join(", "));
+    }
+
+    records.map(|(line, fields)| {
+        let fields = fields.map_err(|e| anyhow::anyhow!("{}:{}: {}", path.display(), line, e))?;
+        if fields.len() > columns.len() {
+            anyhow::bail!("{}:{}: {} fields, the header has {} columns", path.display(), line, fields.len(), columns.len());
+        }
+        // Trailing empty fields may be left off
+        Ok(Row { line, fields: columns.iter().copied().zip(fields).collect() })
+    }).collect()
+}
+
+/// The records of a table, each with the number of its first line. Blank
+/// lines and comments between records are skipped; a record whose quoted
+/// field is still open at the end of its line goes on to the next.
+fn records(text: &str, delimiter: Delimiter) -> Vec<(usize, Result<Vec<String>, String>)> {
+    let mut records = Vec::new();
+    let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line.trim_start_matches('\u{feff}')));
+    while let Some((line, first)) = lines.next() {
+        if first.trim().is_empty() || first.starts_with('#') {
+            continue;
+        }
+        let mut record = first.to_string();
+        let mut fields = split(&record, delimiter);
+        while fields.is_err() {
+            let Some((_, next)) = lines.next() else { break };
+            record.push('\n');
+            record.push_str(next);
+            fields = split(&record, delimiter);
+        }
+        records.push((line, fields));
+    }
+    records
+}
+
+/// The trimmed fields of one record.
+fn split(line: &str, delimiter: Delimiter) -> Result<Vec<String>, String> {
+    if delimiter == Delimiter::Tab {
+        return Ok(line.split('\t').map(|f| f.trim().to_string()).collect());
+    }
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    fields.push(field.trim().to_string());
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Writes `contents` to a file of the temporary directory.
+    fn table(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("atlas-tabular-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    const PARADIGMS: &str = "paradigm,morphology,ending\no-decl,NOUN | NOMINATIVE | SINGULAR,ος\no-decl,4,ου\n";
+
+    #[test]
+    fn test_split() {
+        let fields = split(r#"a, "b, c" ,"say ""hi""",,"#, Delimiter::Comma).unwrap();
+        assert_eq!(fields, ["a", "b, c", "say \"hi\"", "", ""]);
+        assert_eq!(split("a, b\t\"c\"\t", Delimiter::Tab).unwrap(), ["a, b", "\"c\"", ""]);
+        assert!(split(r#"a,"b"#, Delimiter::Comma).is_err());
+    }
+
+    #[test]
+    fn test_multiline_fields() {
+        let text = "# comment\n\nheadword,gloss\nλόγος,\"word,\n\nreason\"\nἄνθρωπος,man\n";
+        let records = records(text, Delimiter::Comma);
+        let lines: Vec<usize> = records.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, [3, 4, 7]);
+        assert_eq!(records[1].1.as_deref().unwrap(), ["λόγος", "word,\n\nreason"]);
+
+        // Still open at the end of the file
+        let open = super::records("a,\"b\nc", Delimiter::Comma);
+        assert_eq!(open, [(1, Err("unterminated quoted field".to_string()))]);
+    }
+
+    #[test]
+    fn test_read_lexicon() {
+        let paradigms = table("paradigms.csv", PARADIGMS);
+        let lexicon = table("lexicon.csv", "Headword,POS,gender,paradigm,gloss\nλόγος,Noun,Masculine,o-decl,\"word, speech\"\nκαί,Conjunction,,,and\n");
+        let dict = read(&lexicon, &[&paradigms], Delimiter::Comma).unwrap();
+        assert_eq!(dict.paradigms.len(), 1);
+        assert_eq!(dict.paradigms[0].endings, [((MorphFlags::NOUN | MorphFlags::NOMINATIVE | MorphFlags::SINGULAR).bits(), "ος".to_string()), (4, "ου".to_string())]);
+        assert_eq!(dict.lemmas[0].text, "λόγ");
+        assert_eq!(dict.lemmas[0].paradigm, Some(ParadigmId(1)));
+        assert_eq!(dict.lemmas[0].gloss.as_deref(), Some("word, speech"));
+        assert_eq!((dict.lemmas[1].text.as_str(), dict.lemmas[1].pos, dict.lemmas[1].paradigm), ("καί", PartOfSpeech::Conjunction, None));
+
+        let paradigms = table("paradigms.tsv", &PARADIGMS.replace(',', "\t"));
+        let lexicon = table("lexicon.tsv", "headword\tpos\tgender\tparadigm\tgloss\nλόγος\tNoun\tMasculine\to-decl\tword, speech\n");
+        let dict = read(&lexicon, &[&paradigms], Delimiter::Tab).unwrap();
+        assert_eq!(dict.lemmas[0].gloss.as_deref(), Some("word, speech"));
+    }
+
+    #[test]
+    fn test_row_errors() {
+        let paradigms = table("bad-paradigms.csv", "paradigm,morphology,ending\no-decl,NOUN | NOMINATIV,ος\n,NOUN,ου\no-decl,NOUN,ος\n");
+        let lexicon = table("bad-lexicon.csv", "headword,pos,gender,paradigm\n\"λόγος\n\",Nonn,,o-decl\nδῶρον,Noun,,o-decl\n,Noun,,\nλόγος,Noun,,a-decl\n");
+        let (dict, errors) = parse(&lexicon, &[&paradigms], Delimiter::Comma).unwrap();
+        assert!(dict.lemmas.is_empty());
+        let errors: Vec<String> = errors.iter().map(|e| e.rsplit_once(std::path::MAIN_SEPARATOR).map_or(e.as_str(), |(_, e)| e).to_string()).collect();
+        let at = |file: &str, line: usize, message: &str| format!("atlas-tabular-{}-{}:{}: {}", std::process::id(), file, line, message);
+        assert_eq!(errors[0], at("bad-paradigms.csv", 2, "morphology: unknown flag 'NOMINATIV'"));
+        assert_eq!(errors[1], at("bad-paradigms.csv", 3, "paradigm: empty name"));
+        assert!(errors[2].starts_with(&at("bad-lexicon.csv", 2, "pos: unknown variant `Nonn`")), "{}", errors[2]);
+        assert_eq!(errors[3], at("bad-lexicon.csv", 4, "paradigm: 'δῶρον' ends in none of the endings of paradigm 'o-decl'"));
+        assert_eq!(errors[4], at("bad-lexicon.csv", 5, "headword: empty"));
+        assert_eq!(errors[5], at("bad-lexicon.csv", 6, "paradigm: unknown paradigm 'a-decl' (not in the paradigm files)"));
+        assert_eq!(errors.len(), 6);
+
+        // Header problems stop the read
+        let extra = table("extra.csv", "headword,pos,gender,paradigm,plural\n");
+        let error = parse(&extra, &[&paradigms], Delimiter::Comma).unwrap_err().to_string();
+        assert!(error.ends_with(":1: unknown column 'plural' (expected headword, pos, gender, paradigm, gloss)"), "{}", error);
+        let short = table("short.csv", "headword,pos,gender\n");
+        let error = parse(&short, &[&paradigms], Delimiter::Comma).unwrap_err().to_string();
+        assert!(error.ends_with(":1: no 'paradigm' column (columns: headword, pos, gender, paradigm)"), "{}", error);
+    }
+}
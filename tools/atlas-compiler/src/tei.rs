@@ -0,0 +1,392 @@
+//! TEI-encoded lexica (the Perseus LSJ XML) to Dictionary JSON: a lemma per
+//! entry with its headword, a part of speech and gender guessed from the
+//! entry's markup, and its first translations as the gloss. Headwords are
+//! kept whole; paradigms are not part of a lexicon and are added separately.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use logos_protocol::{Dialect, Dictionary, Gender, Lemma, LemmaId, PartOfSpeech, Period};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::betacode;
+
+/// Translations (`<tr>`) joined into a gloss.
+const GLOSS_TRANSLATIONS: usize = 3;
+/// Perseus document of the LSJ; entry keys complete the reference.
+const LSJ_DOCUMENT: &str = "Perseus:text:1999.04.0057";
+
+#[derive(clap::Args)]
+pub struct ImportArgs {
+    /// TEI XML files (e.g. the 27 files of grc.lsj.perseus-eng)
+    #[arg(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+
+    /// Dictionary JSON to write
+    #[arg(short, long, value_name = "FILE")]
+    output: PathBuf,
+}
+
+pub fn run(args: &ImportArgs) -> anyhow::Result<()> {
+    let mut lemmas = Vec::new();
+    let mut skipped = 0;
+    for path in &args.files {
+        println!("📖 Reading TEI from {:?}...", path);
+        let xml = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+        for entry in entries(&xml) {
+            match entry.lemma(LemmaId(lemmas.len() as u32 + 1)) {
+                Some(lemma) => lemmas.push(lemma),
+                None => skipped += 1,
+            }
+        }
+    }
+    println!("🏷️  {} lemmas imported, {} entries skipped (no headword or part of speech)", lemmas.len(), skipped);
+
+    let dict = Dictionary { version: 1, lemmas, ..Default::default() };
+    fs::write(&args.output, serde_json::to_string_pretty(&dict)? + "\n").with_context(|| format!("writing {:?}", args.output))?;
+    println!("✅ Dictionary JSON written to {:?}", args.output);
+    Ok(())
+}
+
+/// What is read from one `<entryFree>` (or `<entry>`).
+#[derive(Default)]
+struct Entry {
+    key: String,
+    orth: Option<String>,
+    gen: Option<String>,
+    pos: Option<String>,
+    itype: Option<String>,
+    translations: Vec<String>,
+}
+
+impl Entry {
+    /// None for an entry without a headword or a recognizable part of speech.
+    fn lemma(&self, id: LemmaId) -> Option<Lemma> {
+        let headword = betacode::to_greek(self.orth.as_deref()?.trim().trim_end_matches([',', '.']));
+        if headword.is_empty() {
+            return None;
+        }
+        let (pos, gender) = self.classify(&headword)?;
+        Some(Lemma {
+            id,
+            text: headword,
+            gender,
+            pos,
+            urn: (!self.key.is_empty()).then(|| format!("{}:entry={}", LSJ_DOCUMENT, self.key)),
+            gloss: self.gloss(),
+            dialects: Dialect::empty(),
+            periods: Period::empty(),
+            paradigm: None,
+        })
+    }
+
+    /// An explicit `<pos>` first, then the article of a noun (`<gen>`), a
+    /// verb's first-person ending, an adjective's terminations (`<itype>`)
+    /// and an adverb's -ως. None when nothing gives it away.
+    fn classify(&self, headword: &str) -> Option<(PartOfSpeech, Gender)> {
+        if let Some(pos) = self.pos.as_deref().and_then(explicit_pos) {
+            return Some((pos, Gender::Masculine));
+        }
+        if let Some(gen) = &self.gen {
+            let gen = strip_marks(&betacode::to_greek(gen));
+            // The article may be plural (αἱ Ἀθῆναι, τὰ Διονύσια)
+            let gender = if gen.starts_with("η") || gen.starts_with("αι") {
+                Gender::Feminine
+            } else if gen.starts_with("το") || gen.starts_with("τα") {
+                Gender::Neuter
+            } else {
+                Gender::Masculine
+            };
+            let proper = headword.chars().next().is_some_and(char::is_uppercase);
+            return Some((if proper { PartOfSpeech::ProperNoun } else { PartOfSpeech::Noun }, gender));
+        }
+        let bare = strip_marks(headword);
+        if ["ω", "μι", "μαι"].iter().any(|ending| bare.ends_with(ending)) {
+            // Verbs have no gender; the pipeline marks them Neuter
+            return Some((PartOfSpeech::Verb, Gender::Neuter));
+        }
+        if self.itype.is_some() {
+            return Some((PartOfSpeech::Adjective, Gender::Masculine));
+        }
+        if bare.ends_with("ως") {
+            return Some((PartOfSpeech::Adverb, Gender::Masculine));
+        }
+        None
+    }
+
+    /// The first translations, whitespace collapsed.
+    fn gloss(&self) -> Option<String> {
+        let translations: Vec<String> = self.translations.iter()
+            .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" ").trim_end_matches([',', ';', ':', '.']).to_string())
+            .filter(|t| !t.is_empty())
+            .take(GLOSS_TRANSLATIONS)
+            .collect();
+        Some(translations.join(", ")).filter(|g| !g.is_empty())
+    }
+}
+
+fn explicit_pos(text: &str) -> Option<PartOfSpeech> {
+    let text = text.trim().to_lowercase();
+    let pos = [
+        ("adv", PartOfSpeech::Adverb),
+        ("prep", PartOfSpeech::Preposition),
+        ("conj", PartOfSpeech::Conjunction),
+        ("adj", PartOfSpeech::Adjective),
+        ("pron", PartOfSpeech::Pronoun),
+        ("part", PartOfSpeech::Particle),
+        ("num", PartOfSpeech::Numeral),
+        ("art", PartOfSpeech::Article),
+    ];
+    pos.into_iter().find(|(prefix, _)| text.starts_with(prefix)).map(|(_, pos)| pos)
+}
+
+/// Lowercase letters without accents, breathings or other marks.
+fn strip_marks(text: &str) -> String {
+    text.nfd().filter(|c| !('\u{0300}'..='\u{036f}').contains(c)).flat_map(char::to_lowercase).collect()
+}
+
+/// The entries of a lexicon, in document order.
+fn entries(xml: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let mut entry: Option<Entry> = None;
+    // Element being captured, its nesting depth within itself, and its text
+    let mut capture: Option<(&str, usize, String)> = None;
+
+    let reader = Reader { rest: xml };
+    for event in reader {
+        match event {
+            Event::Start(name, attributes, empty) => {
+                if matches!(name, "entryFree" | "entry") {
+                    entry = Some(Entry { key: attribute(attributes, "key").unwrap_or_default(), ..Default::default() });
+                    continue;
+                }
+                let Some(entry) = &entry else { continue };
+                match &mut capture {
+                    Some((captured, depth, _)) if *captured == name => *depth += 1,
+                    Some(_) => {}
+                    None if empty => {}
+                    None => {
+                        let wanted = match name {
+                            "orth" => entry.orth.is_none(),
+                            "gen" => entry.gen.is_none(),
+                            "pos" => entry.pos.is_none(),
+                            "itype" => entry.itype.is_none(),
+                            "tr" => entry.translations.len() < GLOSS_TRANSLATIONS,
+                            _ => false,
+                        };
+                        if wanted {
+                            capture = Some((name, 0, String::new()));
+                        }
+                    }
+                }
+            }
+            Event::Text(text) => {
+                if let Some((_, _, captured)) = &mut capture {
+                    captured.push_str(&unescape(text));
+                }
+            }
+            Event::End(name) => {
+                if matches!(name, "entryFree" | "entry") {
+                    entries.extend(entry.take());
+                    capture = None;
+                    continue;
+                }
+                let (Some(entry), Some((captured, depth, _))) = (&mut entry, &mut capture) else { continue };
+                if *captured != name {
+                    continue;
+                }
+                if *depth > 0 {
+                    *depth -= 1;
+                    continue;
+                }
+                let Some((_, _, text)) = capture.take() else { continue };
+                match name {
+                    "orth" => entry.orth = Some(text),
+                    "gen" => entry.gen = Some(text),
+                    "pos" => entry.pos = Some(text),
+                    "itype" => entry.itype = Some(text),
+                    _ => entry.translations.push(text),
+                }
+            }
+        }
+    }
+    entries
+}
+
+enum Event<'a> {
+    /// Name, raw attributes, and whether the tag closes itself
+    Start(&'a str, &'a str, bool),
+    End(&'a str),
+    Text(&'a str),
+}
+
+/// Just enough of an XML reader for TEI lexica: tags, text and CDATA;
+/// comments, processing instructions and declarations are skipped.
+struct Reader<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Event<'a>> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            if !self.rest.starts_with('<') {
+                let end = self.rest.find('<').unwrap_or(self.rest.len());
+                let (text, rest) = self.rest.split_at(end);
+                self.rest = rest;
+                return Some(Event::Text(text));
+            }
+            if let Some(body) = self.rest.strip_prefix("<![CDATA[") {
+                let end = body.find("]]>").unwrap_or(body.len());
+                self.rest = body.get(end + 3..).unwrap_or("");
+                return Some(Event::Text(&body[..end]));
+            }
+            let skipped = [("<!--", "-->"), ("<?", "?>"), ("<!", ">")].into_iter()
+                .find_map(|(open, close)| Some((self.rest.strip_prefix(open)?, close)));
+            if let Some((body, close)) = skipped {
+                self.rest = body.find(close).map_or("", |end| &body[end + close.len()..]);
+                continue;
+            }
+
+            let end = tag_end(self.rest);
+            let tag = &self.rest[1..end];
+            self.rest = self.rest.get(end + 1..).unwrap_or("");
+            if let Some(name) = tag.strip_prefix('/') {
+                return Some(Event::End(name.trim()));
+            }
+            let (tag, empty) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+            let split = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            return Some(Event::Start(&tag[..split], &tag[split..], empty));
+        }
+    }
+}
+
+/// Byte offset of the `>` closing the tag `text` starts with (its length
+/// when unclosed); a `>` inside a quoted attribute value does not count.
+fn tag_end(text: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return i,
+            (Some(q), c) if c == q => quote = None,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// The value of attribute `name` in a tag's raw attributes.
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes;
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value.chars().next().filter(|q| matches!(q, '"' | '\''))?;
+        let close = value[1..].find(quote)? + 1;
+        if key == name {
+            return Some(unescape(&value[1..close]));
+        }
+        rest = &value[close + 1..];
+    }
+    None
+}
+
+/// Text with the predefined and numeric character references resolved;
+/// entities a DTD would define are left as written.
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('&') {
+        out.push_str(&rest[..at]);
+        rest = &rest[at..];
+        let Some(end) = rest.find(';') else { break };
+        let reference = &rest[1..end];
+        let decoded = match reference {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => reference.strip_prefix("#x").map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| reference.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(xml: &str) -> Entry {
+        let mut entries = entries(xml);
+        assert_eq!(entries.len(), 1);
+        entries.remove(0)
+    }
+
+    #[test]
+    fn test_nested_translations() {
+        let e = entry(r#"<entryFree key="lo/gos"><orth>lo/gos</orth>, <gen>o(</gen><sense><tr>word, <tr>speech</tr></tr>; <tr>reason</tr></sense></entryFree>"#);
+        assert_eq!(e.key, "lo/gos");
+        assert_eq!(e.translations, ["word, speech", "reason"]);
+        assert_eq!(e.lemma(LemmaId(1)).unwrap().gloss.as_deref(), Some("word, speech, reason"));
+    }
+
+    #[test]
+    fn test_markup_and_references() {
+        let xml = r#"<?xml version="1.0"?><!DOCTYPE TEI><!-- <entryFree key="x"> -->
+            <entry key="a&amp;b" n='x>y'><orth><![CDATA[lo/<gos]]></orth><tr>one &#x3b1; &#955; &lt;&unknown;</tr></entry>"#;
+        let e = entry(xml);
+        assert_eq!(e.key, "a&b");
+        assert_eq!(e.orth.as_deref(), Some("lo/<gos"));
+        assert_eq!(e.translations, ["one α λ <&unknown;"]);
+        assert_eq!(attribute(r#" a="1" b='2>' c="3""#, "c").as_deref(), Some("3"));
+        assert_eq!(tag_end(r#"<w n="a>b">"#), 10);
+    }
+
+    #[test]
+    fn test_classify() {
+        let classify = |orth: &str, gen: Option<&str>, itype: Option<&str>, pos: Option<&str>| Entry {
+            gen: gen.map(str::to_string),
+            itype: itype.map(str::to_string),
+            pos: pos.map(str::to_string),
+            ..Default::default()
+        }.classify(&betacode::to_greek(orth));
+
+        assert_eq!(classify("a)reth/", Some("h("), None, None), Some((PartOfSpeech::Noun, Gender::Feminine)));
+        assert_eq!(classify("*)aqh=nai", Some("ai("), None, None), Some((PartOfSpeech::ProperNoun, Gender::Feminine)));
+        assert_eq!(classify("*dionu/sia", Some("ta/"), None, None), Some((PartOfSpeech::ProperNoun, Gender::Neuter)));
+        assert_eq!(classify("lo/gos", Some("o("), None, None), Some((PartOfSpeech::Noun, Gender::Masculine)));
+        assert_eq!(classify("lu/w", None, None, None), Some((PartOfSpeech::Verb, Gender::Neuter)));
+        assert_eq!(classify("di/dwmi", None, None, None), Some((PartOfSpeech::Verb, Gender::Neuter)));
+        assert_eq!(classify("kalw=s", None, None, None), Some((PartOfSpeech::Adverb, Gender::Masculine)));
+        assert_eq!(classify("kalo/s", None, Some("h/, o/n"), None), Some((PartOfSpeech::Adjective, Gender::Masculine)));
+        assert_eq!(classify("e)n", None, None, Some("Prep.")), Some((PartOfSpeech::Preposition, Gender::Masculine)));
+        assert_eq!(classify("lo/gos", None, None, None), None);
+    }
+}
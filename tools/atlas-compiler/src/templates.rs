@@ -0,0 +1,217 @@
+//! Paradigm templates in the JSON source, so a declension is written once
+//! and reused instead of repeating its endings for every paradigm. A
+//! template lists endings, may extend another template, and may leave
+//! `{name}` placeholders for its users to fill in:
+//!
+//! ```json
+//! "paradigm_templates": {
+//!   "second_declension": { "endings": [[145, "{v}ς"], [146, "{v}υ"]] },
+//!   "second_declension_masc": { "extends": "second_declension", "args": { "v": "ο" } }
+//! },
+//! "paradigms": [
+//!   { "id": 1, "template": "second_declension_masc" },
+//!   { "id": 2, "template": "second_declension", "args": { "v": "ω" }, "endings": [[146, "ω"]] }
+//! ]
+//! ```
+//!
+//! Endings of a template (or paradigm) replace the inherited cells with the
+//! same morphology, in their place; new cells are appended. `args` of a
+//! paradigm override those of its templates, a template's those of the
+//! template it extends. Everything is expanded before the dictionary is
+//! read, so the archive only ever holds concrete paradigms.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+use serde_json::Value;
+
+const TEMPLATES_KEY: &str = "paradigm_templates";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Template {
+    #[serde(default)]
+    extends: Option<String>,
+    #[serde(default)]
+    args: BTreeMap<String, String>,
+    #[serde(default)]
+    endings: Vec<(u32, String)>,
+}
+
+/// A template with its ancestors applied, placeholders still in place.
+#[derive(Clone)]
+struct Resolved {
+    args: BTreeMap<String, String>,
+    endings: Vec<(u32, String)>,
+}
+
+/// Expands the templated paradigms of a JSON source in place and drops the
+/// templates. Returns how many paradigms were expanded.
+pub fn expand(source: &mut Value) -> anyhow::Result<usize> {
+    let Some(object) = source.as_object_mut() else { return Ok(0) };
+    let templates: BTreeMap<String, Template> = match object.remove(TEMPLATES_KEY) {
+        Some(templates) => serde_json::from_value(templates).context(TEMPLATES_KEY)?,
+        None => BTreeMap::new(),
+    };
+    let mut resolved = BTreeMap::new();
+    for name in templates.keys() {
+        resolve(name, &templates, &mut resolved, &mut Vec::new())?;
+    }
+
+    let mut expanded = 0;
+    let Some(Value::Array(paradigms)) = object.get_mut("paradigms") else { return Ok(0) };
+    for paradigm in paradigms.iter_mut().filter_map(Value::as_object_mut) {
+        let Some(template) = paradigm.remove("template") else {
+            if paradigm.contains_key("args") {
+                bail!("paradigm {}: 'args' without a 'template'", paradigm.get("id").unwrap_or(&Value::Null));
+            }
+            continue;
+        };
+        let id = paradigm.get("id").cloned().unwrap_or(Value::Null);
+        let context = || format!("paradigm {}", id);
+        let name = template.as_str().with_context(|| format!("{}: 'template' is not a name", context()))?;
+        let base = resolved.get(name).with_context(|| format!("{}: unknown template '{}'", context(), name))?;
+
+        let mut args = base.args.clone();
+        if let Some(own) = paradigm.remove("args") {
+            args.extend(serde_json::from_value::<BTreeMap<String, String>>(own).with_context(|| format!("{}: args", context()))?);
+        }
+        let own: Vec<(u32, String)> = match paradigm.remove("endings") {
+            Some(endings) => serde_json::from_value(endings).with_context(|| format!("{}: endings", context()))?,
+            None => Vec::new(),
+        };
+        let endings = merge(&base.endings, own)
+            .into_iter()
+            .map(|(flags, ending)| Ok((flags, substitute(&ending, &args)?)))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .with_context(|| format!("{} (template '{}')", context(), name))?;
+        paradigm.insert("endings".to_string(), serde_json::to_value(endings)?);
+        expanded += 1;
+    }
+    Ok(expanded)
+}
+
+/// `name` with the templates it extends applied, memoized in `resolved`;
+/// `chain` holds the templates being resolved, to catch cycles.
+fn resolve<'a>(
+    name: &'a str,
+    templates: &'a BTreeMap<String, Template>,
+    resolved: &mut BTreeMap<&'a str, Resolved>,
+    chain: &mut Vec<&'a str>,
+) -> anyhow::Result<Resolved> {
+    if let Some(done) = resolved.get(name) {
+        return Ok(done.clone());
+    }
+    if chain.contains(&name) {
+        bail!("template '{}' extends itself ({} -> {})", name, chain.join(" -> "), name);
+    }
+    let Some((name, template)) = templates.get_key_value(name) else {
+        bail!("unknown template '{}' (extended by '{}')", name, chain.last().copied().unwrap_or_default());
+    };
+    let name = name.as_str();
+
+    chain.push(name);
+    let parent = match &template.extends {
+        Some(parent) => resolve(parent, templates, resolved, chain)?,
+        None => Resolved { args: BTreeMap::new(), endings: Vec::new() },
+    };
+    chain.pop();
+
+    let mut args = parent.args;
+    args.extend(template.args.iter().map(|(k, v)| (k.clone(), v.clone())));
+    let done = Resolved { args, endings: merge(&parent.endings, template.endings.clone()) };
+    resolved.insert(name, done.clone());
+    Ok(done)
+}
+
+/// Inherited cells with those of `own` laid over them: each morphology
+/// `own` defines replaces every inherited cell with it, at the first one's
+/// position; the rest of `own` follows.
+fn merge(inherited: &[(u32, String)], own: Vec<(u32, String)>) -> Vec<(u32, String)> {
+    let mut merged = Vec::with_capacity(inherited.len() + own.len());
+    let mut placed = Vec::new();
+    for (flags, ending) in inherited {
+        if !own.iter().any(|(f, _)| f == flags) {
+            merged.push((*flags, ending.clone()));
+        } else if !placed.contains(flags) {
+            placed.push(*flags);
+            merged.extend(own.iter().filter(|(f, _)| f == flags).cloned());
+        }
+    }
+    merged.extend(own.into_iter().filter(|(f, _)| !placed.contains(f)));
+    merged
+}
+
+/// `ending` with its `{name}` placeholders filled in from `args`.
+fn substitute(ending: &str, args: &BTreeMap<String, String>) -> anyhow::Result<String> {
+    let mut out = String::with_capacity(ending.len());
+    let mut rest = ending;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let close = rest[open..].find('}').with_context(|| format!("unclosed placeholder in '{}'", ending))? + open;
+        let name = &rest[open + 1..close];
+        let value = args.get(name).with_context(|| format!("no value for '{{{}}}' in '{}'", name, ending))?;
+        out.push_str(value);
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn endings(source: &Value, paradigm: usize) -> Vec<(u32, String)> {
+        serde_json::from_value(source["paradigms"][paradigm]["endings"].clone()).unwrap()
+    }
+
+    #[test]
+    fn test_inheritance() {
+        let mut source = json!({
+            "paradigm_templates": {
+                "second": { "endings": [[1, "{v}ς"], [2, "{v}υ"], [3, "ῳ"]] },
+                "second_masc": { "extends": "second", "args": { "v": "ο" } },
+                "second_masc_voc": { "extends": "second_masc", "endings": [[4, "ε"], [2, "ου"], [2, "οιο"]] }
+            },
+            "paradigms": [
+                { "id": 1, "template": "second_masc" },
+                { "id": 2, "template": "second_masc", "args": { "v": "ω" }, "endings": [[3, "{v}ι"]] },
+                { "id": 3, "template": "second_masc_voc" },
+                { "id": 4, "endings": [[1, "α"]] }
+            ]
+        });
+        assert_eq!(expand(&mut source).unwrap(), 3);
+        assert!(source.get(TEMPLATES_KEY).is_none());
+        let cells = |cells: &[(u32, &str)]| cells.iter().map(|&(f, e)| (f, e.to_string())).collect::<Vec<_>>();
+        assert_eq!(endings(&source, 0), cells(&[(1, "ος"), (2, "ου"), (3, "ῳ")]));
+        // The paradigm's args beat the template's; its cells replace inherited ones in place
+        assert_eq!(endings(&source, 1), cells(&[(1, "ως"), (2, "ωυ"), (3, "ωι")]));
+        assert_eq!(endings(&source, 2), cells(&[(1, "ος"), (2, "ου"), (2, "οιο"), (3, "ῳ"), (4, "ε")]));
+        assert_eq!(source["paradigms"][3], json!({ "id": 4, "endings": [[1, "α"]] }));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |mut source: Value| format!("{:#}", expand(&mut source).unwrap_err());
+        assert_eq!(
+            error(json!({ "paradigm_templates": {}, "paradigms": [{ "id": 7, "template": "third" }] })),
+            "paradigm 7: unknown template 'third'",
+        );
+        assert_eq!(
+            error(json!({ "paradigm_templates": { "a": { "extends": "b" } } })),
+            "unknown template 'b' (extended by 'a')",
+        );
+        assert_eq!(
+            error(json!({ "paradigm_templates": { "a": { "extends": "b" }, "b": { "extends": "c" }, "c": { "extends": "a" } } })),
+            "template 'a' extends itself (a -> b -> c -> a)",
+        );
+        assert_eq!(
+            error(json!({ "paradigm_templates": { "a": { "endings": [[1, "{v}ς"]] } }, "paradigms": [{ "id": 2, "template": "a" }] })),
+            "paradigm 2 (template 'a'): no value for '{v}' in '{v}ς'",
+        );
+        assert_eq!(error(json!({ "paradigms": [{ "id": 3, "args": { "v": "ο" } }] })), "paradigm 3: 'args' without a 'template'");
+    }
+}
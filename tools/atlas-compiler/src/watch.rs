@@ -0,0 +1,166 @@
+//! `--watch`: recompile whenever a source changes, for lexicon authors
+//! iterating on a dictionary. Sources are polled (no file-system events
+//! needed), a failed build is reported and waited out, and each rebuild
+//! prints what changed since the last good one. A rebuild serializes the
+//! whole archive; there is no per-section reuse.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use logos_protocol::Dictionary;
+
+use crate::{build, registry, Cli, Session};
+
+const POLL: Duration = Duration::from_millis(500);
+
+/// Lemmas or paradigms listed per kind of change.
+const CHANGES_SHOWN: usize = 20;
+
+/// Watches the input and every file it depends on until interrupted.
+pub fn run(cli: &Cli, input: &Path, output: &Path, session: &mut Session) -> anyhow::Result<()> {
+    let mut sources = vec![input];
+    sources.extend(cli.paradigms.iter().map(|p| p.as_path()));
+    sources.extend(cli.check_semantics.iter().map(|p| p.as_path()));
+    sources.extend(cli.semantics.iter().map(|p| p.as_path()));
+    println!("👀 Watching {} files for changes (Ctrl-C to stop)", sources.len());
+
+    let mut seen = stamps(&sources);
+    loop {
+        thread::sleep(POLL);
+        if stamps(&sources) == seen {
+            continue;
+        }
+        // Let an editor finish saving before reading
+        thread::sleep(POLL);
+        seen = stamps(&sources);
+        println!();
+        println!("🔄 Sources changed, recompiling...");
+        if let Err(e) = build(cli, input, output, session) {
+            println!("❌ {:#}", e);
+            println!("👀 Waiting for the next change");
+        }
+    }
+}
+
+/// Modification times; None for a file missing (e.g. mid-save).
+fn stamps(paths: &[&Path]) -> Vec<Option<SystemTime>> {
+    paths.iter().map(|p| fs::metadata(p).and_then(|m| m.modified()).ok()).collect()
+}
+
+/// Prints lemmas added, removed and changed (matched by part of speech and
+/// text, as the registry matches them), and paradigms by ID.
+pub fn print_changes(old: &Dictionary, new: &Dictionary) {
+    for line in changes(old, new) {
+        println!("{}", line);
+    }
+}
+
+/// The lines `print_changes` prints.
+fn changes(old: &Dictionary, new: &Dictionary) -> Vec<String> {
+    let mut lines = Vec::new();
+    let keyed = |dict: &Dictionary| -> BTreeMap<String, usize> {
+        registry::keys(&dict.lemmas).into_iter().enumerate().map(|(i, key)| (key, i)).collect()
+    };
+    let (before, after) = (keyed(old), keyed(new));
+    let added: Vec<String> = after.iter()
+        .filter(|(key, _)| !before.contains_key(*key))
+        .map(|(key, &i)| format!("{} (ID {})", key, new.lemmas[i].id.0))
+        .collect();
+    let removed: Vec<String> = before.keys().filter(|key| !after.contains_key(*key)).cloned().collect();
+    let changed: Vec<String> = after.iter()
+        .filter(|(key, &i)| before.get(*key).is_some_and(|&j| old.lemmas[j] != new.lemmas[i]))
+        .map(|(key, _)| key.clone())
+        .collect();
+    list(&mut lines, "➕", "lemmas added", &added);
+    list(&mut lines, "➖", "lemmas removed", &removed);
+    list(&mut lines, "✏️ ", "lemmas changed", &changed);
+
+    let paradigms = |dict: &Dictionary| -> BTreeMap<u32, usize> {
+        dict.paradigms.iter().enumerate().map(|(i, p)| (p.id.0, i)).collect()
+    };
+    let (before, after) = (paradigms(old), paradigms(new));
+    let ids = |ids: Vec<u32>| -> Vec<String> { ids.into_iter().map(|id| id.to_string()).collect() };
+    list(&mut lines, "➕", "paradigms added", &ids(after.keys().filter(|id| !before.contains_key(id)).copied().collect()));
+    list(&mut lines, "➖", "paradigms removed", &ids(before.keys().filter(|id| !after.contains_key(id)).copied().collect()));
+    list(&mut lines, "✏️ ", "paradigms changed", &ids(after.iter()
+        .filter(|(id, &i)| before.get(*id).is_some_and(|&j| old.paradigms[j] != new.paradigms[i]))
+        .map(|(id, _)| *id)
+        .collect()));
+
+    for (what, before, after) in [
+        ("pronoun forms", old.pronouns.iter().map(|p| p.forms.len()).sum(), new.pronouns.iter().map(|p| p.forms.len()).sum()),
+        ("names", old.names.len(), new.names.len()),
+        ("tag transitions", old.transitions.len(), new.transitions.len()),
+    ] {
+        if before != after {
+            lines.push(format!("🔢 {}: {} -> {}", what, before, after));
+        }
+    }
+    lines
+}
+
+fn list(lines: &mut Vec<String>, icon: &str, what: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    lines.push(format!("{} {} {}: {}", icon, items.len(), what, items.iter().take(CHANGES_SHOWN).cloned().collect::<Vec<_>>().join(", ")));
+    if items.len() > CHANGES_SHOWN {
+        lines.push(format!("   ... and {} more", items.len() - CHANGES_SHOWN));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logos_protocol::{Dialect, Gender, Lemma, LemmaId, NameEntry, NameKind, Paradigm, ParadigmId, PartOfSpeech, Period};
+
+    fn lemma(id: u32, text: &str, gloss: &str) -> Lemma {
+        Lemma {
+            id: LemmaId(id),
+            text: text.to_string(),
+            gender: Gender::Masculine,
+            pos: PartOfSpeech::Noun,
+            urn: None,
+            gloss: Some(gloss.to_string()),
+            dialects: Dialect::empty(),
+            periods: Period::empty(),
+            paradigm: None,
+        }
+    }
+
+    fn paradigm(id: u32, ending: &str) -> Paradigm {
+        Paradigm { id: ParadigmId(id), dialects: Dialect::empty(), periods: Period::empty(), endings: vec![(1, ending.to_string())] }
+    }
+
+    #[test]
+    fn test_changes() {
+        let old = Dictionary {
+            lemmas: vec![lemma(1, "λόγ", "word"), lemma(2, "ἵππ", "horse"), lemma(3, "θε", "god")],
+            paradigms: vec![paradigm(1, "ος"), paradigm(2, "η")],
+            ..Default::default()
+        };
+        let new = Dictionary {
+            lemmas: vec![lemma(1, "λόγ", "word, speech"), lemma(3, "θε", "god"), lemma(4, "ἄνθρωπ", "man"), lemma(5, "δῶρ", "gift")],
+            paradigms: vec![paradigm(1, "ος"), paradigm(2, "ᾱ"), paradigm(3, "ον")],
+            names: vec![NameEntry { lemma: LemmaId(3), kind: NameKind::Person }],
+            ..Default::default()
+        };
+        assert_eq!(changes(&old, &new), [
+            "➕ 2 lemmas added: Noun:δῶρ (ID 5), Noun:ἄνθρωπ (ID 4)",
+            "➖ 1 lemmas removed: Noun:ἵππ",
+            "✏️  1 lemmas changed: Noun:λόγ",
+            "➕ 1 paradigms added: 3",
+            "✏️  1 paradigms changed: 2",
+            "🔢 names: 0 -> 1",
+        ]);
+        assert!(changes(&new, &new).is_empty());
+
+        let many = Dictionary { lemmas: (1..=25).map(|i| lemma(i, &format!("λ{}", i), "")).collect(), ..Default::default() };
+        let lines = changes(&Dictionary::default(), &many);
+        assert!(lines[0].starts_with("➕ 25 lemmas added: Noun:λ1 (ID 1), Noun:λ10 (ID 10)"));
+        assert_eq!(lines[1], "   ... and 5 more");
+    }
+}
@@ -0,0 +1,317 @@
+//! WordNet to the semantics DSL: Greek lemmas are mapped to synsets by an
+//! Open Multilingual Wordnet tab file, and the synsets' relations, read from
+//! the WordNet database files, become `isa` (hypernyms) and `has` (a noun's
+//! attribute values) statements between the lemmas, and each lemma is marked
+//! with the animacy of its nearest WordNet class (person, animal, physical
+//! object, abstraction) where its parents do not already carry it. The
+//! output is resolved against a dictionary by `--semantics` like a
+//! hand-written file.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use logos_protocol::{Animacy, Lemma, PartOfSpeech};
+use unicode_normalization::UnicodeNormalization;
+
+/// WordNet 3.0 classes that decide a synset's animacy, the nearest winning.
+const ANIMACY_CLASSES: [(&str, Animacy); 4] = [
+    ("00007846-n", Animacy::Human),     // person, individual
+    ("00015388-n", Animacy::Animal),    // animal, beast
+    ("00002684-n", Animacy::Inanimate), // object, physical object
+    ("00002137-n", Animacy::Abstract),  // abstraction
+];
+
+#[derive(clap::Args)]
+pub struct ImportArgs {
+    /// WordNet database files with the synsets' relations (data.noun,
+    /// data.verb, data.adj, data.adv)
+    #[arg(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+
+    /// Open Multilingual Wordnet tab file mapping synsets to Greek lemmas
+    /// (e.g. wn-data-grc.tab; columns synset, type, lemma)
+    #[arg(long, value_name = "FILE")]
+    mapping: PathBuf,
+
+    /// Keep only lemmas of this Dictionary JSON; a synset without any is
+    /// skipped, and its hypernyms stand in for it
+    #[arg(long, value_name = "FILE")]
+    dictionary: Option<PathBuf>,
+
+    /// Semantics file to write
+    #[arg(short, long, value_name = "FILE")]
+    output: PathBuf,
+}
+
+/// Relations of one synset, by synset key (`02084071-n`).
+#[derive(Default)]
+struct Synset {
+    hypernyms: Vec<String>,
+    attributes: Vec<String>,
+}
+
+pub fn run(args: &ImportArgs) -> anyhow::Result<()> {
+    let known = args.dictionary.as_ref().map(|path| headwords(path)).transpose()?;
+    println!("📖 Reading synset mapping from {:?}...", args.mapping);
+    let text = fs::read_to_string(&args.mapping).with_context(|| format!("reading {:?}", args.mapping))?;
+    let (lemmas, unknown) = mapping(&text, known.as_ref());
+    println!("🏷️  {} synsets have Greek lemmas ({} lemmas not in the dictionary skipped)", lemmas.len(), unknown);
+
+    let mut synsets = BTreeMap::new();
+    for path in &args.files {
+        println!("📖 Reading WordNet from {:?}...", path);
+        let text = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+        read_database(&text, &mut synsets).with_context(|| format!("reading {:?}", path))?;
+    }
+
+    let mut lines = vec!["# Imported from WordNet by atlas-compiler import-wordnet".to_string()];
+    let mut seen = HashSet::new();
+    let (mut isa, mut has, mut marked) = (0, 0, 0);
+    for (key, words) in &lemmas {
+        let parents = nearest_with_lemmas(key, &synsets, &lemmas);
+        // Marks are inherited through isa, so only where the parents differ
+        let own = animacy(key, &synsets);
+        if own.is_some() && (parents.is_empty() || parents.iter().any(|p| animacy(p, &synsets) != own)) {
+            for word in words {
+                lines.push(format!("{} animacy {}  # {}", word, own.map_or("", Animacy::name), key));
+                marked += 1;
+            }
+        }
+        for parent in parents {
+            for from in words {
+                for to in &lemmas[parent] {
+                    if from != to && seen.insert((from, "isa", to)) {
+                        lines.push(format!("{} isa {}  # {} {}", from, to, key, parent));
+                        isa += 1;
+                    }
+                }
+            }
+        }
+        for value in synsets.get(key).into_iter().flat_map(|s| &s.attributes) {
+            for from in words {
+                for to in lemmas.get(value).into_iter().flatten() {
+                    if seen.insert((from, "has", to)) {
+                        lines.push(format!("{} has {}  # {} {}", from, to, key, value));
+                        has += 1;
+                    }
+                }
+            }
+        }
+    }
+    fs::write(&args.output, lines.join("\n") + "\n").with_context(|| format!("writing {:?}", args.output))?;
+    println!("✅ {} isa, {} has and {} animacy statements written to {:?}", isa, has, marked, args.output);
+    Ok(())
+}
+
+/// (part of speech, headword) of the lemmas of a Dictionary JSON.
+fn headwords(path: &Path) -> anyhow::Result<HashSet<(PartOfSpeech, String)>> {
+    let text = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+    let mut value: serde_json::Value = serde_json::from_str(&text).with_context(|| format!("parsing {:?}", path))?;
+    let lemmas: Vec<Lemma> = serde_json::from_value(value["lemmas"].take()).with_context(|| format!("reading the lemmas of {:?}", path))?;
+    Ok(lemmas.into_iter().map(|l| (l.pos, l.text)).collect())
+}
+
+/// Greek lemmas by synset, as DSL words (`Noun:κύων`), and how many were
+/// left out for not being in `known`. Lemmas the DSL cannot spell (several
+/// words) are left out too.
+fn mapping(text: &str, known: Option<&HashSet<(PartOfSpeech, String)>>) -> (BTreeMap<String, BTreeSet<String>>, usize) {
+    let mut lemmas: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut unknown = 0;
+    for line in text.lines().filter(|l| !l.starts_with('#')) {
+        let mut fields = line.split('\t');
+        let (Some(synset), Some(kind), Some(lemma)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let Some((key, pos)) = synset_key(synset.trim()) else {
+            continue;
+        };
+        let lemma: String = lemma.trim().nfc().collect();
+        if !kind.ends_with(":lemma") || lemma.is_empty() || lemma.contains(|c: char| c.is_whitespace() || ":;#".contains(c)) {
+            continue;
+        }
+        if known.is_some_and(|known| !known.contains(&(pos, lemma.clone()))) {
+            unknown += 1;
+            continue;
+        }
+        lemmas.entry(key).or_default().insert(format!("{:?}:{}", pos, lemma));
+    }
+    (lemmas, unknown)
+}
+
+/// `02084071-n` with satellite adjectives (`s`) filed as adjectives (`a`),
+/// and the part of speech it stands for.
+fn synset_key(synset: &str) -> Option<(String, PartOfSpeech)> {
+    let (offset, pos) = synset.split_once('-')?;
+    let (pos, letter) = wordnet_pos(pos)?;
+    Some((format!("{}-{}", offset, letter), pos))
+}
+
+fn wordnet_pos(letter: &str) -> Option<(PartOfSpeech, char)> {
+    match letter {
+        "n" => Some((PartOfSpeech::Noun, 'n')),
+        "v" => Some((PartOfSpeech::Verb, 'v')),
+        "a" | "s" => Some((PartOfSpeech::Adjective, 'a')),
+        "r" => Some((PartOfSpeech::Adverb, 'r')),
+        _ => None,
+    }
+}
+
+/// Reads the pointers of a WordNet `data.*` file: per line, `offset
+/// lex_filenum ss_type w_cnt (word lex_id)* p_cnt (symbol offset pos
+/// source/target)* ... | gloss`. The license header lines start with spaces.
+fn read_database(text: &str, synsets: &mut BTreeMap<String, Synset>) -> anyhow::Result<()> {
+    for (i, line) in text.lines().enumerate().filter(|(_, l)| !l.starts_with(' ') && !l.trim().is_empty()) {
+        let at = || format!("line {}", i + 1);
+        let fields: Vec<&str> = line.split('|').next().unwrap_or_default().split_whitespace().collect();
+        let malformed = || anyhow::anyhow!("line {}: malformed synset", i + 1);
+        let (offset, pos) = (*fields.first().ok_or_else(malformed)?, *fields.get(2).ok_or_else(malformed)?);
+        let Some((_, letter)) = wordnet_pos(pos) else {
+            continue;
+        };
+        let words = usize::from_str_radix(fields.get(3).ok_or_else(malformed)?, 16).with_context(at)?;
+        let pointers_at = 4 + 2 * words;
+        let pointers: usize = fields.get(pointers_at).ok_or_else(malformed)?.parse().with_context(at)?;
+        let synset = synsets.entry(format!("{}-{}", offset, letter)).or_default();
+        for p in 0..pointers {
+            let pointer = fields.get(pointers_at + 1 + 4 * p..pointers_at + 5 + 4 * p).ok_or_else(malformed)?;
+            let Some((target, _)) = synset_key(&format!("{}-{}", pointer[1], pointer[2])) else {
+                continue;
+            };
+            match pointer[0] {
+                "@" | "@i" => synset.hypernyms.push(target),
+                // Only a noun's attributes: an adjective's point back at the noun
+                "=" if letter == 'n' => synset.attributes.push(target),
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The animacy of the nearest of `ANIMACY_CLASSES` above `key` (or `key`
+/// itself), through every hypernym whether mapped or not.
+fn animacy(key: &str, synsets: &BTreeMap<String, Synset>) -> Option<Animacy> {
+    let mut visited = HashSet::from([key]);
+    let mut queue = VecDeque::from([key]);
+    while let Some(at) = queue.pop_front() {
+        if let Some(&(_, animacy)) = ANIMACY_CLASSES.iter().find(|(class, _)| *class == at) {
+            return Some(animacy);
+        }
+        for parent in synsets.get(at).into_iter().flat_map(|s| &s.hypernyms) {
+            if visited.insert(parent.as_str()) {
+                queue.push_back(parent);
+            }
+        }
+    }
+    None
+}
+
+/// The closest hypernyms of `key` that have Greek lemmas: a hypernym
+/// without any is climbed past, so a gap in the mapping does not cut the
+/// lemma off from the hierarchy.
+fn nearest_with_lemmas<'a>(key: &'a str, synsets: &'a BTreeMap<String, Synset>, lemmas: &BTreeMap<String, BTreeSet<String>>) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut visited = HashSet::from([key]);
+    let mut pending: Vec<&str> = vec![key];
+    while let Some(at) = pending.pop() {
+        for parent in synsets.get(at).into_iter().flat_map(|s| &s.hypernyms) {
+            if !visited.insert(parent.as_str()) {
+                continue;
+            }
+            if lemmas.contains_key(parent) {
+                found.push(parent.as_str());
+            } else {
+                pending.push(parent);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA_NOUN: &str = "  1 This software and database is being provided to you, the LICENSEE, by
+  2 Princeton University under the following license.
+00002137 03 n 01 abstraction 0 000 | a general concept formed by extracting common features
+00002684 03 n 02 object 0 physical_object 0 000 | a tangible and visible entity
+00007846 03 n 01 person 0 002 @ 00002684 n 0000 ~ 10000001 n 0000 | a human being
+00015388 03 n 01 animal 0 001 @ 00002684 n 0000 | a living organism
+02000001 05 n 01 dog 0 001 @ 02000002 n 0000 | a domesticated canid
+02000002 05 n 01 canine 0 001 @ 00015388 n 0000 | a carnivore of the dog family
+04000002 07 n 01 size 0 002 @ 00002137 n 0000 = 01000001 a 0000 | the physical magnitude of something
+10000001 18 n 02 man 0 adult_male 0 001 @i 00007846 n 0000 | an adult male person
+";
+    const DATA_ADJ: &str = "01000001 00 a 01 large 0 001 = 04000002 n 0000 | above average in size
+01000002 00 s 01 huge 0 001 & 01000001 a 0000 | unusually great in size
+";
+    const MAPPING: &str = "# synset\ttype\tlemma
+00015388-n\tgrc:lemma\tζῷον
+02000001-n\tgrc:lemma\tκύων
+02000001-n\tgrc:lemma\tκυ\u{301}ων
+00007846-n\tgrc:lemma\tἄνθρωπος
+10000001-n\tgrc:lemma\tἀνήρ
+00002684-n\tgrc:lemma\tσῶμα φυσικόν
+04000002-n\tgrc:def\tτὸ μέγεθος
+04000002-n\tgrc:lemma\tμέγεθος
+01000001-a\tgrc:lemma\tμέγας
+01000002-s\tgrc:lemma\tπελώριος
+";
+
+    /// Writes `contents` to a file of the temporary directory.
+    fn fixture(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("atlas-wordnet-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_mapping() {
+        let (lemmas, unknown) = mapping(MAPPING, None);
+        assert_eq!(unknown, 0);
+        let words = |key: &str| lemmas[key].iter().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(words("02000001-n"), ["Noun:κύων"]);
+        assert_eq!(words("01000002-a"), ["Adjective:πελώριος"]);
+        assert_eq!(words("04000002-n"), ["Noun:μέγεθος"]);
+        assert!(!lemmas.contains_key("00002684-n"));
+
+        let known = HashSet::from([(PartOfSpeech::Noun, "κύων".to_string()), (PartOfSpeech::Adjective, "ζῷον".to_string())]);
+        let (lemmas, unknown) = mapping(MAPPING, Some(&known));
+        assert_eq!(lemmas.keys().collect::<Vec<_>>(), ["02000001-n"]);
+        assert_eq!(unknown, 6);
+    }
+
+    #[test]
+    fn test_import() {
+        let output = std::env::temp_dir().join(format!("atlas-wordnet-{}-out.txt", std::process::id()));
+        let args = ImportArgs {
+            files: vec![fixture("data.noun", DATA_NOUN), fixture("data.adj", DATA_ADJ)],
+            mapping: fixture("wn-data-grc.tab", MAPPING),
+            dictionary: None,
+            output: output.clone(),
+        };
+        run(&args).unwrap();
+        // Hyponym (~) and similar-to (&) pointers and an adjective's attributes
+        // are dropped; the unmapped canine is climbed past
+        assert_eq!(fs::read_to_string(&output).unwrap().lines().collect::<Vec<_>>(), [
+            "# Imported from WordNet by atlas-compiler import-wordnet",
+            "Noun:ἄνθρωπος animacy human  # 00007846-n",
+            "Noun:ζῷον animacy animal  # 00015388-n",
+            "Noun:κύων isa Noun:ζῷον  # 02000001-n 00015388-n",
+            "Noun:μέγεθος animacy abstract  # 04000002-n",
+            "Noun:μέγεθος has Adjective:μέγας  # 04000002-n 01000001-a",
+            "Noun:ἀνήρ isa Noun:ἄνθρωπος  # 10000001-n 00007846-n",
+        ]);
+    }
+
+    #[test]
+    fn test_malformed_database() {
+        let mut synsets = BTreeMap::new();
+        let error = read_database("00000001 03 n 01 thing 0 002 @ 00002684 n 0000 | two pointers promised\n", &mut synsets).unwrap_err();
+        assert_eq!(error.to_string(), "line 1: malformed synset");
+        assert!(read_database("00000001 03 n zz thing 0 000 | bad word count\n", &mut synsets).is_err());
+    }
+}
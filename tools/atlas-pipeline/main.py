@@ -2,7 +2,8 @@ import json
 import sys
 import os
 from pathlib import Path
-from models import Dictionary, Lemma, Gender, Paradigm
+import yaml
+from models import Dictionary, Lemma, Gender, NameConfig, NameEntry, Paradigm, PartOfSpeech, PronounConfig, PronounParadigm
 from wiktionary import WiktionaryParser
 
 def main():
@@ -76,11 +77,17 @@ def main():
         
         paradigms.extend([p_noun_os, p_article_o, p_article_t])
 
-    # 3. Compile Dictionary
+    # 3. Curated pronoun paradigms (suppletive, so not derivable from templates)
+    pronouns = load_pronouns(Path("data/pronouns.yaml"), lemmas)
+    names = load_names(Path("data/names.yaml"), lemmas)
+
+    # 4. Compile Dictionary
     data = Dictionary(
         version=1,
         lemmas=lemmas,
-        paradigms=paradigms
+        paradigms=paradigms,
+        pronouns=pronouns,
+        names=names
     )
 
     # 2. Export to JSON
@@ -88,9 +95,47 @@ def main():
     output_path = Path("dictionary_intermediate.json")
     
     with open(output_path, "w", encoding="utf-8") as f:
-        f.write(data.model_dump_json(indent=2))
+        f.write(data.model_dump_json(indent=2, by_alias=True))
     
-    print(f"✅ Exported {len(data.lemmas)} lemmas and {len(data.paradigms)} paradigms to {output_path}")
+    print(f"✅ Exported {len(data.lemmas)} lemmas, {len(data.paradigms)} paradigms {len(data.pronouns)} pronouns and {len(data.names)} names to {output_path}")
+
+def load_pronouns(path: Path, lemmas: list[Lemma]) -> list[PronounParadigm]:
+    """Appends a Pronoun lemma per configured paradigm (ids after the highest in use)."""
+    if not path.exists():
+        print(f"ℹ️  No pronoun config at {path}")
+        return []
+
+    with open(path, "r", encoding="utf-8") as f:
+        configs = [PronounConfig(**entry) for entry in yaml.safe_load(f) or []]
+
+    next_id = max((l.id for l in lemmas), default=0) + 1
+    pronouns = []
+    for config in configs:
+        lemmas.append(Lemma(id=next_id, text=config.lemma, gender=config.gender, pos=PartOfSpeech.Pronoun))
+        pronouns.append(PronounParadigm(
+            lemma=next_id,
+            pronoun_class=config.pronoun_class,
+            forms=[(f.flags, f.form) for f in config.forms],
+        ))
+        next_id += 1
+    return pronouns
+
+def load_names(path: Path, lemmas: list[Lemma]) -> list[NameEntry]:
+    """Appends a ProperNoun lemma per gazetteer entry (ids after the highest in use)."""
+    if not path.exists():
+        print(f"ℹ️  No names gazetteer at {path}")
+        return []
+
+    with open(path, "r", encoding="utf-8") as f:
+        configs = [NameConfig(**entry) for entry in yaml.safe_load(f) or []]
+
+    next_id = max((l.id for l in lemmas), default=0) + 1
+    names = []
+    for config in configs:
+        lemmas.append(Lemma(id=next_id, text=config.stem, gender=config.gender, pos=PartOfSpeech.ProperNoun))
+        names.append(NameEntry(lemma=next_id, kind=config.kind))
+        next_id += 1
+    return names
 
 if __name__ == "__main__":
     main()
@@ -1,5 +1,5 @@
 from enum import Enum
-from pydantic import BaseModel
+from pydantic import BaseModel, Field
 
 class Gender(str, Enum):
     Masculine = "Masculine"
@@ -17,22 +17,55 @@ class PartOfSpeech(str, Enum):
     Pronoun = "Pronoun"
     Particle = "Particle"
     Numeral = "Numeral"
+    ProperNoun = "ProperNoun"
+
+class PronounClass(str, Enum):
+    Personal = "Personal"
+    Demonstrative = "Demonstrative"
+    Reflexive = "Reflexive"
+    Possessive = "Possessive"
+
+class NameKind(str, Enum):
+    Person = "Person"
+    Place = "Place"
+    People = "People"
+    Deity = "Deity"
 
 class Lemma(BaseModel):
     id: int
     text: str
     gender: Gender
     pos: PartOfSpeech = PartOfSpeech.Noun # Default for backward compatibility during migration
+    urn: str | None = None # CITE/CTS URN or lexicon reference (e.g. an LSJ entry)
+    gloss: str | None = None # Short English meaning, shown when autocompleting
+    dialects: str = "" # e.g. "IONIC" or "ATTIC | KOINE"; empty for all dialects
+    periods: str = "" # e.g. "KOINE" or "ARCHAIC | CLASSICAL"; empty for all periods
 
 class Paradigm(BaseModel):
     id: int
+    dialects: str = "" # Same syntax as Lemma.dialects
+    periods: str = "" # Same syntax as Lemma.periods; "KOINE" marks a Koine paradigm set
     # List of (MorphFlags as int, Suffix string)
     endings: list[tuple[int, str]]
 
+class PronounParadigm(BaseModel):
+    lemma: int
+    pronoun_class: PronounClass = Field(alias="class")
+    # List of (MorphFlags as int, full form) - pronouns are suppletive
+    forms: list[tuple[int, str]]
+
+    model_config = {"populate_by_name": True}
+
+class NameEntry(BaseModel):
+    lemma: int
+    kind: NameKind
+
 class Dictionary(BaseModel):
     version: int = 1
     lemmas: list[Lemma]
     paradigms: list[Paradigm]
+    pronouns: list[PronounParadigm] = []
+    names: list[NameEntry] = []
 
 # --- Configuration Models ---
 
@@ -45,13 +78,37 @@ class ParadigmEnding(BaseModel):
     flags: int
     suffix: str
 
+class DegreeConfig(BaseModel):
+    # Inserted between stem and positive ending: σοφ-ώτερ-ος, σοφ-ώτατ-ος
+    comparative: str
+    superlative: str
+
 class ParadigmConfig(BaseModel):
     id: int
     name: str
     pos: str | None = "Noun" # Default to Noun for now
     example: str | None = None
+    dialects: str = ""
+    periods: str = ""
     triggers: list[ParadigmTrigger] = []
     endings: list[ParadigmEnding]
+    degrees: DegreeConfig | None = None
 
 class MorphologyConfig(BaseModel):
     paradigms: list[ParadigmConfig]
+
+class PronounForm(BaseModel):
+    flags: int
+    form: str
+
+class PronounConfig(BaseModel):
+    lemma: str
+    pronoun_class: PronounClass = Field(alias="class")
+    gender: Gender = Gender.Masculine
+    forms: list[PronounForm]
+
+class NameConfig(BaseModel):
+    # Stem, inflected through the regular noun paradigms
+    stem: str
+    kind: NameKind
+    gender: Gender = Gender.Masculine
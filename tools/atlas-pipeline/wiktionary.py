@@ -8,6 +8,10 @@ import sys
 from typing import Generator, Optional
 from models import Lemma, Paradigm, Gender, ParadigmConfig, PartOfSpeech
 
+# MorphFlags::COMPARATIVE / SUPERLATIVE
+COMPARATIVE = 536870912
+SUPERLATIVE = 1073741824
+
 class ConfigLoader:
     def __init__(self, data_dir: str):
         self.paradigms: dict[int, Paradigm] = {}
@@ -28,9 +32,16 @@ class ConfigLoader:
                     config = ParadigmConfig(**p_data)
                     
                     # Create Runtime Paradigm
+                    endings = [(e.flags, e.suffix) for e in config.endings]
+                    if config.degrees:
+                        # Comparative/superlative forms inflect like the positive
+                        endings += [(e.flags | COMPARATIVE, config.degrees.comparative + e.suffix) for e in config.endings]
+                        endings += [(e.flags | SUPERLATIVE, config.degrees.superlative + e.suffix) for e in config.endings]
                     paradigm = Paradigm(
                         id=config.id,
-                        endings=[(e.flags, e.suffix) for e in config.endings]
+                        dialects=config.dialects,
+                        periods=config.periods,
+                        endings=endings
                     )
                     self.paradigms[config.id] = paradigm
                     
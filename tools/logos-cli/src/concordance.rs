@@ -0,0 +1,116 @@
+//! `logos concordance`: a lemma-keyed KWIC index over a corpus.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use logos_engine::{AnalysisOptions, AnalysisReport, LogosEngine, ParserKind, TextSource};
+use serde::Serialize;
+
+/// Every occurrence of one lemma. Words no dictionary knows are listed
+/// under their surface form, without a `lemma_id`.
+#[derive(Serialize)]
+pub struct Entry {
+    pub lemma: String,
+    pub lemma_id: Option<u32>,
+    pub occurrences: Vec<Occurrence>,
+}
+
+#[derive(Serialize)]
+pub struct Occurrence {
+    pub form: String,
+    /// "path:line"
+    pub citation: String,
+    pub left: String,
+    pub right: String,
+}
+
+fn is_text_file(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "txt")
+}
+
+/// `.txt` files under `paths` (directories are walked recursively), sorted.
+pub fn collect_files(paths: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(path)
+                .with_context(|| format!("reading {:?}", path))?
+                .map(|e| e.map(|e| e.path()))
+                .collect::<Result<_, _>>()?;
+            entries.retain(|p| p.is_dir() || is_text_file(p));
+            files.extend(collect_files(&entries)?);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Only morphology is needed, so parsing and diagnostics are skipped.
+pub fn build(engine: &LogosEngine, files: &[PathBuf], context: usize) -> anyhow::Result<Vec<Entry>> {
+    let options = AnalysisOptions {
+        parser: ParserKind::None,
+        skip_semantics: true,
+        diagnostics: Vec::new(),
+        ..Default::default()
+    };
+
+    let mut index: BTreeMap<(String, Option<u32>), Vec<Occurrence>> = BTreeMap::new();
+    for path in files {
+        let text = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+        if text.trim().is_empty() {
+            continue;
+        }
+        let source = TextSource::numbered(text, &format!("{}:", path.display()), 1);
+        let report = engine.analyze_source(&source, &options).with_context(|| format!("analyzing {:?}", path))?;
+        record(&mut index, &report, context);
+    }
+
+    Ok(index.into_iter().map(|((lemma, lemma_id), occurrences)| Entry { lemma, lemma_id, occurrences }).collect())
+}
+
+fn record(index: &mut BTreeMap<(String, Option<u32>), Vec<Occurrence>>, report: &AnalysisReport, context: usize) {
+    let tokens = &report.tokens;
+    let window = |range: &[logos_engine::TokenDebug]| range.iter().map(|t| t.text.as_str()).collect::<Vec<_>>().join(" ");
+
+    for (i, token) in tokens.iter().enumerate() {
+        if matches!(token.kind.as_str(), "Punctuation" | "Numeral" | "Editorial" | "Foreign") {
+            continue;
+        }
+        let key = match (&token.lemma, token.lemma_id) {
+            (Some(lemma), id) => (lemma.clone(), id),
+            (None, _) => (token.text.clone(), None),
+        };
+        index.entry(key).or_default().push(Occurrence {
+            form: token.text.clone(),
+            citation: token.citation.clone().unwrap_or_default(),
+            left: window(&tokens[i.saturating_sub(context)..i]),
+            right: window(&tokens[i + 1..(i + 1 + context).min(tokens.len())]),
+        });
+    }
+}
+
+/// One block per lemma, then one aligned line per occurrence.
+pub fn write_text(out: &mut impl Write, entries: &[Entry]) -> io::Result<()> {
+    let citation_width = entries.iter().flat_map(|e| &e.occurrences).map(|o| o.citation.chars().count()).max().unwrap_or(0);
+    let left_width = entries.iter().flat_map(|e| &e.occurrences).map(|o| o.left.chars().count()).max().unwrap_or(0);
+    for entry in entries {
+        match entry.lemma_id {
+            Some(id) => writeln!(out, "{} ({}) ×{}", entry.lemma, id, entry.occurrences.len())?,
+            None => writeln!(out, "{} (unknown) ×{}", entry.lemma, entry.occurrences.len())?,
+        }
+        for o in &entry.occurrences {
+            let line = format!(
+                "  {:<cw$}  {:>lw$} [{}] {}",
+                o.citation, o.left, o.form, o.right,
+                cw = citation_width, lw = left_width,
+            );
+            writeln!(out, "{}", line.trim_end())?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,498 @@
+mod concordance;
+mod repl;
+
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use logos_engine::translit::{self, Scheme};
+use logos_engine::{export, AnalysisOptions, AnalysisReport, Confusion, LanguageProfile, LogosEngine, ParserKind, Severity};
+use serde::Serialize;
+
+/// Diagnostic counts above this are reported as this exit code.
+const MAX_ERROR_EXIT: usize = 100;
+/// Exit code for I/O and load failures (distinct from any diagnostic count).
+const FAILURE_EXIT: u8 = 101;
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Parser)]
+#[command(author, version, about = "Analyzes Greek text against a compiled Logos dictionary")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Analyze files (or stdin), one sentence per line.
+    /// Exits with the number of diagnostics found (capped at 100, warnings not
+    /// counted), or 101 on failure.
+    Analyze(AnalyzeArgs),
+    /// Tokens of files (or stdin) as JSON lines, read a line at a time so
+    /// corpora of any size run in bounded memory.
+    Tokenize(TokenizeArgs),
+    /// Interactive analysis with lexicon inspection commands (:help inside).
+    Repl(EngineArgs),
+    /// Lemma-keyed concordance (KWIC) of text files or directories of .txt files.
+    Concordance(ConcordanceArgs),
+    /// Score the parser (UAS/LAS per relation) or the morphology (accuracy
+    /// per feature, confusion matrices) against a gold CoNLL-U corpus.
+    Eval(EvalArgs),
+}
+
+#[derive(Args)]
+struct EngineArgs {
+    /// Compiled dictionary (.rkyv)
+    #[arg(short, long, value_name = "FILE")]
+    dict: PathBuf,
+
+    /// Compiled semantic network (.rkyv)
+    #[arg(short, long, value_name = "FILE")]
+    semantics: Option<PathBuf>,
+
+    /// Analyze Modern Greek (no dative or infinitive)
+    #[arg(long)]
+    modern: bool,
+}
+
+#[derive(Args)]
+struct AnalyzeArgs {
+    #[command(flatten)]
+    engine: EngineArgs,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+
+    /// Re-analyze the files whenever they change (never exits)
+    #[arg(short, long, requires = "files")]
+    watch: bool,
+
+    /// Also give each sentence in Latin letters: ala-lc, iso-843 or scholarly
+    #[arg(long, value_name = "SCHEME", value_parser = parse_scheme)]
+    translit: Option<Scheme>,
+
+    /// Text files to analyze; reads stdin when omitted
+    #[arg(value_name = "FILE")]
+    files: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+struct TokenizeArgs {
+    #[command(flatten)]
+    engine: EngineArgs,
+
+    /// Rejoin words hyphenated across a line break
+    #[arg(long)]
+    rejoin_hyphens: bool,
+
+    /// Text files to tokenize; reads stdin when omitted
+    #[arg(value_name = "FILE")]
+    files: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+struct ConcordanceArgs {
+    #[command(flatten)]
+    engine: EngineArgs,
+
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+
+    /// Tokens of context on each side of an occurrence
+    #[arg(short, long, default_value_t = 5)]
+    context: usize,
+
+    /// Text files, or directories searched recursively for .txt files
+    #[arg(value_name = "PATH", required = true)]
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+struct EvalArgs {
+    #[command(flatten)]
+    engine: EngineArgs,
+
+    #[arg(short, long, value_enum, default_value_t = EvalTask::Syntax)]
+    task: EvalTask,
+
+    /// Trained parser model (from logos-trainer); scores the transition
+    /// parser instead of the greedy one
+    #[arg(short, long, value_name = "FILE")]
+    model: Option<PathBuf>,
+
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+
+    /// Gold treebank (PROIEL or Perseus UD, CoNLL-U)
+    #[arg(value_name = "FILE")]
+    gold: PathBuf,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum EvalTask {
+    Syntax,
+    Morphology,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Json,
+    Text,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Conllu,
+    /// TEI `<s>` elements, one per line, numbered s1, s2, …
+    Tei,
+    Table,
+}
+
+/// One analyzed line, tagged with where it came from.
+#[derive(Serialize)]
+struct Sentence {
+    source: String,
+    line: usize,
+    /// The line in Latin letters (`--translit`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translit: Option<String>,
+    report: AnalysisReport,
+}
+
+impl Sentence {
+    fn diagnostics(&self) -> usize {
+        let errors = self.report.syntax_errors.iter().filter(|e| e.severity == Severity::Error).count();
+        errors + self.report.semantic_errors.len()
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Analyze(args) => run_analyze(&args),
+        Command::Tokenize(args) => run_tokenize(&args).map(|_| 0),
+        Command::Repl(args) => args.load().and_then(|engine| repl::run(&engine)).map(|_| 0),
+        Command::Concordance(args) => run_concordance(&args).map(|_| 0),
+        Command::Eval(args) => run_eval(&args).map(|_| 0),
+    };
+
+    match result {
+        Ok(errors) => ExitCode::from(errors.min(MAX_ERROR_EXIT) as u8),
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            ExitCode::from(FAILURE_EXIT)
+        }
+    }
+}
+
+impl EngineArgs {
+    fn load(&self) -> anyhow::Result<LogosEngine> {
+        let dict = fs::read(&self.dict).with_context(|| format!("reading {:?}", self.dict))?;
+        let mut engine = LogosEngine::from_bytes(&dict).with_context(|| format!("loading {:?}", self.dict))?;
+        if self.modern {
+            engine = engine.with_profile(LanguageProfile::Modern);
+        }
+
+        if let Some(path) = &self.semantics {
+            let data = fs::read(path).with_context(|| format!("reading {:?}", path))?;
+            engine.load_semantics_bytes(&data).with_context(|| format!("loading {:?}", path))?;
+        }
+        Ok(engine)
+    }
+}
+
+fn run_analyze(args: &AnalyzeArgs) -> anyhow::Result<usize> {
+    let engine = args.engine.load()?;
+
+    if args.watch {
+        watch(&engine, args)
+    } else if args.files.is_empty() {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text).context("reading stdin")?;
+        let sentences = analyze_text(&engine, "<stdin>", &text, args.translit)?;
+        emit(args.format, &sentences)?;
+        Ok(sentences.iter().map(Sentence::diagnostics).sum())
+    } else {
+        let mut sentences = Vec::new();
+        for path in &args.files {
+            sentences.extend(analyze_file(&engine, path, args.translit)?);
+        }
+        emit(args.format, &sentences)?;
+        Ok(sentences.iter().map(Sentence::diagnostics).sum())
+    }
+}
+
+fn run_tokenize(args: &TokenizeArgs) -> anyhow::Result<()> {
+    let engine = args.engine.load()?;
+    let mut options = AnalysisOptions::default();
+    options.tokenizer.rejoin_hyphens = args.rejoin_hyphens;
+
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    if args.files.is_empty() {
+        tokenize_reader(&engine, &options, "<stdin>", io::stdin().lock(), &mut out)?;
+    } else {
+        for path in &args.files {
+            let file = fs::File::open(path).with_context(|| format!("reading {:?}", path))?;
+            tokenize_reader(&engine, &options, &path.display().to_string(), io::BufReader::new(file), &mut out)?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// A token and the file it came from, one JSON object per output line.
+#[derive(Serialize)]
+struct SourceToken<'a> {
+    source: &'a str,
+    #[serde(flatten)]
+    token: logos_engine::TokenSpan,
+}
+
+fn tokenize_reader(engine: &LogosEngine, options: &AnalysisOptions, source: &str, mut reader: impl BufRead, out: &mut impl Write) -> anyhow::Result<()> {
+    let mut failure = None;
+    let lines = std::iter::from_fn(|| {
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) => None,
+            Ok(_) => Some(line),
+            Err(e) => {
+                failure = Some(e);
+                None
+            }
+        }
+    });
+    for token in engine.tokenize_stream(lines, options) {
+        serde_json::to_writer(&mut *out, &SourceToken { source, token })?;
+        writeln!(out)?;
+    }
+    match failure {
+        Some(e) => Err(e).with_context(|| format!("reading {}", source)),
+        None => Ok(()),
+    }
+}
+
+fn run_concordance(args: &ConcordanceArgs) -> anyhow::Result<()> {
+    let engine = args.engine.load()?;
+    let files = concordance::collect_files(&args.paths)?;
+    let entries = concordance::build(&engine, &files, args.context)?;
+
+    let mut out = io::stdout().lock();
+    match args.format {
+        ReportFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &entries)?;
+            writeln!(out)?;
+        }
+        ReportFormat::Text => concordance::write_text(&mut out, &entries)?,
+    }
+    out.flush()?;
+    eprintln!("📚 {} lemma(s) from {} file(s)", entries.len(), files.len());
+    Ok(())
+}
+
+fn run_eval(args: &EvalArgs) -> anyhow::Result<()> {
+    let mut engine = args.engine.load()?;
+    let mut options = AnalysisOptions::default();
+    if let Some(path) = &args.model {
+        let data = fs::read(path).with_context(|| format!("reading {:?}", path))?;
+        engine.load_model_bytes(&data).with_context(|| format!("loading {:?}", path))?;
+        options.parser = ParserKind::Transition;
+    }
+    let gold = fs::read_to_string(&args.gold).with_context(|| format!("reading {:?}", args.gold))?;
+    match args.task {
+        EvalTask::Syntax => eval_syntax(&engine, &gold, &options, args),
+        EvalTask::Morphology => eval_morphology(&engine, &gold, &options, args),
+    }
+}
+
+fn eval_syntax(engine: &LogosEngine, gold: &str, options: &AnalysisOptions, args: &EvalArgs) -> anyhow::Result<()> {
+    let eval = engine
+        .evaluate_parser_core(gold, options)
+        .with_context(|| format!("evaluating against {:?}", args.gold))?;
+
+    let mut out = io::stdout().lock();
+    match args.format {
+        ReportFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &eval)?;
+            writeln!(out)?;
+        }
+        ReportFormat::Text => {
+            writeln!(out, "{:<12} {:>7} {:>7} {:>7}", "relation", "gold", "UAS", "LAS")?;
+            for r in &eval.relations {
+                writeln!(out, "{:<12} {:>7} {:>6.1}% {:>6.1}%", r.deprel, r.gold, r.uas, r.las)?;
+            }
+            writeln!(out, "{:<12} {:>7} {:>6.1}% {:>6.1}%", "total", eval.tokens, eval.uas, eval.las)?;
+        }
+    }
+    out.flush()?;
+    eprintln!("📏 {} sentence(s) scored, {} skipped (tokenization differs)", eval.sentences, eval.skipped);
+    Ok(())
+}
+
+fn eval_morphology(engine: &LogosEngine, gold: &str, options: &AnalysisOptions, args: &EvalArgs) -> anyhow::Result<()> {
+    let eval = engine
+        .evaluate_morphology_core(gold, options)
+        .with_context(|| format!("evaluating against {:?}", args.gold))?;
+
+    let mut out = io::stdout().lock();
+    match args.format {
+        ReportFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &eval)?;
+            writeln!(out)?;
+        }
+        ReportFormat::Text => {
+            for feature in &eval.features {
+                writeln!(out, "{}: {:.1}% ({}/{})", feature.feature, feature.accuracy, feature.correct, feature.scored)?;
+                write_confusion(&mut out, &feature.confusion)?;
+                writeln!(out)?;
+            }
+        }
+    }
+    out.flush()?;
+    eprintln!("📏 {} token(s) in {} sentence(s), {} unknown", eval.tokens, eval.sentences, eval.unknown);
+    Ok(())
+}
+
+/// Gold values down, predictions across.
+fn write_confusion(out: &mut impl Write, cells: &[Confusion]) -> io::Result<()> {
+    let mut gold: Vec<&str> = cells.iter().map(|c| c.gold.as_str()).collect();
+    let mut predicted: Vec<&str> = cells.iter().map(|c| c.predicted.as_str()).collect();
+    gold.dedup();
+    predicted.sort_unstable();
+    predicted.dedup();
+
+    write!(out, "{:>6}", "")?;
+    for p in &predicted {
+        write!(out, " {:>6}", p)?;
+    }
+    writeln!(out)?;
+    for g in &gold {
+        write!(out, "{:>6}", g)?;
+        for p in &predicted {
+            let count = cells.iter().find(|c| c.gold == *g && c.predicted == *p).map_or(0, |c| c.count);
+            write!(out, " {:>6}", count)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn parse_scheme(tag: &str) -> Result<Scheme, String> {
+    Scheme::from_tag(tag).ok_or_else(|| {
+        let tags: Vec<&str> = Scheme::ALL.iter().map(|s| s.tag()).collect();
+        format!("expected one of {}", tags.join(", "))
+    })
+}
+
+/// Polls the files' modification times and re-analyzes whichever changed.
+fn watch(engine: &LogosEngine, args: &AnalyzeArgs) -> anyhow::Result<usize> {
+    let mut seen: Vec<Option<SystemTime>> = vec![None; args.files.len()];
+    eprintln!("👀 Watching {} file(s), Ctrl-C to stop", args.files.len());
+
+    loop {
+        for (path, last) in args.files.iter().zip(seen.iter_mut()) {
+            // A file that is mid-save (or temporarily gone) is picked up on a later poll
+            let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) else { continue };
+            if *last == Some(modified) {
+                continue;
+            }
+            *last = Some(modified);
+
+            match analyze_file(engine, path, args.translit) {
+                Ok(sentences) => {
+                    let errors: usize = sentences.iter().map(Sentence::diagnostics).sum();
+                    emit(args.format, &sentences)?;
+                    eprintln!("🔄 {}: {} diagnostic(s)", path.display(), errors);
+                }
+                Err(e) => eprintln!("❌ {:#}", e),
+            }
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+fn analyze_file(engine: &LogosEngine, path: &Path, translit: Option<Scheme>) -> anyhow::Result<Vec<Sentence>> {
+    let text = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+    analyze_text(engine, &path.display().to_string(), &text, translit)
+}
+
+fn analyze_text(engine: &LogosEngine, source: &str, text: &str, translit: Option<Scheme>) -> anyhow::Result<Vec<Sentence>> {
+    let mut sentences = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        sentences.push(Sentence {
+            source: source.to_string(),
+            line: i + 1,
+            translit: translit.map(|scheme| translit::to_latin(line, scheme)),
+            report: engine.analyze_core(line)?,
+        });
+    }
+    Ok(sentences)
+}
+
+fn emit(format: Format, sentences: &[Sentence]) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    match format {
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, sentences)?;
+            writeln!(out)?;
+        }
+        Format::Conllu => {
+            for s in sentences {
+                writeln!(out, "# source = {}:{}", s.source, s.line)?;
+                if let Some(translit) = &s.translit {
+                    writeln!(out, "# translit = {}", translit)?;
+                }
+                write!(out, "{}", export::to_conllu(&s.report))?;
+            }
+        }
+        Format::Tei => {
+            for (i, s) in sentences.iter().enumerate() {
+                let tei = export::to_tei(&s.report, &format!("s{}", i + 1)).map_err(io::Error::other)?;
+                write!(out, "{}", tei)?;
+            }
+        }
+        Format::Table => {
+            for s in sentences {
+                write_table(&mut out, s)?;
+            }
+        }
+    }
+    out.flush()
+}
+
+fn write_table(out: &mut impl Write, sentence: &Sentence) -> io::Result<()> {
+    writeln!(out, "── {}:{}", sentence.source, sentence.line)?;
+    if let Some(translit) = &sentence.translit {
+        writeln!(out, "     {}", translit)?;
+    }
+    for (i, token) in sentence.report.tokens.iter().enumerate() {
+        writeln!(
+            out,
+            "{:>3}  {:<18} {:<18} {:<16} {}",
+            i + 1,
+            token.text,
+            token.lemma.as_deref().unwrap_or("-"),
+            token.kind,
+            token.morphology,
+        )?;
+    }
+    for e in &sentence.report.syntax_errors {
+        match &e.suggestion {
+            Some(fix) => writeln!(out, "  ⚠️  syntax: {} (did you mean \"{}\"?)", e.message, fix)?,
+            None => writeln!(out, "  ⚠️  syntax: {}", e.message)?,
+        }
+    }
+    for e in &sentence.report.semantic_errors {
+        writeln!(out, "  ⚠️  semantic: {}", e.message)?;
+    }
+    writeln!(out)
+}
@@ -0,0 +1,200 @@
+//! `logos repl`: analyzes each line as it is typed, plus `:` commands for
+//! lexicon authors to inspect what the dictionary actually contains.
+
+use std::io::{self, BufRead, Write};
+
+use logos_engine::{AnalysisOptions, DiagnosticKind, LemmaEntry, LogosEngine};
+use logos_protocol::LemmaId;
+
+use crate::{write_table, Sentence};
+
+const HELP: &str = "\
+Type a sentence to analyze it, or a command:
+  :lemma <word>      dictionary entries the word resolves to
+  :decline <word>    paradigm tables licensing the word
+  :find <prefix>     lemmas whose headword starts with the prefix
+  :id <n>            the lemma with this ID
+  :isa <word>        IsA ancestors, relations and animacy in the semantic graph
+  :diag [kind]       show diagnostics, or toggle `agreement` / `semantic` / `definiteness` / `discourse` / `anachronism`
+  :accents           toggle accent-insensitive matching
+  :info              build metadata of the loaded dictionaries
+  :help              this message
+  :quit              leave (Ctrl-D works too)";
+
+pub fn run(engine: &LogosEngine) -> anyhow::Result<()> {
+    let mut options = AnalysisOptions::default();
+    let mut out = io::stdout().lock();
+    let mut line_no = 0;
+
+    writeln!(out, "Logos REPL — :help for commands")?;
+    let stdin = io::stdin();
+    loop {
+        write!(out, "logos> ")?;
+        out.flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            writeln!(out)?;
+            return Ok(());
+        }
+        line_no += 1;
+        let line = line.trim();
+
+        let (command, arg) = match line.strip_prefix(':') {
+            Some(rest) => rest.split_once(char::is_whitespace).unwrap_or((rest, "")),
+            None if line.is_empty() => continue,
+            None => {
+                let report = engine.analyze_with_options(line, &options)?;
+                write_table(&mut out, &Sentence { source: "repl".to_string(), line: line_no, translit: None, report })?;
+                continue;
+            }
+        };
+        let arg = arg.trim();
+
+        match command {
+            "q" | "quit" => return Ok(()),
+            "h" | "help" => writeln!(out, "{}", HELP)?,
+            "lemma" => lemma(&mut out, engine, arg, &options)?,
+            "decline" => decline(&mut out, engine, arg, &options)?,
+            "find" => find(&mut out, engine, arg)?,
+            "id" => id(&mut out, engine, arg)?,
+            "isa" => isa(&mut out, engine, arg, &options)?,
+            "diag" => diag(&mut out, arg, &mut options)?,
+            "info" => info(&mut out, engine)?,
+            "accents" => {
+                options.accent_insensitive = !options.accent_insensitive;
+                writeln!(out, "accent-insensitive matching: {}", on_off(options.accent_insensitive))?;
+            }
+            _ => writeln!(out, "unknown command `:{}` (try :help)", command)?,
+        }
+    }
+}
+
+fn lemma(out: &mut impl Write, engine: &LogosEngine, word: &str, options: &AnalysisOptions) -> io::Result<()> {
+    let matches = engine.lookup_core(word, options);
+    if matches.is_empty() {
+        return writeln!(out, "no entry for '{}'", word);
+    }
+    for m in matches {
+        writeln!(
+            out,
+            "#{:<8} {:<18} {:?}/{:?}  dict {}  stem '{}'  {}",
+            m.lemma_id, m.headword, m.pos, m.gender, m.dictionary, m.stem, m.morphology,
+        )?;
+    }
+    Ok(())
+}
+
+fn decline(out: &mut impl Write, engine: &LogosEngine, word: &str, options: &AnalysisOptions) -> io::Result<()> {
+    let tables = engine.decline_core(word, options);
+    if tables.is_empty() {
+        return writeln!(out, "no paradigm licenses '{}'", word);
+    }
+    for table in tables {
+        writeln!(out, "── lemma #{} / paradigm #{} (stem '{}')", table.lemma_id, table.paradigm_id, table.stem)?;
+        for form in table.forms {
+            writeln!(out, "  {:<18} {}", form.form, form.morphology)?;
+        }
+    }
+    Ok(())
+}
+
+/// Completions listed by `:find`.
+const FIND_LIMIT: usize = 20;
+
+fn find(out: &mut impl Write, engine: &LogosEngine, prefix: &str) -> io::Result<()> {
+    let entries = engine.find_lemmas_core(prefix, FIND_LIMIT);
+    if entries.is_empty() {
+        return writeln!(out, "no headword starts with '{}'", prefix);
+    }
+    for e in entries {
+        write_entry(out, &e)?;
+    }
+    Ok(())
+}
+
+fn id(out: &mut impl Write, engine: &LogosEngine, arg: &str) -> io::Result<()> {
+    let Ok(id) = arg.parse::<u32>() else {
+        return writeln!(out, "usage: :id <number>");
+    };
+    match engine.lemma_core(id) {
+        Some(e) => write_entry(out, &e),
+        None => writeln!(out, "no lemma #{}", id),
+    }
+}
+
+fn write_entry(out: &mut impl Write, e: &LemmaEntry) -> io::Result<()> {
+    writeln!(out, "#{:<8} {:<18} {:?}/{:?}  dict {}  {}  {}", e.lemma_id, e.headword, e.pos, e.gender, e.dictionary, e.gloss.as_deref().unwrap_or(""), e.urn.as_deref().unwrap_or(""))
+}
+
+fn isa(out: &mut impl Write, engine: &LogosEngine, word: &str, options: &AnalysisOptions) -> io::Result<()> {
+    let Some(graph) = engine.semantic_graph() else {
+        return writeln!(out, "no semantic network loaded (pass --semantics)");
+    };
+    let Some(found) = engine.lookup_core(word, options).into_iter().next() else {
+        return writeln!(out, "no entry for '{}'", word);
+    };
+    let concept = LemmaId(found.lemma_id);
+    let name = |id: LemmaId| match engine.lemma_entry(id) {
+        Some((text, _)) => format!("{} (#{})", text, id.0),
+        None => format!("#{}", id.0),
+    };
+
+    let ancestors = graph.ancestors(concept);
+    if ancestors.is_empty() {
+        writeln!(out, "{} has no IsA parents", name(concept))?;
+    } else {
+        let chain: Vec<String> = ancestors.into_iter().map(name).collect();
+        writeln!(out, "{} IsA {}", name(concept), chain.join(", "))?;
+    }
+    for (relation, target) in graph.relations(concept) {
+        writeln!(out, "  {:?} → {}", relation, name(target))?;
+    }
+    if let Some(animacy) = graph.animacy_of(concept) {
+        writeln!(out, "  animacy: {}", animacy.name())?;
+    }
+    Ok(())
+}
+
+fn info(out: &mut impl Write, engine: &LogosEngine) -> io::Result<()> {
+    for d in engine.dictionary_info() {
+        writeln!(
+            out,
+            "dict {}: v{}  {} lemmas, {} paradigms  hash {}  built {}  from {}",
+            d.dictionary, d.version, d.lemma_count, d.paradigm_count, d.content_hash, d.built_at, d.sources.join(", "),
+        )?;
+    }
+    Ok(())
+}
+
+fn diag(out: &mut impl Write, kind: &str, options: &mut AnalysisOptions) -> io::Result<()> {
+    let kind = match kind {
+        "" => None,
+        "agreement" => Some(DiagnosticKind::Agreement),
+        "semantic" => Some(DiagnosticKind::Semantic),
+        "definiteness" => Some(DiagnosticKind::Definiteness),
+        "discourse" => Some(DiagnosticKind::Discourse),
+        "anachronism" => Some(DiagnosticKind::Anachronism),
+        other => return writeln!(out, "unknown diagnostic `{}` (agreement, semantic, definiteness, discourse, anachronism)", other),
+    };
+
+    if let Some(kind) = kind {
+        match options.diagnostics.iter().position(|k| *k == kind) {
+            Some(i) => { options.diagnostics.remove(i); }
+            None => options.diagnostics.push(kind),
+        }
+    }
+    writeln!(
+        out,
+        "agreement: {}  semantic: {}  definiteness: {}  discourse: {}  anachronism: {}",
+        on_off(options.reports(DiagnosticKind::Agreement)),
+        on_off(options.reports(DiagnosticKind::Semantic)),
+        on_off(options.reports(DiagnosticKind::Definiteness)),
+        on_off(options.reports(DiagnosticKind::Discourse)),
+        on_off(options.reports(DiagnosticKind::Anachronism)),
+    )
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "on" } else { "off" }
+}
@@ -0,0 +1,181 @@
+//! Fits `ParserModel` weights on gold CoNLL-U treebanks.
+
+mod perceptron;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand};
+use logos_engine::{eval::read_conllu, gold_flags, GoldSentence};
+use logos_parser::disambiguate::UNSEEN;
+use logos_parser::syntax::{MorphToken, SyntaxRole};
+use logos_parser::transition::{best_action, features, label_of, Config};
+use logos_protocol::{ParserModel, TagTransition};
+
+use perceptron::Perceptron;
+
+const MODEL_VERSION: u32 = 1;
+
+#[derive(Parser)]
+#[command(author, version, about = "Trains Logos parser models on CoNLL-U treebanks")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Fit transition-parser weights (averaged perceptron) and tag bigrams
+    /// for disambiguation on gold treebanks.
+    Train(TrainArgs),
+}
+
+#[derive(Args)]
+struct TrainArgs {
+    /// Where to write the model (.rkyv)
+    #[arg(short, long, value_name = "FILE")]
+    output: PathBuf,
+
+    /// Passes over the training data
+    #[arg(short, long, default_value_t = 10)]
+    epochs: usize,
+
+    /// Gold treebanks (PROIEL or Perseus UD, CoNLL-U), trained on gold morphology
+    #[arg(value_name = "FILE", required = true)]
+    treebanks: Vec<PathBuf>,
+}
+
+/// A sentence ready for training: gold-tagged tokens and the gold tree.
+struct Example<'a> {
+    tokens: Vec<MorphToken<'a>>,
+    /// (head, label) per token, None for the root
+    heads: Vec<Option<(usize, usize)>>,
+}
+
+fn main() -> anyhow::Result<()> {
+    match Cli::parse().command {
+        Command::Train(args) => train(&args),
+    }
+}
+
+fn train(args: &TrainArgs) -> anyhow::Result<()> {
+    let mut sentences = Vec::new();
+    for path in &args.treebanks {
+        println!("📖 Reading {:?}...", path);
+        let text = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+        sentences.extend(read_conllu(&text).with_context(|| format!("parsing {:?}", path))?);
+    }
+
+    let examples: Vec<Example> = sentences.iter().filter_map(example).collect();
+    println!("🌳 {} of {} sentences have a single root and are usable", examples.len(), sentences.len());
+
+    let perceptron = fit(&examples, args.epochs);
+    let model = ParserModel { version: MODEL_VERSION, features: perceptron.finish(), transitions: tag_bigrams(&sentences) };
+    println!("⚖️  {} feature weights, {} tag bigrams", model.features.len(), model.transitions.len());
+
+    let bytes = rkyv::to_bytes::<_, 4096>(&model).context("serializing the model")?;
+    fs::write(&args.output, &bytes).with_context(|| format!("writing {:?}", args.output))?;
+    println!("✅ Model written to {:?}", args.output);
+    Ok(())
+}
+
+/// Trains on `examples` for `epochs` passes, learning from the oracle's
+/// action wherever the model would have chosen another.
+fn fit(examples: &[Example], epochs: usize) -> Perceptron {
+    let mut perceptron = Perceptron::default();
+    for epoch in 1..=epochs {
+        let (mut steps, mut errors) = (0, 0);
+        for example in examples {
+            let mut config = Config::new(example.tokens.len());
+            while let Some(gold) = config.oracle(&example.heads) {
+                let features = features(&example.tokens, &config);
+                if let Some(predicted) = best_action(&perceptron, &features, &config) {
+                    if predicted != gold {
+                        perceptron.update(&features, gold.index(), predicted.index());
+                        errors += 1;
+                    }
+                }
+                perceptron.tick();
+                steps += 1;
+                config.apply(gold);
+            }
+        }
+        let accuracy = if steps == 0 { 0.0 } else { 100.0 * (steps - errors) as f64 / steps as f64 };
+        println!("🔁 Epoch {}: {:.1}% of {} actions right", epoch, accuracy, steps);
+    }
+    perceptron
+}
+
+/// None when the tree is not reachable by the oracle (several roots);
+/// crossing arcs are reached by swapping.
+fn example(sentence: &GoldSentence) -> Option<Example<'_>> {
+    let tokens: Vec<MorphToken> = sentence.tokens.iter()
+        .map(|t| MorphToken { text: &t.form, flags: gold_flags(t) })
+        .collect();
+    let mut heads = Vec::with_capacity(tokens.len());
+    for t in &sentence.tokens {
+        heads.push(match t.head {
+            0 => None,
+            head if head <= tokens.len() => Some((head - 1, label_of(&role(&t.deprel))?)),
+            _ => return None,
+        });
+    }
+
+    let mut config = Config::new(tokens.len());
+    while let Some(action) = config.oracle(&heads) {
+        config.apply(action);
+    }
+    (config.is_terminal() && config.heads == heads).then_some(Example { tokens, heads })
+}
+
+/// Universal Dependencies relation to parser role; the inverse of the
+/// engine's CoNLL-U export where one exists.
+fn role(deprel: &str) -> SyntaxRole {
+    match deprel {
+        "obl:agent" => return SyntaxRole::PassiveAgent,
+        "advcl:abs" => return SyntaxRole::AbsoluteClause,
+        "acl:relcl" => return SyntaxRole::RelativeClause,
+        _ => {}
+    }
+    match deprel.split(':').next().unwrap_or(deprel) {
+        "nsubj" => SyntaxRole::Subject,
+        "obj" => SyntaxRole::Object,
+        "iobj" => SyntaxRole::IndirectObject,
+        "det" | "amod" | "nmod" | "nummod" => SyntaxRole::Modifier,
+        "pobj" => SyntaxRole::PrepositionArg,
+        "cc" => SyntaxRole::Coordinator,
+        "conj" => SyntaxRole::Conjunct,
+        "xcomp" => SyntaxRole::Complement,
+        _ => SyntaxRole::None,
+    }
+}
+
+/// Add-one smoothed log-probabilities (×1000) of each tag given the one
+/// before it, over the gold words. Unobserved bigrams are left out and fall
+/// back to `UNSEEN`.
+fn tag_bigrams(sentences: &[GoldSentence]) -> Vec<TagTransition> {
+    let mut counts: BTreeMap<(u32, u32), u32> = BTreeMap::new();
+    let mut tags = BTreeSet::new();
+    for sentence in sentences {
+        let mut previous = TagTransition::START;
+        for token in sentence.tokens.iter().filter(|t| t.upos != "PUNCT") {
+            let tag = gold_flags(token).tag();
+            *counts.entry((previous, tag)).or_default() += 1;
+            tags.insert(tag);
+            previous = tag;
+        }
+    }
+
+    let mut totals: BTreeMap<u32, u32> = BTreeMap::new();
+    for (&(from, _), &n) in &counts {
+        *totals.entry(from).or_default() += n;
+    }
+    counts.into_iter()
+        .map(|((from, to), n)| {
+            let p = (n + 1) as f64 / (totals[&from] as usize + tags.len()) as f64;
+            TagTransition { from, to, weight: ((p.ln() * 1000.0).round() as i32).max(UNSEEN + 1) }
+        })
+        .collect()
+}
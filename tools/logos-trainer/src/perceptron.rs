@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use logos_parser::transition::Scorer;
+use logos_protocol::FeatureWeight;
+
+/// Averaged perceptron over (feature, action) pairs. Averages are kept
+/// lazily: each weight remembers when it last changed.
+#[derive(Default)]
+pub struct Perceptron {
+    weights: HashMap<(u64, u16), Weight>,
+    /// Updates seen so far (one per training step)
+    clock: u32,
+}
+
+#[derive(Default)]
+struct Weight {
+    current: f32,
+    /// Sum of `current` over all steps up to `stamp`
+    total: f32,
+    stamp: u32,
+}
+
+impl Weight {
+    fn catch_up(&mut self, clock: u32) {
+        self.total += (clock - self.stamp) as f32 * self.current;
+        self.stamp = clock;
+    }
+}
+
+impl Scorer for Perceptron {
+    fn score(&self, features: &[u64], action: u16) -> f32 {
+        features.iter().filter_map(|f| self.weights.get(&(*f, action))).map(|w| w.current).sum()
+    }
+}
+
+impl Perceptron {
+    pub fn tick(&mut self) {
+        self.clock += 1;
+    }
+
+    /// Rewards `gold` and penalizes `predicted` on every active feature.
+    pub fn update(&mut self, features: &[u64], gold: u16, predicted: u16) {
+        for (action, delta) in [(gold, 1.0), (predicted, -1.0)] {
+            for feature in features {
+                let weight = self.weights.entry((*feature, action)).or_default();
+                weight.catch_up(self.clock);
+                weight.current += delta;
+            }
+        }
+    }
+
+    /// The averaged weights, dropping those that came out at zero.
+    pub fn finish(mut self) -> Vec<FeatureWeight> {
+        let clock = self.clock.max(1);
+        let mut out: Vec<FeatureWeight> = self.weights.iter_mut()
+            .filter_map(|(&(feature, action), w)| {
+                w.catch_up(clock);
+                let weight = w.total / clock as f32;
+                (weight.abs() > f32::EPSILON).then_some(FeatureWeight { feature, action, weight })
+            })
+            .collect();
+        out.sort_by_key(|w| (w.feature, w.action));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{example, fit, tag_bigrams, MODEL_VERSION};
+    use logos_engine::{eval::read_conllu, LogosEngine};
+    use logos_parser::transition::{parse_transition, Weights};
+    use logos_protocol::{Dictionary, ParserModel};
+    use rkyv::Deserialize;
+
+    const TREEBANK: &str = "# sent_id = 1
+1\tὁ\tὁ\tDET\t_\tCase=Nom|Gender=Masc|Number=Sing\t2\tdet\t_\t_
+2\tἄνθρωπος\tἄνθρωπος\tNOUN\t_\tCase=Nom|Gender=Masc|Number=Sing\t3\tnsubj\t_\t_
+3\tλέγει\tλέγω\tVERB\t_\tMood=Ind|Number=Sing|Person=3|Tense=Pres|Voice=Act\t0\troot\t_\t_
+4\tλόγον\tλόγος\tNOUN\t_\tCase=Acc|Gender=Masc|Number=Sing\t3\tobj\t_\t_
+
+# sent_id = 2
+1\tτὸν\tὁ\tDET\t_\tCase=Acc|Gender=Masc|Number=Sing\t2\tdet\t_\t_
+2\tἵππον\tἵππος\tNOUN\t_\tCase=Acc|Gender=Masc|Number=Sing\t3\tobj\t_\t_
+3\tὁρᾷ\tὁράω\tVERB\t_\tMood=Ind|Number=Sing|Person=3|Tense=Pres|Voice=Act\t0\troot\t_\t_
+4\tὁ\tὁ\tDET\t_\tCase=Nom|Gender=Masc|Number=Sing\t5\tdet\t_\t_
+5\tθεός\tθεός\tNOUN\t_\tCase=Nom|Gender=Masc|Number=Sing\t3\tnsubj\t_\t_
+
+# sent_id = 3
+1\tἄνθρωποι\tἄνθρωπος\tNOUN\t_\tCase=Nom|Gender=Masc|Number=Plur\t2\tnsubj\t_\t_
+2\tτρέχουσι\tτρέχω\tVERB\t_\tMood=Ind|Number=Plur|Person=3|Tense=Pres|Voice=Act\t0\troot\t_\t_
+";
+
+    /// (dependent, head, label) of every arc.
+    fn arcs(heads: &[Option<(usize, usize)>]) -> Vec<(usize, usize, usize)> {
+        heads.iter().enumerate().filter_map(|(d, h)| h.map(|(h, l)| (d, h, l))).collect()
+    }
+
+    #[test]
+    fn test_train_and_reload() {
+        let sentences = read_conllu(TREEBANK).unwrap();
+        let examples: Vec<_> = sentences.iter().filter_map(example).collect();
+        assert_eq!(examples.len(), 3);
+
+        let perceptron = fit(&examples, 10);
+        let model = ParserModel { version: MODEL_VERSION, features: perceptron.finish(), transitions: tag_bigrams(&sentences) };
+        assert!(!model.features.is_empty());
+        assert!(model.features.windows(2).all(|w| (w[0].feature, w[0].action) < (w[1].feature, w[1].action)));
+
+        // The saved model reads back whole and parses its training data as given
+        let bytes = rkyv::to_bytes::<_, 4096>(&model).unwrap();
+        let archived = rkyv::check_archived_root::<ParserModel>(&bytes).unwrap();
+        let loaded: ParserModel = archived.deserialize(&mut rkyv::Infallible).unwrap();
+        assert_eq!(loaded.features, model.features);
+        assert_eq!(loaded.transitions, model.transitions);
+        let weights = Weights::from_model(&loaded);
+        for example in &examples {
+            let parsed: Vec<(usize, usize, usize)> = parse_transition(&example.tokens, &weights).iter()
+                .map(|d| (d.dependent_index, d.head_index, logos_parser::transition::label_of(&d.role).unwrap()))
+                .collect();
+            assert_eq!(parsed, arcs(&example.heads));
+        }
+
+        let mut engine = LogosEngine::from_bytes(&rkyv::to_bytes::<_, 256>(&Dictionary::default()).unwrap()).unwrap();
+        engine.load_model_bytes(&bytes).unwrap();
+    }
+
+    #[test]
+    fn test_averaging() {
+        let mut perceptron = Perceptron::default();
+        perceptron.update(&[7], 1, 2);
+        perceptron.update(&[8], 3, 3);
+        perceptron.tick();
+        perceptron.tick();
+        perceptron.update(&[7], 2, 1);
+        perceptron.tick();
+        perceptron.tick();
+        assert_eq!(perceptron.score(&[7], 1), 0.0);
+        // ±1 for two of the four steps and 0 for the rest; feature 8 never
+        // moved, so it is dropped
+        let weights: Vec<(u64, u16, f32)> = perceptron.finish().iter().map(|w| (w.feature, w.action, w.weight)).collect();
+        assert_eq!(weights, [(7, 1, 0.5), (7, 2, -0.5)]);
+    }
+}